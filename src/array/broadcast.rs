@@ -0,0 +1,135 @@
+use std::fmt::Display;
+use std::iter::repeat;
+use std::ops::Range;
+
+use array::{NDArray, NDData, NDDataMut, NDView, NDViewable};
+use array::ndindex::NDIndex;
+use parallel;
+
+/// Compute the shape resulting from broadcasting two shapes together. Following the numpy rules, 
+/// shapes are aligned on their last dimension, missing leading dimensions are considered to be of 
+/// length 1 and dimensions of length 1 are stretched to match the other shape. Return None if the 
+/// shapes are not compatible.
+pub fn broadcast_shape(a : &[usize], b : &[usize]) -> Option<Vec<usize>> {
+    let dim = if a.len() > b.len() { a.len() } else { b.len() };
+    let mut shape : Vec<usize> = repeat(0usize).take(dim).collect();
+    for i in 0..dim {
+        let la = if i < dim - a.len() { 1 } else { a[i + a.len() - dim] };
+        let lb = if i < dim - b.len() { 1 } else { b[i + b.len() - dim] };
+        if la == lb || lb == 1 {
+            shape[i] = la;
+        }
+        else if la == 1 {
+            shape[i] = lb;
+        }
+        else {
+            return None;
+        }
+    }
+    return Some(shape);
+}
+
+/// Compute the strides allowing data of a given shape and strides to be read as if it had the 
+/// target shape. Broadcasted dimensions get a stride of 0. Return None if the shape can't be 
+/// broadcasted to the target shape.
+pub fn broadcast_strides(shape : &[usize], strides : &[usize], target : &[usize]) -> Option<Vec<usize>> {
+    if target.len() < shape.len() {
+        return None;
+    }
+    let lead = target.len() - shape.len();
+    let mut broadcasted : Vec<usize> = repeat(0usize).take(target.len()).collect();
+    for i in lead..target.len() {
+        if shape[i - lead] == target[i] {
+            broadcasted[i] = strides[i - lead];
+        }
+        else if shape[i - lead] != 1 {
+            return None;
+        }
+    }
+    return Some(broadcasted);
+}
+
+/// Return the shape resulting from broadcasting a and b together, panicking with the name of the
+/// caller if they are not compatible.
+fn zip_shape<T, S>(a : &NDData<T>, b : &NDData<S>, name : &str) -> Vec<usize> {
+    match broadcast_shape(a.shape(), b.shape()) {
+        Some(s) => s,
+        None => panic!("{}(): shapes can't be broadcasted together ({:?} and {:?})", name, a.shape(), b.shape())
+    }
+}
+
+/// Apply f to the elements of va and vb, which share the same shape, at the row-major positions 
+/// of range.
+fn zip_range<T, S, U, F : Fn(&T, &S) -> U>(va : &NDView<T>, vb : &NDView<S>, range : Range<usize>, f : &F) -> Vec<U> {
+    let mut data = Vec::<U>::with_capacity(range.end - range.start);
+    let mut idx = parallel::ro_index(range.start, va.shape());
+    for _ in range {
+        data.push(f(va.idx(&idx[..]), vb.idx(&idx[..])));
+        idx.inc_ro(va.shape());
+    }
+    data
+}
+
+/// Apply f to every pair of elements of a and b, broadcasted together, and return the results as 
+/// a new NDArray having the broadcasted shape.
+pub fn zip_with<T, S, U : Clone, F : Fn(&T, &S) -> U>(a : &NDData<T>, b : &NDData<S>, f : F) -> NDArray<U> {
+    let shape = zip_shape(a, b, "zip_with");
+    let size = shape.iter().fold(1usize, |acc, &x| acc * x);
+    let data = zip_range(&a.broadcast(&shape[..]), &b.broadcast(&shape[..]), 0..size, &f);
+    NDArray {
+        strides : NDArray::<U>::compute_strides(&shape[..]),
+        shape : shape,
+        data : data.into_boxed_slice(),
+    }
+}
+
+/// Parallel version of zip_with for element types and closures which can be shared between 
+/// threads: large results are computed on the thread pool.
+pub fn par_zip_with<T : Sync, S : Sync, U : Clone + Send, F : Fn(&T, &S) -> U + Sync>(a : &NDData<T>, b : &NDData<S>, f : F) -> NDArray<U> {
+    let shape = zip_shape(a, b, "par_zip_with");
+    let size = shape.iter().fold(1usize, |acc, &x| acc * x);
+    let (va, vb) = (a.broadcast(&shape[..]), b.broadcast(&shape[..]));
+    let chunks = parallel::map_ranges(size, 1, |range| zip_range(&va, &vb, range, &f));
+    let mut data = Vec::<U>::with_capacity(size);
+    for chunk in chunks {
+        data.extend(chunk);
+    }
+    NDArray {
+        strides : NDArray::<U>::compute_strides(&shape[..]),
+        shape : shape,
+        data : data.into_boxed_slice(),
+    }
+}
+
+/// Apply f to every element of a together with the matching element of b, broadcasted to the 
+/// shape of a. Only b can be broadcasted.
+pub fn zip_assign<T : Clone + Display, F : Fn(&mut T, &T)>(a : &mut NDDataMut<T>, b : &NDData<T>, f : F) {
+    let shape = a.shape().to_vec();
+    if broadcast_strides(b.shape(), b.strides(), &shape[..]).is_none() {
+        panic!("zip_assign(): shape {:?} can't be broadcasted to shape {:?}", b.shape(), shape);
+    }
+    let vb = b.broadcast(&shape[..]);
+    let size = a.size();
+    let mut idx : Vec<usize> = repeat(0usize).take(shape.len()).collect();
+    for _ in 0..size {
+        f(a.idx_mut(&idx[..]), vb.idx(&idx[..]));
+        idx.inc_ro(&shape[..]);
+    }
+}
+
+/// Parallel version of zip_assign for element types and closures which can be shared between 
+/// threads: large contiguous arrays are updated on the thread pool.
+pub fn par_zip_assign<T : Clone + Display + Send + Sync, F : Fn(&mut T, &T) + Sync>(a : &mut NDDataMut<T>, b : &NDData<T>, f : F) {
+    if !a.is_contiguous() || broadcast_strides(b.shape(), b.strides(), a.shape()).is_none() {
+        return zip_assign(a, b, f);
+    }
+    let shape = a.shape().to_vec();
+    let vb = b.broadcast(&shape[..]);
+    parallel::for_each_chunk_mut(a.get_data_mut(), |start, chunk| {
+        let mut idx = parallel::ro_index(start, &shape[..]);
+        for x in chunk.iter_mut() {
+            f(x, vb.idx(&idx[..]));
+            idx.inc_ro(&shape[..]);
+        }
+    });
+}
@@ -1,13 +1,13 @@
 extern crate csv;
 
 use std::clone::Clone;
-use std::error::Error;
 use std::fmt::Display;
 use std::fs::File;
 //use std::marker::Sized;
 //use std::ops::Index;
 use std::str::FromStr;
 use array::{NDArray, NDData};
+use error::Error;
 
 /// Structure representing a Comma Separated Value file.
 pub struct CSVFile {
@@ -22,6 +22,15 @@ pub struct CSVFile {
     pub quote : u8,
 }
 
+/// Convert an error of the csv crate. Only the records are read and written, the errors other
+/// than I/O are thus parsing errors and are kept as their source.
+fn csv_error(e : csv::Error) -> Error {
+    match e {
+        csv::Error::Io(e) => Error::Io(e),
+        e => Error::Parse { message : format!("{}", e), source : Some(Box::new(e)) },
+    }
+}
+
 impl CSVFile {
 
     /// Allocate a new CSVFile structure with a given path. This function neither create nor open 
@@ -36,7 +45,7 @@ impl CSVFile {
         }
     }
 
-    fn get_reader(&self) -> Result<csv::Reader<File>, String> {
+    fn get_reader(&self) -> Result<csv::Reader<File>, Error> {
         match csv::Reader::from_file(&self.path[..]) {
             Ok(r) => {
                 return Ok(r.has_headers(self.header)
@@ -45,13 +54,13 @@ impl CSVFile {
                            .quote(self.quote));
             }
             Err(e) => {
-                Err(e.description().to_string())
+                Err(csv_error(e))
             }
         }
     }
 
     #[allow(unused_mut)] 
-    fn get_writer(&mut self) -> Result<csv::Writer<File>, String> {
+    fn get_writer(&mut self) -> Result<csv::Writer<File>, Error> {
         match csv::Writer::from_file(&self.path[..]) {
             Ok(w) => {
                 return Ok(w.flexible(self.flexible)
@@ -59,15 +68,15 @@ impl CSVFile {
                            .quote(self.quote));
             }
             Err(e) => {
-                Err(e.description().to_string())
+                Err(csv_error(e))
             }
         }
     }
 
     /// Open the CSV file for reading and read the row at row_idx (index starting at 0) as a one 
     /// dimensional array.
-    /// In case of failure, returns the error as a string.
-    pub fn read_row<T : FromStr + Clone>(&self, row_idx : usize) -> Result<NDArray<T>, String> {
+    /// In case of failure, returns the error as an `Error`.
+    pub fn read_row<T : FromStr + Clone>(&self, row_idx : usize) -> Result<NDArray<T>, Error> {
         let mut data = Vec::<T>::new();
         let mut shape = [0usize;1];
         let mut reader = match self.get_reader() {
@@ -84,16 +93,16 @@ impl CSVFile {
                             data.push(value);
                         },
                         Err(_) => {
-                            return Err(format!("Failed to parse value '{:}' at column {:} of row {:}", item, shape[1], row_idx));
+                            return Err(Error::Parse { message : format!("Failed to parse value '{:}' at column {:} of row {:}", item, data.len(), row_idx), source : None });
                         }
                     }
                 }
             },
             Some(Err(e)) => {
-                return Err(e.description().to_string());
+                return Err(csv_error(e));
             },
             None => {
-                return Err(format!("Row {:} not found", row_idx));
+                return Err(Error::Format(format!("Row {:} not found", row_idx)));
             }
         }
         return Ok(NDArray::from_slice(&shape[..], &data[..]));
@@ -101,8 +110,8 @@ impl CSVFile {
 
     /// Open the CSV file for reading and read the column at column_idx (index starting at 0) as a 
     /// one dimensional array.
-    /// In case of failure, returns the error as a string.
-    pub fn read_column<T : FromStr + Clone>(&self, column_idx : usize) -> Result<NDArray<T>, String> {
+    /// In case of failure, returns the error as an `Error`.
+    pub fn read_column<T : FromStr + Clone>(&self, column_idx : usize) -> Result<NDArray<T>, Error> {
         let mut data = Vec::<T>::new();
         let mut shape = [0usize;1];
         let mut reader = match self.get_reader() {
@@ -113,23 +122,23 @@ impl CSVFile {
         for record in reader.records() {
             match record {
                 Ok(record) => {
-                    shape[0] += 1;
                     if record.len() > column_idx {
                         match T::from_str(&record[column_idx][..]) {
                             Ok(value) => {
                                 data.push(value);
                             },
                             Err(_) => {
-                                return Err(format!("Failed to parse value '{:}' at row {:} of column {:}", record[column_idx], shape[1], column_idx));
+                                return Err(Error::Parse { message : format!("Failed to parse value '{:}' at row {:} of column {:}", record[column_idx], shape[0], column_idx), source : None });
                             }
                         }
                     }
                     else {
-                        return Err(format!("Column {:} not found in row {:}", column_idx, shape[1]));
+                        return Err(Error::Format(format!("Column {:} not found in row {:}", column_idx, shape[0])));
                     }
+                    shape[0] += 1;
                 },
                 Err(e) => {
-                    return Err(e.description().to_string());
+                    return Err(csv_error(e));
                 }
             }
         }
@@ -137,8 +146,8 @@ impl CSVFile {
     }
 
     /// Open the CSV file for reading and read the whole file as a two dimensional array.
-    /// In case of failure, returns the error as a string.
-    pub fn read_array<T : FromStr + Clone>(&self) -> Result<NDArray<T>, String> {
+    /// In case of failure, returns the error as an `Error`.
+    pub fn read_array<T : FromStr + Clone>(&self) -> Result<NDArray<T>, Error> {
         let mut data = Vec::<T>::new();
         let mut shape = [0usize;2];
         let mut reader = match self.get_reader() {
@@ -149,21 +158,26 @@ impl CSVFile {
         for record in reader.records() {
             match record {
                 Ok(record) => {
-                    shape[0] += 1;
-                    shape[1] = record.len();
+                    if shape[0] == 0 {
+                        shape[1] = record.len();
+                    }
+                    else if record.len() != shape[1] {
+                        return Err(Error::ShapeMismatch { expected : vec![shape[1]], found : vec![record.len()] });
+                    }
                     for item in record {
                         match T::from_str(&item[..]) {
                             Ok(value) => {
                                 data.push(value);
                             },
                             Err(_) => {
-                                return Err(format!("Failed to parse value '{:}' at record {:}", item, shape[1]));
+                                return Err(Error::Parse { message : format!("Failed to parse value '{:}' at record {:}", item, shape[0]), source : None });
                             }
                         }
                     }
+                    shape[0] += 1;
                 },
                 Err(e) => {
-                    return Err(e.description().to_string());
+                    return Err(csv_error(e));
                 }
             }
         }
@@ -172,8 +186,8 @@ impl CSVFile {
 
     /// Open (or create) the CSV file for writing and write a two dimensional array in it. This 
     /// function overwrite any data already present in the file.
-    /// In case of failure, returns the error as a string.
-    pub fn write_data<T : Display>(&mut self, data : &NDData<T>) -> Result<(), String>  {
+    /// In case of failure, returns the error as an `Error`.
+    pub fn write_data<T : Display>(&mut self, data : &NDData<T>) -> Result<(), Error>  {
         assert!(data.dim() <= 2);
 
         let mut writer = match self.get_writer() {
@@ -185,29 +199,27 @@ impl CSVFile {
             for i in 0..data.shape()[0] {
                 let mut record = Vec::<String>::new();
                 for j in 0..data.shape()[1] {
-                    let idx = i * data.strides()[0] + j * data.strides()[1];
-                    record.push(format!("{}", data.get_data()[idx]))
+                    record.push(format!("{}", data.idx(&[i, j])))
                 }
                 if let Err(e) = writer.write(record.into_iter()) {
-                    return Err(e.description().to_string());
+                    return Err(csv_error(e));
                 }
             }
         }
         else if data.dim() == 1 {
             let mut record = Vec::<String>::new();
-            for i in 0..data.shape()[1] {
-                let idx = i * data.strides()[0];
-                record.push(format!("{}", data.get_data()[idx]))
+            for i in 0..data.shape()[0] {
+                record.push(format!("{}", data.idx(&[i])))
             }
             if let Err(e) = writer.write(record.into_iter()) {
-                return Err(e.description().to_string());
+                return Err(csv_error(e));
             }
         }
         else if data.dim() == 0 {
             let mut record = Vec::<String>::new();
-            record.push(format!("{}", data.get_data()[0]));
+            record.push(format!("{}", data.idx(&[])));
             if let Err(e) = writer.write(record.into_iter()) {
-                return Err(e.description().to_string());
+                return Err(csv_error(e));
             }
         }
 
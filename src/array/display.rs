@@ -0,0 +1,152 @@
+use std::fmt;
+use std::fmt::Display;
+
+use array::{NDData, NDArray, NDSlice, NDSliceMut, NDView, NDViewMut};
+
+/// Arrays with more elements than this are elided unless the alternate flag is used.
+const ELISION_THRESHOLD : usize = 1000;
+
+/// Number of elements printed at the start and the end of an elided dimension.
+const EDGE_ITEMS : usize = 3;
+
+/// Return the indices of a dimension which are printed, None standing for the elision marker.
+fn visible(len : usize, elide : bool) -> Vec<Option<usize>> {
+    if elide && len > 2 * EDGE_ITEMS {
+        (0..EDGE_ITEMS).map(|i| Some(i)).chain(Some(None)).chain((len - EDGE_ITEMS..len).map(|i| Some(i))).collect()
+    }
+    else {
+        (0..len).map(|i| Some(i)).collect()
+    }
+}
+
+/// Format data with nested brackets in the numpy style. Continuation lines are indented by
+/// indent spaces so that the output can follow a prefix.
+fn format_nddata<T : Display>(data : &NDData<T>, f : &mut fmt::Formatter, indent : usize) -> fmt::Result {
+    let elide = !f.alternate() && data.size() > ELISION_THRESHOLD;
+    let visibles : Vec<Vec<Option<usize>>> = data.shape().iter().map(|&len| visible(len, elide)).collect();
+    let format_element = |x : &T| match f.precision() {
+        Some(p) => format!("{:.*}", p, x),
+        None => format!("{}", x),
+    };
+
+    if data.dim() == 0 {
+        return write!(f, "{}", format_element(data.idx(&[])));
+    }
+
+    // Format every printed element first to compute the column width
+    let mut elements = Vec::<String>::new();
+    collect_elements(data, &visibles[..], &mut Vec::new(), &mut elements, &format_element);
+    let width = elements.iter().map(|e| e.chars().count()).max().unwrap_or(0);
+
+    let mut next = elements.iter();
+    write_level(f, &visibles[..], &mut next, width, 0, indent)
+}
+
+/// Format the printed elements in row-major order, idx being the index of the current sub-array.
+fn collect_elements<T, F : Fn(&T) -> String>(data : &NDData<T>, visibles : &[Vec<Option<usize>>], idx : &mut Vec<usize>, elements : &mut Vec<String>, format_element : &F) {
+    if idx.len() == visibles.len() {
+        elements.push(format_element(data.idx(&idx[..])));
+        return;
+    }
+    for i in visibles[idx.len()].iter().filter_map(|&i| i) {
+        idx.push(i);
+        collect_elements(data, visibles, idx, elements, format_element);
+        idx.pop();
+    }
+}
+
+/// Write the dimension depth of the data, consuming the formatted elements in row-major order.
+fn write_level<'a, I : Iterator<Item = &'a String>>(f : &mut fmt::Formatter, visibles : &[Vec<Option<usize>>], next : &mut I, width : usize, depth : usize, indent : usize) -> fmt::Result {
+    let dim = visibles.len();
+    write!(f, "[")?;
+    for (k, i) in visibles[depth].iter().enumerate() {
+        if k > 0 {
+            if depth == dim - 1 {
+                write!(f, ", ")?;
+            }
+            else {
+                write!(f, ",")?;
+                for _ in 0..(dim - depth - 1) {
+                    write!(f, "\n")?;
+                }
+                write!(f, "{:1$}", "", indent + depth + 1)?;
+            }
+        }
+        match *i {
+            Some(_) if depth == dim - 1 => write!(f, "{:>1$}", next.next().unwrap(), width)?,
+            Some(_) => write_level(f, visibles, next, width, depth + 1, indent)?,
+            None => write!(f, "...")?,
+        }
+    }
+    write!(f, "]")
+}
+
+impl<T : Display> Display for NDArray<T> {
+    fn fmt(&self, f : &mut fmt::Formatter) -> fmt::Result {
+        format_nddata(self, f, 0)
+    }
+}
+
+impl<'a, T : Display> Display for NDSlice<'a, T> {
+    fn fmt(&self, f : &mut fmt::Formatter) -> fmt::Result {
+        format_nddata(self, f, 0)
+    }
+}
+
+impl<'a, T : Display> Display for NDSliceMut<'a, T> {
+    fn fmt(&self, f : &mut fmt::Formatter) -> fmt::Result {
+        format_nddata(self, f, 0)
+    }
+}
+
+impl<'a, T : Display> Display for NDView<'a, T> {
+    fn fmt(&self, f : &mut fmt::Formatter) -> fmt::Result {
+        format_nddata(self, f, 0)
+    }
+}
+
+impl<'a, T : Display> Display for NDViewMut<'a, T> {
+    fn fmt(&self, f : &mut fmt::Formatter) -> fmt::Result {
+        format_nddata(self, f, 0)
+    }
+}
+
+impl<T : Display> fmt::Debug for NDArray<T> {
+    fn fmt(&self, f : &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "NDArray(")?;
+        format_nddata(self, f, "NDArray(".len())?;
+        write!(f, ", shape={:?})", self.shape())
+    }
+}
+
+impl<'a, T : Display> fmt::Debug for NDSlice<'a, T> {
+    fn fmt(&self, f : &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "NDSlice(")?;
+        format_nddata(self, f, "NDSlice(".len())?;
+        write!(f, ", shape={:?})", self.shape())
+    }
+}
+
+impl<'a, T : Display> fmt::Debug for NDSliceMut<'a, T> {
+    fn fmt(&self, f : &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "NDSliceMut(")?;
+        format_nddata(self, f, "NDSliceMut(".len())?;
+        write!(f, ", shape={:?})", self.shape())
+    }
+}
+
+impl<'a, T : Display> fmt::Debug for NDView<'a, T> {
+    fn fmt(&self, f : &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "NDView(")?;
+        format_nddata(self, f, "NDView(".len())?;
+        write!(f, ", shape={:?})", self.shape())
+    }
+}
+
+impl<'a, T : Display> fmt::Debug for NDViewMut<'a, T> {
+    fn fmt(&self, f : &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "NDViewMut(")?;
+        format_nddata(self, f, "NDViewMut(".len())?;
+        write!(f, ", shape={:?})", self.shape())
+    }
+}
@@ -1,4 +1,3 @@
-use std::error::Error;
 use std::fs::File;
 use std::io::{Read, Seek, SeekFrom};
 use std::iter::repeat;
@@ -8,12 +7,24 @@ use std::str::FromStr;
 
 use types::cast::Cast;
 use array::NDArray;
+use error::Error;
 
 /// Structure representing an EDFFile.
 pub struct EDFFile {
     path : String,
 }
 
+/// Parse an ASCII header field containing an unsigned integer.
+fn parse_field(field : &[u8], name : &str) -> Result<usize, Error> {
+    match str::from_utf8(field) {
+        Ok(s) => match usize::from_str(s.trim()) {
+            Ok(v) => Ok(v),
+            Err(e) => Err(Error::Parse { message : format!("Failed to parse EDF header field {}: '{}'", name, s.trim()), source : Some(Box::new(e)) })
+        },
+        Err(e) => Err(Error::Parse { message : format!("EDF header field {} is not valid ASCII", name), source : Some(Box::new(e)) })
+    }
+}
+
 impl EDFFile {
     
     /// Allocate a new EDFFile structure with a given path. This function neither create nor open 
@@ -25,65 +36,48 @@ impl EDFFile {
     }
 
     /// Read signal number id (id starting at 0) from the EDFFile into an one dimension NDArray.
-    pub fn read_signal<T : Copy>(&self, id : usize) -> Result<NDArray<T>,String> where i16 : Cast<T>{
+    /// In case of failure, returns the error as an `Error`.
+    pub fn read_signal<T : Copy>(&self, id : usize) -> Result<NDArray<T>, Error> where i16 : Cast<T>{
         let mut header = [0u8;256];
         let mut signal = Vec::<T>::new();
 
         let mut reader = match File::open(&self.path[..]) {
             Ok(r) => r,
-            Err(e) => return Err(e.description().to_string())
+            Err(e) => return Err(Error::Io(e))
         };
 
         // Read main header and extract the number of signals
         if let Err(e) = reader.read_exact(&mut header) {
-            return Err(e.description().to_string());
+            return Err(Error::Io(e));
         }
-        let num_signal = match str::from_utf8(&header[252..256]) {
-            Ok(s) => match usize::from_str(s.trim()) {
-                Ok(v) =>v,
-                Err(e) => return Err(e.description().to_string())
-            },
-            Err(e) => return Err(e.description().to_string())
-        };
+        let num_signal = parse_field(&header[252..256], "number of signals")?;
         if id >= num_signal {
-            return Err(format!("EDFFile::read_signal(): The signal id is greater than the number of signal of the file {} ({} >= {})", self.path, id, num_signal));
+            return Err(Error::Format(format!("EDFFile::read_signal(): The signal id is greater than the number of signal of the file {} ({} >= {})", self.path, id, num_signal)));
         }
-        let num_record = match str::from_utf8(&header[236..244]) {
-            Ok(s) => match usize::from_str(s.trim()) {
-                Ok(v) =>v,
-                Err(e) => return Err(e.description().to_string())
-            },
-            Err(e) => return Err(e.description().to_string())
-        };
+        let num_record = parse_field(&header[236..244], "number of data records")?;
 
         // Read each signal header and extract their number of samples
         let mut num_samples = Vec::<usize>::new();
         if let Err(e) = reader.seek(SeekFrom::Current(num_signal as i64 * 216)) {
-            return Err(e.description().to_string());
+            return Err(Error::Io(e));
         }
         for _ in 0..num_signal {
             let mut buffer = [0u8;8];
             if let Err(e) = reader.read_exact(&mut buffer) {
-                return Err(e.description().to_string());
+                return Err(Error::Io(e));
             }
-            num_samples.push(match str::from_utf8(&buffer) {
-                Ok(s) => match usize::from_str(s.trim()) {
-                    Ok(v) =>v,
-                    Err(e) => return Err(e.description().to_string())
-                },
-                Err(e) => return Err(e.description().to_string())
-            });
+            num_samples.push(parse_field(&buffer, "number of samples")?);
         }
 
         if let Err(e) = reader.seek(SeekFrom::Current(num_signal as i64 * 32)) {
-            return Err(e.description().to_string());
+            return Err(Error::Io(e));
         }
         let mut data_record : Vec<u8> = repeat(0u8).take(num_samples[id] * 2).collect();
         for _ in 0..num_record {
             for i in 0..num_signal {
                 if id == i {
                     if let Err(e) = reader.read_exact(&mut data_record[..]) {
-                        return Err(e.description().to_string());
+                        return Err(Error::Io(e));
                     }
                     unsafe {
                         let transmuted : &[i16]= slice::from_raw_parts(data_record.as_ptr() as *const i16, num_samples[id]);
@@ -94,7 +88,7 @@ impl EDFFile {
                 }
                 else {
                     if let Err(e) = reader.seek(SeekFrom::Current(num_samples[i] as i64 * 2)) {
-                        return Err(e.description().to_string());
+                        return Err(Error::Io(e));
                     }
                 }
             }
@@ -0,0 +1,267 @@
+use std::fmt::Display;
+
+use types::complex::{c32, c64};
+use array::{NDData, NDArray, NDView, NDViewable};
+use array::ndindex::NDIndex;
+use array::reduce::Reducible;
+use blas::{Blas, Transpose};
+use error::Error;
+use parallel;
+
+/// Trait implemented by every RDS type to select how the contractions of two operands are
+/// computed. Floating point and complex types use Blas (dot, gemv or gemm) while other types use
+/// the generic loop.
+pub trait Einsum : Reducible {
+
+    /// Compute the product of a and b, which are either vectors of length k or matrices of shape
+    /// [m, k] for a and [k, n] for b. Return None if the type is not supported by Blas.
+    fn blas_product(a : &NDArray<Self>, b : &NDArray<Self>) -> Option<NDArray<Self>>;
+}
+
+macro_rules! loop_einsum {
+    ($($t:ty),*) => {
+        $(
+            impl Einsum for $t {
+
+                fn blas_product(_ : &NDArray<$t>, _ : &NDArray<$t>) -> Option<NDArray<$t>> {
+                    None
+                }
+            }
+        )*
+    }
+}
+
+macro_rules! blas_einsum {
+    ($($t:ty),*) => {
+        $(
+            impl Einsum for $t {
+
+                fn blas_product(a : &NDArray<$t>, b : &NDArray<$t>) -> Option<NDArray<$t>> {
+                    Some(product(a, b))
+                }
+            }
+        )*
+    }
+}
+
+loop_einsum!(u8, u16, u32, u64, i8, i16, i32, i64);
+blas_einsum!(f32, f64, c32, c64);
+
+/// Compute the product of a and b with dot, gemv or gemm depending on their dimensions.
+fn product<T : Reducible + Display>(a : &NDArray<T>, b : &NDArray<T>) -> NDArray<T> where NDArray<T> : Blas<T> {
+    match (a.dim(), b.dim()) {
+        (1, 1) => NDArray::<T>::new(&[], a.dot(b)),
+        (2, 1) => {
+            let mut y = NDArray::<T>::new(&[a.shape()[0]], T::zero());
+            y.gemv(T::one(), a, b, T::zero());
+            y
+        },
+        (1, 2) => {
+            let mut y = NDArray::<T>::new(&[b.shape()[1]], T::zero());
+            y.gemv_trans(T::one(), b, Transpose::Trans, a, T::zero());
+            y
+        },
+        _ => {
+            let mut c = NDArray::<T>::new(&[a.shape()[0], b.shape()[1]], T::zero());
+            c.gemm_trans(T::one(), a, Transpose::NoTrans, b, Transpose::NoTrans, T::zero());
+            c
+        }
+    }
+}
+
+/// Parsed subscripts: the labels of every axis of the operands and of the output.
+struct Subscripts {
+    inputs : Vec<Vec<char>>,
+    output : Vec<char>,
+}
+
+impl Subscripts {
+
+    /// Parse subscripts such as "ij,jk->ik". Without an explicit output, the output labels are
+    /// those appearing only once, in alphabetical order.
+    fn parse(subscripts : &str, n : usize) -> Result<Subscripts, Error> {
+        let subscripts : String = subscripts.chars().filter(|c| !c.is_whitespace()).collect();
+        let (inputs, output) = match subscripts.find("->") {
+            Some(pos) => (&subscripts[..pos], Some(&subscripts[pos + 2..])),
+            None => (&subscripts[..], None),
+        };
+        let inputs : Vec<Vec<char>> = inputs.split(',').map(|s| s.chars().collect()).collect();
+        if inputs.len() != n {
+            return Err(Error::InvalidArgument(format!("einsum subscripts '{}' describe {} operands but {} were given", subscripts, inputs.len(), n)));
+        }
+        for &c in inputs.iter().flat_map(|labels| labels.iter()) {
+            if !c.is_ascii_alphabetic() {
+                return Err(Error::Parse { message : format!("Invalid label '{}' in einsum subscripts '{}'", c, subscripts), source : None });
+            }
+        }
+        let output : Vec<char> = match output {
+            Some(output) => output.chars().collect(),
+            None => {
+                let mut output : Vec<char> = Vec::new();
+                for &c in inputs.iter().flat_map(|labels| labels.iter()) {
+                    if inputs.iter().flat_map(|labels| labels.iter()).filter(|&&l| l == c).count() == 1 {
+                        output.push(c);
+                    }
+                }
+                output.sort();
+                output
+            }
+        };
+        for (i, &c) in output.iter().enumerate() {
+            if !inputs.iter().any(|labels| labels.contains(&c)) {
+                return Err(Error::Parse { message : format!("Output label '{}' of einsum subscripts '{}' is not in the inputs", c, subscripts), source : None });
+            }
+            if output[..i].contains(&c) {
+                return Err(Error::Parse { message : format!("Output label '{}' is repeated in einsum subscripts '{}'", c, subscripts), source : None });
+            }
+        }
+        Ok(Subscripts {
+            inputs : inputs,
+            output : output,
+        })
+    }
+
+    /// Check the dimension of every operand and that the axes sharing a label have the same
+    /// length, then return the labels in order of first appearance together with their length.
+    fn label_sizes<T>(&self, operands : &[&NDData<T>]) -> Result<Vec<(char, usize)>, Error> {
+        let mut sizes : Vec<(char, usize)> = Vec::new();
+        for (labels, operand) in self.inputs.iter().zip(operands) {
+            if labels.len() != operand.dim() {
+                return Err(Error::DimensionMismatch { expected : labels.len(), found : operand.dim() });
+            }
+            let mut expected = operand.shape().to_vec();
+            for (axis, &c) in labels.iter().enumerate() {
+                match sizes.iter().find(|&&(l, _)| l == c) {
+                    Some(&(_, size)) => expected[axis] = size,
+                    None => sizes.push((c, operand.shape()[axis])),
+                }
+            }
+            if expected != operand.shape() {
+                return Err(Error::ShapeMismatch { expected : expected, found : operand.shape().to_vec() });
+            }
+        }
+        Ok(sizes)
+    }
+}
+
+/// Return the length of label c.
+fn size_of(sizes : &[(char, usize)], c : char) -> usize {
+    sizes.iter().find(|&&(l, _)| l == c).unwrap().1
+}
+
+/// Return the strides with which each label moves through data, labels repeated in data (such as
+/// the diagonal "ii") accumulate the strides of their axes.
+fn label_strides<T>(data : &NDData<T>, labels : &[char], select : &[char]) -> Vec<usize> {
+    select.iter().map(|&c| {
+        labels.iter().zip(data.strides()).filter(|&(&l, _)| l == c).fold(0usize, |acc, (_, &s)| acc.wrapping_add(s))
+    }).collect()
+}
+
+/// Contract the operands with an explicit loop over every label, in parallel over the output
+/// elements for the RDS types.
+fn contract<T : Reducible>(subscripts : &Subscripts, sizes : &[(char, usize)], operands : &[&NDData<T>]) -> NDArray<T> {
+    let summed : Vec<char> = sizes.iter().map(|&(c, _)| c).filter(|c| !subscripts.output.contains(c)).collect();
+    let shape : Vec<usize> = subscripts.output.iter().map(|&c| size_of(sizes, c)).collect();
+    let sum_shape : Vec<usize> = summed.iter().map(|&c| size_of(sizes, c)).collect();
+    let size = shape.iter().fold(1usize, |acc, &x| acc * x);
+    let sum_size = sum_shape.iter().fold(1usize, |acc, &x| acc * x);
+    let views : Vec<NDView<T>> = operands.iter().map(|op| op.view(&[])).collect();
+    let strides : Vec<Vec<usize>> = views.iter().zip(&subscripts.inputs).map(|(v, labels)| label_strides(v, labels, &subscripts.output)).collect();
+    let sum_strides : Vec<Vec<usize>> = views.iter().zip(&subscripts.inputs).map(|(v, labels)| label_strides(v, labels, &summed)).collect();
+
+    // The views only borrow elements of type T and the chunks hold T values
+    let chunks = unsafe { parallel::map_ranges_of::<T, _, _>(size, sum_size, |range| {
+        let mut chunk = Vec::<T>::with_capacity(range.end - range.start);
+        let mut idx = parallel::ro_index(range.start, &shape[..]);
+        for _ in range {
+            let base : Vec<usize> = views.iter().zip(&strides).map(|(v, s)| v.offset().wrapping_add(idx.to_pos(&shape[..], s))).collect();
+            let mut acc = T::zero();
+            let mut sum_idx = vec![0usize; summed.len()];
+            for _ in 0..sum_size {
+                let mut prod = T::one();
+                for k in 0..views.len() {
+                    prod = prod * views[k].get_data()[base[k].wrapping_add(sum_idx.to_pos(&sum_shape[..], &sum_strides[k]))];
+                }
+                acc = acc + prod;
+                sum_idx.inc_ro(&sum_shape[..]);
+            }
+            chunk.push(acc);
+            idx.inc_ro(&shape[..]);
+        }
+        chunk
+    }) };
+    let mut data = Vec::<T>::with_capacity(size);
+    for chunk in chunks {
+        data.extend(chunk);
+    }
+    NDArray {
+        strides : NDArray::<T>::compute_strides(&shape[..]),
+        shape : shape,
+        data : data.into_boxed_slice(),
+    }
+}
+
+/// Lower the contraction of two operands to a product of matrices, vectors or both. Return None
+/// if the contraction has labels shared with the output (batch dimensions), repeated labels
+/// within an operand, labels summed within a single operand, empty axes, or if the type is not
+/// supported by Blas.
+fn lower<T : Einsum>(subscripts : &Subscripts, sizes : &[(char, usize)], a : &NDData<T>, b : &NDData<T>) -> Option<NDArray<T>> {
+    let (la, lb, out) = (&subscripts.inputs[0], &subscripts.inputs[1], &subscripts.output);
+    for labels in &[la, lb] {
+        for (i, c) in labels.iter().enumerate() {
+            if labels[..i].contains(c) {
+                return None;
+            }
+        }
+    }
+    if sizes.iter().any(|&(_, size)| size == 0) {
+        return None;
+    }
+    let m : Vec<char> = la.iter().cloned().filter(|c| !lb.contains(c)).collect();
+    let n : Vec<char> = lb.iter().cloned().filter(|c| !la.contains(c)).collect();
+    let k : Vec<char> = la.iter().cloned().filter(|c| lb.contains(c)).collect();
+    if k.iter().any(|c| out.contains(c)) || m.iter().chain(n.iter()).any(|c| !out.contains(c)) {
+        return None;
+    }
+
+    let (ms, ns, ks) = (m.iter().fold(1, |acc, &c| acc * size_of(sizes, c)), n.iter().fold(1, |acc, &c| acc * size_of(sizes, c)), k.iter().fold(1, |acc, &c| acc * size_of(sizes, c)));
+    let axes = |labels : &Vec<char>, order : &Vec<char>| -> Vec<usize> { order.iter().map(|c| labels.iter().position(|l| l == c).unwrap()).collect() };
+    let mut a2 = NDArray::<T>::copy(&a.permute_axes(&axes(la, &m.iter().chain(k.iter()).cloned().collect())[..]));
+    let mut b2 = NDArray::<T>::copy(&b.permute_axes(&axes(lb, &k.iter().chain(n.iter()).cloned().collect())[..]));
+    a2.reshape(&if m.is_empty() { vec![ks] } else { vec![ms, ks] }[..]);
+    b2.reshape(&if n.is_empty() { vec![ks] } else { vec![ks, ns] }[..]);
+
+    let mut c = T::blas_product(&a2, &b2)?;
+    let mn : Vec<char> = m.iter().chain(n.iter()).cloned().collect();
+    c.reshape(&mn.iter().map(|&l| size_of(sizes, l)).collect::<Vec<usize>>()[..]);
+    Some(NDArray::<T>::copy(&c.permute_axes(&axes(&mn, out)[..])))
+}
+
+/// Evaluate the Einstein summation convention described by subscripts over the operands.
+/// Subscripts label each axis of each operand with a letter, operands being separated by commas,
+/// and optionally the output after "->": "ij,jk->ik" is a matrix product, "ii->" a trace and
+/// "ij->ji" a transposition. Labels absent from the output are summed over. Without "->", the
+/// output has the labels appearing only once, in alphabetical order.
+///
+/// Contractions of two operands are lowered to `dot`, `gemv` or `gemm` for floating point and
+/// complex types when the operands don't share batch dimensions with the output, other cases use
+/// a generic loop.
+pub fn einsum<T : Einsum>(subscripts : &str, operands : &[&NDData<T>]) -> NDArray<T> {
+    match try_einsum(subscripts, operands) {
+        Ok(result) => result,
+        Err(e) => panic!("einsum(\"{}\"): {}", subscripts, e),
+    }
+}
+
+/// Fallible version of einsum: return an error if the subscripts can't be parsed or don't match
+/// the number, the dimensions or the shapes of the operands.
+pub fn try_einsum<T : Einsum>(subscripts : &str, operands : &[&NDData<T>]) -> Result<NDArray<T>, Error> {
+    let subscripts = Subscripts::parse(subscripts, operands.len())?;
+    let sizes = subscripts.label_sizes(operands)?;
+    if operands.len() == 2 {
+        if let Some(result) = lower(&subscripts, &sizes, operands[0], operands[1]) {
+            return Ok(result);
+        }
+    }
+    Ok(contract(&subscripts, &sizes, operands))
+}
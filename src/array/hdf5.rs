@@ -0,0 +1,1841 @@
+extern crate byteorder;
+extern crate flate2;
+
+use std::cmp::{max, min};
+use std::fmt::Display;
+use std::fs::{File, OpenOptions};
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::str;
+
+use self::byteorder::{ByteOrder, BigEndian, LittleEndian};
+use self::flate2::Compression;
+use self::flate2::read::ZlibDecoder;
+use self::flate2::write::ZlibEncoder;
+
+use types::{RDSType, RDSTyped};
+use types::complex::{c32, c64};
+use types::cast::Cast;
+use array::{NDArray, NDData, NDDataMut};
+use array::ndindex::NDIndex;
+use array::iter::NDIterable;
+use array::numpy::Endianess;
+use error::Error;
+
+const HDF5_MAGIC : [u8;8] = [0x89u8, b'H', b'D', b'F', b'\r', b'\n', 0x1a, b'\n'];
+const UNDEFINED_ADDRESS : u64 = !0u64;
+const SUPERBLOCK_SIZE : usize = 96;
+const GROUP_LEAF_K : usize = 4;
+const GROUP_INTERNAL_K : usize = 16;
+const CHUNK_K : usize = 32;
+
+const MSG_DATASPACE : u16 = 0x01;
+const MSG_LINK_INFO : u16 = 0x02;
+const MSG_DATATYPE : u16 = 0x03;
+const MSG_FILL_VALUE : u16 = 0x05;
+const MSG_LINK : u16 = 0x06;
+const MSG_LAYOUT : u16 = 0x08;
+const MSG_FILTER_PIPELINE : u16 = 0x0b;
+const MSG_ATTRIBUTE : u16 = 0x0c;
+const MSG_CONTINUATION : u16 = 0x10;
+const MSG_SYMBOL_TABLE : u16 = 0x11;
+
+const FILTER_DEFLATE : u16 = 1;
+const FILTER_SHUFFLE : u16 = 2;
+const FILTER_FLETCHER32 : u16 = 3;
+
+/// Structure representing an HDF5 file (.h5). Objects are designated by their absolute path
+/// from the root group, for example "/group/dataset".
+///
+/// An HDF5File writes a new file, and fails if the file already exists. The raw data of each
+/// dataset is appended to the file as soon as it is written, while the groups, object headers and
+/// attributes are only written by `close`, or when the HDF5File is dropped: the file can not be
+/// read before. A dataset replaced by another one at the same path leaves its raw data unused in
+/// the file.
+pub struct HDF5File {
+    path : String,
+    file : Option<File>,
+    end : u64,
+    objects : Vec<Object>,
+    /// The shape of the chunks of the datasets written, None (the default) for a contiguous 
+    /// layout. Chunk dimensions larger than the dataset are reduced to the dataset dimensions.
+    pub chunk_shape : Option<Vec<usize>>,
+    /// The deflate compression level, from 0 to 9, of the datasets written, None (the default) 
+    /// for no compression. Compressed datasets without a chunk_shape are stored in a single chunk.
+    pub compression : Option<u32>,
+}
+
+/// Little-endian cursor over the metadata of an HDF5 file, using the size of offsets and lengths
+/// declared in the superblock.
+struct Cursor<'a> {
+    data : &'a [u8],
+    pos : usize,
+    offset_size : usize,
+    length_size : usize,
+}
+
+/// A message of an object header.
+struct Message {
+    kind : u16,
+    flags : u8,
+    data : Vec<u8>,
+}
+
+/// Element type of a dataset.
+struct Datatype {
+    dtype : RDSType,
+    endianess : Endianess,
+    size : usize,
+}
+
+/// Storage layout of the raw data of a dataset.
+enum Layout<'a> {
+    Compact(&'a [u8]),
+    Contiguous(u64),
+    Chunked(u64, Vec<usize>),
+}
+
+/// A filter of the pipeline applied to the chunks of a dataset.
+struct Filter {
+    id : u16,
+    values : Vec<u32>,
+}
+
+/// A chunk of a dataset, as indexed by the chunk B-tree.
+struct Chunk {
+    address : u64,
+    size : usize,
+    filter_mask : u32,
+    offsets : Vec<usize>,
+}
+
+/// An HDF5 file opened for reading together with the parameters of its superblock. Only the
+/// structures and raw data needed are read, at their address.
+struct Reader {
+    file : File,
+    len : u64,
+    base : u64,
+    offset_size : usize,
+    length_size : usize,
+    root : u64,
+}
+
+/// A dataset header: its shape, datatype, layout and filter pipeline.
+struct Dataset<'a> {
+    shape : Vec<usize>,
+    datatype : Datatype,
+    layout : Layout<'a>,
+    filters : Vec<Filter>,
+}
+
+/// An attribute message of an object header.
+struct Attribute<'a> {
+    name : String,
+    datatype : &'a [u8],
+    data : &'a [u8],
+}
+
+/// Location of the raw data of a dataset written with an HDF5File: the address and size of a
+/// contiguous block, or the chunk shape with the B-tree keys and addresses of the chunks.
+enum Storage {
+    Contiguous(u64, usize),
+    Chunked(Vec<usize>, Vec<Vec<u8>>, Vec<u64>),
+}
+
+/// A dataset written with an HDF5File, whose raw data is already stored in the file.
+struct StoredDataset {
+    shape : Vec<usize>,
+    dtype : RDSType,
+    storage : Storage,
+    compression : Option<u32>,
+}
+
+/// A group or dataset written with an HDF5File, with its encoded attribute messages.
+struct Object {
+    path : Vec<String>,
+    dataset : Option<StoredDataset>,
+    attributes : Vec<(String, Vec<u8>)>,
+}
+
+/// Serializer of the structures appended to the file at base by an HDF5File. It only uses the
+/// structures of the original file format (superblock version 0, object header version 1 and
+/// symbol table groups) which every version of the HDF5 library can read.
+struct Writer {
+    buf : Vec<u8>,
+    base : u64,
+}
+
+impl<'a> Cursor<'a> {
+
+    fn new(data : &'a [u8], pos : usize, offset_size : usize, length_size : usize) -> Cursor<'a> {
+        Cursor {
+            data : data,
+            pos : pos,
+            offset_size : offset_size,
+            length_size : length_size,
+        }
+    }
+
+    fn bytes(&mut self, n : usize) -> Result<&'a [u8], Error> {
+        if n > self.data.len() || self.pos > self.data.len() - n {
+            return Err(Error::Format(format!("HDF5 structure at offset {} extends past the end of the file", self.pos)));
+        }
+        let bytes = &self.data[self.pos..self.pos + n];
+        self.pos += n;
+        Ok(bytes)
+    }
+
+    fn skip(&mut self, n : usize) -> Result<(), Error> {
+        self.bytes(n).map(|_| ())
+    }
+
+    fn uint(&mut self, n : usize) -> Result<u64, Error> {
+        let bytes = self.bytes(n)?;
+        Ok(bytes.iter().rev().fold(0u64, |acc, &b| (acc << 8) | b as u64))
+    }
+
+    fn u8(&mut self) -> Result<u8, Error> {
+        self.uint(1).map(|v| v as u8)
+    }
+
+    fn u16(&mut self) -> Result<u16, Error> {
+        self.uint(2).map(|v| v as u16)
+    }
+
+    fn u32(&mut self) -> Result<u32, Error> {
+        self.uint(4).map(|v| v as u32)
+    }
+
+    fn address(&mut self) -> Result<u64, Error> {
+        let bytes = self.bytes(self.offset_size)?;
+        if bytes.iter().all(|&b| b == 0xff) {
+            return Ok(UNDEFINED_ADDRESS);
+        }
+        Ok(bytes.iter().rev().fold(0u64, |acc, &b| (acc << 8) | b as u64))
+    }
+
+    fn length(&mut self) -> Result<u64, Error> {
+        let n = self.length_size;
+        self.uint(n)
+    }
+
+    fn signature(&mut self, signature : &[u8], structure : &str) -> Result<(), Error> {
+        let pos = self.pos;
+        if self.bytes(signature.len())? != signature {
+            return Err(Error::Format(format!("Invalid HDF5 {} signature at offset {}", structure, pos)));
+        }
+        Ok(())
+    }
+
+    fn string(&mut self) -> Result<String, Error> {
+        let len = match self.data[self.pos.min(self.data.len())..].iter().position(|&b| b == 0) {
+            Some(l) => l,
+            None => return Err(Error::Format(format!("Unterminated HDF5 string at offset {}", self.pos)))
+        };
+        let bytes = self.bytes(len + 1)?;
+        match str::from_utf8(&bytes[..len]) {
+            Ok(s) => Ok(s.to_string()),
+            Err(e) => Err(Error::Parse { message : format!("HDF5 string at offset {} is not valid UTF-8", self.pos - len - 1), source : Some(Box::new(e)) })
+        }
+    }
+}
+
+fn usize_field(v : u64) -> Result<usize, Error> {
+    if v > usize::max_value() as u64 {
+        return Err(Error::Format(format!("HDF5 field value {} does not fit in memory", v)));
+    }
+    Ok(v as usize)
+}
+
+/// Parse a datatype message, recursing in the members of compound types. The only supported
+/// compound types are complex numbers made of two floating point members.
+fn parse_datatype(c : &mut Cursor) -> Result<Datatype, Error> {
+    let class_version = c.u8()?;
+    let class = class_version & 0xf;
+    let version = class_version >> 4;
+    let bits = c.bytes(3)?;
+    let size = c.u32()? as usize;
+    let endianess = if bits[0] & 0x1 == 0 { Endianess::LittleEndian } else { Endianess::BigEndian };
+
+    let dtype = match class {
+        // Fixed-point: bit offset and bit precision
+        0 => {
+            c.skip(4)?;
+            match (size, bits[0] & 0x8 != 0) {
+                (1, false) => RDSType::U8,
+                (2, false) => RDSType::U16,
+                (4, false) => RDSType::U32,
+                (8, false) => RDSType::U64,
+                (1, true) => RDSType::I8,
+                (2, true) => RDSType::I16,
+                (4, true) => RDSType::I32,
+                (8, true) => RDSType::I64,
+                _ => return Err(Error::UnsupportedType(format!("HDF5 {} bytes integer", size)))
+            }
+        },
+        // Floating-point: bit offset, bit precision, exponent and mantissa location and size,
+        // exponent bias
+        1 => {
+            c.skip(12)?;
+            if bits[0] & 0x40 != 0 {
+                return Err(Error::UnsupportedType("HDF5 VAX floating point".to_string()));
+            }
+            match size {
+                4 => RDSType::F32,
+                8 => RDSType::F64,
+                _ => return Err(Error::UnsupportedType(format!("HDF5 {} bytes floating point", size)))
+            }
+        },
+        // Compound
+        6 => {
+            let num_members = bits[0] as usize + ((bits[1] as usize) << 8);
+            let mut members = Vec::<(usize, Datatype)>::new();
+            for _ in 0..num_members {
+                let name = c.string()?;
+                let offset = if version < 3 {
+                    c.skip((name.len() + 1).wrapping_neg() % 8)?;
+                    c.u32()? as usize
+                }
+                else {
+                    let n = if size < 1 << 8 { 1 } else if size < 1 << 16 { 2 } else if size < 1 << 24 { 3 } else { 4 };
+                    c.uint(n)? as usize
+                };
+                if version == 1 {
+                    // Dimensionality, reserved, dimension permutation, reserved and dimension sizes
+                    c.skip(28)?;
+                }
+                members.push((offset, parse_datatype(c)?));
+            }
+            let complex = if members.len() == 2 && members[0].0 == 0 && members[1].0 == size / 2 {
+                match (&members[0].1.dtype, &members[1].1.dtype, size) {
+                    (&RDSType::F32, &RDSType::F32, 8) => Some(RDSType::C32),
+                    (&RDSType::F64, &RDSType::F64, 16) => Some(RDSType::C64),
+                    _ => None
+                }
+            }
+            else {
+                None
+            };
+            match complex {
+                Some(dtype) => {
+                    let member = members.swap_remove(0).1;
+                    return Ok(Datatype {
+                        dtype : dtype,
+                        endianess : member.endianess,
+                        size : size,
+                    });
+                },
+                None => return Err(Error::UnsupportedType(format!("HDF5 compound type with {} members which is not a complex number", num_members)))
+            }
+        },
+        _ => return Err(Error::UnsupportedType(format!("HDF5 datatype class {}", class)))
+    };
+
+    Ok(Datatype {
+        dtype : dtype,
+        endianess : endianess,
+        size : size,
+    })
+}
+
+/// Parse a dataspace message and return the shape. Scalar dataspaces have an empty shape.
+fn parse_dataspace(c : &mut Cursor) -> Result<Vec<usize>, Error> {
+    let version = c.u8()?;
+    let rank = c.u8()? as usize;
+    c.skip(1)?;
+    match version {
+        1 => c.skip(5)?,
+        2 => {
+            // Null dataspace, without any element
+            if c.u8()? == 2 {
+                return Ok(vec![0]);
+            }
+        },
+        _ => return Err(Error::Format(format!("HDF5 dataspace message version {} not supported", version)))
+    }
+    let mut shape = Vec::<usize>::with_capacity(rank);
+    for _ in 0..rank {
+        shape.push(usize_field(c.length()?)?);
+    }
+    Ok(shape)
+}
+
+fn parse_layout<'a>(c : &mut Cursor<'a>) -> Result<Layout<'a>, Error> {
+    let version = c.u8()?;
+    match version {
+        1 | 2 => {
+            let dimensionality = c.u8()? as usize;
+            let class = c.u8()?;
+            c.skip(5)?;
+            let address = if class != 0 { c.address()? } else { UNDEFINED_ADDRESS };
+            let mut dims = Vec::<usize>::with_capacity(dimensionality);
+            for _ in 0..dimensionality {
+                dims.push(c.u32()? as usize);
+            }
+            match class {
+                0 => {
+                    let size = c.u32()? as usize;
+                    Ok(Layout::Compact(c.bytes(size)?))
+                },
+                1 => Ok(Layout::Contiguous(address)),
+                2 => {
+                    // The last dimension is the size of an element
+                    dims.pop();
+                    Ok(Layout::Chunked(address, dims))
+                },
+                _ => Err(Error::Format(format!("Unknown HDF5 layout class {}", class)))
+            }
+        },
+        3 | 4 => {
+            let class = c.u8()?;
+            match class {
+                0 => {
+                    let size = c.u16()? as usize;
+                    Ok(Layout::Compact(c.bytes(size)?))
+                },
+                1 => Ok(Layout::Contiguous(c.address()?)),
+                2 if version == 3 => {
+                    let dimensionality = c.u8()? as usize;
+                    let address = c.address()?;
+                    let mut dims = Vec::<usize>::with_capacity(dimensionality);
+                    for _ in 0..dimensionality {
+                        dims.push(c.u32()? as usize);
+                    }
+                    dims.pop();
+                    Ok(Layout::Chunked(address, dims))
+                },
+                2 => Err(Error::Format("HDF5 chunk indexes of layout message version 4 are not supported".to_string())),
+                _ => Err(Error::Format(format!("Unknown HDF5 layout class {}", class)))
+            }
+        },
+        _ => Err(Error::Format(format!("HDF5 layout message version {} not supported", version)))
+    }
+}
+
+fn parse_filters(c : &mut Cursor) -> Result<Vec<Filter>, Error> {
+    let version = c.u8()?;
+    let num_filters = c.u8()? as usize;
+    if version == 1 {
+        c.skip(6)?;
+    }
+    let mut filters = Vec::<Filter>::with_capacity(num_filters);
+    for _ in 0..num_filters {
+        let id = c.u16()?;
+        let name_len = if version == 1 || id >= 256 { c.u16()? as usize } else { 0 };
+        c.skip(2)?;
+        let num_values = c.u16()? as usize;
+        c.skip(name_len)?;
+        let mut values = Vec::<u32>::with_capacity(num_values);
+        for _ in 0..num_values {
+            values.push(c.u32()?);
+        }
+        if version == 1 && num_values % 2 == 1 {
+            c.skip(4)?;
+        }
+        filters.push(Filter {
+            id : id,
+            values : values,
+        });
+    }
+    Ok(filters)
+}
+
+/// Revert the filters of the pipeline, in reverse order, on a chunk. Filters whose bit is set in
+/// filter_mask were not applied to this chunk.
+fn unfilter(mut data : Vec<u8>, filters : &[Filter], filter_mask : u32, element_size : usize) -> Result<Vec<u8>, Error> {
+    for (i, filter) in filters.iter().enumerate().rev() {
+        if i < 32 && filter_mask & (1 << i) != 0 {
+            continue;
+        }
+        data = match filter.id {
+            FILTER_DEFLATE => {
+                let mut inflated = Vec::<u8>::new();
+                if let Err(e) = ZlibDecoder::new(&data[..]).read_to_end(&mut inflated) {
+                    return Err(Error::Format(format!("Failed to inflate HDF5 chunk: {}", e)));
+                }
+                inflated
+            },
+            FILTER_SHUFFLE => {
+                let size = filter.values.first().map(|&v| v as usize).unwrap_or(element_size);
+                if size <= 1 {
+                    data
+                }
+                else {
+                    let n = data.len() / size;
+                    let mut unshuffled = data.clone();
+                    for i in 0..n {
+                        for j in 0..size {
+                            unshuffled[i * size + j] = data[j * n + i];
+                        }
+                    }
+                    unshuffled
+                }
+            },
+            FILTER_FLETCHER32 => {
+                if data.len() < 4 {
+                    return Err(Error::Format("HDF5 chunk too small for its fletcher32 checksum".to_string()));
+                }
+                let len = data.len() - 4;
+                data.truncate(len);
+                data
+            },
+            id => return Err(Error::UnsupportedType(format!("HDF5 filter {}", id)))
+        };
+    }
+    Ok(data)
+}
+
+fn decode_u8<T : Copy>(b : &[u8]) -> T where u8 : Cast<T> {
+    Cast::<T>::cast(b[0])
+}
+
+fn decode_u16<T : Copy, B : ByteOrder>(b : &[u8]) -> T where u16 : Cast<T> {
+    Cast::<T>::cast(B::read_u16(b))
+}
+
+fn decode_u32<T : Copy, B : ByteOrder>(b : &[u8]) -> T where u32 : Cast<T> {
+    Cast::<T>::cast(B::read_u32(b))
+}
+
+fn decode_u64<T : Copy, B : ByteOrder>(b : &[u8]) -> T where u64 : Cast<T> {
+    Cast::<T>::cast(B::read_u64(b))
+}
+
+fn decode_i8<T : Copy>(b : &[u8]) -> T where i8 : Cast<T> {
+    Cast::<T>::cast(b[0] as i8)
+}
+
+fn decode_i16<T : Copy, B : ByteOrder>(b : &[u8]) -> T where i16 : Cast<T> {
+    Cast::<T>::cast(B::read_i16(b))
+}
+
+fn decode_i32<T : Copy, B : ByteOrder>(b : &[u8]) -> T where i32 : Cast<T> {
+    Cast::<T>::cast(B::read_i32(b))
+}
+
+fn decode_i64<T : Copy, B : ByteOrder>(b : &[u8]) -> T where i64 : Cast<T> {
+    Cast::<T>::cast(B::read_i64(b))
+}
+
+fn decode_f32<T : Copy, B : ByteOrder>(b : &[u8]) -> T where f32 : Cast<T> {
+    Cast::<T>::cast(B::read_f32(b))
+}
+
+fn decode_f64<T : Copy, B : ByteOrder>(b : &[u8]) -> T where f64 : Cast<T> {
+    Cast::<T>::cast(B::read_f64(b))
+}
+
+fn decode_c32<T : Copy, B : ByteOrder>(b : &[u8]) -> T where c32 : Cast<T> {
+    Cast::<T>::cast(c32::new(B::read_f32(&b[0..4]), B::read_f32(&b[4..8])))
+}
+
+fn decode_c64<T : Copy, B : ByteOrder>(b : &[u8]) -> T where c64 : Cast<T> {
+    Cast::<T>::cast(c64::new(B::read_f64(&b[0..8]), B::read_f64(&b[8..16])))
+}
+
+/// Decode the consecutive elements of raw into values.
+fn decode_into<T : Copy>(values : &mut [T], raw : &[u8], decode : fn(&[u8]) -> T, element_size : usize) {
+    for (v, b) in values.iter_mut().zip(raw.chunks(element_size)) {
+        *v = decode(b);
+    }
+}
+
+/// Select the function decoding one element of the datatype and casting it to T.
+fn decoder<T : Copy>(datatype : &Datatype) -> fn(&[u8]) -> T
+    where u8 : Cast<T>, u16 : Cast<T>, u32 : Cast<T>, u64 : Cast<T>,
+          i8 : Cast<T>, i16 : Cast<T>, i32 : Cast<T>, i64 : Cast<T>,
+          f32 : Cast<T>, f64 : Cast<T>, c32 : Cast<T>, c64 : Cast<T> {
+    match (&datatype.dtype, &datatype.endianess) {
+        (&RDSType::U8, _) => decode_u8::<T>,
+        (&RDSType::U16, &Endianess::BigEndian) => decode_u16::<T, BigEndian>,
+        (&RDSType::U16, &Endianess::LittleEndian) => decode_u16::<T, LittleEndian>,
+        (&RDSType::U32, &Endianess::BigEndian) => decode_u32::<T, BigEndian>,
+        (&RDSType::U32, &Endianess::LittleEndian) => decode_u32::<T, LittleEndian>,
+        (&RDSType::U64, &Endianess::BigEndian) => decode_u64::<T, BigEndian>,
+        (&RDSType::U64, &Endianess::LittleEndian) => decode_u64::<T, LittleEndian>,
+        (&RDSType::I8, _) => decode_i8::<T>,
+        (&RDSType::I16, &Endianess::BigEndian) => decode_i16::<T, BigEndian>,
+        (&RDSType::I16, &Endianess::LittleEndian) => decode_i16::<T, LittleEndian>,
+        (&RDSType::I32, &Endianess::BigEndian) => decode_i32::<T, BigEndian>,
+        (&RDSType::I32, &Endianess::LittleEndian) => decode_i32::<T, LittleEndian>,
+        (&RDSType::I64, &Endianess::BigEndian) => decode_i64::<T, BigEndian>,
+        (&RDSType::I64, &Endianess::LittleEndian) => decode_i64::<T, LittleEndian>,
+        (&RDSType::F32, &Endianess::BigEndian) => decode_f32::<T, BigEndian>,
+        (&RDSType::F32, &Endianess::LittleEndian) => decode_f32::<T, LittleEndian>,
+        (&RDSType::F64, &Endianess::BigEndian) => decode_f64::<T, BigEndian>,
+        (&RDSType::F64, &Endianess::LittleEndian) => decode_f64::<T, LittleEndian>,
+        (&RDSType::C32, &Endianess::BigEndian) => decode_c32::<T, BigEndian>,
+        (&RDSType::C32, &Endianess::LittleEndian) => decode_c32::<T, LittleEndian>,
+        (&RDSType::C64, &Endianess::BigEndian) => decode_c64::<T, BigEndian>,
+        (&RDSType::C64, &Endianess::LittleEndian) => decode_c64::<T, LittleEndian>,
+    }
+}
+
+/// Write data in file at the given position.
+fn write_at(file : &mut File, pos : u64, data : &[u8]) -> Result<(), Error> {
+    if let Err(e) = file.seek(SeekFrom::Start(pos)).and_then(|_| file.write_all(data)) {
+        return Err(Error::Io(e));
+    }
+    Ok(())
+}
+
+/// Product of the dimensions of a shape read from a file, None if it overflows.
+fn checked_product(shape : &[usize]) -> Option<usize> {
+    shape.iter().fold(Some(1usize), |acc, &x| acc.and_then(|acc| acc.checked_mul(x)))
+}
+
+/// Allocate an NDArray of the given shape and size filled with value, returning the error built
+/// by too_large if the memory can not be reserved.
+fn allocate<T : Copy, F : Fn() -> Error>(shape : &[usize], size : usize, value : T, too_large : &F) -> Result<NDArray<T>, Error> {
+    let mut data = Vec::<T>::new();
+    if data.try_reserve_exact(size).is_err() {
+        return Err(too_large());
+    }
+    data.resize(size, value);
+    Ok(NDArray {
+        strides : NDArray::<T>::compute_strides(shape),
+        shape : shape.to_vec(),
+        data : data.into_boxed_slice(),
+    })
+}
+
+/// Read size bytes of file, which is len bytes long, at the given position.
+fn read_at(file : &File, len : u64, pos : u64, size : usize) -> Result<Vec<u8>, Error> {
+    if pos > len || size as u64 > len - pos {
+        return Err(Error::Format(format!("HDF5 structure of {} bytes at offset {} extends past the end of the file", size, pos)));
+    }
+    let mut data = vec![0u8; size];
+    let mut f = file;
+    if let Err(e) = f.seek(SeekFrom::Start(pos)).and_then(|_| f.read_exact(&mut data)) {
+        return Err(Error::Io(e));
+    }
+    Ok(data)
+}
+
+impl Reader {
+
+    /// Open the file and parse its superblock, which is either at the beginning of the file or
+    /// after a user block of 512 bytes times a power of two.
+    fn open(path : &str) -> Result<Reader, Error> {
+        let file = match File::open(path) {
+            Ok(f) => f,
+            Err(e) => return Err(Error::Io(e))
+        };
+        let len = match file.metadata() {
+            Ok(m) => m.len(),
+            Err(e) => return Err(Error::Io(e))
+        };
+
+        let mut start = 0u64;
+        loop {
+            if start + HDF5_MAGIC.len() as u64 > len {
+                return Err(Error::Format(format!("File {} does not have a valid HDF5 superblock", path)));
+            }
+            if read_at(&file, len, start, HDF5_MAGIC.len())? == HDF5_MAGIC {
+                break;
+            }
+            start = if start == 0 { 512 } else { start * 2 };
+        }
+
+        // The fields needed are within the size of a version 0 superblock
+        let superblock = read_at(&file, len, start, min(SUPERBLOCK_SIZE as u64, len - start) as usize)?;
+        let (base, offset_size, length_size, root) = {
+            let mut c = Cursor::new(&superblock, HDF5_MAGIC.len(), 0, 0);
+            let version = c.u8()?;
+            match version {
+                0 | 1 => {
+                    // Free-space, root group symbol table entry, reserved and shared header
+                    // message versions
+                    c.skip(4)?;
+                    c.offset_size = c.u8()? as usize;
+                    c.length_size = c.u8()? as usize;
+                    // Reserved, group leaf and internal node K, file consistency flags
+                    c.skip(9)?;
+                    if version == 1 {
+                        // Indexed storage internal node K, reserved
+                        c.skip(4)?;
+                    }
+                    let base = c.address()?;
+                    // Free-space info, end of file and driver information block addresses, then
+                    // the link name offset of the root group symbol table entry
+                    c.skip(4 * c.offset_size)?;
+                    (base, c.offset_size, c.length_size, c.address()?)
+                },
+                2 | 3 => {
+                    c.offset_size = c.u8()? as usize;
+                    c.length_size = c.u8()? as usize;
+                    c.skip(1)?;
+                    let base = c.address()?;
+                    // Superblock extension and end of file addresses
+                    c.skip(2 * c.offset_size)?;
+                    (base, c.offset_size, c.length_size, c.address()?)
+                },
+                _ => return Err(Error::Format(format!("HDF5 superblock version {} not supported", version)))
+            }
+        };
+        if ![2, 4, 8].contains(&offset_size) || ![2, 4, 8].contains(&length_size) {
+            return Err(Error::Format(format!("Invalid HDF5 offset ({}) or length ({}) size", offset_size, length_size)));
+        }
+
+        Ok(Reader {
+            file : file,
+            len : len,
+            base : base,
+            offset_size : offset_size,
+            length_size : length_size,
+            root : root,
+        })
+    }
+
+    /// Read size bytes at address.
+    fn read(&self, address : u64, size : usize) -> Result<Vec<u8>, Error> {
+        let pos = self.base.wrapping_add(address);
+        if address == UNDEFINED_ADDRESS || pos > self.len {
+            return Err(Error::Format(format!("HDF5 address {} is outside of the file", address)));
+        }
+        read_at(&self.file, self.len, pos, size)
+    }
+
+    /// Read at most size bytes at address, less if the end of the file is reached.
+    fn read_partial(&self, address : u64, size : usize) -> Result<Vec<u8>, Error> {
+        let available = self.len.saturating_sub(self.base.wrapping_add(address));
+        self.read(address, min(size as u64, available) as usize)
+    }
+
+    fn cursor<'a>(&self, data : &'a [u8]) -> Cursor<'a> {
+        Cursor::new(data, 0, self.offset_size, self.length_size)
+    }
+
+    /// Parse the object header at address, following the continuation messages.
+    fn object_header(&self, address : u64) -> Result<Vec<Message>, Error> {
+        // The prefix of an object header is at most 34 bytes long, up to its size field
+        let prefix = self.read_partial(address, 34)?;
+        let mut c = self.cursor(&prefix);
+        let v2 = prefix.starts_with(b"OHDR");
+
+        let (size, flags) = if v2 {
+            c.skip(4)?;
+            let version = c.u8()?;
+            if version != 2 {
+                return Err(Error::Format(format!("HDF5 object header version {} not supported", version)));
+            }
+            let flags = c.u8()?;
+            if flags & 0x20 != 0 {
+                // Access, modification, change and birth times
+                c.skip(16)?;
+            }
+            if flags & 0x10 != 0 {
+                // Maximum compact and minimum dense attributes
+                c.skip(4)?;
+            }
+            (usize_field(c.uint(1 << (flags & 0x3))?)?, flags)
+        }
+        else {
+            let version = c.u8()?;
+            if version != 1 {
+                return Err(Error::Format(format!("HDF5 object header version {} not supported", version)));
+            }
+            // Reserved, number of messages, reference count
+            c.skip(7)?;
+            let size = c.u32()? as usize;
+            // Padding to align the messages on 8 bytes
+            c.skip(4)?;
+            (size, 0)
+        };
+
+        let mut messages = Vec::<Message>::new();
+        // Blocks of messages as address, length and position of the first message
+        let mut blocks = vec![(address, c.pos + size, c.pos)];
+        let mut next = 0;
+        while next < blocks.len() {
+            let (address, length, start) = blocks[next];
+            let block = self.read(address, length)?;
+            let mut c = Cursor::new(&block, start, self.offset_size, self.length_size);
+            let mut end = length;
+            if v2 && next > 0 {
+                c.signature(b"OCHK", "object header continuation")?;
+                // Checksum
+                end = end.saturating_sub(4);
+            }
+            next += 1;
+
+            let header_size = if !v2 { 8 } else if flags & 0x4 != 0 { 6 } else { 4 };
+            while c.pos + header_size <= end {
+                let (kind, size, msg_flags) = if v2 {
+                    let kind = c.u8()? as u16;
+                    let size = c.u16()? as usize;
+                    let msg_flags = c.u8()?;
+                    if flags & 0x4 != 0 {
+                        c.skip(2)?;
+                    }
+                    (kind, size, msg_flags)
+                }
+                else {
+                    let kind = c.u16()?;
+                    let size = c.u16()? as usize;
+                    let msg_flags = c.u8()?;
+                    c.skip(3)?;
+                    (kind, size, msg_flags)
+                };
+                let data = c.bytes(size)?;
+                if kind == MSG_CONTINUATION {
+                    let mut m = self.cursor(data);
+                    let address = m.address()?;
+                    blocks.push((address, usize_field(m.length()?)?, 0));
+                }
+                else {
+                    messages.push(Message {
+                        kind : kind,
+                        flags : msg_flags,
+                        data : data.to_vec(),
+                    });
+                }
+            }
+        }
+
+        Ok(messages)
+    }
+
+    /// Return the hard links of a group given the messages of its object header, as pairs of
+    /// name and object header address.
+    fn links(&self, messages : &[Message]) -> Result<Vec<(String, u64)>, Error> {
+        let mut links = Vec::<(String, u64)>::new();
+        for message in messages {
+            let mut c = self.cursor(&message.data);
+            match message.kind {
+                MSG_SYMBOL_TABLE => {
+                    let btree = c.address()?;
+                    let heap = c.address()?;
+                    let header = self.read(heap, 8 + 2 * self.length_size + self.offset_size)?;
+                    let mut h = self.cursor(&header);
+                    h.signature(b"HEAP", "local heap")?;
+                    // Version and reserved, then the data segment size, the offset to the head of
+                    // the free list and the data segment address
+                    h.skip(4)?;
+                    let heap_size = usize_field(h.length()?)?;
+                    h.length()?;
+                    let heap_data = self.read(h.address()?, heap_size)?;
+                    self.group_btree(btree, &heap_data, &mut links)?;
+                },
+                MSG_LINK => {
+                    c.skip(1)?;
+                    let flags = c.u8()?;
+                    let link_type = if flags & 0x8 != 0 { c.u8()? } else { 0 };
+                    if flags & 0x4 != 0 {
+                        c.skip(8)?;
+                    }
+                    if flags & 0x10 != 0 {
+                        c.skip(1)?;
+                    }
+                    let len = usize_field(c.uint(1 << (flags & 0x3))?)?;
+                    let name = match str::from_utf8(c.bytes(len)?) {
+                        Ok(s) => s.to_string(),
+                        Err(e) => return Err(Error::Parse { message : "HDF5 link name is not valid UTF-8".to_string(), source : Some(Box::new(e)) })
+                    };
+                    // Soft and external links are ignored
+                    if link_type == 0 {
+                        links.push((name, c.address()?));
+                    }
+                },
+                MSG_LINK_INFO => {
+                    c.skip(1)?;
+                    if c.u8()? & 0x1 != 0 {
+                        c.skip(8)?;
+                    }
+                    if c.address()? != UNDEFINED_ADDRESS {
+                        return Err(Error::Format("HDF5 groups with dense link storage are not supported".to_string()));
+                    }
+                },
+                _ => {}
+            }
+        }
+        Ok(links)
+    }
+
+    /// Read the header of the B-tree node at address, checking its node type, and return its
+    /// level together with its entries, each of entry_size bytes.
+    fn btree_node(&self, address : u64, node_type : u8, entry_size : usize) -> Result<(u8, usize, Vec<u8>), Error> {
+        // Signature, node type, level, entries used, left and right siblings
+        let header_size = 8 + 2 * self.offset_size;
+        let header = self.read(address, header_size)?;
+        let mut c = self.cursor(&header);
+        c.signature(b"TREE", "B-tree node")?;
+        if c.u8()? != node_type {
+            let kind = if node_type == 0 { "group" } else { "chunk" };
+            return Err(Error::Format(format!("HDF5 B-tree node at {} is not a {} node", address, kind)));
+        }
+        let level = c.u8()?;
+        let entries = c.u16()? as usize;
+        let data = self.read(address.wrapping_add(header_size as u64), entries * entry_size)?;
+        Ok((level, entries, data))
+    }
+
+    /// Walk a group B-tree, collecting the entries of its symbol table nodes whose names are in
+    /// the data segment of the local heap.
+    fn group_btree(&self, address : u64, heap : &[u8], links : &mut Vec<(String, u64)>) -> Result<(), Error> {
+        let (level, entries, data) = self.btree_node(address, 0, self.length_size + self.offset_size)?;
+        let mut c = self.cursor(&data);
+        for _ in 0..entries {
+            c.length()?;
+            let child = c.address()?;
+            if level > 0 {
+                self.group_btree(child, heap, links)?;
+                continue;
+            }
+            let header = self.read(child, 8)?;
+            let mut s = self.cursor(&header);
+            s.signature(b"SNOD", "symbol table node")?;
+            s.skip(2)?;
+            let symbols = s.u16()? as usize;
+            // Link name offset, object header address, cache type, reserved and scratch-pad
+            let entry_size = 2 * self.offset_size + 24;
+            let table = self.read(child.wrapping_add(8), symbols * entry_size)?;
+            let mut s = self.cursor(&table);
+            for _ in 0..symbols {
+                let name_offset = usize_field(s.address()?)?;
+                let header = s.address()?;
+                s.skip(24)?;
+                let name = Cursor::new(heap, name_offset, self.offset_size, self.length_size).string()?;
+                links.push((name, header));
+            }
+        }
+        Ok(())
+    }
+
+    /// Walk a chunk B-tree, collecting the chunks of its leaves.
+    fn chunk_btree(&self, address : u64, rank : usize, chunks : &mut Vec<Chunk>) -> Result<(), Error> {
+        // Chunk size, filter mask, offsets including the one of the element size, child address
+        let entry_size = 8 + 8 * (rank + 1) + self.offset_size;
+        let (level, entries, data) = self.btree_node(address, 1, entry_size)?;
+        let mut c = self.cursor(&data);
+        for _ in 0..entries {
+            let size = c.u32()? as usize;
+            let filter_mask = c.u32()?;
+            let mut offsets = Vec::<usize>::with_capacity(rank + 1);
+            for _ in 0..rank + 1 {
+                offsets.push(usize_field(c.uint(8)?)?);
+            }
+            offsets.pop();
+            let child = c.address()?;
+            if level > 0 {
+                self.chunk_btree(child, rank, chunks)?;
+            }
+            else {
+                chunks.push(Chunk {
+                    address : child,
+                    size : size,
+                    filter_mask : filter_mask,
+                    offsets : offsets,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Return the attributes of an object given the messages of its object header.
+    fn attributes<'a>(&self, messages : &'a [Message]) -> Result<Vec<Attribute<'a>>, Error> {
+        let mut attributes = Vec::<Attribute>::new();
+        for message in messages.iter().filter(|m| m.kind == MSG_ATTRIBUTE) {
+            let mut c = self.cursor(&message.data);
+            let version = c.u8()?;
+            if version < 1 || version > 3 {
+                return Err(Error::Format(format!("HDF5 attribute message version {} not supported", version)));
+            }
+            c.skip(1)?;
+            let name_size = c.u16()? as usize;
+            let datatype_size = c.u16()? as usize;
+            let dataspace_size = c.u16()? as usize;
+            if version == 3 {
+                // Name character set encoding
+                c.skip(1)?;
+            }
+            // Version 1 pads each field to a multiple of 8 bytes
+            let padded = |size : usize| if version == 1 { (size + 7) / 8 * 8 } else { size };
+            let name = c.bytes(padded(name_size))?;
+            let name = match str::from_utf8(&name[..name_size.saturating_sub(1)]) {
+                Ok(s) => s.to_string(),
+                Err(e) => return Err(Error::Parse { message : "HDF5 attribute name is not valid UTF-8".to_string(), source : Some(Box::new(e)) })
+            };
+            let datatype = &c.bytes(padded(datatype_size))?[..datatype_size];
+            c.skip(padded(dataspace_size))?;
+            attributes.push(Attribute {
+                name : name,
+                datatype : datatype,
+                data : &message.data[c.pos..],
+            });
+        }
+        Ok(attributes)
+    }
+
+    /// Return the address of the object header of the object at path.
+    fn lookup(&self, path : &str) -> Result<u64, Error> {
+        let mut address = self.root;
+        for name in path.split('/').filter(|n| n.len() > 0) {
+            let links = self.links(&self.object_header(address)?)?;
+            address = match links.into_iter().find(|l| l.0 == name) {
+                Some(l) => l.1,
+                None => return Err(Error::Format(format!("No HDF5 object named {} in {}", name, path)))
+            };
+        }
+        Ok(address)
+    }
+
+    fn dataset<'a>(&self, messages : &'a [Message]) -> Result<Dataset<'a>, Error> {
+        let mut shape = None;
+        let mut datatype = None;
+        let mut layout = None;
+        let mut filters = Vec::<Filter>::new();
+        for message in messages {
+            let mut c = self.cursor(&message.data);
+            match message.kind {
+                MSG_DATASPACE => shape = Some(parse_dataspace(&mut c)?),
+                MSG_DATATYPE => {
+                    if message.flags & 0x2 != 0 {
+                        return Err(Error::UnsupportedType("HDF5 committed datatype".to_string()));
+                    }
+                    datatype = Some(parse_datatype(&mut c)?);
+                },
+                MSG_LAYOUT => layout = Some(parse_layout(&mut c)?),
+                MSG_FILTER_PIPELINE => filters = parse_filters(&mut c)?,
+                _ => {}
+            }
+        }
+        match (shape, datatype, layout) {
+            (Some(shape), Some(datatype), Some(layout)) => Ok(Dataset {
+                shape : shape,
+                datatype : datatype,
+                layout : layout,
+                filters : filters,
+            }),
+            _ => Err(Error::Format("HDF5 object is not a dataset".to_string()))
+        }
+    }
+}
+
+fn push_uint(buf : &mut Vec<u8>, v : u64, n : usize) {
+    for i in 0..n {
+        buf.push((v >> (8 * i)) as u8);
+    }
+}
+
+fn pad(buf : &mut Vec<u8>) {
+    while buf.len() % 8 != 0 {
+        buf.push(0);
+    }
+}
+
+fn element_size(dtype : &RDSType) -> usize {
+    match *dtype {
+        RDSType::U8 | RDSType::I8 => 1,
+        RDSType::U16 | RDSType::I16 => 2,
+        RDSType::U32 | RDSType::I32 | RDSType::F32 => 4,
+        RDSType::U64 | RDSType::I64 | RDSType::F64 | RDSType::C32 => 8,
+        RDSType::C64 => 16,
+    }
+}
+
+/// Append the little endian representation of v, stored as its own type, to buf.
+fn encode<T : Copy + RDSTyped>(buf : &mut Vec<u8>, v : T) {
+    match T::t() {
+        RDSType::U8 => push_uint(buf, Cast::<u8>::cast(v) as u64, 1),
+        RDSType::U16 => push_uint(buf, Cast::<u16>::cast(v) as u64, 2),
+        RDSType::U32 => push_uint(buf, Cast::<u32>::cast(v) as u64, 4),
+        RDSType::U64 => push_uint(buf, Cast::<u64>::cast(v), 8),
+        RDSType::I8 => push_uint(buf, Cast::<i8>::cast(v) as u64, 1),
+        RDSType::I16 => push_uint(buf, Cast::<i16>::cast(v) as u64, 2),
+        RDSType::I32 => push_uint(buf, Cast::<i32>::cast(v) as u64, 4),
+        RDSType::I64 => push_uint(buf, Cast::<i64>::cast(v) as u64, 8),
+        RDSType::F32 => push_uint(buf, Cast::<f32>::cast(v).to_bits() as u64, 4),
+        RDSType::F64 => push_uint(buf, Cast::<f64>::cast(v).to_bits(), 8),
+        RDSType::C32 => {
+            let c = Cast::<c32>::cast(v);
+            push_uint(buf, c.re.to_bits() as u64, 4);
+            push_uint(buf, c.im.to_bits() as u64, 4);
+        },
+        RDSType::C64 => {
+            let c = Cast::<c64>::cast(v);
+            push_uint(buf, c.re.to_bits(), 8);
+            push_uint(buf, c.im.to_bits(), 8);
+        },
+    }
+}
+
+/// Encode the datatype message of the little endian representation of dtype. Complex numbers
+/// are compound types with an "r" and an "i" member, following the h5py convention.
+fn datatype_message(dtype : &RDSType) -> Vec<u8> {
+    let mut m = Vec::<u8>::new();
+    let size = element_size(dtype);
+    match *dtype {
+        RDSType::C32 | RDSType::C64 => {
+            let member = if size == 8 { RDSType::F32 } else { RDSType::F64 };
+            m.extend_from_slice(&[0x16, 2, 0, 0]);
+            push_uint(&mut m, size as u64, 4);
+            for &(name, offset) in &[(b'r', 0), (b'i', size / 2)] {
+                m.extend_from_slice(&[name, 0, 0, 0, 0, 0, 0, 0]);
+                push_uint(&mut m, offset as u64, 4);
+                // Dimensionality, reserved, dimension permutation, reserved and dimension sizes
+                m.extend_from_slice(&[0u8; 28]);
+                m.extend(datatype_message(&member));
+            }
+        },
+        RDSType::F32 | RDSType::F64 => {
+            // IEEE 754 with an implied mantissa most significant bit
+            let (exponent_size, mantissa_size, bias) = if size == 4 { (8, 23, 127) } else { (11, 52, 1023) };
+            m.extend_from_slice(&[0x11, 0x20, (size * 8 - 1) as u8, 0]);
+            push_uint(&mut m, size as u64, 4);
+            push_uint(&mut m, 0, 2);
+            push_uint(&mut m, (size * 8) as u64, 2);
+            m.extend_from_slice(&[mantissa_size, exponent_size, 0, mantissa_size]);
+            push_uint(&mut m, bias, 4);
+        },
+        _ => {
+            let signed = match *dtype {
+                RDSType::I8 | RDSType::I16 | RDSType::I32 | RDSType::I64 => 0x8,
+                _ => 0x0
+            };
+            m.extend_from_slice(&[0x10, signed, 0, 0]);
+            push_uint(&mut m, size as u64, 4);
+            push_uint(&mut m, 0, 2);
+            push_uint(&mut m, (size * 8) as u64, 2);
+        }
+    }
+    m
+}
+
+/// Encode a version 1 attribute message with a scalar dataspace.
+fn attribute_message(name : &str, datatype : &[u8], value : &[u8]) -> Vec<u8> {
+    let dataspace = [1u8, 0, 0, 0, 0, 0, 0, 0];
+    let mut m = vec![1u8, 0];
+    push_uint(&mut m, (name.len() + 1) as u64, 2);
+    push_uint(&mut m, datatype.len() as u64, 2);
+    push_uint(&mut m, dataspace.len() as u64, 2);
+    m.extend_from_slice(name.as_bytes());
+    m.push(0);
+    pad(&mut m);
+    m.extend_from_slice(datatype);
+    pad(&mut m);
+    m.extend_from_slice(&dataspace);
+    m.extend_from_slice(value);
+    m
+}
+
+impl Writer {
+
+    fn address(&mut self) -> u64 {
+        pad(&mut self.buf);
+        self.base + self.buf.len() as u64
+    }
+
+    /// Append a version 1 object header and return its address.
+    fn object_header(&mut self, messages : &[(u16, u8, &[u8])]) -> Result<u64, Error> {
+        let address = self.address();
+        let size = messages.iter().fold(0, |acc, m| acc + 8 + (m.2.len() + 7) / 8 * 8);
+        self.buf.extend_from_slice(&[1, 0]);
+        push_uint(&mut self.buf, messages.len() as u64, 2);
+        push_uint(&mut self.buf, 1, 4);
+        push_uint(&mut self.buf, size as u64, 4);
+        push_uint(&mut self.buf, 0, 4);
+        for &(kind, flags, data) in messages {
+            let size = (data.len() + 7) / 8 * 8;
+            if size > 0xffff {
+                return Err(Error::Format(format!("HDF5 object header message of {} bytes is too large", data.len())));
+            }
+            push_uint(&mut self.buf, kind as u64, 2);
+            push_uint(&mut self.buf, size as u64, 2);
+            self.buf.extend_from_slice(&[flags, 0, 0, 0]);
+            self.buf.extend_from_slice(data);
+            pad(&mut self.buf);
+        }
+        Ok(address)
+    }
+
+    /// Append a version 1 B-tree indexing children, where child i covers the keys from keys[i] to
+    /// keys[i + 1], and return the address of its root node. Every node is allocated with the
+    /// capacity of 2k children expected by the HDF5 library.
+    fn btree(&mut self, node_type : u8, k : usize, mut keys : Vec<Vec<u8>>, mut children : Vec<u64>) -> u64 {
+        let key_size = keys[0].len();
+        let node_size = 24 + (2 * k + 1) * key_size + 2 * k * 8;
+        let mut level = 0u8;
+        loop {
+            let num_nodes = max(1, (children.len() + 2 * k - 1) / (2 * k));
+            let start = self.address();
+            let mut node_keys = Vec::<Vec<u8>>::with_capacity(num_nodes + 1);
+            let mut nodes = Vec::<u64>::with_capacity(num_nodes);
+            for i in 0..num_nodes {
+                let address = start + (i * node_size) as u64;
+                let first = i * 2 * k;
+                let last = min(first + 2 * k, children.len());
+                self.buf.extend_from_slice(b"TREE");
+                self.buf.extend_from_slice(&[node_type, level]);
+                push_uint(&mut self.buf, (last - first) as u64, 2);
+                push_uint(&mut self.buf, if i > 0 { address - node_size as u64 } else { UNDEFINED_ADDRESS }, 8);
+                push_uint(&mut self.buf, if i + 1 < num_nodes { address + node_size as u64 } else { UNDEFINED_ADDRESS }, 8);
+                for j in first..last {
+                    self.buf.extend_from_slice(&keys[j]);
+                    push_uint(&mut self.buf, children[j], 8);
+                }
+                self.buf.extend_from_slice(&keys[last]);
+                self.buf.resize((address - self.base) as usize + node_size, 0);
+                node_keys.push(keys[first].clone());
+                nodes.push(address);
+            }
+            if num_nodes == 1 {
+                return start;
+            }
+            node_keys.push(keys[children.len()].clone());
+            keys = node_keys;
+            children = nodes;
+            level += 1;
+        }
+    }
+
+    /// Append a group with its children and return the addresses of its object header, B-tree
+    /// and local heap.
+    fn group(&mut self, objects : &[Object], group : &Object) -> Result<(u64, u64, u64), Error> {
+        let mut children : Vec<&Object> = objects.iter().filter(|o| {
+            o.path.len() == group.path.len() + 1 && o.path.starts_with(&group.path)
+        }).collect();
+        // The symbol table nodes are searched with the names in strcmp order
+        children.sort_by(|a, b| a.path.last().unwrap().as_bytes().cmp(b.path.last().unwrap().as_bytes()));
+
+        let mut headers = Vec::<u64>::with_capacity(children.len());
+        for child in &children {
+            headers.push(match child.dataset {
+                Some(ref dataset) => self.dataset(dataset, &child.attributes)?,
+                None => self.group(objects, child)?.0
+            });
+        }
+
+        // The local heap starts with the empty name, and ends with a free block which is the
+        // last of the free list
+        let mut heap = vec![0u8; 8];
+        let mut name_offsets = Vec::<u64>::with_capacity(children.len());
+        for child in &children {
+            name_offsets.push(heap.len() as u64);
+            heap.extend_from_slice(child.path.last().unwrap().as_bytes());
+            heap.push(0);
+            pad(&mut heap);
+        }
+        let free_block = heap.len() as u64;
+        push_uint(&mut heap, 1, 8);
+        push_uint(&mut heap, 16, 8);
+        let heap_data = self.address();
+        self.buf.extend_from_slice(&heap);
+        let heap_address = self.address();
+        self.buf.extend_from_slice(b"HEAP");
+        self.buf.extend_from_slice(&[0, 0, 0, 0]);
+        push_uint(&mut self.buf, heap.len() as u64, 8);
+        push_uint(&mut self.buf, free_block, 8);
+        push_uint(&mut self.buf, heap_data, 8);
+
+        let mut keys = vec![vec![0u8; 8]];
+        let mut nodes = Vec::<u64>::new();
+        let mut first = 0;
+        while first < children.len() {
+            let last = min(first + 2 * GROUP_LEAF_K, children.len());
+            let address = self.address();
+            self.buf.extend_from_slice(b"SNOD");
+            self.buf.extend_from_slice(&[1, 0]);
+            push_uint(&mut self.buf, (last - first) as u64, 2);
+            for i in first..last {
+                push_uint(&mut self.buf, name_offsets[i], 8);
+                push_uint(&mut self.buf, headers[i], 8);
+                // No cached information
+                self.buf.extend_from_slice(&[0u8; 24]);
+            }
+            self.buf.resize((address - self.base) as usize + 8 + 2 * GROUP_LEAF_K * 40, 0);
+            let mut key = Vec::<u8>::with_capacity(8);
+            push_uint(&mut key, name_offsets[last - 1], 8);
+            keys.push(key);
+            nodes.push(address);
+            first = last;
+        }
+        let btree = self.btree(0, GROUP_INTERNAL_K, keys, nodes);
+
+        let mut symbol_table = Vec::<u8>::with_capacity(16);
+        push_uint(&mut symbol_table, btree, 8);
+        push_uint(&mut symbol_table, heap_address, 8);
+        let mut messages = vec![(MSG_SYMBOL_TABLE, 0u8, &symbol_table[..])];
+        for &(_, ref attribute) in &group.attributes {
+            messages.push((MSG_ATTRIBUTE, 0u8, &attribute[..]));
+        }
+        Ok((self.object_header(&messages)?, btree, heap_address))
+    }
+
+    /// Append the raw data of a dataset of the given shape and type, encoded in little endian
+    /// row-major order, either contiguously or split in chunks which are deflated if a
+    /// compression level is given.
+    fn raw_data(&mut self, shape : &[usize], dtype : &RDSType, raw : &[u8], chunk_shape : Option<&[usize]>, compression : Option<u32>) -> Result<Storage, Error> {
+        let chunk_shape = match chunk_shape {
+            Some(chunk_shape) => chunk_shape,
+            None => {
+                let address = if raw.len() > 0 { self.address() } else { UNDEFINED_ADDRESS };
+                self.buf.extend_from_slice(raw);
+                return Ok(Storage::Contiguous(address, raw.len()));
+            }
+        };
+        let rank = shape.len();
+        let size = element_size(dtype);
+        let key = |size : usize, offsets : &[usize]| {
+            let mut key = Vec::<u8>::with_capacity(16 + 8 * rank);
+            push_uint(&mut key, size as u64, 4);
+            push_uint(&mut key, 0, 4);
+            for &o in offsets {
+                push_uint(&mut key, o as u64, 8);
+            }
+            push_uint(&mut key, 0, 8);
+            key
+        };
+        let grid : Vec<usize> = shape.iter().zip(chunk_shape).map(|(&s, &c)| (s + c - 1) / c).collect();
+        let num_chunks = grid.iter().fold(1usize, |acc, &x| acc * x);
+        let chunk_size = chunk_shape.iter().fold(1usize, |acc, &x| acc * x);
+        let mut strides = vec![1usize; rank];
+        for i in (0..rank.saturating_sub(1)).rev() {
+            strides[i] = strides[i + 1] * shape[i + 1];
+        }
+
+        let mut keys = Vec::<Vec<u8>>::with_capacity(num_chunks + 1);
+        let mut chunks = Vec::<u64>::with_capacity(num_chunks);
+        let mut grid_idx = vec![0usize; rank];
+        let mut offsets = vec![0usize; rank];
+        for _ in 0..num_chunks {
+            let mut chunk = vec![0u8; chunk_size * size];
+            let mut local = vec![0usize; rank];
+            for i in 0..chunk_size {
+                let mut inside = true;
+                let mut pos = 0;
+                for j in 0..rank {
+                    offsets[j] = grid_idx[j] * chunk_shape[j];
+                    inside &= offsets[j] + local[j] < shape[j];
+                    pos += (offsets[j] + local[j]) * strides[j];
+                }
+                if inside {
+                    chunk[i * size..(i + 1) * size].copy_from_slice(&raw[pos * size..(pos + 1) * size]);
+                }
+                local.inc_ro(chunk_shape);
+            }
+            if let Some(level) = compression {
+                let mut encoder = ZlibEncoder::new(Vec::<u8>::new(), Compression::new(min(level, 9)));
+                let compressed = encoder.write_all(&chunk).and_then(|_| encoder.finish());
+                chunk = match compressed {
+                    Ok(c) => c,
+                    Err(e) => return Err(Error::Io(e))
+                };
+            }
+            keys.push(key(chunk.len(), &offsets));
+            chunks.push(self.address());
+            self.buf.extend_from_slice(&chunk);
+            grid_idx.inc_ro(&grid);
+        }
+        // The last key is past the last chunk
+        for j in 0..rank {
+            offsets[j] += chunk_shape[j];
+        }
+        keys.push(key(0, &offsets));
+        Ok(Storage::Chunked(chunk_shape.to_vec(), keys, chunks))
+    }
+
+    /// Append the object header of a dataset, with the B-tree indexing its chunks, and return
+    /// the address of the object header.
+    fn dataset(&mut self, dataset : &StoredDataset, attributes : &[(String, Vec<u8>)]) -> Result<u64, Error> {
+        let rank = dataset.shape.len();
+        let size = element_size(&dataset.dtype);
+
+        let mut dataspace = vec![1u8, rank as u8, 0, 0, 0, 0, 0, 0];
+        for &s in &dataset.shape {
+            push_uint(&mut dataspace, s as u64, 8);
+        }
+        let datatype = datatype_message(&dataset.dtype);
+
+        let mut layout = vec![3u8];
+        let mut filters = Vec::<u8>::new();
+        match dataset.storage {
+            Storage::Contiguous(address, len) => {
+                layout.push(1);
+                push_uint(&mut layout, address, 8);
+                push_uint(&mut layout, len as u64, 8);
+            },
+            Storage::Chunked(ref chunk_shape, ref keys, ref chunks) => {
+                let btree = if chunks.len() > 0 { self.btree(1, CHUNK_K, keys.clone(), chunks.clone()) } else { UNDEFINED_ADDRESS };
+                layout.extend_from_slice(&[2, rank as u8 + 1]);
+                push_uint(&mut layout, btree, 8);
+                for &c in chunk_shape {
+                    push_uint(&mut layout, c as u64, 4);
+                }
+                push_uint(&mut layout, size as u64, 4);
+
+                if let Some(level) = dataset.compression {
+                    filters.extend_from_slice(&[1, 1, 0, 0, 0, 0, 0, 0]);
+                    push_uint(&mut filters, FILTER_DEFLATE as u64, 2);
+                    push_uint(&mut filters, 8, 2);
+                    push_uint(&mut filters, 0, 2);
+                    push_uint(&mut filters, 1, 2);
+                    filters.extend_from_slice(b"deflate\0");
+                    push_uint(&mut filters, min(level, 9) as u64, 4);
+                    push_uint(&mut filters, 0, 4);
+                }
+            }
+        }
+
+        // Fill value version 2: late or incremental allocation, written if set, undefined
+        let chunked = match dataset.storage {
+            Storage::Chunked(..) => true,
+            Storage::Contiguous(..) => false
+        };
+        let fill_value = [2u8, if chunked { 3 } else { 2 }, 2, 0];
+        let mut messages = vec![
+            (MSG_DATASPACE, 0u8, &dataspace[..]),
+            (MSG_DATATYPE, 1u8, &datatype[..]),
+            (MSG_FILL_VALUE, 1u8, &fill_value[..]),
+            (MSG_LAYOUT, 0u8, &layout[..]),
+        ];
+        if filters.len() > 0 {
+            messages.push((MSG_FILTER_PIPELINE, 0u8, &filters[..]));
+        }
+        for &(_, ref attribute) in attributes {
+            messages.push((MSG_ATTRIBUTE, 0u8, &attribute[..]));
+        }
+        self.object_header(&messages)
+    }
+
+    /// Append the objects, the first one being the root group, and return the version 0
+    /// superblock referencing them.
+    fn metadata(&mut self, objects : &[Object]) -> Result<Vec<u8>, Error> {
+        let (header, btree, heap) = self.group(objects, &objects[0])?;
+        let eof = self.address();
+
+        let mut superblock = HDF5_MAGIC.to_vec();
+        // Superblock, free-space, root group symbol table entry, reserved and shared header
+        // message versions, size of offsets and lengths, reserved
+        superblock.extend_from_slice(&[0, 0, 0, 0, 0, 8, 8, 0]);
+        push_uint(&mut superblock, GROUP_LEAF_K as u64, 2);
+        push_uint(&mut superblock, GROUP_INTERNAL_K as u64, 2);
+        push_uint(&mut superblock, 0, 4);
+        // Base, free-space info, end of file and driver information block addresses
+        push_uint(&mut superblock, 0, 8);
+        push_uint(&mut superblock, UNDEFINED_ADDRESS, 8);
+        push_uint(&mut superblock, eof, 8);
+        push_uint(&mut superblock, UNDEFINED_ADDRESS, 8);
+        // Root group symbol table entry, caching the addresses of its B-tree and local heap
+        push_uint(&mut superblock, 0, 8);
+        push_uint(&mut superblock, header, 8);
+        push_uint(&mut superblock, 1, 4);
+        push_uint(&mut superblock, 0, 4);
+        push_uint(&mut superblock, btree, 8);
+        push_uint(&mut superblock, heap, 8);
+        Ok(superblock)
+    }
+}
+
+fn split_path(path : &str) -> Vec<String> {
+    path.split('/').filter(|n| n.len() > 0).map(|n| n.to_string()).collect()
+}
+
+fn is_dataset(messages : &[Message]) -> bool {
+    messages.iter().any(|m| m.kind == MSG_LAYOUT)
+}
+
+fn is_group(messages : &[Message]) -> bool {
+    messages.iter().any(|m| m.kind == MSG_SYMBOL_TABLE || m.kind == MSG_LINK_INFO)
+}
+
+impl HDF5File {
+
+    /// Allocate a new HDF5File structure with a given path. This function neither create nor open
+    /// the file specified by the path.
+    pub fn new(path : &str) -> HDF5File {
+        HDF5File {
+            path : path.to_string(),
+            file : None,
+            end : 0,
+            objects : vec![Object {
+                path : Vec::new(),
+                dataset : None,
+                attributes : Vec::new(),
+            }],
+            chunk_shape : None,
+            compression : None,
+        }
+    }
+
+    fn children(&self, group : &str, datasets : bool) -> Result<Vec<String>, Error> {
+        let reader = Reader::open(&self.path)?;
+        let messages = reader.object_header(reader.lookup(group)?)?;
+        if !is_group(&messages) {
+            return Err(Error::Format(format!("HDF5 object {} is not a group", group)));
+        }
+        let mut names = Vec::<String>::new();
+        for (name, address) in reader.links(&messages)? {
+            let child = reader.object_header(address)?;
+            if (datasets && is_dataset(&child)) || (!datasets && is_group(&child)) {
+                names.push(name);
+            }
+        }
+        Ok(names)
+    }
+
+    /// Open the HDF5 file and return the names of the groups in group, "/" being the root group.
+    /// In case of failure, returns the error as an `Error`.
+    pub fn groups(&self, group : &str) -> Result<Vec<String>, Error> {
+        self.children(group, false)
+    }
+
+    /// Open the HDF5 file and return the names of the datasets in group, "/" being the root group.
+    /// In case of failure, returns the error as an `Error`.
+    pub fn datasets(&self, group : &str) -> Result<Vec<String>, Error> {
+        self.children(group, true)
+    }
+
+    /// Open the HDF5 file and return the shape of a dataset.
+    /// In case of failure, returns the error as an `Error`.
+    pub fn shape(&self, dataset : &str) -> Result<Vec<usize>, Error> {
+        let reader = Reader::open(&self.path)?;
+        let messages = reader.object_header(reader.lookup(dataset)?)?;
+        Ok(reader.dataset(&messages)?.shape)
+    }
+
+    /// Open the HDF5 file and return the type of the elements of a dataset.
+    /// In case of failure, returns the error as an `Error`.
+    pub fn dtype(&self, dataset : &str) -> Result<RDSType, Error> {
+        let reader = Reader::open(&self.path)?;
+        let messages = reader.object_header(reader.lookup(dataset)?)?;
+        Ok(reader.dataset(&messages)?.datatype.dtype)
+    }
+
+    /// Open the HDF5 file and read an entire dataset, with a contiguous, compact or chunked layout,
+    /// as a NDArray<T>. This function operates its own type convertion from the datatype of the
+    /// dataset to the type T. Chunks which were never written are filled with zeros.
+    /// In case of failure, returns the error as an `Error`.
+    pub fn read_dataset<T : Copy + RDSTyped + Display>(&self, name : &str) -> Result<NDArray<T>, Error>
+        where u8 : Cast<T>, u16 : Cast<T>, u32 : Cast<T>, u64 : Cast<T>,
+              i8 : Cast<T>, i16 : Cast<T>, i32 : Cast<T>, i64 : Cast<T>,
+              f32 : Cast<T>, f64 : Cast<T>, c32 : Cast<T>, c64 : Cast<T> {
+        let reader = Reader::open(&self.path)?;
+        let messages = reader.object_header(reader.lookup(name)?)?;
+        let dataset = reader.dataset(&messages)?;
+        let decode = decoder::<T>(&dataset.datatype);
+        let element_size = dataset.datatype.size;
+        let too_large = || Error::Format(format!("HDF5 dataset {} of shape {:?} is too large", name, dataset.shape));
+        let size = match checked_product(&dataset.shape) {
+            Some(s) => s,
+            None => return Err(too_large())
+        };
+        let bytes = match size.checked_mul(element_size) {
+            Some(b) => b,
+            None => return Err(too_large())
+        };
+
+        let array = match dataset.layout {
+            Layout::Compact(raw) => {
+                if raw.len() < bytes {
+                    return Err(Error::Format(format!("HDF5 dataset {} data is too small", name)));
+                }
+                let mut array = allocate(&dataset.shape, size, Cast::<T>::cast(0u8), &too_large)?;
+                decode_into(array.get_data_mut(), raw, decode, element_size);
+                array
+            },
+            Layout::Contiguous(address) if address != UNDEFINED_ADDRESS => {
+                // The read is bounded by the file size, which in turn bounds the allocation
+                let raw = reader.read(address, bytes)?;
+                let mut array = allocate(&dataset.shape, size, Cast::<T>::cast(0u8), &too_large)?;
+                decode_into(array.get_data_mut(), &raw, decode, element_size);
+                array
+            },
+            // Never written, the dataset only holds its fill value
+            Layout::Contiguous(_) => allocate(&dataset.shape, size, Cast::<T>::cast(0u8), &too_large)?,
+            Layout::Chunked(address, ref chunk_shape) => {
+                let rank = chunk_shape.len();
+                if rank != dataset.shape.len() {
+                    return Err(Error::DimensionMismatch {
+                        expected : dataset.shape.len(),
+                        found : rank,
+                    });
+                }
+                if rank == 0 || chunk_shape.contains(&0) {
+                    return Err(Error::Format(format!("Invalid HDF5 chunk shape {:?}", chunk_shape)));
+                }
+                let chunk_size = match checked_product(chunk_shape).and_then(|s| s.checked_mul(element_size).map(|_| s)) {
+                    Some(s) => s,
+                    None => return Err(Error::Format(format!("Invalid HDF5 chunk shape {:?}", chunk_shape)))
+                };
+                let mut chunks = Vec::<Chunk>::new();
+                if address != UNDEFINED_ADDRESS {
+                    reader.chunk_btree(address, rank, &mut chunks)?;
+                }
+                // Chunks never written hold the fill value, so the dataset may be larger than its
+                // storage: the allocation fails with an error instead of aborting
+                let mut array = allocate(&dataset.shape, size, Cast::<T>::cast(0u8), &too_large)?;
+                let shape = dataset.shape.clone();
+                let strides = array.strides().to_vec();
+                let values = array.get_data_mut();
+                // The chunks are copied row by row, a row being the last dimension of the chunk
+                // clipped to the dataset
+                let row = chunk_shape[rank - 1];
+                for chunk in chunks {
+                    let data = unfilter(reader.read(chunk.address, chunk.size)?, &dataset.filters, chunk.filter_mask, element_size)?;
+                    if data.len() < chunk_size * element_size {
+                        return Err(Error::Format(format!("HDF5 chunk at offset {:?} is too small", chunk.offsets)));
+                    }
+                    if chunk.offsets[rank - 1] >= shape[rank - 1] {
+                        continue;
+                    }
+                    let len = min(row, shape[rank - 1] - chunk.offsets[rank - 1]);
+                    let mut local = vec![0usize; rank - 1];
+                    for r in 0..chunk_size / row {
+                        // The offsets come from the file and may overflow
+                        let idx : Option<Vec<usize>> = (0..rank - 1).map(|j| {
+                            chunk.offsets[j].checked_add(local[j]).and_then(|i| if i < shape[j] { Some(i) } else { None })
+                        }).collect();
+                        if let Some(idx) = idx {
+                            let start = idx.iter().zip(&strides).fold(chunk.offsets[rank - 1] * strides[rank - 1], |acc, (&i, &s)| acc + i * s);
+                            decode_into(&mut values[start..start + len], &data[r * row * element_size..], decode, element_size);
+                        }
+                        local.inc_ro(&chunk_shape[..rank - 1]);
+                    }
+                }
+                array
+            }
+        };
+
+        Ok(array)
+    }
+
+    /// Open the HDF5 file and return the names of the attributes of an object.
+    /// In case of failure, returns the error as an `Error`.
+    pub fn attributes(&self, object : &str) -> Result<Vec<String>, Error> {
+        let reader = Reader::open(&self.path)?;
+        let messages = reader.object_header(reader.lookup(object)?)?;
+        Ok(reader.attributes(&messages)?.into_iter().map(|a| a.name).collect())
+    }
+
+    /// Open the HDF5 file and read a numerical attribute of an object. Only the first element of
+    /// array attributes is returned.
+    /// In case of failure, returns the error as an `Error`.
+    pub fn read_attribute<T : Copy + RDSTyped>(&self, object : &str, name : &str) -> Result<T, Error>
+        where u8 : Cast<T>, u16 : Cast<T>, u32 : Cast<T>, u64 : Cast<T>,
+              i8 : Cast<T>, i16 : Cast<T>, i32 : Cast<T>, i64 : Cast<T>,
+              f32 : Cast<T>, f64 : Cast<T>, c32 : Cast<T>, c64 : Cast<T> {
+        let reader = Reader::open(&self.path)?;
+        let messages = reader.object_header(reader.lookup(object)?)?;
+        let attributes = reader.attributes(&messages)?;
+        let attribute = match attributes.iter().find(|a| a.name == name) {
+            Some(a) => a,
+            None => return Err(Error::Format(format!("No HDF5 attribute named {} on {}", name, object)))
+        };
+        let datatype = parse_datatype(&mut Cursor::new(attribute.datatype, 0, reader.offset_size, reader.length_size))?;
+        if attribute.data.len() < datatype.size {
+            return Err(Error::Format(format!("HDF5 attribute {} of {} has no value", name, object)));
+        }
+        Ok(decoder::<T>(&datatype)(&attribute.data[..datatype.size]))
+    }
+
+    /// Open the HDF5 file and read a fixed-length string attribute of an object.
+    /// In case of failure, returns the error as an `Error`.
+    pub fn read_string_attribute(&self, object : &str, name : &str) -> Result<String, Error> {
+        let reader = Reader::open(&self.path)?;
+        let messages = reader.object_header(reader.lookup(object)?)?;
+        let attributes = reader.attributes(&messages)?;
+        let attribute = match attributes.iter().find(|a| a.name == name) {
+            Some(a) => a,
+            None => return Err(Error::Format(format!("No HDF5 attribute named {} on {}", name, object)))
+        };
+        let mut c = Cursor::new(attribute.datatype, 0, reader.offset_size, reader.length_size);
+        let class = c.u8()? & 0xf;
+        c.skip(3)?;
+        let size = c.u32()? as usize;
+        match class {
+            3 => {},
+            9 => return Err(Error::UnsupportedType("HDF5 variable-length string".to_string())),
+            _ => return Err(Error::UnsupportedType(format!("HDF5 datatype class {} for a string attribute", class)))
+        }
+        let value = &attribute.data[..size.min(attribute.data.len())];
+        match str::from_utf8(value) {
+            Ok(s) => Ok(s.trim_end_matches(|c| c == '\0' || c == ' ').to_string()),
+            Err(e) => Err(Error::Parse { message : format!("HDF5 attribute {} of {} is not valid UTF-8", name, object), source : Some(Box::new(e)) })
+        }
+    }
+
+    fn find(&self, path : &[String]) -> Option<usize> {
+        self.objects.iter().position(|o| &o.path[..] == path)
+    }
+
+    /// Create the groups of path which do not exist yet, failing if one of them is a dataset.
+    fn require_groups(&mut self, path : &[String]) -> Result<(), Error> {
+        for i in 1..path.len() + 1 {
+            match self.find(&path[..i]) {
+                Some(j) => {
+                    if self.objects[j].dataset.is_some() {
+                        return Err(Error::Format(format!("HDF5 object /{} is a dataset, not a group", path[..i].join("/"))));
+                    }
+                },
+                None => self.objects.push(Object {
+                    path : path[..i].to_vec(),
+                    dataset : None,
+                    attributes : Vec::new(),
+                })
+            }
+        }
+        Ok(())
+    }
+
+    fn set_attribute(&mut self, object : &str, name : &str, message : Vec<u8>) -> Result<(), Error> {
+        let i = match self.find(&split_path(object)) {
+            Some(i) => i,
+            None => return Err(Error::Format(format!("No HDF5 object named {} was written", object)))
+        };
+        let attributes = &mut self.objects[i].attributes;
+        match attributes.iter().position(|a| a.0 == name) {
+            Some(j) => attributes[j].1 = message,
+            None => attributes.push((name.to_string(), message))
+        }
+        Ok(())
+    }
+
+    /// Create the file, with room for its superblock, unless it was already created by this
+    /// HDF5File. An existing file is never replaced.
+    fn create(&mut self) -> Result<(), Error> {
+        if self.file.is_none() {
+            let mut file = match OpenOptions::new().write(true).create_new(true).open(&self.path) {
+                Ok(f) => f,
+                Err(e) => return Err(Error::Io(e))
+            };
+            write_at(&mut file, 0, &[0u8; SUPERBLOCK_SIZE])?;
+            self.file = Some(file);
+            self.end = SUPERBLOCK_SIZE as u64;
+        }
+        Ok(())
+    }
+
+    /// Append the structures serialized by writer at the end of the file.
+    fn append(&mut self, mut writer : Writer) -> Result<(), Error> {
+        pad(&mut writer.buf);
+        if let Some(ref mut file) = self.file {
+            write_at(file, writer.base, &writer.buf)?;
+        }
+        self.end = writer.base + writer.buf.len() as u64;
+        Ok(())
+    }
+
+    /// Create a group, together with its missing parent groups, creating the HDF5 file on the
+    /// first write.
+    /// In case of failure, returns the error as an `Error`.
+    pub fn create_group(&mut self, group : &str) -> Result<(), Error> {
+        self.create()?;
+        self.require_groups(&split_path(group))
+    }
+
+    /// Write the entire NDData<T> as a dataset of type T, creating its missing parent groups and,
+    /// on the first write, the HDF5 file. The raw data is appended to the file immediately. A 
+    /// dataset previously written at the same path is replaced. The layout of the dataset is 
+    /// given by the chunk_shape and compression fields.
+    /// In case of failure, returns the error as an `Error`.
+    pub fn write_data<T : Copy + RDSTyped + Display>(&mut self, dataset : &str, data : &NDData<T>) -> Result<(), Error> {
+        let path = split_path(dataset);
+        if path.is_empty() {
+            return Err(Error::Format("The HDF5 root group can not be replaced by a dataset".to_string()));
+        }
+        if let Some(i) = self.find(&path) {
+            if self.objects[i].dataset.is_none() {
+                return Err(Error::Format(format!("HDF5 object {} is a group, not a dataset", dataset)));
+            }
+        }
+
+        let chunk_shape = match self.chunk_shape {
+            Some(ref chunk_shape) => {
+                if chunk_shape.len() != data.dim() {
+                    return Err(Error::DimensionMismatch {
+                        expected : data.dim(),
+                        found : chunk_shape.len(),
+                    });
+                }
+                if data.dim() == 0 || chunk_shape.contains(&0) {
+                    return Err(Error::Format(format!("Invalid HDF5 chunk shape {:?}", chunk_shape)));
+                }
+                Some(chunk_shape.iter().zip(data.shape()).map(|(&c, &s)| min(c, max(s, 1))).collect::<Vec<usize>>())
+            },
+            None if self.compression.is_some() && data.dim() > 0 => Some(data.shape().iter().map(|&s| max(s, 1)).collect()),
+            None => None
+        };
+        let compression = if chunk_shape.is_some() { self.compression } else { None };
+
+        self.create()?;
+        self.require_groups(&path[..path.len() - 1])?;
+        let mut raw = Vec::<u8>::with_capacity(data.size() * element_size(&T::t()));
+        for &v in data.iter() {
+            encode(&mut raw, v);
+        }
+        let mut writer = Writer {
+            buf : Vec::new(),
+            base : self.end,
+        };
+        let storage = writer.raw_data(data.shape(), &T::t(), &raw, chunk_shape.as_ref().map(|c| &c[..]), compression)?;
+        self.append(writer)?;
+
+        let object = Object {
+            path : path,
+            dataset : Some(StoredDataset {
+                shape : data.shape().to_vec(),
+                dtype : T::t(),
+                storage : storage,
+                compression : compression,
+            }),
+            attributes : Vec::new(),
+        };
+        match self.find(&object.path) {
+            Some(i) => self.objects[i] = object,
+            None => self.objects.push(object)
+        }
+        Ok(())
+    }
+
+    /// Attach a numerical attribute of type T to a group or dataset previously written with this
+    /// HDF5File, replacing the attribute of the same name.
+    /// In case of failure, returns the error as an `Error`.
+    pub fn write_attribute<T : Copy + RDSTyped>(&mut self, object : &str, name : &str, value : T) -> Result<(), Error> {
+        let mut raw = Vec::<u8>::with_capacity(element_size(&T::t()));
+        encode(&mut raw, value);
+        self.set_attribute(object, name, attribute_message(name, &datatype_message(&T::t()), &raw))
+    }
+
+    /// Attach a fixed-length UTF-8 string attribute to a group or dataset previously written with
+    /// this HDF5File, replacing the attribute of the same name.
+    /// In case of failure, returns the error as an `Error`.
+    pub fn write_string_attribute(&mut self, object : &str, name : &str, value : &str) -> Result<(), Error> {
+        let size = max(value.len(), 1);
+        // String class with null padding and UTF-8 character set
+        let mut datatype = vec![0x13u8, 0x11, 0, 0];
+        push_uint(&mut datatype, size as u64, 4);
+        let mut raw = value.as_bytes().to_vec();
+        raw.resize(size, 0);
+        self.set_attribute(object, name, attribute_message(name, &datatype, &raw))
+    }
+
+    /// Write the groups, object headers and attributes of the objects written, then the
+    /// superblock, completing the HDF5 file. Nothing is done if nothing was written. Writing again
+    /// with this HDF5File afterwards fails, as the file exists.
+    /// In case of failure, returns the error as an `Error`.
+    pub fn close(&mut self) -> Result<(), Error> {
+        if self.file.is_none() {
+            return Ok(());
+        }
+        let mut writer = Writer {
+            buf : Vec::new(),
+            base : self.end,
+        };
+        let superblock = writer.metadata(&self.objects)?;
+        self.append(writer)?;
+        if let Some(mut file) = self.file.take() {
+            write_at(&mut file, 0, &superblock)?;
+        }
+        self.objects.truncate(1);
+        self.objects[0].attributes.clear();
+        Ok(())
+    }
+}
+
+impl Drop for HDF5File {
+
+    /// Complete the HDF5 file if it was written and not closed, ignoring the errors.
+    fn drop(&mut self) {
+        let _ = self.close();
+    }
+}
@@ -0,0 +1,314 @@
+use std::fmt::Display;
+use std::iter::repeat;
+use std::marker::PhantomData;
+
+use array::{NDData, NDDataMut, NDView};
+use array::ndindex::NDIndex;
+
+/// A trait for N-dimensional data which can be iterated over.
+pub trait NDIterable<'a, T : 'a> {
+
+    /// Return an iterator over the elements in row-major order.
+    fn iter(&'a self) -> NDIter<'a, T>;
+
+    /// Return an iterator over the elements in column-major order.
+    fn iter_co(&'a self) -> NDIter<'a, T>;
+
+    /// Return an iterator over the (index, element) pairs in row-major order.
+    fn indexed_iter(&'a self) -> NDIndexedIter<'a, T>;
+
+    /// Return an iterator over the (index, element) pairs in column-major order.
+    fn indexed_iter_co(&'a self) -> NDIndexedIter<'a, T>;
+
+    /// Return an iterator over the sub-views along the first dimension, for example the rows of
+    /// a matrix.
+    fn outer_iter(&'a self) -> NDAxisIter<'a, T>;
+
+    /// Return an iterator over the sub-views along a given dimension. Each view has all the
+    /// dimensions of the data except axis, for example `axis_iter(1)` iterates over the columns
+    /// of a matrix.
+    fn axis_iter(&'a self, axis : usize) -> NDAxisIter<'a, T>;
+}
+
+/// A trait for N-dimensional data whose elements can be mutably iterated over.
+pub trait NDIterableMut<'a, T : 'a> : NDIterable<'a, T> {
+
+    /// Return an iterator over mutable references to the elements in row-major order. Panics if 
+    /// the strides make two indices designate the same element.
+    fn iter_mut(&'a mut self) -> NDIterMut<'a, T>;
+
+    /// Return an iterator over mutable references to the elements in column-major order. Panics 
+    /// if the strides make two indices designate the same element.
+    fn iter_mut_co(&'a mut self) -> NDIterMut<'a, T>;
+}
+
+/// Structure keeping track of the current index of an iteration over N-dimensional data.
+struct NDCursor<'a> {
+    shape : &'a [usize],
+    strides : &'a [usize],
+    offset : usize,
+    idx : Vec<usize>,
+    remaining : usize,
+    col_major : bool,
+}
+
+/// Iterator over the elements of N-dimensional data.
+pub struct NDIter<'a, T : 'a> {
+    cursor : NDCursor<'a>,
+    data : &'a [T],
+}
+
+/// Iterator over the (index, element) pairs of N-dimensional data.
+pub struct NDIndexedIter<'a, T : 'a> {
+    cursor : NDCursor<'a>,
+    data : &'a [T],
+}
+
+/// Iterator over mutable references to the elements of N-dimensional data.
+pub struct NDIterMut<'a, T : 'a> {
+    cursor : NDCursor<'a>,
+    data : *mut T,
+    len : usize,
+    marker : PhantomData<&'a mut T>,
+}
+
+/// Iterator over the sub-views of N-dimensional data along one dimension.
+pub struct NDAxisIter<'a, T : 'a> {
+    shape : Vec<usize>,
+    strides : Vec<usize>,
+    offset : usize,
+    stride : usize,
+    len : usize,
+    i : usize,
+    data : &'a [T],
+}
+
+impl<'a> NDCursor<'a> {
+
+    fn new(shape : &'a [usize], strides : &'a [usize], offset : usize, col_major : bool) -> NDCursor<'a> {
+        NDCursor {
+            shape : shape,
+            strides : strides,
+            offset : offset,
+            idx : repeat(0usize).take(shape.len()).collect(),
+            remaining : shape.iter().fold(1, |acc, &x| acc * x),
+            col_major : col_major,
+        }
+    }
+
+    /// Return the current index and its position in the storage array then move to the next one.
+    fn next(&mut self) -> Option<(Vec<usize>, usize)> {
+        if self.remaining == 0 {
+            return None;
+        }
+        let idx = self.idx.clone();
+        let pos = self.offset.wrapping_add(idx.to_pos(self.shape, self.strides));
+        if self.col_major {
+            self.idx.inc_co(self.shape);
+        }
+        else {
+            self.idx.inc_ro(self.shape);
+        }
+        self.remaining -= 1;
+        Some((idx, pos))
+    }
+
+    /// Same as next() but without returning the index.
+    fn next_pos(&mut self) -> Option<usize> {
+        if self.remaining == 0 {
+            return None;
+        }
+        let pos = self.offset.wrapping_add(self.idx.to_pos(self.shape, self.strides));
+        if self.col_major {
+            self.idx.inc_co(self.shape);
+        }
+        else {
+            self.idx.inc_ro(self.shape);
+        }
+        self.remaining -= 1;
+        Some(pos)
+    }
+}
+
+impl<'a, T : 'a> Iterator for NDIter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        let data = self.data;
+        self.cursor.next_pos().map(|pos| &data[pos])
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.cursor.remaining, Some(self.cursor.remaining))
+    }
+}
+
+impl<'a, T : 'a> ExactSizeIterator for NDIter<'a, T> {}
+
+impl<'a, T : 'a> Iterator for NDIndexedIter<'a, T> {
+    type Item = (Vec<usize>, &'a T);
+
+    fn next(&mut self) -> Option<(Vec<usize>, &'a T)> {
+        let data = self.data;
+        self.cursor.next().map(|(idx, pos)| (idx, &data[pos]))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.cursor.remaining, Some(self.cursor.remaining))
+    }
+}
+
+impl<'a, T : 'a> ExactSizeIterator for NDIndexedIter<'a, T> {}
+
+impl<'a, T : 'a> Iterator for NDIterMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<&'a mut T> {
+        match self.cursor.next_pos() {
+            Some(pos) => {
+                if pos >= self.len {
+                    panic!("NDIterMut::next(): position out of bound ({} >= {})", pos, self.len);
+                }
+                // check_disjoint ensured that every index maps to a different element.
+                unsafe {
+                    Some(&mut *self.data.offset(pos as isize))
+                }
+            },
+            None => None
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.cursor.remaining, Some(self.cursor.remaining))
+    }
+}
+
+impl<'a, T : 'a> ExactSizeIterator for NDIterMut<'a, T> {}
+
+impl<'a, T : 'a> Iterator for NDAxisIter<'a, T> {
+    type Item = NDView<'a, T>;
+
+    fn next(&mut self) -> Option<NDView<'a, T>> {
+        if self.i >= self.len {
+            return None;
+        }
+        let view = NDView {
+            shape : self.shape.clone(),
+            strides : self.strides.clone(),
+            offset : self.offset.wrapping_add(self.i.wrapping_mul(self.stride)),
+            data : self.data,
+        };
+        self.i += 1;
+        Some(view)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len - self.i, Some(self.len - self.i))
+    }
+}
+
+impl<'a, T : 'a> ExactSizeIterator for NDAxisIter<'a, T> {}
+
+impl<'a, T : 'a, R : NDData<T> + ?Sized> NDIterable<'a, T> for R {
+
+    fn iter(&'a self) -> NDIter<'a, T> {
+        NDIter {
+            cursor : NDCursor::new(self.shape(), self.strides(), self.offset(), false),
+            data : self.get_data(),
+        }
+    }
+
+    fn iter_co(&'a self) -> NDIter<'a, T> {
+        NDIter {
+            cursor : NDCursor::new(self.shape(), self.strides(), self.offset(), true),
+            data : self.get_data(),
+        }
+    }
+
+    fn indexed_iter(&'a self) -> NDIndexedIter<'a, T> {
+        NDIndexedIter {
+            cursor : NDCursor::new(self.shape(), self.strides(), self.offset(), false),
+            data : self.get_data(),
+        }
+    }
+
+    fn indexed_iter_co(&'a self) -> NDIndexedIter<'a, T> {
+        NDIndexedIter {
+            cursor : NDCursor::new(self.shape(), self.strides(), self.offset(), true),
+            data : self.get_data(),
+        }
+    }
+
+    fn outer_iter(&'a self) -> NDAxisIter<'a, T> {
+        self.axis_iter(0)
+    }
+
+    fn axis_iter(&'a self, axis : usize) -> NDAxisIter<'a, T> {
+        if axis >= self.dim() {
+            panic!("NDIterable::axis_iter({}): axis is greater than data dimension ({} >= {})", axis, axis, self.dim());
+        }
+        let mut shape = self.shape().to_vec();
+        let mut strides = self.strides().to_vec();
+        let len = shape.remove(axis);
+        let stride = strides.remove(axis);
+        NDAxisIter {
+            shape : shape,
+            strides : strides,
+            offset : self.offset(),
+            stride : stride,
+            len : len,
+            i : 0,
+            data : self.get_data(),
+        }
+    }
+}
+
+/// Panics unless every index of the shape designates a different position: sorted by magnitude, 
+/// the stride of each dimension longer than one must step over all the positions reached by the 
+/// smaller ones. NDIterMut relies on it to never hand out two references to the same element.
+fn check_disjoint(shape : &[usize], strides : &[usize]) {
+    if shape.iter().any(|&n| n == 0) {
+        return;
+    }
+    let mut dims : Vec<(usize, usize)> = shape.iter().zip(strides).filter(|&(&n, _)| n > 1)
+        .map(|(&n, &s)| (n, (s as isize).wrapping_abs() as usize)).collect();
+    dims.sort_by_key(|&(_, s)| s);
+    let mut extent = 1usize;
+    for &(n, s) in &dims {
+        if s < extent {
+            panic!("NDIterableMut: strides {:?} of shape {:?} designate an element several times", strides, shape);
+        }
+        extent = extent.saturating_add(s.saturating_mul(n - 1));
+    }
+}
+
+impl<'a, T : 'a + Clone + Display, R : NDDataMut<T> + ?Sized> NDIterableMut<'a, T> for R {
+
+    fn iter_mut(&'a mut self) -> NDIterMut<'a, T> {
+        check_disjoint(self.shape(), self.strides());
+        let offset = self.offset();
+        let data = self.get_data_mut();
+        let len = data.len();
+        let ptr = data.as_mut_ptr();
+        NDIterMut {
+            cursor : NDCursor::new(self.shape(), self.strides(), offset, false),
+            data : ptr,
+            len : len,
+            marker : PhantomData,
+        }
+    }
+
+    fn iter_mut_co(&'a mut self) -> NDIterMut<'a, T> {
+        check_disjoint(self.shape(), self.strides());
+        let offset = self.offset();
+        let data = self.get_data_mut();
+        let len = data.len();
+        let ptr = data.as_mut_ptr();
+        NDIterMut {
+            cursor : NDCursor::new(self.shape(), self.strides(), offset, true),
+            data : ptr,
+            len : len,
+            marker : PhantomData,
+        }
+    }
+}
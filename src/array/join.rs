@@ -0,0 +1,116 @@
+use std::iter::repeat;
+
+use array::{NDData, NDArray, NDView, NDViewable};
+use array::ndindex::NDIndex;
+
+/// Return a view of data with a new dimension of length 1 inserted at axis.
+fn expand<'a, T>(data : &'a NDData<T>, axis : usize) -> NDView<'a, T> {
+    let mut shape = data.shape().to_vec();
+    let mut strides = data.strides().to_vec();
+    shape.insert(axis, 1);
+    strides.insert(axis, 0);
+    NDView {
+        shape : shape,
+        strides : strides,
+        offset : data.offset(),
+        data : data.get_data(),
+    }
+}
+
+/// Join a sequence of arrays along an existing dimension. The arrays need to have the same shape
+/// except along axis. The result is allocated once.
+pub fn concatenate<T : Clone>(arrays : &[&NDData<T>], axis : usize) -> NDArray<T> {
+    if arrays.len() == 0 {
+        panic!("concatenate(): at least one array is needed");
+    }
+    let dim = arrays[0].dim();
+    if axis >= dim {
+        panic!("concatenate(): axis is greater than array dimension ({} >= {})", axis, dim);
+    }
+    let mut shape = arrays[0].shape().to_vec();
+    for k in 1..arrays.len() {
+        if arrays[k].dim() != dim {
+            panic!("concatenate(): dimensions are differents for array {} ({} != {})", k, arrays[k].dim(), dim);
+        }
+        for i in 0..dim {
+            if i != axis && arrays[k].shape()[i] != shape[i] {
+                panic!("concatenate(): shapes are different for array {} at dimension {} ({} != {})", k, i, arrays[k].shape()[i], shape[i]);
+            }
+        }
+        shape[axis] += arrays[k].shape()[axis];
+    }
+
+    let size = shape.iter().fold(1, |acc, &x| acc * x);
+    let mut data = Vec::<T>::with_capacity(size);
+    let outer = shape[..axis].iter().fold(1, |acc, &x| acc * x);
+    let mut outer_idx : Vec<usize> = repeat(0usize).take(axis).collect();
+    for _ in 0..outer {
+        for array in arrays {
+            let inner_shape = &array.shape()[axis..];
+            let inner = inner_shape.iter().fold(1, |acc, &x| acc * x);
+            let mut idx = outer_idx.clone();
+            idx.extend(repeat(0usize).take(dim - axis));
+            for _ in 0..inner {
+                data.push(array.idx(&idx[..]).clone());
+                idx[axis..].inc_ro(inner_shape);
+            }
+        }
+        outer_idx.inc_ro(&shape[..axis]);
+    }
+
+    NDArray {
+        strides : NDArray::<T>::compute_strides(&shape[..]),
+        shape : shape,
+        data : data.into_boxed_slice(),
+    }
+}
+
+/// Join a sequence of arrays of the same shape along a new dimension inserted at axis.
+pub fn stack<T : Clone>(arrays : &[&NDData<T>], axis : usize) -> NDArray<T> {
+    if arrays.len() == 0 {
+        panic!("stack(): at least one array is needed");
+    }
+    if axis > arrays[0].dim() {
+        panic!("stack(): axis is greater than array dimension ({} > {})", axis, arrays[0].dim());
+    }
+    for k in 1..arrays.len() {
+        if arrays[k].shape() != arrays[0].shape() {
+            panic!("stack(): shapes are different for array {} ({:?} != {:?})", k, arrays[k].shape(), arrays[0].shape());
+        }
+    }
+    let views : Vec<NDView<T>> = arrays.iter().map(|a| expand(*a, axis)).collect();
+    let refs : Vec<&NDData<T>> = views.iter().map(|v| v as &NDData<T>).collect();
+    concatenate(&refs[..], axis)
+}
+
+/// Join a sequence of arrays row wise: one dimensional arrays of length N are treated as 1xN
+/// matrices then the arrays are concatenated along the first dimension.
+pub fn vstack<T : Clone>(arrays : &[&NDData<T>]) -> NDArray<T> {
+    if arrays.len() == 0 {
+        panic!("vstack(): at least one array is needed");
+    }
+    let views : Vec<NDView<T>> = arrays.iter().map(|a| {
+        if a.dim() == 1 {
+            expand(*a, 0)
+        }
+        else {
+            a.view(&[])
+        }
+    }).collect();
+    let refs : Vec<&NDData<T>> = views.iter().map(|v| v as &NDData<T>).collect();
+    concatenate(&refs[..], 0)
+}
+
+/// Join a sequence of arrays column wise: one dimensional arrays are concatenated along their
+/// only dimension, other arrays along the second dimension.
+pub fn hstack<T : Clone>(arrays : &[&NDData<T>]) -> NDArray<T> {
+    if arrays.len() == 0 {
+        panic!("hstack(): at least one array is needed");
+    }
+    if arrays[0].dim() == 1 {
+        concatenate(arrays, 0)
+    }
+    else {
+        concatenate(arrays, 1)
+    }
+}
@@ -0,0 +1,94 @@
+use std::fmt::Display;
+
+use array::{NDData, NDDataMut, NDArray, NDViewable};
+use array::broadcast::{zip_with, par_zip_with};
+use array::iter::{NDIterable, NDIterableMut};
+use parallel;
+
+/// A trait for N-dimensional data to which closures can be applied element-wise. The shape and
+/// the strides of the data are respected and the results are stored in row-major order.
+///
+/// The `par_` variants split large data across the thread pool and therefore require the
+/// elements, the results and the closure to be shareable between threads.
+pub trait NDMappable<T> {
+
+    /// Apply f to every element and return the results as a new NDArray of the same shape.
+    fn map<U : Clone, F : Fn(&T) -> U>(&self, f : F) -> NDArray<U>;
+
+    /// Apply f to every pair of elements of self and other, broadcasted together, and return the
+    /// results as a new NDArray having the broadcasted shape.
+    fn zip_map<S, U : Clone, F : Fn(&T, &S) -> U>(&self, other : &NDData<S>, f : F) -> NDArray<U>;
+
+    /// Parallel version of map.
+    fn par_map<U : Clone + Send, F : Fn(&T) -> U + Sync>(&self, f : F) -> NDArray<U> where T : Sync;
+
+    /// Parallel version of zip_map.
+    fn par_zip_map<S : Sync, U : Clone + Send, F : Fn(&T, &S) -> U + Sync>(&self, other : &NDData<S>, f : F) -> NDArray<U> where T : Sync;
+
+    /// Accumulate the elements in row-major order, starting from init.
+    fn fold<A, F : Fn(A, &T) -> A>(&self, init : A, f : F) -> A;
+}
+
+/// A trait for mutable N-dimensional data which can be modified element-wise by a closure.
+pub trait NDMappableMut<T> : NDMappable<T> {
+
+    /// Apply f to every element in place.
+    fn map_inplace<F : Fn(&mut T)>(&mut self, f : F);
+
+    /// Parallel version of map_inplace. Only contiguous data is split across threads.
+    fn par_map_inplace<F : Fn(&mut T) + Sync>(&mut self, f : F) where T : Send;
+}
+
+impl<T, R : NDData<T> + ?Sized> NDMappable<T> for R {
+
+    fn map<U : Clone, F : Fn(&T) -> U>(&self, f : F) -> NDArray<U> {
+        let data : Vec<U> = self.iter().map(f).collect();
+        NDArray {
+            shape : self.shape().to_vec(),
+            strides : NDArray::<U>::compute_strides(self.shape()),
+            data : data.into_boxed_slice(),
+        }
+    }
+
+    fn zip_map<S, U : Clone, F : Fn(&T, &S) -> U>(&self, other : &NDData<S>, f : F) -> NDArray<U> {
+        // The view gives a sized NDData even when Self is a trait object
+        zip_with(&self.view(&[]), other, f)
+    }
+
+    fn par_map<U : Clone + Send, F : Fn(&T) -> U + Sync>(&self, f : F) -> NDArray<U> where T : Sync {
+        let data : Vec<U> = parallel::map_elements(&self.view(&[]), f);
+        NDArray {
+            shape : self.shape().to_vec(),
+            strides : NDArray::<U>::compute_strides(self.shape()),
+            data : data.into_boxed_slice(),
+        }
+    }
+
+    fn par_zip_map<S : Sync, U : Clone + Send, F : Fn(&T, &S) -> U + Sync>(&self, other : &NDData<S>, f : F) -> NDArray<U> where T : Sync {
+        par_zip_with(&self.view(&[]), other, f)
+    }
+
+    fn fold<A, F : Fn(A, &T) -> A>(&self, init : A, f : F) -> A {
+        self.iter().fold(init, f)
+    }
+}
+
+impl<T : Clone + Display, R : NDDataMut<T> + ?Sized> NDMappableMut<T> for R {
+
+    fn map_inplace<F : Fn(&mut T)>(&mut self, f : F) {
+        for x in self.iter_mut() {
+            f(x);
+        }
+    }
+
+    fn par_map_inplace<F : Fn(&mut T) + Sync>(&mut self, f : F) where T : Send {
+        if !self.is_contiguous() {
+            return self.map_inplace(f);
+        }
+        parallel::for_each_chunk_mut(self.get_data_mut(), |_, chunk| {
+            for x in chunk.iter_mut() {
+                f(x);
+            }
+        });
+    }
+}
@@ -0,0 +1,203 @@
+use std::fmt::Display;
+
+use array::{NDData, NDDataMut, NDArray, NDRange, NDViewable, NDViewableMut};
+use array::broadcast::zip_with;
+use array::iter::{NDIterable, NDIterableMut};
+use array::join::concatenate;
+
+/// A trait for N-dimensional data which can be compared element-wise to produce boolean masks.
+/// The `elem_*` functions broadcast the operands together while the scalar variants compare every
+/// element to a single value. The scalar equality tests are named `equal` and `not_equal` as `eq`
+/// and `ne` are already taken by `PartialEq`.
+pub trait NDComparable<T> {
+
+    /// Element-wise self == other.
+    fn elem_eq(&self, other : &NDData<T>) -> NDArray<bool> where T : PartialEq;
+
+    /// Element-wise self != other.
+    fn elem_ne(&self, other : &NDData<T>) -> NDArray<bool> where T : PartialEq;
+
+    /// Element-wise self < other.
+    fn elem_lt(&self, other : &NDData<T>) -> NDArray<bool> where T : PartialOrd;
+
+    /// Element-wise self <= other.
+    fn elem_le(&self, other : &NDData<T>) -> NDArray<bool> where T : PartialOrd;
+
+    /// Element-wise self > other.
+    fn elem_gt(&self, other : &NDData<T>) -> NDArray<bool> where T : PartialOrd;
+
+    /// Element-wise self >= other.
+    fn elem_ge(&self, other : &NDData<T>) -> NDArray<bool> where T : PartialOrd;
+
+    /// Element-wise self == value.
+    fn equal(&self, value : T) -> NDArray<bool> where T : Clone + PartialEq;
+
+    /// Element-wise self != value.
+    fn not_equal(&self, value : T) -> NDArray<bool> where T : Clone + PartialEq;
+
+    /// Element-wise self < value.
+    fn lt(&self, value : T) -> NDArray<bool> where T : Clone + PartialOrd;
+
+    /// Element-wise self <= value.
+    fn le(&self, value : T) -> NDArray<bool> where T : Clone + PartialOrd;
+
+    /// Element-wise self > value.
+    fn gt(&self, value : T) -> NDArray<bool> where T : Clone + PartialOrd;
+
+    /// Element-wise self >= value.
+    fn ge(&self, value : T) -> NDArray<bool> where T : Clone + PartialOrd;
+}
+
+/// A trait for N-dimensional data from which elements can be gathered with a boolean mask or a
+/// list of indices.
+pub trait NDSelectable<T> {
+
+    /// Return the elements for which mask is true, in row-major order, as a one dimensional
+    /// NDArray. The mask needs to have the same shape as the data.
+    fn select(&self, mask : &NDData<bool>) -> NDArray<T>;
+
+    /// Return a new NDArray made of the sub-arrays at the given indices along axis. Indices can be
+    /// repeated and in any order.
+    fn take(&self, indices : &[usize], axis : usize) -> NDArray<T>;
+}
+
+/// A trait for mutable N-dimensional data which can be modified through a boolean mask or a list
+/// of indices.
+pub trait NDSelectableMut<T> : NDSelectable<T> {
+
+    /// Assign value to every element for which mask is true. The mask needs to have the same
+    /// shape as the data.
+    fn masked_assign(&mut self, mask : &NDData<bool>, value : T);
+
+    /// Assign the sub-arrays of values to the given indices along axis. values needs the same
+    /// shape as the data except along axis where its length is the number of indices.
+    fn put(&mut self, indices : &[usize], values : &NDData<T>, axis : usize);
+}
+
+/// Return the ranges applying range to axis and selecting the whole previous dimensions.
+fn axis_ranges(axis : usize, range : NDRange) -> Vec<NDRange> {
+    let mut ranges = vec![NDRange::all(); axis];
+    ranges.push(range);
+    return ranges;
+}
+
+impl<T, R : NDData<T> + ?Sized> NDComparable<T> for R {
+
+    fn elem_eq(&self, other : &NDData<T>) -> NDArray<bool> where T : PartialEq {
+        zip_with(&self.view(&[]), other, |a, b| a == b)
+    }
+
+    fn elem_ne(&self, other : &NDData<T>) -> NDArray<bool> where T : PartialEq {
+        zip_with(&self.view(&[]), other, |a, b| a != b)
+    }
+
+    fn elem_lt(&self, other : &NDData<T>) -> NDArray<bool> where T : PartialOrd {
+        zip_with(&self.view(&[]), other, |a, b| a < b)
+    }
+
+    fn elem_le(&self, other : &NDData<T>) -> NDArray<bool> where T : PartialOrd {
+        zip_with(&self.view(&[]), other, |a, b| a <= b)
+    }
+
+    fn elem_gt(&self, other : &NDData<T>) -> NDArray<bool> where T : PartialOrd {
+        zip_with(&self.view(&[]), other, |a, b| a > b)
+    }
+
+    fn elem_ge(&self, other : &NDData<T>) -> NDArray<bool> where T : PartialOrd {
+        zip_with(&self.view(&[]), other, |a, b| a >= b)
+    }
+
+    fn equal(&self, value : T) -> NDArray<bool> where T : Clone + PartialEq {
+        self.elem_eq(&NDArray::<T>::new(&[], value))
+    }
+
+    fn not_equal(&self, value : T) -> NDArray<bool> where T : Clone + PartialEq {
+        self.elem_ne(&NDArray::<T>::new(&[], value))
+    }
+
+    fn lt(&self, value : T) -> NDArray<bool> where T : Clone + PartialOrd {
+        self.elem_lt(&NDArray::<T>::new(&[], value))
+    }
+
+    fn le(&self, value : T) -> NDArray<bool> where T : Clone + PartialOrd {
+        self.elem_le(&NDArray::<T>::new(&[], value))
+    }
+
+    fn gt(&self, value : T) -> NDArray<bool> where T : Clone + PartialOrd {
+        self.elem_gt(&NDArray::<T>::new(&[], value))
+    }
+
+    fn ge(&self, value : T) -> NDArray<bool> where T : Clone + PartialOrd {
+        self.elem_ge(&NDArray::<T>::new(&[], value))
+    }
+}
+
+impl<T : Clone, R : NDData<T> + ?Sized> NDSelectable<T> for R {
+
+    fn select(&self, mask : &NDData<bool>) -> NDArray<T> {
+        if mask.shape() != self.shape() {
+            panic!("NDSelectable::select(): mask is not of the same shape ({:?} != {:?})", mask.shape(), self.shape());
+        }
+        let data : Vec<T> = self.iter().zip(mask.iter()).filter(|&(_, &m)| m).map(|(x, _)| x.clone()).collect();
+        NDArray {
+            shape : vec![data.len()],
+            strides : vec![1],
+            data : data.into_boxed_slice(),
+        }
+    }
+
+    fn take(&self, indices : &[usize], axis : usize) -> NDArray<T> {
+        if axis >= self.dim() {
+            panic!("NDSelectable::take(): axis is greater than data dimension ({} >= {})", axis, self.dim());
+        }
+        for &i in indices {
+            if i >= self.shape()[axis] {
+                panic!("NDSelectable::take(): index is out of bound ({} >= {})", i, self.shape()[axis]);
+            }
+        }
+        if indices.len() == 0 {
+            let mut shape = self.shape().to_vec();
+            shape[axis] = 0;
+            return NDArray {
+                strides : NDArray::<T>::compute_strides(&shape[..]),
+                shape : shape,
+                data : Vec::new().into_boxed_slice(),
+            };
+        }
+        let views : Vec<_> = indices.iter().map(|&i| self.view(&axis_ranges(axis, NDRange::new(i, i + 1))[..])).collect();
+        let refs : Vec<&NDData<T>> = views.iter().map(|v| v as &NDData<T>).collect();
+        concatenate(&refs[..], axis)
+    }
+}
+
+impl<T : Clone + Display, R : NDDataMut<T> + ?Sized> NDSelectableMut<T> for R {
+
+    fn masked_assign(&mut self, mask : &NDData<bool>, value : T) {
+        if mask.shape() != self.shape() {
+            panic!("NDSelectableMut::masked_assign(): mask is not of the same shape ({:?} != {:?})", mask.shape(), self.shape());
+        }
+        for (x, &m) in self.iter_mut().zip(mask.iter()) {
+            if m {
+                *x = value.clone();
+            }
+        }
+    }
+
+    fn put(&mut self, indices : &[usize], values : &NDData<T>, axis : usize) {
+        if axis >= self.dim() {
+            panic!("NDSelectableMut::put(): axis is greater than data dimension ({} >= {})", axis, self.dim());
+        }
+        let mut shape = self.shape().to_vec();
+        shape[axis] = indices.len();
+        if values.shape() != &shape[..] {
+            panic!("NDSelectableMut::put(): values is not of the right shape ({:?} != {:?})", values.shape(), shape);
+        }
+        for (k, &i) in indices.iter().enumerate() {
+            if i >= self.shape()[axis] {
+                panic!("NDSelectableMut::put(): index is out of bound ({} >= {})", i, self.shape()[axis]);
+            }
+            let source = values.view(&axis_ranges(axis, NDRange::index(k))[..]);
+            self.view_mut(&axis_ranges(axis, NDRange::index(i))[..]).assign(&source);
+        }
+    }
+}
@@ -0,0 +1,545 @@
+extern crate byteorder;
+extern crate flate2;
+
+use std::fmt::Display;
+use std::fs::{File, OpenOptions};
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::str;
+
+use self::byteorder::{ByteOrder, BigEndian, LittleEndian};
+use self::flate2::Compression;
+use self::flate2::read::ZlibDecoder;
+use self::flate2::write::ZlibEncoder;
+
+use types::{RDSType, RDSTyped};
+use types::complex::{c32, c64};
+use types::cast::Cast;
+use array::{NDArray, NDData, NDViewable};
+use array::iter::NDIterable;
+use error::Error;
+
+const HEADER_SIZE : usize = 128;
+
+const MI_INT8 : u32 = 1;
+const MI_UINT8 : u32 = 2;
+const MI_INT16 : u32 = 3;
+const MI_UINT16 : u32 = 4;
+const MI_INT32 : u32 = 5;
+const MI_UINT32 : u32 = 6;
+const MI_SINGLE : u32 = 7;
+const MI_DOUBLE : u32 = 9;
+const MI_INT64 : u32 = 12;
+const MI_UINT64 : u32 = 13;
+const MI_MATRIX : u32 = 14;
+const MI_COMPRESSED : u32 = 15;
+const MI_UTF8 : u32 = 16;
+
+const MX_DOUBLE : u8 = 6;
+const MX_SINGLE : u8 = 7;
+const MX_INT8 : u8 = 8;
+const MX_UINT8 : u8 = 9;
+const MX_INT16 : u8 = 10;
+const MX_UINT16 : u8 = 11;
+const MX_INT32 : u8 = 12;
+const MX_UINT32 : u8 = 13;
+const MX_INT64 : u8 = 14;
+const MX_UINT64 : u8 = 15;
+
+const FLAG_COMPLEX : u32 = 0x800;
+
+/// Structure representing a MATLAB Level 5 MAT-file (.mat).
+///
+/// A MatlabFile creates the MAT-file on its first write, and fails if the file already exists. As
+/// the data elements of a MAT-file delimit themselves, each variable written is appended to the
+/// file, which is complete after every write. Only the variables following a replaced one are 
+/// written again.
+pub struct MatlabFile {
+    path : String,
+    file : Option<File>,
+    /// The names of the variables written, with the size of their data element.
+    variables : Vec<(String, u64)>,
+    /// A boolean indicating whether the variables written should be compressed, false by default.
+    pub compression : bool,
+}
+
+/// A matrix data element, of which only the numeric classes can be read.
+struct Matrix<'a> {
+    class : u8,
+    name : String,
+    shape : Vec<usize>,
+    real : Option<(u32, &'a [u8])>,
+    imag : Option<(u32, &'a [u8])>,
+}
+
+/// The content of a MAT-file loaded in memory, with the compressed elements inflated.
+struct Reader {
+    big_endian : bool,
+    matrices : Vec<Vec<u8>>,
+}
+
+fn decode_u8<T : Copy>(b : &[u8]) -> T where u8 : Cast<T> {
+    Cast::<T>::cast(b[0])
+}
+
+fn decode_u16<T : Copy, B : ByteOrder>(b : &[u8]) -> T where u16 : Cast<T> {
+    Cast::<T>::cast(B::read_u16(b))
+}
+
+fn decode_u32<T : Copy, B : ByteOrder>(b : &[u8]) -> T where u32 : Cast<T> {
+    Cast::<T>::cast(B::read_u32(b))
+}
+
+fn decode_u64<T : Copy, B : ByteOrder>(b : &[u8]) -> T where u64 : Cast<T> {
+    Cast::<T>::cast(B::read_u64(b))
+}
+
+fn decode_i8<T : Copy>(b : &[u8]) -> T where i8 : Cast<T> {
+    Cast::<T>::cast(b[0] as i8)
+}
+
+fn decode_i16<T : Copy, B : ByteOrder>(b : &[u8]) -> T where i16 : Cast<T> {
+    Cast::<T>::cast(B::read_i16(b))
+}
+
+fn decode_i32<T : Copy, B : ByteOrder>(b : &[u8]) -> T where i32 : Cast<T> {
+    Cast::<T>::cast(B::read_i32(b))
+}
+
+fn decode_i64<T : Copy, B : ByteOrder>(b : &[u8]) -> T where i64 : Cast<T> {
+    Cast::<T>::cast(B::read_i64(b))
+}
+
+fn decode_f32<T : Copy, B : ByteOrder>(b : &[u8]) -> T where f32 : Cast<T> {
+    Cast::<T>::cast(B::read_f32(b))
+}
+
+fn decode_f64<T : Copy, B : ByteOrder>(b : &[u8]) -> T where f64 : Cast<T> {
+    Cast::<T>::cast(B::read_f64(b))
+}
+
+/// Select the function decoding one value stored as the data type kind and casting it to T,
+/// together with the size of the stored value. MATLAB may store a matrix with a smaller data type
+/// than its class, for example a double matrix of small integers as miUINT8.
+fn decoder<T : Copy>(kind : u32, big_endian : bool) -> Result<(fn(&[u8]) -> T, usize), Error>
+    where u8 : Cast<T>, u16 : Cast<T>, u32 : Cast<T>, u64 : Cast<T>,
+          i8 : Cast<T>, i16 : Cast<T>, i32 : Cast<T>, i64 : Cast<T>,
+          f32 : Cast<T>, f64 : Cast<T> {
+    Ok(match (kind, big_endian) {
+        (MI_INT8, _) => (decode_i8::<T>, 1),
+        (MI_UINT8, _) => (decode_u8::<T>, 1),
+        (MI_INT16, true) => (decode_i16::<T, BigEndian>, 2),
+        (MI_INT16, false) => (decode_i16::<T, LittleEndian>, 2),
+        (MI_UINT16, true) => (decode_u16::<T, BigEndian>, 2),
+        (MI_UINT16, false) => (decode_u16::<T, LittleEndian>, 2),
+        (MI_INT32, true) => (decode_i32::<T, BigEndian>, 4),
+        (MI_INT32, false) => (decode_i32::<T, LittleEndian>, 4),
+        (MI_UINT32, true) => (decode_u32::<T, BigEndian>, 4),
+        (MI_UINT32, false) => (decode_u32::<T, LittleEndian>, 4),
+        (MI_INT64, true) => (decode_i64::<T, BigEndian>, 8),
+        (MI_INT64, false) => (decode_i64::<T, LittleEndian>, 8),
+        (MI_UINT64, true) => (decode_u64::<T, BigEndian>, 8),
+        (MI_UINT64, false) => (decode_u64::<T, LittleEndian>, 8),
+        (MI_SINGLE, true) => (decode_f32::<T, BigEndian>, 4),
+        (MI_SINGLE, false) => (decode_f32::<T, LittleEndian>, 4),
+        (MI_DOUBLE, true) => (decode_f64::<T, BigEndian>, 8),
+        (MI_DOUBLE, false) => (decode_f64::<T, LittleEndian>, 8),
+        _ => return Err(Error::Format(format!("Invalid MATLAB numeric data type {}", kind)))
+    })
+}
+
+/// Decode size values of a numeric data element, in column-major order.
+fn decode_values<T : Copy>(element : (u32, &[u8]), size : usize, big_endian : bool) -> Result<Vec<T>, Error>
+    where u8 : Cast<T>, u16 : Cast<T>, u32 : Cast<T>, u64 : Cast<T>,
+          i8 : Cast<T>, i16 : Cast<T>, i32 : Cast<T>, i64 : Cast<T>,
+          f32 : Cast<T>, f64 : Cast<T> {
+    let (decode, value_size) = decoder::<T>(element.0, big_endian)?;
+    match size.checked_mul(value_size) {
+        Some(bytes) if bytes <= element.1.len() => {},
+        _ => return Err(Error::Format(format!("MATLAB numeric data element holds {} bytes, not enough for {} values", element.1.len(), size)))
+    }
+    Ok(element.1.chunks(value_size).take(size).map(decode).collect())
+}
+
+impl Reader {
+
+    /// Load the file, check its header and collect the content of its matrix elements.
+    fn open(path : &str) -> Result<Reader, Error> {
+        let mut data = Vec::<u8>::new();
+        match File::open(path) {
+            Ok(mut f) => {
+                if let Err(e) = f.read_to_end(&mut data) {
+                    return Err(Error::Io(e));
+                }
+            },
+            Err(e) => return Err(Error::Io(e))
+        }
+
+        if data.len() < HEADER_SIZE || !data.starts_with(b"MATLAB") {
+            return Err(Error::Format(format!("File {} does not have a valid MATLAB Level 5 header", path)));
+        }
+        let big_endian = match &data[126..128] {
+            b"IM" => false,
+            b"MI" => true,
+            _ => return Err(Error::Format(format!("Invalid MATLAB endian indicator in {}", path)))
+        };
+        let mut reader = Reader {
+            big_endian : big_endian,
+            matrices : Vec::new(),
+        };
+        let version = reader.u16(&data[124..126]);
+        if version != 0x0100 {
+            // Version 7.3 MAT-files are HDF5 files which can be read with array::hdf5
+            return Err(Error::Format(format!("MATLAB MAT-file version {:#x} not supported", version)));
+        }
+
+        let mut pos = HEADER_SIZE;
+        while pos < data.len() {
+            let (kind, content) = reader.element(&data, &mut pos)?;
+            match kind {
+                MI_MATRIX => reader.matrices.push(content.to_vec()),
+                MI_COMPRESSED => {
+                    let mut inflated = Vec::<u8>::new();
+                    if let Err(e) = ZlibDecoder::new(content).read_to_end(&mut inflated) {
+                        return Err(Error::Format(format!("Failed to inflate MATLAB compressed element: {}", e)));
+                    }
+                    let mut inner = 0;
+                    let (kind, content) = reader.element(&inflated, &mut inner)?;
+                    if kind == MI_MATRIX {
+                        reader.matrices.push(content.to_vec());
+                    }
+                },
+                _ => {}
+            }
+        }
+        Ok(reader)
+    }
+
+    fn u16(&self, b : &[u8]) -> u16 {
+        if self.big_endian { BigEndian::read_u16(b) } else { LittleEndian::read_u16(b) }
+    }
+
+    fn u32(&self, b : &[u8]) -> u32 {
+        if self.big_endian { BigEndian::read_u32(b) } else { LittleEndian::read_u32(b) }
+    }
+
+    /// Parse the data element at pos, in the normal or small data element format, and move pos to
+    /// the next element. Elements are aligned on 8 bytes, except after compressed elements.
+    fn element<'a>(&self, data : &'a [u8], pos : &mut usize) -> Result<(u32, &'a [u8]), Error> {
+        if data.len() < *pos + 8 {
+            return Err(Error::Format(format!("Truncated MATLAB data element tag at offset {}", *pos)));
+        }
+        let tag = self.u32(&data[*pos..*pos + 4]);
+        if tag >> 16 != 0 {
+            let size = (tag >> 16) as usize;
+            if size > 4 {
+                return Err(Error::Format(format!("Invalid MATLAB small data element size {} at offset {}", size, *pos)));
+            }
+            let content = &data[*pos + 4..*pos + 4 + size];
+            *pos += 8;
+            return Ok((tag & 0xffff, content));
+        }
+        let size = self.u32(&data[*pos + 4..*pos + 8]) as usize;
+        let start = *pos + 8;
+        if data.len() - start < size {
+            return Err(Error::Format(format!("MATLAB data element at offset {} extends past the end of its data", *pos)));
+        }
+        *pos = if tag == MI_COMPRESSED { start + size } else { start + (size + 7) / 8 * 8 };
+        Ok((tag, &data[start..start + size]))
+    }
+
+    /// Parse the array flags, dimensions, name and, for the numeric classes, the real and
+    /// imaginary parts of a matrix element.
+    fn matrix<'a>(&self, data : &'a [u8]) -> Result<Matrix<'a>, Error> {
+        let mut pos = 0;
+        let (kind, flags) = self.element(data, &mut pos)?;
+        if kind != MI_UINT32 || flags.len() < 8 {
+            return Err(Error::Format("Invalid MATLAB array flags".to_string()));
+        }
+        let flags = self.u32(&flags[0..4]);
+        let class = (flags & 0xff) as u8;
+
+        let (kind, dims) = self.element(data, &mut pos)?;
+        if kind != MI_INT32 {
+            return Err(Error::Format("Invalid MATLAB array dimensions".to_string()));
+        }
+        let shape : Vec<usize> = dims.chunks(4).map(|d| self.u32(d) as usize).collect();
+
+        let (kind, name) = self.element(data, &mut pos)?;
+        if kind != MI_INT8 && kind != MI_UTF8 {
+            return Err(Error::Format("Invalid MATLAB array name".to_string()));
+        }
+        let name = match str::from_utf8(name) {
+            Ok(s) => s.to_string(),
+            Err(e) => return Err(Error::Parse { message : "MATLAB array name is not valid UTF-8".to_string(), source : Some(Box::new(e)) })
+        };
+
+        let mut matrix = Matrix {
+            class : class,
+            name : name,
+            shape : shape,
+            real : None,
+            imag : None,
+        };
+        if class >= MX_DOUBLE && class <= MX_UINT64 {
+            matrix.real = Some(self.element(data, &mut pos)?);
+            if flags & FLAG_COMPLEX != 0 {
+                matrix.imag = Some(self.element(data, &mut pos)?);
+            }
+        }
+        Ok(matrix)
+    }
+}
+
+fn push_uint(buf : &mut Vec<u8>, v : u64, n : usize) {
+    for i in 0..n {
+        buf.push((v >> (8 * i)) as u8);
+    }
+}
+
+/// Append a little endian data element in the normal format, padded to 8 bytes.
+fn push_element(buf : &mut Vec<u8>, kind : u32, data : &[u8]) {
+    push_uint(buf, kind as u64, 4);
+    push_uint(buf, data.len() as u64, 4);
+    buf.extend_from_slice(data);
+    while buf.len() % 8 != 0 {
+        buf.push(0);
+    }
+}
+
+/// Return the class, the data type and the size of a value of the MATLAB representation of dtype.
+/// Complex numbers are stored as two parts of their real type.
+fn matlab_type(dtype : &RDSType) -> (u8, u32, usize) {
+    match *dtype {
+        RDSType::U8 => (MX_UINT8, MI_UINT8, 1),
+        RDSType::U16 => (MX_UINT16, MI_UINT16, 2),
+        RDSType::U32 => (MX_UINT32, MI_UINT32, 4),
+        RDSType::U64 => (MX_UINT64, MI_UINT64, 8),
+        RDSType::I8 => (MX_INT8, MI_INT8, 1),
+        RDSType::I16 => (MX_INT16, MI_INT16, 2),
+        RDSType::I32 => (MX_INT32, MI_INT32, 4),
+        RDSType::I64 => (MX_INT64, MI_INT64, 8),
+        RDSType::F32 | RDSType::C32 => (MX_SINGLE, MI_SINGLE, 4),
+        RDSType::F64 | RDSType::C64 => (MX_DOUBLE, MI_DOUBLE, 8),
+    }
+}
+
+/// Append the little endian representation of v, stored as its own type, to real and, for the
+/// complex types, of its imaginary part to imag.
+fn encode<T : Copy + RDSTyped>(real : &mut Vec<u8>, imag : &mut Vec<u8>, v : T) {
+    match T::t() {
+        RDSType::U8 => push_uint(real, Cast::<u8>::cast(v) as u64, 1),
+        RDSType::U16 => push_uint(real, Cast::<u16>::cast(v) as u64, 2),
+        RDSType::U32 => push_uint(real, Cast::<u32>::cast(v) as u64, 4),
+        RDSType::U64 => push_uint(real, Cast::<u64>::cast(v), 8),
+        RDSType::I8 => push_uint(real, Cast::<i8>::cast(v) as u64, 1),
+        RDSType::I16 => push_uint(real, Cast::<i16>::cast(v) as u64, 2),
+        RDSType::I32 => push_uint(real, Cast::<i32>::cast(v) as u64, 4),
+        RDSType::I64 => push_uint(real, Cast::<i64>::cast(v) as u64, 8),
+        RDSType::F32 => push_uint(real, Cast::<f32>::cast(v).to_bits() as u64, 4),
+        RDSType::F64 => push_uint(real, Cast::<f64>::cast(v).to_bits(), 8),
+        RDSType::C32 => {
+            let c = Cast::<c32>::cast(v);
+            push_uint(real, c.re.to_bits() as u64, 4);
+            push_uint(imag, c.im.to_bits() as u64, 4);
+        },
+        RDSType::C64 => {
+            let c = Cast::<c64>::cast(v);
+            push_uint(real, c.re.to_bits(), 8);
+            push_uint(imag, c.im.to_bits(), 8);
+        },
+    }
+}
+
+/// Encode data as a matrix element named name, in column-major order. MATLAB matrices have at
+/// least two dimensions: a scalar is stored as a 1x1 matrix and a vector as a row vector.
+fn matrix_element<T : Copy + RDSTyped>(name : &str, data : &NDData<T>) -> Result<Vec<u8>, Error> {
+    let (class, kind, size) = matlab_type(&T::t());
+    let complex = match T::t() {
+        RDSType::C32 | RDSType::C64 => true,
+        _ => false
+    };
+    let mut shape = data.shape().to_vec();
+    while shape.len() < 2 {
+        shape.insert(0, 1);
+    }
+    if shape.iter().any(|&s| s > i32::max_value() as usize) || data.size() * size > u32::max_value() as usize / 2 {
+        return Err(Error::Format(format!("Array of shape {:?} is too large for a MATLAB Level 5 MAT-file", data.shape())));
+    }
+
+    let mut real = Vec::<u8>::with_capacity(data.size() * size);
+    let mut imag = Vec::<u8>::with_capacity(if complex { data.size() * size } else { 0 });
+    for &v in data.iter_co() {
+        encode(&mut real, &mut imag, v);
+    }
+
+    let mut matrix = Vec::<u8>::new();
+    let mut flags = Vec::<u8>::with_capacity(8);
+    push_uint(&mut flags, class as u64 | if complex { FLAG_COMPLEX as u64 } else { 0 }, 4);
+    push_uint(&mut flags, 0, 4);
+    push_element(&mut matrix, MI_UINT32, &flags);
+    let mut dims = Vec::<u8>::with_capacity(4 * shape.len());
+    for &s in &shape {
+        push_uint(&mut dims, s as u64, 4);
+    }
+    push_element(&mut matrix, MI_INT32, &dims);
+    if name.len() <= 4 {
+        // Small data element format
+        push_uint(&mut matrix, ((name.len() as u64) << 16) | MI_INT8 as u64, 4);
+        matrix.extend_from_slice(name.as_bytes());
+        matrix.resize(matrix.len() + 4 - name.len(), 0);
+    }
+    else {
+        push_element(&mut matrix, MI_INT8, name.as_bytes());
+    }
+    push_element(&mut matrix, kind, &real);
+    if complex {
+        push_element(&mut matrix, kind, &imag);
+    }
+
+    let mut element = Vec::<u8>::with_capacity(matrix.len() + 8);
+    push_element(&mut element, MI_MATRIX, &matrix);
+    Ok(element)
+}
+
+impl MatlabFile {
+
+    /// Allocate a new MatlabFile structure with a given path. This function neither create nor open
+    /// the file specified by the path.
+    pub fn new(path : &str) -> MatlabFile {
+        MatlabFile {
+            path : path.to_string(),
+            file : None,
+            variables : Vec::new(),
+            compression : false,
+        }
+    }
+
+    /// Open the MAT-file and return the names of its variables, in the order they are stored.
+    /// In case of failure, returns the error as an `Error`.
+    pub fn variables(&self) -> Result<Vec<String>, Error> {
+        let reader = Reader::open(&self.path)?;
+        let mut names = Vec::<String>::with_capacity(reader.matrices.len());
+        for data in &reader.matrices {
+            names.push(reader.matrix(data)?.name);
+        }
+        Ok(names)
+    }
+
+    /// Open the MAT-file and read a numeric variable, real or complex, as a NDArray<T>. MATLAB
+    /// stores matrices in column-major order, they are converted to the row-major order of
+    /// NDArray. This function operates its own type convertion from the class of the variable to
+    /// the type T. Cell arrays, structures, objects, characters and sparse matrices are not
+    /// supported.
+    /// In case of failure, returns the error as an `Error`.
+    pub fn read_variable<T : Copy + RDSTyped + Display>(&self, name : &str) -> Result<NDArray<T>, Error>
+        where u8 : Cast<T>, u16 : Cast<T>, u32 : Cast<T>, u64 : Cast<T>,
+              i8 : Cast<T>, i16 : Cast<T>, i32 : Cast<T>, i64 : Cast<T>,
+              f32 : Cast<T>, f64 : Cast<T>, c32 : Cast<T>, c64 : Cast<T> {
+        let reader = Reader::open(&self.path)?;
+        for data in &reader.matrices {
+            let matrix = reader.matrix(data)?;
+            if matrix.name != name {
+                continue;
+            }
+            let real = match matrix.real {
+                Some(real) => real,
+                None => return Err(Error::UnsupportedType(format!("MATLAB class {} of variable {}", matrix.class, name)))
+            };
+            // Every value takes at least one byte, which bounds the dimensions read from the file
+            let size = match matrix.shape.iter().fold(Some(1usize), |acc, &x| acc.and_then(|acc| acc.checked_mul(x))) {
+                Some(s) if s <= real.1.len() => s,
+                _ => return Err(Error::Format(format!("MATLAB variable {} of shape {:?} exceeds its data", name, matrix.shape)))
+            };
+            let values : Vec<T> = match matrix.imag {
+                Some(imag) => {
+                    let re = decode_values::<f64>(real, size, reader.big_endian)?;
+                    let im = decode_values::<f64>(imag, size, reader.big_endian)?;
+                    re.into_iter().zip(im).map(|(r, i)| Cast::<T>::cast(c64::new(r, i))).collect()
+                },
+                None => decode_values::<T>(real, size, reader.big_endian)?
+            };
+
+            // The column-major values are the row-major values of the reversed shape
+            let reversed : Vec<usize> = matrix.shape.iter().rev().cloned().collect();
+            let column_major = NDArray {
+                strides : NDArray::<T>::compute_strides(&reversed),
+                shape : reversed,
+                data : values.into_boxed_slice(),
+            };
+            return Ok(NDArray::copy(&column_major.transposed()));
+        }
+        Err(Error::Format(format!("No variable named {} in MATLAB file {}", name, self.path)))
+    }
+
+    /// Create the MAT-file with its header, unless it was already created by this MatlabFile, and
+    /// return it. An existing file is never replaced.
+    fn create(&mut self) -> Result<&mut File, Error> {
+        if let Some(ref mut file) = self.file {
+            return Ok(file);
+        }
+        let mut file = match OpenOptions::new().read(true).write(true).create_new(true).open(&self.path) {
+            Ok(f) => f,
+            Err(e) => return Err(Error::Io(e))
+        };
+        let mut header = b"MATLAB 5.0 MAT-file, Platform: rds".to_vec();
+        header.resize(116, b' ');
+        // No subsystem data, version 0x0100 and little endian indicator
+        header.extend_from_slice(&[0, 0, 0, 0, 0, 0, 0, 0, 0x00, 0x01, b'I', b'M']);
+        if let Err(e) = file.write_all(&header) {
+            return Err(Error::Io(e));
+        }
+        self.file = Some(file);
+        Ok(self.file.as_mut().unwrap())
+    }
+
+    /// Write the entire NDData<T> as a variable of the MATLAB class matching T, in column-major 
+    /// order, appending it to the MAT-file which is created on the first write. A variable 
+    /// previously written with the same name is replaced, keeping its position. The variable is 
+    /// compressed if the compression field is true.
+    /// In case of failure, returns the error as an `Error`.
+    pub fn write_data<T : Copy + RDSTyped + Display>(&mut self, name : &str, data : &NDData<T>) -> Result<(), Error> {
+        let valid = name.len() <= 63 && name.chars().next().map_or(false, |c| c.is_ascii_alphabetic()) &&
+            name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+        if !valid {
+            return Err(Error::Format(format!("Invalid MATLAB variable name '{}'", name)));
+        }
+        let mut element = matrix_element(name, data)?;
+        if self.compression {
+            let mut encoder = ZlibEncoder::new(Vec::<u8>::new(), Compression::default());
+            let compressed = match encoder.write_all(&element).and_then(|_| encoder.finish()) {
+                Ok(c) => c,
+                Err(e) => return Err(Error::Io(e))
+            };
+            // Compressed elements are not padded
+            element = Vec::<u8>::with_capacity(compressed.len() + 8);
+            push_uint(&mut element, MI_COMPRESSED as u64, 4);
+            push_uint(&mut element, compressed.len() as u64, 4);
+            element.extend(compressed);
+        }
+
+        let position = self.variables.iter().position(|v| v.0 == name);
+        let start = self.variables[..position.unwrap_or(self.variables.len())].iter().fold(HEADER_SIZE as u64, |acc, v| acc + v.1);
+        let replaced = position.map_or(0, |i| self.variables[i].1);
+        let file = self.create()?;
+        // The elements following a replaced one are moved after the new element
+        let mut tail = Vec::<u8>::new();
+        if position.is_some() {
+            let result = file.seek(SeekFrom::Start(start + replaced)).and_then(|_| file.read_to_end(&mut tail));
+            if let Err(e) = result {
+                return Err(Error::Io(e));
+            }
+        }
+        let result = file.seek(SeekFrom::Start(start))
+            .and_then(|_| file.write_all(&element))
+            .and_then(|_| file.write_all(&tail))
+            .and_then(|_| file.set_len(start + (element.len() + tail.len()) as u64));
+        if let Err(e) = result {
+            return Err(Error::Io(e));
+        }
+        match position {
+            Some(i) => self.variables[i].1 = element.len() as u64,
+            None => self.variables.push((name.to_string(), element.len() as u64))
+        }
+        Ok(())
+    }
+}
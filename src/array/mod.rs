@@ -9,12 +9,52 @@ pub mod ndindex;
 pub mod csv;
 /// Numpy file support for loading and saving N-dimensional arrays.
 pub mod numpy;
+/// Numpy archive support for loading and saving several named N-dimensional arrays.
+pub mod npz;
+/// HDF5 file support for loading and saving N-dimensional datasets.
+pub mod hdf5;
+/// MATLAB Level 5 MAT-file support for loading and saving N-dimensional matrices.
+pub mod matlab;
 /// EDF file support for loading one dimensional signals.
 pub mod edf;
-
+/// Numpy style broadcasting of N-dimensional arrays.
+pub mod broadcast;
+/// Element-wise arithmetic operators with broadcasting.
+pub mod ops;
+/// Element, index and axis iterators over N-dimensional data.
+pub mod iter;
+/// Reductions of N-dimensional data along an axis.
+pub mod reduce;
+/// Concatenation and stacking of N-dimensional arrays.
+pub mod join;
+/// Element-wise closure combinators over N-dimensional data.
+pub mod map;
+/// Boolean masks and integer array indexing.
+pub mod mask;
+/// Einstein summation over N-dimensional data.
+pub mod einsum;
+/// Numpy style Display and Debug implementations.
+mod display;
+
+use array::broadcast::broadcast_strides;
+use array::iter::NDIterable;
 use array::ndindex::NDIndex;
+use error::Error;
 use types::cast::Cast;
 
+/// Check that idx is a valid index for data of the given shape.
+fn check_index(shape : &[usize], idx : &[usize]) -> Result<(), Error> {
+    if idx.len() != shape.len() {
+        return Err(Error::DimensionMismatch { expected : shape.len(), found : idx.len() });
+    }
+    for i in 0..shape.len() {
+        if idx[i] >= shape[i] {
+            return Err(Error::IndexOutOfBounds { axis : i, index : idx[i], len : shape[i] });
+        }
+    }
+    Ok(())
+}
+
 /// A trait for struture giving immutable access to a N-dimensional array of type T
 pub trait NDData<T>{
 
@@ -24,11 +64,19 @@ pub trait NDData<T>{
     fn shape(&self) -> &[usize];
 
     /// Return a slice of length N where each element is the stride of the dimension.
+    /// Negative strides, which only views can have, are stored as their two's complement: 
+    /// `stride as isize` gives back the signed value.
     fn strides(&self) -> &[usize]; 
 
     /// Return the underlying storage array as a slice.
     fn get_data(&self) -> &[T];
 
+    /// Return the position of the first element (index zero in every dimension) in the underlying 
+    /// storage array. It is only non zero for views with negative strides.
+    fn offset(&self) -> usize {
+        0
+    }
+
     /// Return N, the number of dimensions.
     fn dim(&self) -> usize {
         self.shape().len()
@@ -39,15 +87,37 @@ pub trait NDData<T>{
         self.shape().iter().fold(1usize, |acc, &x| acc * x)
     }
 
+    /// Return true if the elements are stored in row-major order and without gap from the start of 
+    /// the underlying storage array. This is always the case for NDArray and NDSlice.
+    fn is_contiguous(&self) -> bool {
+        if self.offset() != 0 || self.get_data().len() != self.size() {
+            return false;
+        }
+        let mut size = 1usize;
+        for i in (0..self.dim()).rev() {
+            if self.shape()[i] != 1 && self.strides()[i] != size {
+                return false;
+            }
+            size *= self.shape()[i];
+        }
+        return true;
+    }
+
     /// Take a slice of length N representing an N-dimensional index in the array and return a reference to 
     /// the element at this position.
     fn idx<'a>(&'a self, idx : &[usize]) -> &'a T {
-        if idx.len() != self.shape().len() {
-            panic!("NDData::idx({:?}): idx is not of the right dimension ({} != {})", 
-                   idx, idx.len(), self.dim());
+        match self.try_idx(idx) {
+            Ok(x) => x,
+            Err(e) => panic!("NDData::idx({:?}): {}", idx, e),
         }
-        let pos = idx.to_pos(self.shape(), self.strides());
-        return &self.get_data()[pos];
+    }
+
+    /// Fallible version of idx: return an error if idx is not of the right dimension or is out of 
+    /// bounds.
+    fn try_idx<'a>(&'a self, idx : &[usize]) -> Result<&'a T, Error> {
+        check_index(self.shape(), idx)?;
+        let pos = self.offset().wrapping_add(idx.to_pos(self.shape(), self.strides()));
+        return Ok(&self.get_data()[pos]);
     }
 }
 
@@ -60,11 +130,18 @@ pub trait NDDataMut<T : Clone + Display> : NDData<T> {
     /// Take a slice of length N representing an -index in the array and return a mutable 
     /// reference to the element at this position.
     fn idx_mut<'a>(&'a mut self, idx : &[usize]) -> &'a mut T {
-        if idx.len() != self.shape().len() {
-            panic!("NDDataMut::idx_mut({:?}): idx is not of the right dimension ({} != {})", idx, idx.len(), self.dim());
+        match self.try_idx_mut(idx) {
+            Ok(x) => x,
+            Err(e) => panic!("NDDataMut::idx_mut({:?}): {}", idx, e),
         }
-        let pos = idx.to_pos(self.shape(), self.strides());
-        return &mut self.get_data_mut()[pos];
+    }
+
+    /// Fallible version of idx_mut: return an error if idx is not of the right dimension or is 
+    /// out of bounds.
+    fn try_idx_mut<'a>(&'a mut self, idx : &[usize]) -> Result<&'a mut T, Error> {
+        check_index(self.shape(), idx)?;
+        let pos = self.offset().wrapping_add(idx.to_pos(self.shape(), self.strides()));
+        return Ok(&mut self.get_data_mut()[pos]);
     }
 
     /// Perform a generic transpose. All the dimensions need to be the same.
@@ -78,7 +155,7 @@ pub trait NDDataMut<T : Clone + Display> : NDData<T> {
         }
 
         let mut idx : Vec<usize>= repeat(0usize).take(self.dim()).collect();
-        let copy = NDArray::<T>::from_slice(&self.shape()[..], self.get_data());
+        let copy = NDArray::<T>::copy(self);
         loop {
             let revidx : Vec<usize> = idx.iter().rev().cloned().collect();
             *self.idx_mut(&idx[..]) = copy.idx(&revidx[..]).clone();
@@ -92,12 +169,22 @@ pub trait NDDataMut<T : Clone + Display> : NDData<T> {
 
     /// Assign another NDData<T> to the NDDataMut<T>. The shapes need to be identical.
     fn assign(&mut self, other : &NDData<T>) {
-        if self.dim() != self.dim() {
-            panic!("NDDataMut::assign(): other is not of the same dimensionality ({} != {})",  other.dim(), self.dim());
+        if let Err(e) = self.try_assign(other) {
+            panic!("NDDataMut::assign(): {}", e);
+        }
+    }
+
+    /// Fallible version of assign: return an error instead of panicking if the shapes differ.
+    fn try_assign(&mut self, other : &NDData<T>) -> Result<(), Error> {
+        if self.dim() != other.dim() {
+            return Err(Error::DimensionMismatch { expected : self.dim(), found : other.dim() });
         }
 
         if self.shape() != other.shape() {
-            panic!("NDDataMut::assign(): other is not of the same shape ({:?} != {:?})",  other.shape(), self.shape());
+            return Err(Error::ShapeMismatch { expected : self.shape().to_vec(), found : other.shape().to_vec() });
+        }
+        if self.size() == 0 {
+            return Ok(());
         }
 
         let mut idx : Vec<usize>= repeat(0usize).take(self.dim()).collect();
@@ -109,6 +196,7 @@ pub trait NDDataMut<T : Clone + Display> : NDData<T> {
                 break;
             }
         }
+        Ok(())
     }
 }
 
@@ -132,6 +220,62 @@ pub trait NDSliceableMut<'a, T : 'a> : NDSliceable<'a, T> {
     fn slice_mut(&'a mut self, idx : &[usize]) -> NDSliceMut<'a, T>;
 }
 
+/// A trait for N-dimensional data from which an immutable strided view can be borrowed.
+pub trait NDViewable<'a, T : 'a> {
+
+    /// Take a slice of NDRange, one for each of the first dimensions, and return an immutable 
+    /// borrow of the selected elements as an NDView. Dimensions without a range are entirely 
+    /// selected while dimensions selected with `NDRange::index` are removed from the view.
+    /// Contrary to slices, views can select any part of the data, for example a column of a 
+    /// matrix or every other element of a dimension in reverse order. A range whose start equals
+    /// its end selects no element and gives a dimension of length 0.
+    fn view(&'a self, ranges : &[NDRange]) -> NDView<'a, T>;
+
+    /// Return an immutable view of the data broadcasted to a given shape following the numpy 
+    /// rules: missing leading dimensions are added and dimensions of length 1 are repeated to 
+    /// match the shape.
+    fn broadcast(&'a self, shape : &[usize]) -> NDView<'a, T>;
+
+    /// Return an immutable view where the dimensions are reordered: the dimension i of the view 
+    /// is the dimension axes[i] of the data. No data is copied.
+    fn permute_axes(&'a self, axes : &[usize]) -> NDView<'a, T>;
+
+    /// Return an immutable view where the dimensions a and b are swapped. No data is copied.
+    fn swap_axes(&'a self, a : usize, b : usize) -> NDView<'a, T>;
+
+    /// Return an immutable view where the order of the dimensions is reversed. For a matrix this 
+    /// is the usual transpose. No data is copied.
+    fn transposed(&'a self) -> NDView<'a, T>;
+}
+
+/// A trait for N-dimensional data from which a mutable strided view can be borrowed.
+pub trait NDViewableMut<'a, T : 'a> : NDViewable<'a, T> {
+
+    /// Take a slice of NDRange, one for each of the first dimensions, and return a mutable 
+    /// borrow of the selected elements as an NDViewMut. Dimensions without a range are entirely 
+    /// selected while dimensions selected with `NDRange::index` are removed from the view.
+    fn view_mut(&'a mut self, ranges : &[NDRange]) -> NDViewMut<'a, T>;
+
+    /// Return a mutable view where the dimension i of the view is the dimension axes[i] of the 
+    /// data. No data is copied.
+    fn permute_axes_mut(&'a mut self, axes : &[usize]) -> NDViewMut<'a, T>;
+
+    /// Return a mutable view where the dimensions a and b are swapped. No data is copied.
+    fn swap_axes_mut(&'a mut self, a : usize, b : usize) -> NDViewMut<'a, T>;
+
+    /// Return a mutable view where the order of the dimensions is reversed. No data is copied.
+    fn transposed_mut(&'a mut self) -> NDViewMut<'a, T>;
+}
+
+/// Structure describing the indices selected in one dimension when borrowing a view.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct NDRange {
+    start : usize,
+    end : Option<usize>,
+    step : isize,
+    single : bool,
+}
+
 /// Structure representing an immutable borrow of a n-dimensional array sub slice.
 pub struct NDSlice<'a, T : 'a> {
     shape : &'a [usize],
@@ -146,6 +290,24 @@ pub struct NDSliceMut<'a, T : 'a> {
     data : &'a mut [T],
 }
 
+/// Structure representing an immutable strided view of a n-dimensional array. Strides can be 
+/// negative, in which case the first element is not at the start of the underlying storage array.
+pub struct NDView<'a, T : 'a> {
+    shape : Vec<usize>,
+    strides : Vec<usize>,
+    offset : usize,
+    data : &'a [T],
+}
+
+/// Structure representing a mutable strided view of a n-dimensional array. Strides can be 
+/// negative, in which case the first element is not at the start of the underlying storage array.
+pub struct NDViewMut<'a, T : 'a> {
+    shape : Vec<usize>,
+    strides : Vec<usize>,
+    offset : usize,
+    data : &'a mut [T],
+}
+
 /// Structure representing an owned n-dimensional array. The underlying storage is in row-major 
 /// order.
 pub struct NDArray<T> {
@@ -179,12 +341,21 @@ impl<T : Clone> NDArray<T> {
         }
     }
 
-    /// Allocate a new array which is a copy of data.
-    pub fn copy<R : NDData<T>>(data : &R) -> NDArray<T> {
+    /// Allocate a new array which is a copy of data. The copy is always stored contiguously in 
+    /// row-major order, even if data is a strided view.
+    pub fn copy<R : NDData<T> + ?Sized>(data : &R) -> NDArray<T> {
+        if data.is_contiguous() {
+            return NDArray {
+                shape : data.shape().to_vec(),
+                strides : NDArray::<T>::compute_strides(data.shape()),
+                data : data.get_data().to_vec().into_boxed_slice(),
+            };
+        }
+
         NDArray {
             shape : data.shape().to_vec(),
-            strides : data.strides().to_vec(),
-            data : data.get_data().to_vec().into_boxed_slice(),
+            strides : NDArray::<T>::compute_strides(data.shape()),
+            data : data.iter().cloned().collect::<Vec<T>>().into_boxed_slice(),
         }
     }
 
@@ -204,10 +375,27 @@ impl<T : Clone> NDArray<T> {
     
     /// Allocate a new array where each element has been casted from data.
     pub fn cast<U : Copy>(data : &NDData<U>) -> NDArray<T> where T : Copy, U : Cast<T> {
-        let alloc : Vec<T> = data.get_data().iter().map(|x| Cast::<T>::cast(x.clone())).collect();
+        if data.is_contiguous() {
+            let alloc : Vec<T> = data.get_data().iter().map(|x| Cast::<T>::cast(x.clone())).collect();
+            return NDArray {
+                shape : data.shape().to_vec(),
+                strides : NDArray::<T>::compute_strides(data.shape()),
+                data : alloc.into_boxed_slice()
+            };
+        }
+
+        let mut alloc = Vec::<T>::with_capacity(data.size());
+        let mut idx : Vec<usize> = repeat(0usize).take(data.dim()).collect();
+        loop {
+            alloc.push(Cast::<T>::cast(data.idx(&idx[..]).clone()));
+            idx.inc_ro(data.shape());
+            if idx.is_zero() {
+                break;
+            }
+        }
         NDArray {
             shape : data.shape().to_vec(),
-            strides : data.strides().to_vec(),
+            strides : NDArray::<T>::compute_strides(data.shape()),
             data : alloc.into_boxed_slice()
         }
     }
@@ -215,34 +403,56 @@ impl<T : Clone> NDArray<T> {
     /// Reshape this NDArray. The size of the new shape (product of all its elements) must be equal 
     /// to the size of the current shape.
     pub fn reshape(&mut self, new_shape : &[usize]) {
+        if let Err(e) = self.try_reshape(new_shape) {
+            panic!("NDArray::reshape({:?}): {}", new_shape, e);
+        }
+    }
+
+    /// Fallible version of reshape: return a shape mismatch error, leaving this NDArray 
+    /// unchanged, if the size of the new shape is different.
+    pub fn try_reshape(&mut self, new_shape : &[usize]) -> Result<(), Error> {
         let size1 = self.shape.iter().fold(1usize, |acc, &x| acc * x);
         let size2 = new_shape.iter().fold(1usize, |acc, &x| acc * x);
 
         if size1 != size2 {
-            panic!(format!("NDArray::reshape({:?}): New shape has a different size than the previous shape: {} != {}", new_shape, size1, size2));
+            return Err(Error::ShapeMismatch { expected : self.shape.clone(), found : new_shape.to_vec() });
         }
         self.shape = new_shape.to_vec();
         self.strides = NDArray::<T>::compute_strides(&new_shape);
+        Ok(())
     }
 
     /// Insert another NDData of the same dimensionality in this NDArray. The insertion is made 
     /// at the position pos in the dimension dim. The shape of NDData need to be the same as 
     /// this NDArray in every dimension except for the insertion dimension.
     pub fn insert(&mut self, dim : usize, pos : usize, other : &NDData<T>) {
+        if let Err(e) = self.try_insert(dim, pos, other) {
+            panic!("NDArray::insert(): {}", e);
+        }
+    }
+
+    /// Fallible version of insert: return an error, leaving this NDArray unchanged, if dim or pos 
+    /// is out of bound or if the shape of other doesn't match.
+    pub fn try_insert(&mut self, dim : usize, pos : usize, other : &NDData<T>) -> Result<(), Error> {
         if dim >= self.dim() {
-            panic!("NDArray::insert(): dim is greater than array dimension ({} >= {})", dim, self.dim());
+            return Err(Error::InvalidAxis { axis : dim, dim : self.dim() });
         }
         if pos > self.shape[dim] {
-            panic!("NDArray::insert(): pos is out of bound ({} > {})", pos, self.shape[dim]);
+            return Err(Error::IndexOutOfBounds { axis : dim, index : pos, len : self.shape[dim] });
         }
         if self.dim() != other.dim() {
-            panic!("NDArray::insert(): dimensions are differents ({} != {})", other.dim(), self.dim());
+            return Err(Error::DimensionMismatch { expected : self.dim(), found : other.dim() });
         }
         for i in 0..self.dim() {
             if i != dim && self.shape[i] != other.shape()[i] {
-                panic!("NDArray::insert(): Shapes are different at dimension {} ({} != {})", i, other.shape()[i], self.shape()[i]);
+                let mut expected = self.shape.clone();
+                expected[dim] = other.shape()[dim];
+                return Err(Error::ShapeMismatch { expected : expected, found : other.shape().to_vec() });
             }
         }
+        if other.size() == 0 {
+            return Ok(());
+        }
 
         let old_shape = self.shape.clone();
         let old_strides = self.strides.clone();
@@ -275,23 +485,36 @@ impl<T : Clone> NDArray<T> {
                 break;
             }
         }
+        Ok(())
     }
 
     /// Extract a sub part of this NDArray as a new NDArray. start and end are two index of the 
     /// same dimensionality ad this NDArray.
     pub fn extract(&self, start : &[usize], end : &[usize]) -> NDArray<T> {
-        if self.dim() != start.len() || self.dim() != end.len() {
-            panic!("NDArray::extract(): start or end dimension is not the same as array dimension ({} != {} || {} != {})", start.len(), self.dim(), end.len(), self.dim());
+        match self.try_extract(start, end) {
+            Ok(extracted) => extracted,
+            Err(e) => panic!("NDArray::extract(): {}", e),
+        }
+    }
+
+    /// Fallible version of extract: return an error if start or end is not of the right 
+    /// dimension, out of bound or if a range is empty.
+    pub fn try_extract(&self, start : &[usize], end : &[usize]) -> Result<NDArray<T>, Error> {
+        if self.dim() != start.len() {
+            return Err(Error::DimensionMismatch { expected : self.dim(), found : start.len() });
+        }
+        if self.dim() != end.len() {
+            return Err(Error::DimensionMismatch { expected : self.dim(), found : end.len() });
         }
         for i in 0..self.dim() {
             if start[i] >= self.shape[i] {
-                panic!("NDArray::extract(): start is out of bound at dimension {} ({} >= {})", i, start[i], self.shape[i]);
+                return Err(Error::IndexOutOfBounds { axis : i, index : start[i], len : self.shape[i] });
             }
             if end[i] > self.shape[i] {
-                panic!("NDArray::extract(): end is out of bound at dimension {} ({} > {})", i, end[i], self.shape[i]);
+                return Err(Error::IndexOutOfBounds { axis : i, index : end[i], len : self.shape[i] });
             }
             if start[i] >= end[i] {
-                panic!("NDArray::extract(): start is not before end at dimension {} ({} >= {})", i, start[i], end[i]);
+                return Err(Error::EmptyRange { axis : i, start : start[i], end : end[i] });
             }
         }
 
@@ -315,24 +538,35 @@ impl<T : Clone> NDArray<T> {
             }
         }
 
-        return NDArray::<T> {
+        return Ok(NDArray::<T> {
             shape : shape,
             strides : strides,
             data : data.into_boxed_slice()
-        };
+        });
     }
 
     /// Remove a part of this NDArray. The removal is made in dimension dim from the position start 
     /// until end (not included). This can be seen as the opposite operation of insert.
     pub fn remove(&mut self, dim : usize, start : usize, end : usize) {
+        if let Err(e) = self.try_remove(dim, start, end) {
+            panic!("NDArray::remove(): {}", e);
+        }
+    }
+
+    /// Fallible version of remove: return an error, leaving this NDArray unchanged, if dim, start 
+    /// or end is out of bound or if the range is empty.
+    pub fn try_remove(&mut self, dim : usize, start : usize, end : usize) -> Result<(), Error> {
         if dim >= self.dim() {
-            panic!("NDArray::remove(): dim is greater than array dimension ({} >= {})", dim, self.dim());
+            return Err(Error::InvalidAxis { axis : dim, dim : self.dim() });
         }
-        if start >= self.shape[dim] || end > self.shape[dim]{
-            panic!("NDArray::remove(): start or end is out of bound ({} >= {} || {} > {})", start, self.shape[dim], end, self.shape[dim]);
+        if start >= self.shape[dim] {
+            return Err(Error::IndexOutOfBounds { axis : dim, index : start, len : self.shape[dim] });
+        }
+        if end > self.shape[dim] {
+            return Err(Error::IndexOutOfBounds { axis : dim, index : end, len : self.shape[dim] });
         }
         if start >= end {
-            panic!("NDArray::remove(): start is not before end ({} >= {})", start, end);
+            return Err(Error::EmptyRange { axis : dim, start : start, end : end });
         }
 
         let old_shape = self.shape.clone();
@@ -360,24 +594,241 @@ impl<T : Clone> NDArray<T> {
                 break;
             }
         }
+        Ok(())
     }
 
     /// Split this NDArray at position pos of dimension dim. The lower indices are kept in dim 
     /// while the upper indices are returned. This is in fact an extract operation followed by a 
     /// remove operation.
     pub fn split(&mut self, dim : usize, pos : usize) -> NDArray<T> {
+        match self.try_split(dim, pos) {
+            Ok(splitted) => splitted,
+            Err(e) => panic!("NDArray::split(): {}", e),
+        }
+    }
+
+    /// Fallible version of split: return an error, leaving this NDArray unchanged, if dim or pos 
+    /// is out of bound.
+    pub fn try_split(&mut self, dim : usize, pos : usize) -> Result<NDArray<T>, Error> {
         if dim >= self.dim() {
-            panic!("NDArray::split(): dim is greater than array dimension ({} >= {})", dim, self.dim());
+            return Err(Error::InvalidAxis { axis : dim, dim : self.dim() });
         }
         let end = self.shape.clone();
         let mut start : Vec<usize> = repeat(0usize).take(self.dim()).collect();
         start[dim] = pos;
-        let splitted = self.extract(&start[..], &end[..]);
-        self.remove(dim, start[dim], end[dim]);
-        return splitted;
+        let splitted = self.try_extract(&start[..], &end[..])?;
+        self.try_remove(dim, start[dim], end[dim])?;
+        return Ok(splitted);
+    }
+}
+
+
+impl NDRange {
+
+    /// Select every index of the dimension.
+    pub fn all() -> NDRange {
+        NDRange {
+            start : 0,
+            end : None,
+            step : 1,
+            single : false,
+        }
+    }
+
+    /// Select the indices from start (included) to end (not included).
+    pub fn new(start : usize, end : usize) -> NDRange {
+        NDRange {
+            start : start,
+            end : Some(end),
+            step : 1,
+            single : false,
+        }
+    }
+
+    /// Select the single index i. The dimension is removed from the resulting view.
+    pub fn index(i : usize) -> NDRange {
+        NDRange {
+            start : i,
+            end : Some(i + 1),
+            step : 1,
+            single : true,
+        }
+    }
+
+    /// Only select one index every step indices of the range. A negative step traverses the range 
+    /// backward, starting from its last index.
+    pub fn step(self, step : isize) -> NDRange {
+        NDRange {
+            start : self.start,
+            end : self.end,
+            step : step,
+            single : self.single,
+        }
+    }
+}
+
+/// Compute the shape and strides of a view where the dimension i is the dimension axes[i] of data,
+/// checking that axes is a permutation of the dimensions.
+fn compute_permutation<T, R : NDData<T> + ?Sized>(data : &R, axes : &[usize]) -> (Vec<usize>, Vec<usize>) {
+    if axes.len() != data.dim() {
+        panic!("NDViewable::permute_axes({:?}): axes is not of the right dimension ({} != {})", axes, axes.len(), data.dim());
+    }
+    let mut used : Vec<bool> = repeat(false).take(data.dim()).collect();
+    for &a in axes {
+        if a >= data.dim() || used[a] {
+            panic!("NDViewable::permute_axes({:?}): axes is not a permutation of the dimensions", axes);
+        }
+        used[a] = true;
+    }
+    (axes.iter().map(|&a| data.shape()[a]).collect(), axes.iter().map(|&a| data.strides()[a]).collect())
+}
+
+/// Compute the permutation swapping the dimensions a and b of a n-dimensional data.
+fn swap_permutation(dim : usize, a : usize, b : usize) -> Vec<usize> {
+    if a >= dim || b >= dim {
+        panic!("NDViewable::swap_axes({}, {}): axis is greater than data dimension ({})", a, b, dim);
+    }
+    let mut axes : Vec<usize> = (0..dim).collect();
+    axes.swap(a, b);
+    return axes;
+}
+
+/// Compute the shape, strides and offset of a view of data together with the bounds of the part of 
+/// the underlying storage array it covers.
+fn compute_view<T, R : NDData<T> + ?Sized>(data : &R, ranges : &[NDRange]) -> (Vec<usize>, Vec<usize>, usize, usize, usize) {
+    if ranges.len() > data.dim() {
+        panic!("NDViewable::view({:?}): ranges is not of the right dimension ({} > {})", ranges, ranges.len(), data.dim());
+    }
+
+    let mut shape = Vec::<usize>::with_capacity(data.dim());
+    let mut strides = Vec::<usize>::with_capacity(data.dim());
+    let mut first = data.offset() as isize;
+    for i in 0..data.dim() {
+        let range = if i < ranges.len() { ranges[i] } else { NDRange::all() };
+        let stride = data.strides()[i] as isize;
+        let end = range.end.unwrap_or(data.shape()[i]);
+        if end > data.shape()[i] {
+            panic!("NDViewable::view({:?}): range is out of bound for dimension {} ({} > {})", ranges, i, end, data.shape()[i]);
+        }
+        if range.start > end {
+            panic!("NDViewable::view({:?}): range start is after end for dimension {} ({} > {})", ranges, i, range.start, end);
+        }
+        if range.step == 0 {
+            panic!("NDViewable::view({:?}): range step is zero for dimension {}", ranges, i);
+        }
+
+        if range.single {
+            first += range.start as isize * stride;
+        }
+        else {
+            let step = range.step.abs() as usize;
+            if range.step > 0 {
+                first += range.start as isize * stride;
+            }
+            else {
+                first += (end - 1) as isize * stride;
+            }
+            shape.push((end - range.start + step - 1) / step);
+            strides.push((stride * range.step) as usize);
+        }
+    }
+
+    // An empty view designates no element of the data
+    if shape.contains(&0) {
+        return (shape, strides, 0, 0, 0);
+    }
+
+    let mut low = first;
+    let mut high = first;
+    for i in 0..shape.len() {
+        let extent = (shape[i] as isize - 1) * (strides[i] as isize);
+        if extent < 0 {
+            low += extent;
+        }
+        else {
+            high += extent;
+        }
+    }
+
+    return (shape, strides, (first - low) as usize, low as usize, high as usize + 1);
+}
+
+impl<'a, T : 'a, R : NDData<T> + ?Sized> NDViewable<'a, T> for R {
+
+    fn view(&'a self, ranges : &[NDRange]) -> NDView<'a, T> {
+        let (shape, strides, offset, low, high) = compute_view(self, ranges);
+        NDView {
+            shape : shape,
+            strides : strides,
+            offset : offset,
+            data : &self.get_data()[low..high],
+        }
+    }
+
+    fn broadcast(&'a self, shape : &[usize]) -> NDView<'a, T> {
+        match broadcast_strides(self.shape(), self.strides(), shape) {
+            Some(strides) => NDView {
+                shape : shape.to_vec(),
+                strides : strides,
+                offset : self.offset(),
+                data : self.get_data(),
+            },
+            None => panic!("NDViewable::broadcast({:?}): shape {:?} can't be broadcasted", shape, self.shape())
+        }
+    }
+
+    fn permute_axes(&'a self, axes : &[usize]) -> NDView<'a, T> {
+        let (shape, strides) = compute_permutation(self, axes);
+        NDView {
+            shape : shape,
+            strides : strides,
+            offset : self.offset(),
+            data : self.get_data(),
+        }
+    }
+
+    fn swap_axes(&'a self, a : usize, b : usize) -> NDView<'a, T> {
+        self.permute_axes(&swap_permutation(self.dim(), a, b)[..])
+    }
+
+    fn transposed(&'a self) -> NDView<'a, T> {
+        let axes : Vec<usize> = (0..self.dim()).rev().collect();
+        self.permute_axes(&axes[..])
     }
 }
 
+impl<'a, T : 'a + Clone + Display, R : NDDataMut<T> + ?Sized> NDViewableMut<'a, T> for R {
+
+    fn view_mut(&'a mut self, ranges : &[NDRange]) -> NDViewMut<'a, T> {
+        let (shape, strides, offset, low, high) = compute_view(self, ranges);
+        NDViewMut {
+            shape : shape,
+            strides : strides,
+            offset : offset,
+            data : &mut self.get_data_mut()[low..high],
+        }
+    }
+
+    fn permute_axes_mut(&'a mut self, axes : &[usize]) -> NDViewMut<'a, T> {
+        let (shape, strides) = compute_permutation(self, axes);
+        NDViewMut {
+            shape : shape,
+            strides : strides,
+            offset : self.offset(),
+            data : self.get_data_mut(),
+        }
+    }
+
+    fn swap_axes_mut(&'a mut self, a : usize, b : usize) -> NDViewMut<'a, T> {
+        let axes = swap_permutation(self.dim(), a, b);
+        self.permute_axes_mut(&axes[..])
+    }
+
+    fn transposed_mut(&'a mut self) -> NDViewMut<'a, T> {
+        let axes : Vec<usize> = (0..self.dim()).rev().collect();
+        self.permute_axes_mut(&axes[..])
+    }
+}
 
 impl<T> NDData<T> for NDArray<T> { 
 
@@ -400,21 +851,11 @@ impl<T : Clone + Display> NDDataMut<T> for NDArray<T> {
         &mut self.data[..]
     }
 
-    /// The transpose function of a NDArray allow to transpose any shape.
+    /// The transpose function of a NDArray allow to transpose any shape. The data is reordered 
+    /// in row-major order, use `transposed()` to obtain a transpose without copying the data.
     fn transpose(&mut self) {
-        let copy = NDArray::<T>::copy(self);
-        let mut idx : Vec<usize>= repeat(0usize).take(self.dim()).collect();
-        self.shape.reverse();
-        self.strides = NDArray::<T>::compute_strides(&self.shape[..]);
-        loop {
-            let revidx : Vec<usize> = idx.iter().rev().cloned().collect();
-            *self.idx_mut(&idx[..]) = copy.idx(&revidx[..]).clone();
-            // Update idx
-            idx.inc_ro(self.shape());
-            if idx.is_zero() {
-                break;
-            }
-        }
+        let transposed = self.transposed().to_contiguous();
+        *self = transposed;
     }
 }
 
@@ -722,62 +1163,178 @@ impl<'a, 'b, T : Clone + Display> IndexMut<&'b [usize;3]> for NDSliceMut<'a, T>
     }
 }
 
-impl<T : PartialEq, O : NDData<T> + Sized> PartialEq<O> for NDArray<T> {
+impl<'a, T : Clone> NDView<'a, T> {
 
-    fn eq(&self, other: &O) -> bool {
-        if self.dim() != other.dim() {
-            return false;
-        }
+    /// Copy the elements of the view into a new NDArray with a contiguous row-major layout.
+    pub fn to_contiguous(&self) -> NDArray<T> {
+        NDArray::<T>::copy(self)
+    }
+}
 
-        for i in 0..self.dim() {
-            if self.shape()[i] != other.shape()[i] {
-                return false;
-            }
-        }
+impl<'a, T> NDData<T> for NDView<'a, T> {
 
-        let mut idx : Vec<usize>= repeat(0usize).take(self.dim()).collect();
-        loop {
-            if *self.idx(&idx[..]) != *other.idx(&idx[..]) {
-                return false;
-            }
-            // Update idx
-            idx.inc_ro(self.shape());
-            if idx.is_zero() {
-                break;
-            }
-        }
-        return true;
+    fn shape(&self) -> &[usize] {
+        &self.shape[..]
+    }
+
+    fn strides(&self) -> &[usize] {
+        &self.strides[..]
+    }
+
+    fn get_data(&self) -> &[T] {
+        self.data
+    }
+
+    fn offset(&self) -> usize {
+        self.offset
     }
 }
 
-impl<T : Eq> Eq for NDArray<T> {
+impl<'a, 'b, T> Index<&'b [usize]> for NDView<'a, T> {
+    type Output = T;
+
+    fn index<'c>(&'c self, idx : &'b [usize]) -> &'c T {
+        self.idx(idx)
+    }
 }
 
-impl<'a, T : PartialEq, O : NDData<T> + Sized> PartialEq<O> for NDSlice<'a, T> {
+impl<'a, 'b, T> Index<&'b [usize;1]> for NDView<'a, T> {
+    type Output = T;
+
+    fn index<'c>(&'c self, idx : &'b [usize;1]) -> &'c T {
+        self.idx(idx)
+    }
+}
+
+impl<'a, 'b, T> Index<&'b [usize;2]> for NDView<'a, T> {
+    type Output = T;
+
+    fn index<'c>(&'c self, idx : &'b [usize;2]) -> &'c T {
+        self.idx(idx)
+    }
+}
+
+impl<'a, 'b, T> Index<&'b [usize;3]> for NDView<'a, T> {
+    type Output = T;
+
+    fn index<'c>(&'c self, idx : &'b [usize;3]) -> &'c T {
+        self.idx(idx)
+    }
+}
+
+impl<'a, T : Clone> NDViewMut<'a, T> {
+
+    /// Copy the elements of the view into a new NDArray with a contiguous row-major layout.
+    pub fn to_contiguous(&self) -> NDArray<T> {
+        NDArray::<T>::copy(self)
+    }
+}
+
+impl<'a, T> NDData<T> for NDViewMut<'a, T> {
+
+    fn shape(&self) -> &[usize] {
+        &self.shape[..]
+    }
+
+    fn strides(&self) -> &[usize] {
+        &self.strides[..]
+    }
+
+    fn get_data(&self) -> &[T] {
+        self.data
+    }
+
+    fn offset(&self) -> usize {
+        self.offset
+    }
+}
+
+impl<'a, T : Clone + Display> NDDataMut<T> for NDViewMut<'a, T> {
+
+    fn get_data_mut(&mut self) -> &mut [T] {
+        self.data
+    }
+}
+
+impl<'a, 'b, T> Index<&'b [usize]> for NDViewMut<'a, T> {
+    type Output = T;
+
+    fn index<'c>(&'c self, idx : &'b [usize]) -> &'c T {
+        self.idx(idx)
+    }
+}
+
+impl<'a, 'b, T> Index<&'b [usize;1]> for NDViewMut<'a, T> {
+    type Output = T;
+
+    fn index<'c>(&'c self, idx : &'b [usize;1]) -> &'c T {
+        self.idx(idx)
+    }
+}
+
+impl<'a, 'b, T> Index<&'b [usize;2]> for NDViewMut<'a, T> {
+    type Output = T;
+
+    fn index<'c>(&'c self, idx : &'b [usize;2]) -> &'c T {
+        self.idx(idx)
+    }
+}
+
+impl<'a, 'b, T> Index<&'b [usize;3]> for NDViewMut<'a, T> {
+    type Output = T;
+
+    fn index<'c>(&'c self, idx : &'b [usize;3]) -> &'c T {
+        self.idx(idx)
+    }
+}
+
+impl<'a, 'b, T : Clone + Display> IndexMut<&'b [usize]> for NDViewMut<'a, T> {
+
+    fn index_mut<'c>(&'c mut self, idx : &'b [usize]) -> &'c mut T {
+        self.idx_mut(idx)
+    }
+}
+
+impl<'a, 'b, T : Clone + Display> IndexMut<&'b [usize;1]> for NDViewMut<'a, T> {
+
+    fn index_mut<'c>(&'c mut self, idx : &'b [usize;1]) -> &'c mut T {
+        self.idx_mut(idx)
+    }
+}
+
+impl<'a, 'b, T : Clone + Display> IndexMut<&'b [usize;2]> for NDViewMut<'a, T> {
+
+    fn index_mut<'c>(&'c mut self, idx : &'b [usize;2]) -> &'c mut T {
+        self.idx_mut(idx)
+    }
+}
+
+impl<'a, 'b, T : Clone + Display> IndexMut<&'b [usize;3]> for NDViewMut<'a, T> {
+
+    fn index_mut<'c>(&'c mut self, idx : &'b [usize;3]) -> &'c mut T {
+        self.idx_mut(idx)
+    }
+}
+
+/// Return true if a and b have the same shape and the same elements.
+fn data_eq<T : PartialEq>(a : &NDData<T>, b : &NDData<T>) -> bool {
+    a.shape() == b.shape() && a.iter().zip(b.iter()).all(|(x, y)| x == y)
+}
+
+impl<T : PartialEq, O : NDData<T> + Sized> PartialEq<O> for NDArray<T> {
 
     fn eq(&self, other: &O) -> bool {
-        if self.dim() != other.dim() {
-            return false;
-        }
+        data_eq(self, other)
+    }
+}
 
-        for i in 0..self.dim() {
-            if self.shape()[i] != other.shape()[i] {
-                return false;
-            }
-        }
+impl<T : Eq> Eq for NDArray<T> {
+}
 
-        let mut idx : Vec<usize>= repeat(0usize).take(self.dim()).collect();
-        loop {
-            if *self.idx(&idx[..]) != *other.idx(&idx[..]) {
-                return false;
-            }
-            // Update idx
-            idx.inc_ro(self.shape());
-            if idx.is_zero() {
-                break;
-            }
-        }
-        return true;
+impl<'a, T : PartialEq, O : NDData<T> + Sized> PartialEq<O> for NDSlice<'a, T> {
+
+    fn eq(&self, other: &O) -> bool {
+        data_eq(self, other)
     }
 }
 
@@ -787,30 +1344,29 @@ impl<'a, T : Eq> Eq for NDSlice<'a, T> {
 impl<'a, T : PartialEq, O : NDData<T> + Sized> PartialEq<O> for NDSliceMut<'a, T> {
 
     fn eq(&self, other: &O) -> bool {
-        if self.dim() != other.dim() {
-            return false;
-        }
+        data_eq(self, other)
+    }
+}
 
-        for i in 0..self.dim() {
-            if self.shape()[i] != other.shape()[i] {
-                return false;
-            }
-        }
+impl<'a, T : Eq> Eq for NDSliceMut<'a, T> {
+}
 
-        let mut idx : Vec<usize>= repeat(0usize).take(self.dim()).collect();
-        loop {
-            if *self.idx(&idx[..]) != *other.idx(&idx[..]) {
-                return false;
-            }
-            // Update idx
-            idx.inc_ro(self.shape());
-            if idx.is_zero() {
-                break;
-            }
-        }
-        return true;
+impl<'a, T : PartialEq, O : NDData<T> + Sized> PartialEq<O> for NDView<'a, T> {
+
+    fn eq(&self, other: &O) -> bool {
+        data_eq(self, other)
     }
 }
 
-impl<'a, T : Eq> Eq for NDSliceMut<'a, T> {
+impl<'a, T : Eq> Eq for NDView<'a, T> {
+}
+
+impl<'a, T : PartialEq, O : NDData<T> + Sized> PartialEq<O> for NDViewMut<'a, T> {
+
+    fn eq(&self, other: &O) -> bool {
+        data_eq(self, other)
+    }
+}
+
+impl<'a, T : Eq> Eq for NDViewMut<'a, T> {
 }
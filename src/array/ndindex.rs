@@ -16,7 +16,8 @@ pub trait NDIndex {
     /// Return true if the index is zero for all dimensions.
     fn is_zero(&mut self) -> bool;
     
-    /// Compute the resulting position in the underlying storage array.
+    /// Compute the resulting position in the underlying storage array. Negative strides, stored as 
+    /// their two's complement, are supported through wrapping arithmetic.
     fn to_pos(&self, shape : &[usize], strides : &[usize]) -> usize;
 }
 
@@ -93,7 +94,7 @@ impl NDIndex for [usize] {
             if self[i] >= shape[i] {
                 panic!("NDIndex::to_pos(): idx is out of bound for dimension {} ({} >= {})", i, self[i], shape[i]);
             }
-            pos += self[i] * strides[i];
+            pos = pos.wrapping_add(self[i].wrapping_mul(strides[i]));
         }
         return pos;
     }
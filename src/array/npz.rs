@@ -0,0 +1,454 @@
+extern crate flate2;
+
+use std::cmp::{max, min};
+use std::fmt::Display;
+use std::fs::{File, OpenOptions};
+use std::io::{BufReader, Read, Seek, SeekFrom, Write};
+
+use self::flate2::{Compression, Crc, CrcReader};
+use self::flate2::read::DeflateDecoder;
+use self::flate2::write::DeflateEncoder;
+
+use types::RDSTyped;
+use types::complex::{c32, c64};
+use types::cast::Cast;
+use array::{NDArray, NDData};
+use array::numpy::NumpyFile;
+use error::Error;
+
+const LOCAL_HEADER_SIGNATURE : u32 = 0x04034b50;
+const CENTRAL_HEADER_SIGNATURE : u32 = 0x02014b50;
+const END_SIGNATURE : u32 = 0x06054b50;
+const ZIP64_END_SIGNATURE : u32 = 0x06064b50;
+const ZIP64_LOCATOR_SIGNATURE : u32 = 0x07064b50;
+
+const LOCAL_HEADER_SIZE : usize = 30;
+const CENTRAL_HEADER_SIZE : usize = 46;
+const END_SIZE : usize = 22;
+const ZIP64_END_SIZE : usize = 56;
+const ZIP64_LOCATOR_SIZE : usize = 20;
+
+const METHOD_STORED : u16 = 0;
+const METHOD_DEFLATED : u16 = 8;
+const FLAG_ENCRYPTED : u16 = 0x1;
+const FLAG_UTF8 : u16 = 0x800;
+const ZIP64_EXTRA_ID : u16 = 0x1;
+const VERSION : u16 = 20;
+const VERSION_ZIP64 : u16 = 45;
+// Fields of 32 bits saturated to this value are given in the ZIP64 extra field or end record
+const ZIP64_LIMIT : u64 = 0xffffffff;
+// 1980-01-01 00:00:00, the earliest MS-DOS date
+const DOS_DATE : u16 = 0x21;
+
+/// Structure representing a numpy archive file (.npz), a zip archive holding one `.npy` member
+/// per array.
+///
+/// Writing starts a new archive and never touches an existing one: the first `write_data` fails
+/// if a file is already at the path. Every member is streamed to the archive as soon as it is
+/// written, and the central directory listing them is only added by `close`, or when the NpzFile
+/// goes out of scope, so the archive can not be read back before. Rewriting a name drops the
+/// older member from the directory, but its bytes remain in the archive. ZIP64 records are used
+/// once the archive outgrows the 4 GiB or 65535 members of a plain zip archive.
+pub struct NpzFile {
+    path : String,
+    file : Option<File>,
+    end : u64,
+    members : Vec<Member>,
+    /// A boolean indicating whether the arrays written should be deflated, false by default.
+    pub compression : bool,
+}
+
+/// An entry of the zip central directory.
+struct Member {
+    name : String,
+    flags : u16,
+    method : u16,
+    crc : u32,
+    compressed_size : u64,
+    size : u64,
+    offset : u64,
+}
+
+fn uint(b : &[u8]) -> u64 {
+    b.iter().rev().fold(0u64, |acc, &x| (acc << 8) | x as u64)
+}
+
+fn push_uint(buf : &mut Vec<u8>, v : u64, n : usize) {
+    for i in 0..n {
+        buf.push((v >> (8 * i)) as u8);
+    }
+}
+
+fn write_at(file : &mut File, offset : u64, data : &[u8]) -> Result<(), Error> {
+    if let Err(e) = file.seek(SeekFrom::Start(offset)).and_then(|_| file.write_all(data)) {
+        return Err(Error::Io(e));
+    }
+    Ok(())
+}
+
+/// ZIP64 extended information extra field holding the given 64 bits values, empty if there are
+/// none.
+fn zip64_extra(values : &[u64]) -> Vec<u8> {
+    let mut extra = Vec::<u8>::new();
+    if !values.is_empty() {
+        push_uint(&mut extra, ZIP64_EXTRA_ID as u64, 2);
+        push_uint(&mut extra, 8 * values.len() as u64, 2);
+        for &v in values {
+            push_uint(&mut extra, v, 8);
+        }
+    }
+    extra
+}
+
+impl Member {
+
+    /// The fields shared by the local and central headers, from the version needed to extract to
+    /// the length of the extra field.
+    fn fields(&self, version : u16, compressed_size : u64, size : u64, extra_len : usize) -> Vec<u8> {
+        let mut fields = Vec::<u8>::new();
+        push_uint(&mut fields, version as u64, 2);
+        push_uint(&mut fields, self.flags as u64, 2);
+        push_uint(&mut fields, self.method as u64, 2);
+        push_uint(&mut fields, 0, 2);
+        push_uint(&mut fields, DOS_DATE as u64, 2);
+        push_uint(&mut fields, self.crc as u64, 4);
+        push_uint(&mut fields, compressed_size, 4);
+        push_uint(&mut fields, size, 4);
+        push_uint(&mut fields, self.name.len() as u64, 2);
+        push_uint(&mut fields, extra_len as u64, 2);
+        fields
+    }
+}
+
+fn read_at(file : &mut File, offset : u64, size : usize) -> Result<Vec<u8>, Error> {
+    let mut buf = vec![0u8; size];
+    if let Err(e) = file.seek(SeekFrom::Start(offset)).and_then(|_| file.read_exact(&mut buf)) {
+        return Err(Error::Io(e));
+    }
+    Ok(buf)
+}
+
+/// Read a `.npy` member through the numpy reader chain and check the CRC-32 of the bytes read.
+fn read_npy<T : Copy + RDSTyped + Display, R : Read>(path : &str, member : &Member, reader : R) -> Result<NDArray<T>, Error>
+    where u8 : Cast<T>, u16 : Cast<T>, u32 : Cast<T>, u64 : Cast<T>,
+          i8 : Cast<T>, i16 : Cast<T>, i32 : Cast<T>, i64 : Cast<T>,
+          f32 : Cast<T>, f64 : Cast<T>, c32 : Cast<T>, c64 : Cast<T> {
+    let mut reader = BufReader::new(CrcReader::new(reader));
+    let array = NumpyFile::new(path).read_array_from(&mut reader)?;
+    // The buffer may hold bytes not consumed by the array, the CRC covers the whole member
+    if let Err(e) = reader.read_to_end(&mut Vec::new()) {
+        return Err(Error::Io(e));
+    }
+    let crc = reader.get_ref().crc();
+    if crc.amount() != member.size as u32 || crc.sum() != member.crc {
+        return Err(Error::Format(format!("Corrupted member {} (CRC-32 or size mismatch)", path)));
+    }
+    Ok(array)
+}
+
+impl NpzFile {
+
+    /// Allocate a new NpzFile structure with a given path. This function neither create nor open
+    /// the file specified by the path.
+    pub fn new(path : &str) -> NpzFile {
+        NpzFile {
+            path : path.to_string(),
+            file : None,
+            end : 0,
+            members : Vec::new(),
+            compression : false,
+        }
+    }
+
+    fn get_reader(&self) -> Result<File, Error> {
+        match File::open(&self.path[..]) {
+            Ok(r) => Ok(r),
+            Err(e) => Err(Error::Io(e)),
+        }
+    }
+
+    /// Locate the end of central directory record, ZIP64 or not, and parse the central directory.
+    fn directory(&self, file : &mut File) -> Result<Vec<Member>, Error> {
+        let len = match file.seek(SeekFrom::End(0)) {
+            Ok(l) => l,
+            Err(e) => return Err(Error::Io(e))
+        };
+        // The record is followed by a comment of at most 65535 bytes
+        let tail_start = len - min(len, (END_SIZE + 0xffff) as u64);
+        let tail = read_at(file, tail_start, (len - tail_start) as usize)?;
+        let pos = match (0..(tail.len() + 1).saturating_sub(END_SIZE)).rev().find(|&i| uint(&tail[i..i+4]) == END_SIGNATURE as u64) {
+            Some(p) => p,
+            None => return Err(Error::Format(format!("File {} is not a zip archive", self.path)))
+        };
+        let end = &tail[pos..pos+END_SIZE];
+        let mut count = uint(&end[10..12]);
+        let mut directory_size = uint(&end[12..16]);
+        let mut directory_offset = uint(&end[16..20]);
+
+        if count == 0xffff || directory_size == 0xffffffff || directory_offset == 0xffffffff {
+            if pos < ZIP64_LOCATOR_SIZE || uint(&tail[pos-ZIP64_LOCATOR_SIZE..pos-ZIP64_LOCATOR_SIZE+4]) != ZIP64_LOCATOR_SIGNATURE as u64 {
+                return Err(Error::Format(format!("Missing ZIP64 end of central directory locator in {}", self.path)));
+            }
+            let locator = &tail[pos-ZIP64_LOCATOR_SIZE..pos];
+            let end64 = read_at(file, uint(&locator[8..16]), ZIP64_END_SIZE)?;
+            if uint(&end64[0..4]) != ZIP64_END_SIGNATURE as u64 {
+                return Err(Error::Format(format!("Invalid ZIP64 end of central directory in {}", self.path)));
+            }
+            count = uint(&end64[32..40]);
+            directory_size = uint(&end64[40..48]);
+            directory_offset = uint(&end64[48..56]);
+        }
+        match directory_offset.checked_add(directory_size) {
+            Some(end) if end <= len => {},
+            _ => return Err(Error::Format(format!("Central directory of {} is out of bounds", self.path)))
+        }
+
+        let directory = read_at(file, directory_offset, directory_size as usize)?;
+        let mut members = Vec::<Member>::new();
+        let mut p = 0usize;
+        for _ in 0..count {
+            if p + CENTRAL_HEADER_SIZE > directory.len() || uint(&directory[p..p+4]) != CENTRAL_HEADER_SIGNATURE as u64 {
+                return Err(Error::Format(format!("Invalid central directory entry in {}", self.path)));
+            }
+            let header = &directory[p..p+CENTRAL_HEADER_SIZE];
+            let name_len = uint(&header[28..30]) as usize;
+            let extra_len = uint(&header[30..32]) as usize;
+            let comment_len = uint(&header[32..34]) as usize;
+            let next = p + CENTRAL_HEADER_SIZE + name_len + extra_len + comment_len;
+            if next > directory.len() {
+                return Err(Error::Format(format!("Invalid central directory entry in {}", self.path)));
+            }
+            let name = &directory[p+CENTRAL_HEADER_SIZE..p+CENTRAL_HEADER_SIZE+name_len];
+            let mut member = Member {
+                name : String::from_utf8_lossy(name).into_owned(),
+                flags : uint(&header[8..10]) as u16,
+                method : uint(&header[10..12]) as u16,
+                crc : uint(&header[16..20]) as u32,
+                compressed_size : uint(&header[20..24]),
+                size : uint(&header[24..28]),
+                offset : uint(&header[42..46]),
+            };
+
+            // The ZIP64 extra field holds, in order, the fields saturated in the header
+            let mut extra = &directory[p+CENTRAL_HEADER_SIZE+name_len..p+CENTRAL_HEADER_SIZE+name_len+extra_len];
+            while extra.len() >= 4 {
+                let id = uint(&extra[0..2]) as u16;
+                let size = min(uint(&extra[2..4]) as usize, extra.len() - 4);
+                if id == ZIP64_EXTRA_ID {
+                    let mut field = &extra[4..4+size];
+                    for value in [&mut member.size, &mut member.compressed_size, &mut member.offset].iter_mut() {
+                        if **value == 0xffffffff && field.len() >= 8 {
+                            **value = uint(&field[0..8]);
+                            field = &field[8..];
+                        }
+                    }
+                }
+                extra = &extra[4+size..];
+            }
+            members.push(member);
+            p = next;
+        }
+        Ok(members)
+    }
+
+    /// Open the archive and return the names of its members, in the order they are stored. As for
+    /// numpy, the `.npy` extension is removed from the names.
+    /// In case of failure, returns the error as an `Error`.
+    pub fn members(&self) -> Result<Vec<String>, Error> {
+        let mut file = self.get_reader()?;
+        let members = self.directory(&mut file)?;
+        Ok(members.into_iter().map(|m| {
+            if m.name.ends_with(".npy") {
+                m.name[..m.name.len()-4].to_string()
+            }
+            else {
+                m.name
+            }
+        }).collect())
+    }
+
+    /// Open the archive and read a member, designated with or without its `.npy` extension, as a
+    /// NDArray<T>. The member is parsed by the `NumpyFile` reader, which operates its own type
+    /// convertion from the dtype to the type T. Stored and deflated members are supported.
+    /// In case of failure, returns the error as an `Error`.
+    pub fn read_array<T : Copy + RDSTyped + Display>(&self, name : &str) -> Result<NDArray<T>, Error>
+        where u8 : Cast<T>, u16 : Cast<T>, u32 : Cast<T>, u64 : Cast<T>,
+              i8 : Cast<T>, i16 : Cast<T>, i32 : Cast<T>, i64 : Cast<T>,
+              f32 : Cast<T>, f64 : Cast<T>, c32 : Cast<T>, c64 : Cast<T> {
+        let mut file = self.get_reader()?;
+        let members = self.directory(&mut file)?;
+        let npy = format!("{}.npy", name);
+        let member = match members.iter().find(|m| m.name == npy).or_else(|| members.iter().find(|m| m.name == name)) {
+            Some(m) => m,
+            None => return Err(Error::Format(format!("No member named {} in numpy archive {}", name, self.path)))
+        };
+        if member.flags & FLAG_ENCRYPTED != 0 {
+            return Err(Error::UnsupportedType(format!("encrypted zip member {}", member.name)));
+        }
+
+        let header = read_at(&mut file, member.offset, LOCAL_HEADER_SIZE)?;
+        if uint(&header[0..4]) != LOCAL_HEADER_SIGNATURE as u64 {
+            return Err(Error::Format(format!("Invalid local header for member {} in {}", member.name, self.path)));
+        }
+        let start = match member.offset.checked_add(LOCAL_HEADER_SIZE as u64 + uint(&header[26..28]) + uint(&header[28..30])) {
+            Some(s) => s,
+            None => return Err(Error::Format(format!("Member {} of {} is out of bounds", member.name, self.path)))
+        };
+        if let Err(e) = file.seek(SeekFrom::Start(start)) {
+            return Err(Error::Io(e));
+        }
+        let data = file.take(member.compressed_size);
+        let path = format!("{}/{}", self.path, member.name);
+        match member.method {
+            METHOD_STORED => read_npy(&path, member, data),
+            METHOD_DEFLATED => read_npy(&path, member, DeflateDecoder::new(data)),
+            m => Err(Error::UnsupportedType(format!("zip compression method {} of member {}", m, member.name)))
+        }
+    }
+
+    /// Create the archive on the first write, failing if the file already exists.
+    fn create(&mut self) -> Result<(), Error> {
+        if self.file.is_none() {
+            let file = match OpenOptions::new().write(true).create_new(true).open(&self.path) {
+                Ok(f) => f,
+                Err(e) => return Err(Error::Io(e))
+            };
+            self.file = Some(file);
+            self.end = 0;
+        }
+        Ok(())
+    }
+
+    /// Add an NDData<T> to the archive as the member `name.npy`, creating the archive on the
+    /// first write. The member is encoded by the `NumpyFile` writer with the dtype matching T,
+    /// deflated if `compression` is set, and appended to the archive immediately. An array written
+    /// under an existing name replaces the previous one.
+    /// In case of failure, returns the error as an `Error`.
+    pub fn write_data<T : Copy + RDSTyped + Display>(&mut self, name : &str, data : &NDData<T>) -> Result<(), Error> {
+        if name.is_empty() || name.len() > 0xffff - 4 {
+            return Err(Error::Format(format!("Invalid numpy archive member name '{}'", name)));
+        }
+        let name = format!("{}.npy", name);
+        let mut numpy_file = NumpyFile::new(&name);
+        numpy_file.dtype = T::t();
+        let mut npy = Vec::<u8>::new();
+        numpy_file.write_data_to(&mut npy, data)?;
+
+        let mut crc = Crc::new();
+        crc.update(&npy);
+        let mut member = Member {
+            flags : if name.is_ascii() { 0 } else { FLAG_UTF8 },
+            method : METHOD_STORED,
+            crc : crc.sum(),
+            compressed_size : npy.len() as u64,
+            size : npy.len() as u64,
+            offset : 0,
+            name : name,
+        };
+        if self.compression {
+            let mut encoder = DeflateEncoder::new(Vec::<u8>::new(), Compression::default());
+            npy = match encoder.write_all(&npy).and_then(|_| encoder.finish()) {
+                Ok(c) => c,
+                Err(e) => return Err(Error::Io(e))
+            };
+            member.method = METHOD_DEFLATED;
+            member.compressed_size = npy.len() as u64;
+        }
+
+        self.create()?;
+        member.offset = self.end;
+        // A ZIP64 extra field in a local header holds both sizes
+        let mut local = Vec::<u8>::new();
+        push_uint(&mut local, LOCAL_HEADER_SIGNATURE as u64, 4);
+        if member.size >= ZIP64_LIMIT || member.compressed_size >= ZIP64_LIMIT {
+            let extra = zip64_extra(&[member.size, member.compressed_size]);
+            local.extend(member.fields(VERSION_ZIP64, ZIP64_LIMIT, ZIP64_LIMIT, extra.len()));
+            local.extend_from_slice(member.name.as_bytes());
+            local.extend(extra);
+        }
+        else {
+            local.extend(member.fields(VERSION, member.compressed_size, member.size, 0));
+            local.extend_from_slice(member.name.as_bytes());
+        }
+        local.extend(npy);
+        if let Some(ref mut file) = self.file {
+            write_at(file, member.offset, &local)?;
+        }
+        self.end += local.len() as u64;
+
+        match self.members.iter().position(|m| m.name == member.name) {
+            Some(i) => self.members[i] = member,
+            None => self.members.push(member)
+        }
+        Ok(())
+    }
+
+    /// Write the central directory of the members written and the end of central directory
+    /// record, completing the archive. Nothing is done if nothing was written. Writing again
+    /// with this NpzFile afterwards fails, as the file exists.
+    /// In case of failure, returns the error as an `Error`.
+    pub fn close(&mut self) -> Result<(), Error> {
+        let mut file = match self.file.take() {
+            Some(f) => f,
+            None => return Ok(())
+        };
+        let mut directory = Vec::<u8>::new();
+        let mut version = VERSION;
+        for member in &self.members {
+            // The ZIP64 extra field of a central header only holds the saturated fields
+            let large : Vec<u64> = [member.size, member.compressed_size, member.offset].iter()
+                .cloned().filter(|&v| v >= ZIP64_LIMIT).collect();
+            let extra = zip64_extra(&large);
+            let member_version = if large.is_empty() { VERSION } else { VERSION_ZIP64 };
+            version = max(version, member_version);
+            push_uint(&mut directory, CENTRAL_HEADER_SIGNATURE as u64, 4);
+            push_uint(&mut directory, member_version as u64, 2);
+            directory.extend(member.fields(member_version, min(member.compressed_size, ZIP64_LIMIT), min(member.size, ZIP64_LIMIT), extra.len()));
+            // No comment, disk number 0, no internal nor external attributes
+            directory.extend_from_slice(&[0u8; 10]);
+            push_uint(&mut directory, min(member.offset, ZIP64_LIMIT), 4);
+            directory.extend_from_slice(member.name.as_bytes());
+            directory.extend(extra);
+        }
+
+        let count = self.members.len() as u64;
+        let directory_offset = self.end;
+        let directory_size = directory.len() as u64;
+        if count >= 0xffff || directory_offset >= ZIP64_LIMIT || directory_size >= ZIP64_LIMIT {
+            let end64 = directory_offset + directory_size;
+            push_uint(&mut directory, ZIP64_END_SIGNATURE as u64, 4);
+            push_uint(&mut directory, (ZIP64_END_SIZE - 12) as u64, 8);
+            push_uint(&mut directory, VERSION_ZIP64 as u64, 2);
+            push_uint(&mut directory, VERSION_ZIP64 as u64, 2);
+            push_uint(&mut directory, 0, 8);
+            push_uint(&mut directory, count, 8);
+            push_uint(&mut directory, count, 8);
+            push_uint(&mut directory, directory_size, 8);
+            push_uint(&mut directory, directory_offset, 8);
+            push_uint(&mut directory, ZIP64_LOCATOR_SIGNATURE as u64, 4);
+            push_uint(&mut directory, 0, 4);
+            push_uint(&mut directory, end64, 8);
+            push_uint(&mut directory, 1, 4);
+        }
+        push_uint(&mut directory, END_SIGNATURE as u64, 4);
+        push_uint(&mut directory, 0, 4);
+        push_uint(&mut directory, min(count, 0xffff), 2);
+        push_uint(&mut directory, min(count, 0xffff), 2);
+        push_uint(&mut directory, min(directory_size, ZIP64_LIMIT), 4);
+        push_uint(&mut directory, min(directory_offset, ZIP64_LIMIT), 4);
+        push_uint(&mut directory, 0, 2);
+        write_at(&mut file, directory_offset, &directory)?;
+
+        self.members.clear();
+        self.end = 0;
+        Ok(())
+    }
+}
+
+impl Drop for NpzFile {
+
+    /// Complete the archive if it was written and not closed, ignoring the errors.
+    fn drop(&mut self) {
+        let _ = self.close();
+    }
+}
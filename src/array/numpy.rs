@@ -1,6 +1,5 @@
 extern crate byteorder;
 
-use std::error::Error;
 use std::fmt::Display;
 use std::fs::File;
 use std::io::{Read,Write};
@@ -14,6 +13,7 @@ use types::complex::{c32, c64};
 use types::cast::Cast;
 use array::{NDArray, NDData};
 use array::ndindex::NDIndex;
+use error::Error;
 
 const NUMPY_MAGIC : [u8;6] = [0x93u8, b'N', b'U', b'M', b'P', b'Y'];
 
@@ -50,193 +50,193 @@ fn extract_in_between(source : &str, start : &str, end : &str) -> Option<String>
     return Some(source[idx1..idx2].to_string());
 }
 
-fn read_from_u8<T : Copy>(reader : &mut File) -> Result<T, String> where u8 : Cast<T> {
+fn read_from_u8<T : Copy, R : Read>(reader : &mut R) -> Result<T, Error> where u8 : Cast<T> {
     match reader.read_u8() { 
         Ok(v) => Ok(Cast::<T>::cast(v)),
-        Err(e) => Err(e.description().to_string())
+        Err(e) => Err(Error::Io(e))
     }
 }
 
-fn read_from_u16<T : Copy, B : ByteOrder>(reader : &mut File) -> Result<T, String> where u16 : Cast<T> {
+fn read_from_u16<T : Copy, B : ByteOrder, R : Read>(reader : &mut R) -> Result<T, Error> where u16 : Cast<T> {
     match reader.read_u16::<B>() { 
         Ok(v) => Ok(Cast::<T>::cast(v)),
-        Err(e) => Err(e.description().to_string())
+        Err(e) => Err(Error::Io(e))
     }
 }
 
-fn read_from_u32<T : Copy, B : ByteOrder>(reader : &mut File) -> Result<T, String> where u32 : Cast<T> {
+fn read_from_u32<T : Copy, B : ByteOrder, R : Read>(reader : &mut R) -> Result<T, Error> where u32 : Cast<T> {
     match reader.read_u32::<B>() { 
         Ok(v) => Ok(Cast::<T>::cast(v)),
-        Err(e) => Err(e.description().to_string())
+        Err(e) => Err(Error::Io(e))
     }
 }
 
-fn read_from_u64<T : Copy, B : ByteOrder>(reader : &mut File) -> Result<T, String> where u64 : Cast<T> {
+fn read_from_u64<T : Copy, B : ByteOrder, R : Read>(reader : &mut R) -> Result<T, Error> where u64 : Cast<T> {
     match reader.read_u64::<B>() { 
         Ok(v) => Ok(Cast::<T>::cast(v)),
-        Err(e) => Err(e.description().to_string())
+        Err(e) => Err(Error::Io(e))
     }
 }
 
-fn read_from_i8<T : Copy>(reader : &mut File) -> Result<T, String> where i8 : Cast<T> {
+fn read_from_i8<T : Copy, R : Read>(reader : &mut R) -> Result<T, Error> where i8 : Cast<T> {
     match reader.read_i8() { 
         Ok(v) => Ok(Cast::<T>::cast(v)),
-        Err(e) => Err(e.description().to_string())
+        Err(e) => Err(Error::Io(e))
     }
 }
 
-fn read_from_i16<T : Copy, B : ByteOrder>(reader : &mut File) -> Result<T, String> where i16 : Cast<T> {
+fn read_from_i16<T : Copy, B : ByteOrder, R : Read>(reader : &mut R) -> Result<T, Error> where i16 : Cast<T> {
     match reader.read_i16::<B>() { 
         Ok(v) => Ok(Cast::<T>::cast(v)),
-        Err(e) => Err(e.description().to_string())
+        Err(e) => Err(Error::Io(e))
     }
 }
 
-fn read_from_i32<T : Copy, B : ByteOrder>(reader : &mut File) -> Result<T, String> where i32 : Cast<T> {
+fn read_from_i32<T : Copy, B : ByteOrder, R : Read>(reader : &mut R) -> Result<T, Error> where i32 : Cast<T> {
     match reader.read_i32::<B>() { 
         Ok(v) => Ok(Cast::<T>::cast(v)),
-        Err(e) => Err(e.description().to_string())
+        Err(e) => Err(Error::Io(e))
     }
 }
 
-fn read_from_i64<T : Copy, B : ByteOrder>(reader : &mut File) -> Result<T, String> where i64 : Cast<T> {
+fn read_from_i64<T : Copy, B : ByteOrder, R : Read>(reader : &mut R) -> Result<T, Error> where i64 : Cast<T> {
     match reader.read_i64::<B>() { 
         Ok(v) => Ok(Cast::<T>::cast(v)),
-        Err(e) => Err(e.description().to_string())
+        Err(e) => Err(Error::Io(e))
     }
 }
 
-fn read_from_f32<T : Copy, B : ByteOrder>(reader : &mut File) -> Result<T, String> where f32 : Cast<T> {
+fn read_from_f32<T : Copy, B : ByteOrder, R : Read>(reader : &mut R) -> Result<T, Error> where f32 : Cast<T> {
     match reader.read_f32::<B>() { 
         Ok(v) => Ok(Cast::<T>::cast(v)),
-        Err(e) => Err(e.description().to_string())
+        Err(e) => Err(Error::Io(e))
     }
 }
 
-fn read_from_f64<T : Copy, B : ByteOrder>(reader : &mut File) -> Result<T, String> where f64 : Cast<T> {
+fn read_from_f64<T : Copy, B : ByteOrder, R : Read>(reader : &mut R) -> Result<T, Error> where f64 : Cast<T> {
     match reader.read_f64::<B>() { 
         Ok(v) => Ok(Cast::<T>::cast(v)),
-        Err(e) => Err(e.description().to_string())
+        Err(e) => Err(Error::Io(e))
     }
 }
 
-fn read_from_c32<T : Copy, B : ByteOrder>(reader : &mut File) -> Result<T, String> where c32 : Cast<T> {
+fn read_from_c32<T : Copy, B : ByteOrder, R : Read>(reader : &mut R) -> Result<T, Error> where c32 : Cast<T> {
     match reader.read_f32::<B>() { 
         Ok(r) => {
             match reader.read_f32::<B>() { 
                 Ok(i) => Ok(Cast::<T>::cast(c32::new(r,i))),
-                Err(e) => Err(e.description().to_string())
+                Err(e) => Err(Error::Io(e))
             }
         },
-        Err(e) => Err(e.description().to_string())
+        Err(e) => Err(Error::Io(e))
     }
 }
 
-fn read_from_c64<T : Copy, B : ByteOrder>(reader : &mut File) -> Result<T, String> where c64 : Cast<T> {
+fn read_from_c64<T : Copy, B : ByteOrder, R : Read>(reader : &mut R) -> Result<T, Error> where c64 : Cast<T> {
     match reader.read_f64::<B>() { 
         Ok(r) => {
             match reader.read_f64::<B>() { 
                 Ok(i) => Ok(Cast::<T>::cast(c64::new(r,i))),
-                Err(e) => Err(e.description().to_string())
+                Err(e) => Err(Error::Io(e))
             }
         },
-        Err(e) => Err(e.description().to_string())
+        Err(e) => Err(Error::Io(e))
     }
 }
 
-fn write_to_u8<T : Copy + RDSTyped>(writer : &mut File, v : T) -> Result<(), String> {
+fn write_to_u8<T : Copy + RDSTyped, W : Write>(writer : &mut W, v : T) -> Result<(), Error> {
     match writer.write_u8(Cast::<u8>::cast(v)) {
         Ok(()) => return Ok(()),
-        Err(e) => return Err(e.description().to_string())
+        Err(e) => return Err(Error::Io(e))
     }
 }
 
-fn write_to_u16<T : Copy + RDSTyped, B : ByteOrder>(writer : &mut File, v : T) -> Result<(), String> {
+fn write_to_u16<T : Copy + RDSTyped, B : ByteOrder, W : Write>(writer : &mut W, v : T) -> Result<(), Error> {
     match writer.write_u16::<B>(Cast::<u16>::cast(v)) {
         Ok(()) => return Ok(()),
-        Err(e) => return Err(e.description().to_string())
+        Err(e) => return Err(Error::Io(e))
     }
 }
 
-fn write_to_u32<T : Copy + RDSTyped, B : ByteOrder>(writer : &mut File, v : T) -> Result<(), String> {
+fn write_to_u32<T : Copy + RDSTyped, B : ByteOrder, W : Write>(writer : &mut W, v : T) -> Result<(), Error> {
     match writer.write_u32::<B>(Cast::<u32>::cast(v)) {
         Ok(()) => return Ok(()),
-        Err(e) => return Err(e.description().to_string())
+        Err(e) => return Err(Error::Io(e))
     }
 }
 
-fn write_to_u64<T : Copy + RDSTyped, B : ByteOrder>(writer : &mut File, v : T) -> Result<(), String> {
+fn write_to_u64<T : Copy + RDSTyped, B : ByteOrder, W : Write>(writer : &mut W, v : T) -> Result<(), Error> {
     match writer.write_u64::<B>(Cast::<u64>::cast(v)) {
         Ok(()) => return Ok(()),
-        Err(e) => return Err(e.description().to_string())
+        Err(e) => return Err(Error::Io(e))
     }
 }
 
-fn write_to_i8<T : Copy + RDSTyped>(writer : &mut File, v : T) -> Result<(), String> {
+fn write_to_i8<T : Copy + RDSTyped, W : Write>(writer : &mut W, v : T) -> Result<(), Error> {
     match writer.write_i8(Cast::<i8>::cast(v)) {
         Ok(()) => return Ok(()),
-        Err(e) => return Err(e.description().to_string())
+        Err(e) => return Err(Error::Io(e))
     }
 }
 
-fn write_to_i16<T : Copy + RDSTyped, B : ByteOrder>(writer : &mut File, v : T) -> Result<(), String> {
+fn write_to_i16<T : Copy + RDSTyped, B : ByteOrder, W : Write>(writer : &mut W, v : T) -> Result<(), Error> {
     match writer.write_i16::<B>(Cast::<i16>::cast(v)) {
         Ok(()) => return Ok(()),
-        Err(e) => return Err(e.description().to_string())
+        Err(e) => return Err(Error::Io(e))
     }
 }
 
-fn write_to_i32<T : Copy + RDSTyped, B : ByteOrder>(writer : &mut File, v : T) -> Result<(), String> {
+fn write_to_i32<T : Copy + RDSTyped, B : ByteOrder, W : Write>(writer : &mut W, v : T) -> Result<(), Error> {
     match writer.write_i32::<B>(Cast::<i32>::cast(v)) {
         Ok(()) => return Ok(()),
-        Err(e) => return Err(e.description().to_string())
+        Err(e) => return Err(Error::Io(e))
     }
 }
 
-fn write_to_i64<T : Copy + RDSTyped, B : ByteOrder>(writer : &mut File, v : T) -> Result<(), String> {
+fn write_to_i64<T : Copy + RDSTyped, B : ByteOrder, W : Write>(writer : &mut W, v : T) -> Result<(), Error> {
     match writer.write_i64::<B>(Cast::<i64>::cast(v)) {
         Ok(()) => return Ok(()),
-        Err(e) => return Err(e.description().to_string())
+        Err(e) => return Err(Error::Io(e))
     }
 }
 
-fn write_to_f32<T : Copy + RDSTyped, B : ByteOrder>(writer : &mut File, v : T) -> Result<(), String> {
+fn write_to_f32<T : Copy + RDSTyped, B : ByteOrder, W : Write>(writer : &mut W, v : T) -> Result<(), Error> {
     match writer.write_f32::<B>(Cast::<f32>::cast(v)) {
         Ok(()) => return Ok(()),
-        Err(e) => return Err(e.description().to_string())
+        Err(e) => return Err(Error::Io(e))
     }
 }
 
-fn write_to_f64<T : Copy + RDSTyped, B : ByteOrder>(writer : &mut File, v : T) -> Result<(), String> {
+fn write_to_f64<T : Copy + RDSTyped, B : ByteOrder, W : Write>(writer : &mut W, v : T) -> Result<(), Error> {
     match writer.write_f64::<B>(Cast::<f64>::cast(v)) {
         Ok(()) => return Ok(()),
-        Err(e) => return Err(e.description().to_string())
+        Err(e) => return Err(Error::Io(e))
     }
 }
 
-fn write_to_c32<T : Copy + RDSTyped, B : ByteOrder>(writer : &mut File, v : T) -> Result<(), String> {
+fn write_to_c32<T : Copy + RDSTyped, B : ByteOrder, W : Write>(writer : &mut W, v : T) -> Result<(), Error> {
     let c = Cast::<c32>::cast(v);
     match writer.write_f32::<B>(c.re) {
         Ok(()) => {
             match writer.write_f32::<B>(c.im) {
                 Ok(()) => return Ok(()),
-                Err(e) => return Err(e.description().to_string())
+                Err(e) => return Err(Error::Io(e))
             }
         },
-        Err(e) => return Err(e.description().to_string())
+        Err(e) => return Err(Error::Io(e))
     }
 }
 
-fn write_to_c64<T : Copy + RDSTyped, B : ByteOrder>(writer : &mut File, v : T) -> Result<(), String> {
+fn write_to_c64<T : Copy + RDSTyped, B : ByteOrder, W : Write>(writer : &mut W, v : T) -> Result<(), Error> {
     let c = Cast::<c64>::cast(v);
     match writer.write_f64::<B>(c.re) {
         Ok(()) => {
             match writer.write_f64::<B>(c.im) {
                 Ok(()) => return Ok(()),
-                Err(e) => return Err(e.description().to_string())
+                Err(e) => return Err(Error::Io(e))
             }
         },
-        Err(e) => return Err(e.description().to_string())
+        Err(e) => return Err(Error::Io(e))
     }
 }
 
@@ -254,89 +254,89 @@ impl NumpyFile {
         }
     }
     
-    fn get_reader(&self) -> Result<File, String> {
+    fn get_reader(&self) -> Result<File, Error> {
         match File::open(&self.path[..]) {
             Ok(r) => Ok(r),
-            Err(e) => Err(e.description().to_string()),
+            Err(e) => Err(Error::Io(e)),
         }
     }
 
-    fn get_writer(&self) -> Result<File, String> {
+    fn get_writer(&self) -> Result<File, Error> {
         match File::create(&self.path[..]) {
             Ok(r) => Ok(r),
-            Err(e) => Err(e.description().to_string()),
+            Err(e) => Err(Error::Io(e)),
         }
     }
 
     /// Open the Numpy file for reading and parse the header, storing the results in the dtype, 
     /// order and endianess fields.
-    /// In case of failure, returns the error as a string.
+    /// In case of failure, returns the error as an `Error`.
     #[allow(unused_assignments)]
-    pub fn read_header(&mut self, file : &mut File) -> Result<(),String> {
+    pub fn read_header<R : Read>(&mut self, file : &mut R) -> Result<(),Error> {
         let mut magic = [0u8;6];
         let mut version = [0u8;2];
         let mut header_size = 0u32;
 
         if let Err(e) = file.read_exact(&mut magic) {
-            return Err(e.description().to_string());
+            return Err(Error::Io(e));
         }
         if magic != NUMPY_MAGIC {
-            return Err(format!("File {} does not have a valid numpy magic", self.path));
+            return Err(Error::Format(format!("File {} does not have a valid numpy magic", self.path)));
         }
 
         if let Err(e) = file.read_exact(&mut version) {
-            return Err(e.description().to_string());
+            return Err(Error::Io(e));
         }
 
         if version[0] == 0x1 {
             let mut buf = [0u8;2];
             if let Err(e) = file.read_exact(&mut buf) {
-                return Err(e.description().to_string());
+                return Err(Error::Io(e));
             }
             header_size = (buf[0] as u32) + ((buf[1] as u32) << 8);
         }
         else if version[0] == 0x2 {
             let mut buf = [0u8;4];
             if let Err(e) = file.read_exact(&mut buf) {
-                return Err(e.description().to_string());
+                return Err(Error::Io(e));
             }
             header_size = (buf[0] as u32) + ((buf[1] as u32) << 8) + ((buf[2] as u32) << 16) + ((buf[3] as u32) << 24);
         }
         else {
-            return Err(format!("Numpy file major version number {} not suppored", version[0]));
+            return Err(Error::Format(format!("Numpy file major version number {} not suppored", version[0])));
         }
 
         let mut header_raw : Vec<u8>= repeat(0u8).take(header_size as usize).collect();
         if let Err(e) = file.read_exact(&mut header_raw[..]) {
-            return Err(e.description().to_string());
+            return Err(Error::Io(e));
         }
         let header = match String::from_utf8(header_raw) {
             Ok(s) => s,
-            Err(e) => return Err(e.description().to_string())
+            Err(e) => return Err(Error::Parse { message : "Numpy header is not valid UTF-8".to_string(), source : Some(Box::new(e)) })
         };
 
         // {'descr': '<i8', 'fortran_order': False, 'shape': (5,), }
         let descr = match extract_in_between(&header[..], "'descr': '", "',") {
             Some(s) => s,
-            None => return Err(format!("descr not present in numpy header : {}", header))
+            None => return Err(Error::Format(format!("descr not present in numpy header : {}", header)))
         };
         let fortran_order = match extract_in_between(&header[..], "'fortran_order': ", ",") {
             Some(s) => s,
-            None => return Err(format!("fortran_order not present in numpy header : {}", header))
+            None => return Err(Error::Format(format!("fortran_order not present in numpy header : {}", header)))
         };
         let shape = match extract_in_between(&header[..], "'shape': (", ")") {
             Some(s) => s,
-            None => return Err(format!("shape not present in numpy header : {}", header))
+            None => return Err(Error::Format(format!("shape not present in numpy header : {}", header)))
         };
 
         if descr.len() < 1 {
-            return Err(format!("descr empty"));
+            return Err(Error::Format("descr empty".to_string()));
         }
 
         self.endianess = match &descr[0..1] {
             "<" | "|" => Endianess::LittleEndian,
             ">" => Endianess::BigEndian,
-            _ => return Err(format!("Failed to parse descr endianess: {}", descr))
+            _ => return Err(Error::Format(format!("Failed to parse descr endianess: {}", descr)))
         };
 
         self.dtype = match &descr[1..] {
@@ -352,13 +352,13 @@ impl NumpyFile {
             "f8" => RDSType::F64,
             "c8" => RDSType::C32,
             "c16" => RDSType::C64,
-            _ => return Err(format!("Unsuported descr type: {}", descr))
+            _ => return Err(Error::UnsupportedType(format!("numpy descr {}", descr)))
         };
         
         self.order = match &fortran_order[..] {
             "False" => Order::RowMajor,
             "True" => Order::ColumnMajor,
-            _ => return Err(format!("Invalid fortran_order: {}", fortran_order))
+            _ => return Err(Error::Format(format!("Invalid fortran_order: {}", fortran_order)))
         };
 
         self.shape.clear();
@@ -367,7 +367,7 @@ impl NumpyFile {
             if s.len() > 0 {
                 match usize::from_str(s) {
                     Ok(u) => self.shape.push(u),
-                    Err(e) => return Err(e.description().to_string())
+                    Err(e) => return Err(Error::Parse { message : format!("Failed to parse dimension '{}' of numpy shape ({})", s, shape), source : Some(Box::new(e)) })
                 }
             }
         }
@@ -375,14 +375,14 @@ impl NumpyFile {
         return Ok(());
     }
     
-    fn write_header(&mut self, file : &mut File) -> Result<(),String> {
+    fn write_header<W : Write>(&mut self, file : &mut W) -> Result<(),Error> {
         let mut header = Vec::<u8>::new();
 
         if let Err(e) = file.write_all(&NUMPY_MAGIC) {
-            return Err(e.description().to_string());
+            return Err(Error::Io(e));
         }
         if let Err(e) = file.write_all(&[1u8,0u8]) {
-            return Err(e.description().to_string());
+            return Err(Error::Io(e));
         }
 
         header.extend_from_slice("{'descr': '".as_bytes());
@@ -426,10 +426,10 @@ impl NumpyFile {
         header.push(b'\n');
 
         if let Err(e) = file.write_u16::<LittleEndian>(header.len() as u16) {
-            return Err(e.description().to_string());
+            return Err(Error::Io(e));
         }
         if let Err(e) = file.write_all(&header[..]) {
-            return Err(e.description().to_string());
+            return Err(Error::Io(e));
         }
 
         return Ok(());
@@ -437,8 +437,8 @@ impl NumpyFile {
 
     /// Open the Numpy file for reading and read the entire numpy array as a NDArray<T>. This 
     /// function operates its own type convertion from the dtype to the type T.
-    /// In case of failure, returns the error as a string.
-    pub fn read_array<T : Copy + RDSTyped + Display>(&mut self) -> Result<NDArray<T>, String>
+    /// In case of failure, returns the error as an `Error`.
+    pub fn read_array<T : Copy + RDSTyped + Display>(&mut self) -> Result<NDArray<T>, Error>
         where u8 : Cast<T>, u16 : Cast<T>, u32 : Cast<T>, u64 : Cast<T>, 
               i8 : Cast<T>, i16 : Cast<T>, i32 : Cast<T>, i64 : Cast<T>, 
               f32 : Cast<T>, f64 : Cast<T>, c32 : Cast<T>, c64 : Cast<T> {
@@ -446,71 +446,82 @@ impl NumpyFile {
             Ok(r) => r,
             Err(e) => return Err(e)
         };
-        if let Err(e) = self.read_header(&mut reader) {
+        return self.read_array_from(&mut reader);
+    }
+
+    /// Parse the header and read the entire numpy array as a NDArray<T> from an arbitrary reader, 
+    /// for example a member of a `.npz` archive. This function operates its own type convertion 
+    /// from the dtype to the type T.
+    /// In case of failure, returns the error as an `Error`.
+    pub fn read_array_from<T : Copy + RDSTyped + Display, R : Read>(&mut self, reader : &mut R) -> Result<NDArray<T>, Error>
+        where u8 : Cast<T>, u16 : Cast<T>, u32 : Cast<T>, u64 : Cast<T>, 
+              i8 : Cast<T>, i16 : Cast<T>, i32 : Cast<T>, i64 : Cast<T>, 
+              f32 : Cast<T>, f64 : Cast<T>, c32 : Cast<T>, c64 : Cast<T> {
+        if let Err(e) = self.read_header(reader) {
             return Err(e);
         }
 
         let readchain = match self.dtype {
-            RDSType::U8 => read_from_u8::<T>,
+            RDSType::U8 => read_from_u8::<T, R>,
             RDSType::U16 => {
                 match self.endianess {
-                    Endianess::BigEndian    => read_from_u16::<T, BigEndian>,
-                    Endianess::LittleEndian => read_from_u16::<T, LittleEndian>,
+                    Endianess::BigEndian    => read_from_u16::<T, BigEndian, R>,
+                    Endianess::LittleEndian => read_from_u16::<T, LittleEndian, R>,
                 }
             },
             RDSType::U32 => {
                 match self.endianess {
-                    Endianess::BigEndian    => read_from_u32::<T, BigEndian>,
-                    Endianess::LittleEndian => read_from_u32::<T, LittleEndian>,
+                    Endianess::BigEndian    => read_from_u32::<T, BigEndian, R>,
+                    Endianess::LittleEndian => read_from_u32::<T, LittleEndian, R>,
                 }
             },
             RDSType::U64 => {
                 match self.endianess {
-                    Endianess::BigEndian    => read_from_u64::<T, BigEndian>,
-                    Endianess::LittleEndian => read_from_u64::<T, LittleEndian>,
+                    Endianess::BigEndian    => read_from_u64::<T, BigEndian, R>,
+                    Endianess::LittleEndian => read_from_u64::<T, LittleEndian, R>,
                 }
             },
-            RDSType::I8 => read_from_i8::<T>,
+            RDSType::I8 => read_from_i8::<T, R>,
             RDSType::I16 => {
                 match self.endianess {
-                    Endianess::BigEndian    => read_from_i16::<T, BigEndian>,
-                    Endianess::LittleEndian => read_from_i16::<T, LittleEndian>,
+                    Endianess::BigEndian    => read_from_i16::<T, BigEndian, R>,
+                    Endianess::LittleEndian => read_from_i16::<T, LittleEndian, R>,
                 }
             },
             RDSType::I32 => {
                 match self.endianess {
-                    Endianess::BigEndian    => read_from_i32::<T, BigEndian>,
-                    Endianess::LittleEndian => read_from_i32::<T, LittleEndian>,
+                    Endianess::BigEndian    => read_from_i32::<T, BigEndian, R>,
+                    Endianess::LittleEndian => read_from_i32::<T, LittleEndian, R>,
                 }
             },
             RDSType::I64 => {
                 match self.endianess {
-                    Endianess::BigEndian    => read_from_i64::<T, BigEndian>,
-                    Endianess::LittleEndian => read_from_i64::<T, LittleEndian>,
+                    Endianess::BigEndian    => read_from_i64::<T, BigEndian, R>,
+                    Endianess::LittleEndian => read_from_i64::<T, LittleEndian, R>,
                 }
             },
             RDSType::F32 => {
                 match self.endianess {
-                    Endianess::BigEndian    => read_from_f32::<T, BigEndian>,
-                    Endianess::LittleEndian => read_from_f32::<T, LittleEndian>,
+                    Endianess::BigEndian    => read_from_f32::<T, BigEndian, R>,
+                    Endianess::LittleEndian => read_from_f32::<T, LittleEndian, R>,
                 }
             },
             RDSType::F64 => {
                 match self.endianess {
-                    Endianess::BigEndian    => read_from_f64::<T, BigEndian>,
-                    Endianess::LittleEndian => read_from_f64::<T, LittleEndian>,
+                    Endianess::BigEndian    => read_from_f64::<T, BigEndian, R>,
+                    Endianess::LittleEndian => read_from_f64::<T, LittleEndian, R>,
                 }
             },
             RDSType::C32 => {
                 match self.endianess {
-                    Endianess::BigEndian    => read_from_c32::<T, BigEndian>,
-                    Endianess::LittleEndian => read_from_c32::<T, LittleEndian>,
+                    Endianess::BigEndian    => read_from_c32::<T, BigEndian, R>,
+                    Endianess::LittleEndian => read_from_c32::<T, LittleEndian, R>,
                 }
             },
             RDSType::C64 => {
                 match self.endianess {
-                    Endianess::BigEndian    => read_from_c64::<T, BigEndian>,
-                    Endianess::LittleEndian => read_from_c64::<T, LittleEndian>,
+                    Endianess::BigEndian    => read_from_c64::<T, BigEndian, R>,
+                    Endianess::LittleEndian => read_from_c64::<T, LittleEndian, R>,
                 }
             },
         };
@@ -519,7 +530,7 @@ impl NumpyFile {
         let mut idx : Vec<usize> = repeat(0usize).take(self.shape.len()).collect();
 
         loop {
-            array[&idx[..]] = match readchain(&mut reader) {
+            array[&idx[..]] = match readchain(reader) {
                 Ok(v) => v,
                 Err(e) => return Err(e)
             };
@@ -542,78 +553,86 @@ impl NumpyFile {
     /// Open (or create) the Numpy file for writing and write the entire NDData<T> in it. This 
     /// function operates its own type convertion from the type T to the dtype. It is thus 
     /// important to specify the desired dtype in the NumpyFile structure.
-    /// In case of failure, returns the error as a string.
-    pub fn write_data<T : Copy + RDSTyped + Display>(&mut self, array : &NDData<T>) -> Result<(), String>  {
+    /// In case of failure, returns the error as an `Error`.
+    pub fn write_data<T : Copy + RDSTyped + Display>(&mut self, array : &NDData<T>) -> Result<(), Error>  {
         let mut writer = match self.get_writer() {
             Ok(w) => w,
             Err(e) => return Err(e)
         };
+        return self.write_data_to(&mut writer, array);
+    }
+
+    /// Write the header and the entire NDData<T> to an arbitrary writer, for example a buffer 
+    /// later stored in a `.npz` archive. The conversion to the dtype is the same as for 
+    /// `write_data`.
+    /// In case of failure, returns the error as an `Error`.
+    pub fn write_data_to<T : Copy + RDSTyped + Display, W : Write>(&mut self, writer : &mut W, array : &NDData<T>) -> Result<(), Error>  {
         self.shape = array.shape().to_vec();
-        if let Err(e) = self.write_header(&mut writer) {
+        if let Err(e) = self.write_header(writer) {
             return Err(e);
         }
 
         let writechain = match self.dtype {
-            RDSType::U8 => write_to_u8::<T>,
+            RDSType::U8 => write_to_u8::<T, W>,
             RDSType::U16 => {
                 match self.endianess {
-                    Endianess::BigEndian    => write_to_u16::<T, BigEndian>,
-                    Endianess::LittleEndian => write_to_u16::<T, LittleEndian>,
+                    Endianess::BigEndian    => write_to_u16::<T, BigEndian, W>,
+                    Endianess::LittleEndian => write_to_u16::<T, LittleEndian, W>,
                 }
             },
             RDSType::U32 => {
                 match self.endianess {
-                    Endianess::BigEndian    => write_to_u32::<T, BigEndian>,
-                    Endianess::LittleEndian => write_to_u32::<T, LittleEndian>,
+                    Endianess::BigEndian    => write_to_u32::<T, BigEndian, W>,
+                    Endianess::LittleEndian => write_to_u32::<T, LittleEndian, W>,
                 }
             },
             RDSType::U64 => {
                 match self.endianess {
-                    Endianess::BigEndian    => write_to_u64::<T, BigEndian>,
-                    Endianess::LittleEndian => write_to_u64::<T, LittleEndian>,
+                    Endianess::BigEndian    => write_to_u64::<T, BigEndian, W>,
+                    Endianess::LittleEndian => write_to_u64::<T, LittleEndian, W>,
                 }
             },
-            RDSType::I8 => write_to_i8::<T>,
+            RDSType::I8 => write_to_i8::<T, W>,
             RDSType::I16 => {
                 match self.endianess {
-                    Endianess::BigEndian    => write_to_i16::<T, BigEndian>,
-                    Endianess::LittleEndian => write_to_i16::<T, LittleEndian>,
+                    Endianess::BigEndian    => write_to_i16::<T, BigEndian, W>,
+                    Endianess::LittleEndian => write_to_i16::<T, LittleEndian, W>,
                 }
             },
             RDSType::I32 => {
                 match self.endianess {
-                    Endianess::BigEndian    => write_to_i32::<T, BigEndian>,
-                    Endianess::LittleEndian => write_to_i32::<T, LittleEndian>,
+                    Endianess::BigEndian    => write_to_i32::<T, BigEndian, W>,
+                    Endianess::LittleEndian => write_to_i32::<T, LittleEndian, W>,
                 }
             },
             RDSType::I64 => {
                 match self.endianess {
-                    Endianess::BigEndian    => write_to_i64::<T, BigEndian>,
-                    Endianess::LittleEndian => write_to_i64::<T, LittleEndian>,
+                    Endianess::BigEndian    => write_to_i64::<T, BigEndian, W>,
+                    Endianess::LittleEndian => write_to_i64::<T, LittleEndian, W>,
                 }
             },
             RDSType::F32 => {
                 match self.endianess {
-                    Endianess::BigEndian    => write_to_f32::<T, BigEndian>,
-                    Endianess::LittleEndian => write_to_f32::<T, LittleEndian>,
+                    Endianess::BigEndian    => write_to_f32::<T, BigEndian, W>,
+                    Endianess::LittleEndian => write_to_f32::<T, LittleEndian, W>,
                 }
             },
             RDSType::F64 => {
                 match self.endianess {
-                    Endianess::BigEndian    => write_to_f64::<T, BigEndian>,
-                    Endianess::LittleEndian => write_to_f64::<T, LittleEndian>,
+                    Endianess::BigEndian    => write_to_f64::<T, BigEndian, W>,
+                    Endianess::LittleEndian => write_to_f64::<T, LittleEndian, W>,
                 }
             },
             RDSType::C32 => {
                 match self.endianess {
-                    Endianess::BigEndian    => write_to_c32::<T, BigEndian>,
-                    Endianess::LittleEndian => write_to_c32::<T, LittleEndian>,
+                    Endianess::BigEndian    => write_to_c32::<T, BigEndian, W>,
+                    Endianess::LittleEndian => write_to_c32::<T, LittleEndian, W>,
                 }
             },
             RDSType::C64 => {
                 match self.endianess {
-                    Endianess::BigEndian    => write_to_c64::<T, BigEndian>,
-                    Endianess::LittleEndian => write_to_c64::<T, LittleEndian>,
+                    Endianess::BigEndian    => write_to_c64::<T, BigEndian, W>,
+                    Endianess::LittleEndian => write_to_c64::<T, LittleEndian, W>,
                 }
             },
         };
@@ -621,7 +640,7 @@ impl NumpyFile {
         let mut idx : Vec<usize> = repeat(0usize).take(array.dim()).collect();
 
         loop {
-            if let Err(e) = writechain(&mut writer, array.idx(&idx[..]).clone()) {
+            if let Err(e) = writechain(writer, array.idx(&idx[..]).clone()) {
                 return Err(e)
             }
             match self.order {
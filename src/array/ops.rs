@@ -0,0 +1,635 @@
+use std::fmt::Display;
+use std::ops::{Add, AddAssign, Sub, SubAssign, Mul, MulAssign, Div, DivAssign, Rem, RemAssign, Neg};
+
+use types::complex::{c32, c64};
+use array::{NDData, NDDataMut, NDArray, NDSlice, NDSliceMut, NDView, NDViewMut};
+use array::broadcast::{broadcast_shape, zip_with, zip_assign, par_zip_with, par_zip_assign};
+use blas::Blas;
+
+/// Trait implemented by every RDS type to select how the in place additive and multiplicative 
+/// operators are computed. Floating point and complex types use Blas (axpy or scal) when the 
+/// operands allow it while other types use a generic loop.
+pub trait ElementWise : Clone + Display + Sized {
+
+    /// Compute a += b, broadcasting b to the shape of a.
+    fn add_assign<A : NDDataMut<Self>, B : NDData<Self>>(a : &mut A, b : &B);
+
+    /// Compute a -= b, broadcasting b to the shape of a.
+    fn sub_assign<A : NDDataMut<Self>, B : NDData<Self>>(a : &mut A, b : &B);
+
+    /// Compute a *= b, broadcasting b to the shape of a.
+    fn mul_assign<A : NDDataMut<Self>, B : NDData<Self>>(a : &mut A, b : &B);
+
+    /// Return a + b, broadcasting a and b together.
+    fn elem_add<A : NDData<Self>, B : NDData<Self>>(a : &A, b : &B) -> NDArray<Self>;
+
+    /// Return a - b, broadcasting a and b together.
+    fn elem_sub<A : NDData<Self>, B : NDData<Self>>(a : &A, b : &B) -> NDArray<Self>;
+
+    /// Return a * b, broadcasting a and b together.
+    fn elem_mul<A : NDData<Self>, B : NDData<Self>>(a : &A, b : &B) -> NDArray<Self>;
+}
+
+/// Compute the binary operation op on a and b. When the result has the shape of a, it is computed
+/// in place on a copy of a with op_assign, otherwise op is applied to every pair of broadcasted
+/// elements on the thread pool.
+fn binary_op<T, A, B, F>(a : &A, b : &B, op_assign : fn(&mut NDArray<T>, &B), op : F) -> NDArray<T>
+    where T : ElementWise + Send + Sync, A : NDData<T>, B : NDData<T>, F : Fn(&T, &T) -> T + Sync {
+    if broadcast_shape(a.shape(), b.shape()).as_ref().map(|s| &s[..]) == Some(a.shape()) {
+        let mut res = NDArray::<T>::copy(a);
+        op_assign(&mut res, b);
+        return res;
+    }
+    par_zip_with(a, b, op)
+}
+
+// The element-wise operations of every RDS type are shared between threads with the parallel 
+// variants of the broadcasting functions.
+macro_rules! element_wise_ops {
+    ($t:ty) => {
+        fn elem_add<A : NDData<$t>, B : NDData<$t>>(a : &A, b : &B) -> NDArray<$t> {
+            binary_op(a, b, <$t as ElementWise>::add_assign, |x, y| *x + *y)
+        }
+
+        fn elem_sub<A : NDData<$t>, B : NDData<$t>>(a : &A, b : &B) -> NDArray<$t> {
+            binary_op(a, b, <$t as ElementWise>::sub_assign, |x, y| *x - *y)
+        }
+
+        fn elem_mul<A : NDData<$t>, B : NDData<$t>>(a : &A, b : &B) -> NDArray<$t> {
+            binary_op(a, b, <$t as ElementWise>::mul_assign, |x, y| *x * *y)
+        }
+    }
+}
+
+// Integer types have no Blas routine and always use the generic loop.
+macro_rules! loop_element_wise {
+    ($($t:ty),*) => {
+        $(
+            impl ElementWise for $t {
+
+                fn add_assign<A : NDDataMut<$t>, B : NDData<$t>>(a : &mut A, b : &B) {
+                    par_zip_assign(a, b, |x, y| *x = *x + *y);
+                }
+
+                fn sub_assign<A : NDDataMut<$t>, B : NDData<$t>>(a : &mut A, b : &B) {
+                    par_zip_assign(a, b, |x, y| *x = *x - *y);
+                }
+
+                fn mul_assign<A : NDDataMut<$t>, B : NDData<$t>>(a : &mut A, b : &B) {
+                    par_zip_assign(a, b, |x, y| *x = *x * *y);
+                }
+
+                element_wise_ops!($t);
+            }
+        )*
+    }
+}
+
+// Floating point and complex types use axpy for operands of the same shape and scal for a zero 
+// dimensional multiplier.
+macro_rules! blas_element_wise {
+    ($($t:ty : $one:expr, $minus_one:expr);*) => {
+        $(
+            impl ElementWise for $t {
+
+                fn add_assign<A : NDDataMut<$t>, B : NDData<$t>>(a : &mut A, b : &B) {
+                    if a.shape() == b.shape() {
+                        a.axpy($one, b);
+                    }
+                    else {
+                        par_zip_assign(a, b, |x, y| *x = *x + *y);
+                    }
+                }
+
+                fn sub_assign<A : NDDataMut<$t>, B : NDData<$t>>(a : &mut A, b : &B) {
+                    if a.shape() == b.shape() {
+                        a.axpy($minus_one, b);
+                    }
+                    else {
+                        par_zip_assign(a, b, |x, y| *x = *x - *y);
+                    }
+                }
+
+                fn mul_assign<A : NDDataMut<$t>, B : NDData<$t>>(a : &mut A, b : &B) {
+                    if b.dim() == 0 {
+                        a.scal(*b.idx(&[]));
+                    }
+                    else {
+                        par_zip_assign(a, b, |x, y| *x = *x * *y);
+                    }
+                }
+
+                element_wise_ops!($t);
+            }
+        )*
+    }
+}
+
+loop_element_wise!(u8, u16, u32, u64, i8, i16, i32, i64);
+blas_element_wise!(f32 : 1.0, -1.0;
+                   f64 : 1.0, -1.0;
+                   c32 : c32::new(1.0, 0.0), c32::new(-1.0, 0.0);
+                   c64 : c64::new(1.0, 0.0), c64::new(-1.0, 0.0));
+
+/*
+==================== AddAssign ====================
+*/
+
+impl<'a, T : ElementWise, R : NDData<T> + Sized> AddAssign<&'a R> for NDArray<T> {
+
+    fn add_assign(&mut self, rhs: &'a R) {
+        T::add_assign(self, rhs);
+    }
+}
+
+impl<'a, 'b, T : ElementWise, R : NDData<T> + Sized> AddAssign<&'a R> for NDSliceMut<'b, T> {
+
+    fn add_assign(&mut self, rhs: &'a R) {
+        T::add_assign(self, rhs);
+    }
+}
+
+impl<'a, 'b, T : ElementWise, R : NDData<T> + Sized> AddAssign<&'a R> for NDViewMut<'b, T> {
+
+    fn add_assign(&mut self, rhs: &'a R) {
+        T::add_assign(self, rhs);
+    }
+}
+
+/*
+==================== Add ====================
+*/
+
+impl<'a, 'b, T : ElementWise + Add<Output = T>, R : NDData<T> + Sized> Add<&'a R> for &'b NDArray<T> {
+    type Output = NDArray<T>;
+
+    fn add(self, rhs: &'a R) -> NDArray<T> {
+        T::elem_add(self, rhs)
+    }
+}
+
+impl<'a, 'b, 'c, T : ElementWise + Add<Output = T>, R : NDData<T> + Sized> Add<&'a R> for &'b NDSlice<'c, T> {
+    type Output = NDArray<T>;
+
+    fn add(self, rhs: &'a R) -> NDArray<T> {
+        T::elem_add(self, rhs)
+    }
+}
+
+impl<'a, 'b, 'c, T : ElementWise + Add<Output = T>, R : NDData<T> + Sized> Add<&'a R> for &'b NDSliceMut<'c, T> {
+    type Output = NDArray<T>;
+
+    fn add(self, rhs: &'a R) -> NDArray<T> {
+        T::elem_add(self, rhs)
+    }
+}
+
+impl<'a, 'b, 'c, T : ElementWise + Add<Output = T>, R : NDData<T> + Sized> Add<&'a R> for &'b NDView<'c, T> {
+    type Output = NDArray<T>;
+
+    fn add(self, rhs: &'a R) -> NDArray<T> {
+        T::elem_add(self, rhs)
+    }
+}
+
+impl<'a, 'b, 'c, T : ElementWise + Add<Output = T>, R : NDData<T> + Sized> Add<&'a R> for &'b NDViewMut<'c, T> {
+    type Output = NDArray<T>;
+
+    fn add(self, rhs: &'a R) -> NDArray<T> {
+        T::elem_add(self, rhs)
+    }
+}
+
+/*
+==================== SubAssign ====================
+*/
+
+impl<'a, T : ElementWise, R : NDData<T> + Sized> SubAssign<&'a R> for NDArray<T> {
+
+    fn sub_assign(&mut self, rhs: &'a R) {
+        T::sub_assign(self, rhs);
+    }
+}
+
+impl<'a, 'b, T : ElementWise, R : NDData<T> + Sized> SubAssign<&'a R> for NDSliceMut<'b, T> {
+
+    fn sub_assign(&mut self, rhs: &'a R) {
+        T::sub_assign(self, rhs);
+    }
+}
+
+impl<'a, 'b, T : ElementWise, R : NDData<T> + Sized> SubAssign<&'a R> for NDViewMut<'b, T> {
+
+    fn sub_assign(&mut self, rhs: &'a R) {
+        T::sub_assign(self, rhs);
+    }
+}
+
+/*
+==================== Sub ====================
+*/
+
+impl<'a, 'b, T : ElementWise + Sub<Output = T>, R : NDData<T> + Sized> Sub<&'a R> for &'b NDArray<T> {
+    type Output = NDArray<T>;
+
+    fn sub(self, rhs: &'a R) -> NDArray<T> {
+        T::elem_sub(self, rhs)
+    }
+}
+
+impl<'a, 'b, 'c, T : ElementWise + Sub<Output = T>, R : NDData<T> + Sized> Sub<&'a R> for &'b NDSlice<'c, T> {
+    type Output = NDArray<T>;
+
+    fn sub(self, rhs: &'a R) -> NDArray<T> {
+        T::elem_sub(self, rhs)
+    }
+}
+
+impl<'a, 'b, 'c, T : ElementWise + Sub<Output = T>, R : NDData<T> + Sized> Sub<&'a R> for &'b NDSliceMut<'c, T> {
+    type Output = NDArray<T>;
+
+    fn sub(self, rhs: &'a R) -> NDArray<T> {
+        T::elem_sub(self, rhs)
+    }
+}
+
+impl<'a, 'b, 'c, T : ElementWise + Sub<Output = T>, R : NDData<T> + Sized> Sub<&'a R> for &'b NDView<'c, T> {
+    type Output = NDArray<T>;
+
+    fn sub(self, rhs: &'a R) -> NDArray<T> {
+        T::elem_sub(self, rhs)
+    }
+}
+
+impl<'a, 'b, 'c, T : ElementWise + Sub<Output = T>, R : NDData<T> + Sized> Sub<&'a R> for &'b NDViewMut<'c, T> {
+    type Output = NDArray<T>;
+
+    fn sub(self, rhs: &'a R) -> NDArray<T> {
+        T::elem_sub(self, rhs)
+    }
+}
+
+/*
+==================== MulAssign ====================
+*/
+
+impl<'a, T : ElementWise, R : NDData<T> + Sized> MulAssign<&'a R> for NDArray<T> {
+
+    fn mul_assign(&mut self, rhs: &'a R) {
+        T::mul_assign(self, rhs);
+    }
+}
+
+impl<'a, 'b, T : ElementWise, R : NDData<T> + Sized> MulAssign<&'a R> for NDSliceMut<'b, T> {
+
+    fn mul_assign(&mut self, rhs: &'a R) {
+        T::mul_assign(self, rhs);
+    }
+}
+
+impl<'a, 'b, T : ElementWise, R : NDData<T> + Sized> MulAssign<&'a R> for NDViewMut<'b, T> {
+
+    fn mul_assign(&mut self, rhs: &'a R) {
+        T::mul_assign(self, rhs);
+    }
+}
+
+/*
+==================== Mul ====================
+*/
+
+impl<'a, 'b, T : ElementWise + Mul<Output = T>, R : NDData<T> + Sized> Mul<&'a R> for &'b NDArray<T> {
+    type Output = NDArray<T>;
+
+    fn mul(self, rhs: &'a R) -> NDArray<T> {
+        T::elem_mul(self, rhs)
+    }
+}
+
+impl<'a, 'b, 'c, T : ElementWise + Mul<Output = T>, R : NDData<T> + Sized> Mul<&'a R> for &'b NDSlice<'c, T> {
+    type Output = NDArray<T>;
+
+    fn mul(self, rhs: &'a R) -> NDArray<T> {
+        T::elem_mul(self, rhs)
+    }
+}
+
+impl<'a, 'b, 'c, T : ElementWise + Mul<Output = T>, R : NDData<T> + Sized> Mul<&'a R> for &'b NDSliceMut<'c, T> {
+    type Output = NDArray<T>;
+
+    fn mul(self, rhs: &'a R) -> NDArray<T> {
+        T::elem_mul(self, rhs)
+    }
+}
+
+impl<'a, 'b, 'c, T : ElementWise + Mul<Output = T>, R : NDData<T> + Sized> Mul<&'a R> for &'b NDView<'c, T> {
+    type Output = NDArray<T>;
+
+    fn mul(self, rhs: &'a R) -> NDArray<T> {
+        T::elem_mul(self, rhs)
+    }
+}
+
+impl<'a, 'b, 'c, T : ElementWise + Mul<Output = T>, R : NDData<T> + Sized> Mul<&'a R> for &'b NDViewMut<'c, T> {
+    type Output = NDArray<T>;
+
+    fn mul(self, rhs: &'a R) -> NDArray<T> {
+        T::elem_mul(self, rhs)
+    }
+}
+
+/*
+==================== DivAssign ====================
+*/
+
+impl<'a, T : Clone + Display + Div<Output = T>, R : NDData<T> + Sized> DivAssign<&'a R> for NDArray<T> {
+
+    fn div_assign(&mut self, rhs: &'a R) {
+        zip_assign(self, rhs, |a, b| *a = a.clone() / b.clone());
+    }
+}
+
+impl<'a, 'b, T : Clone + Display + Div<Output = T>, R : NDData<T> + Sized> DivAssign<&'a R> for NDSliceMut<'b, T> {
+
+    fn div_assign(&mut self, rhs: &'a R) {
+        zip_assign(self, rhs, |a, b| *a = a.clone() / b.clone());
+    }
+}
+
+impl<'a, 'b, T : Clone + Display + Div<Output = T>, R : NDData<T> + Sized> DivAssign<&'a R> for NDViewMut<'b, T> {
+
+    fn div_assign(&mut self, rhs: &'a R) {
+        zip_assign(self, rhs, |a, b| *a = a.clone() / b.clone());
+    }
+}
+
+/*
+==================== Div ====================
+*/
+
+impl<'a, 'b, T : Clone + Div<Output = T>, R : NDData<T> + Sized> Div<&'a R> for &'b NDArray<T> {
+    type Output = NDArray<T>;
+
+    fn div(self, rhs: &'a R) -> NDArray<T> {
+        zip_with(self, rhs, |a, b| a.clone() / b.clone())
+    }
+}
+
+impl<'a, 'b, 'c, T : Clone + Div<Output = T>, R : NDData<T> + Sized> Div<&'a R> for &'b NDSlice<'c, T> {
+    type Output = NDArray<T>;
+
+    fn div(self, rhs: &'a R) -> NDArray<T> {
+        zip_with(self, rhs, |a, b| a.clone() / b.clone())
+    }
+}
+
+impl<'a, 'b, 'c, T : Clone + Div<Output = T>, R : NDData<T> + Sized> Div<&'a R> for &'b NDSliceMut<'c, T> {
+    type Output = NDArray<T>;
+
+    fn div(self, rhs: &'a R) -> NDArray<T> {
+        zip_with(self, rhs, |a, b| a.clone() / b.clone())
+    }
+}
+
+impl<'a, 'b, 'c, T : Clone + Div<Output = T>, R : NDData<T> + Sized> Div<&'a R> for &'b NDView<'c, T> {
+    type Output = NDArray<T>;
+
+    fn div(self, rhs: &'a R) -> NDArray<T> {
+        zip_with(self, rhs, |a, b| a.clone() / b.clone())
+    }
+}
+
+impl<'a, 'b, 'c, T : Clone + Div<Output = T>, R : NDData<T> + Sized> Div<&'a R> for &'b NDViewMut<'c, T> {
+    type Output = NDArray<T>;
+
+    fn div(self, rhs: &'a R) -> NDArray<T> {
+        zip_with(self, rhs, |a, b| a.clone() / b.clone())
+    }
+}
+
+/*
+==================== RemAssign ====================
+*/
+
+impl<'a, T : Clone + Display + Rem<Output = T>, R : NDData<T> + Sized> RemAssign<&'a R> for NDArray<T> {
+
+    fn rem_assign(&mut self, rhs: &'a R) {
+        zip_assign(self, rhs, |a, b| *a = a.clone() % b.clone());
+    }
+}
+
+impl<'a, 'b, T : Clone + Display + Rem<Output = T>, R : NDData<T> + Sized> RemAssign<&'a R> for NDSliceMut<'b, T> {
+
+    fn rem_assign(&mut self, rhs: &'a R) {
+        zip_assign(self, rhs, |a, b| *a = a.clone() % b.clone());
+    }
+}
+
+impl<'a, 'b, T : Clone + Display + Rem<Output = T>, R : NDData<T> + Sized> RemAssign<&'a R> for NDViewMut<'b, T> {
+
+    fn rem_assign(&mut self, rhs: &'a R) {
+        zip_assign(self, rhs, |a, b| *a = a.clone() % b.clone());
+    }
+}
+
+/*
+==================== Rem ====================
+*/
+
+impl<'a, 'b, T : Clone + Rem<Output = T>, R : NDData<T> + Sized> Rem<&'a R> for &'b NDArray<T> {
+    type Output = NDArray<T>;
+
+    fn rem(self, rhs: &'a R) -> NDArray<T> {
+        zip_with(self, rhs, |a, b| a.clone() % b.clone())
+    }
+}
+
+impl<'a, 'b, 'c, T : Clone + Rem<Output = T>, R : NDData<T> + Sized> Rem<&'a R> for &'b NDSlice<'c, T> {
+    type Output = NDArray<T>;
+
+    fn rem(self, rhs: &'a R) -> NDArray<T> {
+        zip_with(self, rhs, |a, b| a.clone() % b.clone())
+    }
+}
+
+impl<'a, 'b, 'c, T : Clone + Rem<Output = T>, R : NDData<T> + Sized> Rem<&'a R> for &'b NDSliceMut<'c, T> {
+    type Output = NDArray<T>;
+
+    fn rem(self, rhs: &'a R) -> NDArray<T> {
+        zip_with(self, rhs, |a, b| a.clone() % b.clone())
+    }
+}
+
+impl<'a, 'b, 'c, T : Clone + Rem<Output = T>, R : NDData<T> + Sized> Rem<&'a R> for &'b NDView<'c, T> {
+    type Output = NDArray<T>;
+
+    fn rem(self, rhs: &'a R) -> NDArray<T> {
+        zip_with(self, rhs, |a, b| a.clone() % b.clone())
+    }
+}
+
+impl<'a, 'b, 'c, T : Clone + Rem<Output = T>, R : NDData<T> + Sized> Rem<&'a R> for &'b NDViewMut<'c, T> {
+    type Output = NDArray<T>;
+
+    fn rem(self, rhs: &'a R) -> NDArray<T> {
+        zip_with(self, rhs, |a, b| a.clone() % b.clone())
+    }
+}
+
+/*
+==================== Neg ====================
+*/
+
+impl<'b, T : Clone + Neg<Output = T>> Neg for &'b NDArray<T> {
+    type Output = NDArray<T>;
+
+    fn neg(self) -> NDArray<T> {
+        let mut res = NDArray::<T>::copy(self);
+        for x in res.data.iter_mut() {
+            *x = -x.clone();
+        }
+        return res;
+    }
+}
+
+impl<'b, 'c, T : Clone + Neg<Output = T>> Neg for &'b NDSlice<'c, T> {
+    type Output = NDArray<T>;
+
+    fn neg(self) -> NDArray<T> {
+        let mut res = NDArray::<T>::copy(self);
+        for x in res.data.iter_mut() {
+            *x = -x.clone();
+        }
+        return res;
+    }
+}
+
+impl<'b, 'c, T : Clone + Neg<Output = T>> Neg for &'b NDSliceMut<'c, T> {
+    type Output = NDArray<T>;
+
+    fn neg(self) -> NDArray<T> {
+        let mut res = NDArray::<T>::copy(self);
+        for x in res.data.iter_mut() {
+            *x = -x.clone();
+        }
+        return res;
+    }
+}
+
+impl<'b, 'c, T : Clone + Neg<Output = T>> Neg for &'b NDView<'c, T> {
+    type Output = NDArray<T>;
+
+    fn neg(self) -> NDArray<T> {
+        let mut res = NDArray::<T>::copy(self);
+        for x in res.data.iter_mut() {
+            *x = -x.clone();
+        }
+        return res;
+    }
+}
+
+impl<'b, 'c, T : Clone + Neg<Output = T>> Neg for &'b NDViewMut<'c, T> {
+    type Output = NDArray<T>;
+
+    fn neg(self) -> NDArray<T> {
+        let mut res = NDArray::<T>::copy(self);
+        for x in res.data.iter_mut() {
+            *x = -x.clone();
+        }
+        return res;
+    }
+}
+
+/*
+==================== Scalar operands ====================
+*/
+
+// Scalar operands need one implementation per type as a generic implementation would conflict 
+// with the NDData operands. They are broadcasted as a zero dimensional NDArray.
+macro_rules! scalar_op {
+    ($t:ty, $Op:ident, $op:ident, $OpAssign:ident, $op_assign:ident) => {
+        impl $OpAssign<$t> for NDArray<$t> {
+
+            fn $op_assign(&mut self, rhs: $t) {
+                $OpAssign::<&NDArray<$t>>::$op_assign(self, &NDArray::<$t>::new(&[], rhs));
+            }
+        }
+
+        impl<'b> $OpAssign<$t> for NDSliceMut<'b, $t> {
+
+            fn $op_assign(&mut self, rhs: $t) {
+                $OpAssign::<&NDArray<$t>>::$op_assign(self, &NDArray::<$t>::new(&[], rhs));
+            }
+        }
+
+        impl<'b> $OpAssign<$t> for NDViewMut<'b, $t> {
+
+            fn $op_assign(&mut self, rhs: $t) {
+                $OpAssign::<&NDArray<$t>>::$op_assign(self, &NDArray::<$t>::new(&[], rhs));
+            }
+        }
+
+        impl<'b> $Op<$t> for &'b NDArray<$t> {
+            type Output = NDArray<$t>;
+
+            fn $op(self, rhs: $t) -> NDArray<$t> {
+                $Op::<&NDArray<$t>>::$op(self, &NDArray::<$t>::new(&[], rhs))
+            }
+        }
+
+        impl<'b, 'c> $Op<$t> for &'b NDSlice<'c, $t> {
+            type Output = NDArray<$t>;
+
+            fn $op(self, rhs: $t) -> NDArray<$t> {
+                $Op::<&NDArray<$t>>::$op(self, &NDArray::<$t>::new(&[], rhs))
+            }
+        }
+
+        impl<'b, 'c> $Op<$t> for &'b NDSliceMut<'c, $t> {
+            type Output = NDArray<$t>;
+
+            fn $op(self, rhs: $t) -> NDArray<$t> {
+                $Op::<&NDArray<$t>>::$op(self, &NDArray::<$t>::new(&[], rhs))
+            }
+        }
+
+        impl<'b, 'c> $Op<$t> for &'b NDView<'c, $t> {
+            type Output = NDArray<$t>;
+
+            fn $op(self, rhs: $t) -> NDArray<$t> {
+                $Op::<&NDArray<$t>>::$op(self, &NDArray::<$t>::new(&[], rhs))
+            }
+        }
+
+        impl<'b, 'c> $Op<$t> for &'b NDViewMut<'c, $t> {
+            type Output = NDArray<$t>;
+
+            fn $op(self, rhs: $t) -> NDArray<$t> {
+                $Op::<&NDArray<$t>>::$op(self, &NDArray::<$t>::new(&[], rhs))
+            }
+        }
+    }
+}
+
+macro_rules! scalar_ops {
+    ($($t:ty),*) => {
+        $(
+            scalar_op!($t, Add, add, AddAssign, add_assign);
+            scalar_op!($t, Sub, sub, SubAssign, sub_assign);
+            scalar_op!($t, Mul, mul, MulAssign, mul_assign);
+            scalar_op!($t, Div, div, DivAssign, div_assign);
+        )*
+    }
+}
+
+scalar_ops!(u8, u16, u32, u64, i8, i16, i32, i64, f32, f64, c32, c64);
+scalar_op!(u8, Rem, rem, RemAssign, rem_assign);
+scalar_op!(u16, Rem, rem, RemAssign, rem_assign);
+scalar_op!(u32, Rem, rem, RemAssign, rem_assign);
+scalar_op!(u64, Rem, rem, RemAssign, rem_assign);
+scalar_op!(i8, Rem, rem, RemAssign, rem_assign);
+scalar_op!(i16, Rem, rem, RemAssign, rem_assign);
+scalar_op!(i32, Rem, rem, RemAssign, rem_assign);
+scalar_op!(i64, Rem, rem, RemAssign, rem_assign);
+scalar_op!(f32, Rem, rem, RemAssign, rem_assign);
+scalar_op!(f64, Rem, rem, RemAssign, rem_assign);
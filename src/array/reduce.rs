@@ -0,0 +1,350 @@
+use std::ops::{Add, Mul, Div};
+
+use types::RDSTyped;
+use types::complex::{c32, c64};
+use array::{NDData, NDArray};
+use array::ndindex::NDIndex;
+use parallel;
+
+/// Trait implemented by the RDS types to provide the values needed by the reductions.
+pub trait Reducible : RDSTyped + Copy + 'static + Add<Output = Self> + Mul<Output = Self> + Div<Output = Self> {
+
+    /// Neutral element of the addition.
+    fn zero() -> Self;
+
+    /// Neutral element of the multiplication.
+    fn one() -> Self;
+
+    /// Mean of the values, accumulated in a type wider than Self so that it does not overflow.
+    /// Without values, the mean is NaN for floating point and complex types and panics for
+    /// integers.
+    fn mean<I : Iterator<Item = Self>>(values : I) -> Self;
+
+    /// Return true if the value is a floating point NaN.
+    fn is_nan(&self) -> bool;
+}
+
+// Integer means are accumulated in a 128 bits integer, which cannot overflow before 2^64 elements,
+// and rounded toward zero.
+macro_rules! integer_reducible {
+    ($($t:ty : $acc:ty),*) => {
+        $(
+            impl Reducible for $t {
+
+                fn zero() -> $t {
+                    0
+                }
+
+                fn one() -> $t {
+                    1
+                }
+
+                fn mean<I : Iterator<Item = $t>>(values : I) -> $t {
+                    let (sum, n) = values.fold((0 as $acc, 0 as $acc), |(acc, n), x| (acc + x as $acc, n + 1));
+                    if n == 0 {
+                        panic!("Reducible::mean(): the mean of an empty set of integers is undefined");
+                    }
+                    (sum / n) as $t
+                }
+
+                fn is_nan(&self) -> bool {
+                    false
+                }
+            }
+        )*
+    }
+}
+
+// Floating point means are accumulated in double precision.
+macro_rules! float_reducible {
+    ($($t:ty),*) => {
+        $(
+            impl Reducible for $t {
+
+                fn zero() -> $t {
+                    0.0
+                }
+
+                fn one() -> $t {
+                    1.0
+                }
+
+                fn mean<I : Iterator<Item = $t>>(values : I) -> $t {
+                    let (sum, n) = values.fold((0f64, 0usize), |(acc, n), x| (acc + x as f64, n + 1));
+                    (sum / n as f64) as $t
+                }
+
+                fn is_nan(&self) -> bool {
+                    <$t>::is_nan(*self)
+                }
+            }
+        )*
+    }
+}
+
+// Complex means are accumulated in double precision.
+macro_rules! complex_reducible {
+    ($($t:ident : $f:ty),*) => {
+        $(
+            impl Reducible for $t {
+
+                fn zero() -> $t {
+                    $t::new(0.0, 0.0)
+                }
+
+                fn one() -> $t {
+                    $t::new(1.0, 0.0)
+                }
+
+                fn mean<I : Iterator<Item = $t>>(values : I) -> $t {
+                    let (re, im, n) = values.fold((0f64, 0f64, 0usize), |(re, im, n), x| (re + x.re as f64, im + x.im as f64, n + 1));
+                    $t::new((re / n as f64) as $f, (im / n as f64) as $f)
+                }
+
+                fn is_nan(&self) -> bool {
+                    self.re.is_nan() || self.im.is_nan()
+                }
+            }
+        )*
+    }
+}
+
+integer_reducible!(u8 : u128, u16 : u128, u32 : u128, u64 : u128, i8 : i128, i16 : i128, i32 : i128, i64 : i128);
+float_reducible!(f32, f64);
+complex_reducible!(c32 : f32, c64 : f64);
+
+/// A trait for N-dimensional data which can be reduced along one of its dimensions. The result
+/// has one dimension less than the data, unless keep_dims is true in which case the reduced
+/// dimension is kept with a length of 1 so that the result can be broadcasted against the data.
+///
+/// Sums and products are accumulated in the type of the data while means are accumulated in a
+/// wider type: the mean of integers is exact and rounded toward zero. The comparison based reductions are not available for complex types. The plain variants
+/// propagate NaN values while the `nan` variants ignore them.
+pub trait NDReducible<T : Reducible> {
+
+    /// Sum of the elements along axis.
+    fn sum(&self, axis : usize, keep_dims : bool) -> NDArray<T>;
+
+    /// Product of the elements along axis.
+    fn product(&self, axis : usize, keep_dims : bool) -> NDArray<T>;
+
+    /// Mean of the elements along axis. Panic if the axis is empty, whatever the type, as for
+    /// the comparison based reductions.
+    fn mean(&self, axis : usize, keep_dims : bool) -> NDArray<T>;
+
+    /// Minimum of the elements along axis.
+    fn min(&self, axis : usize, keep_dims : bool) -> NDArray<T> where T : PartialOrd;
+
+    /// Maximum of the elements along axis.
+    fn max(&self, axis : usize, keep_dims : bool) -> NDArray<T> where T : PartialOrd;
+
+    /// Index along axis of the first minimum.
+    fn argmin(&self, axis : usize, keep_dims : bool) -> NDArray<usize> where T : PartialOrd;
+
+    /// Index along axis of the first maximum.
+    fn argmax(&self, axis : usize, keep_dims : bool) -> NDArray<usize> where T : PartialOrd;
+
+    /// Sum of the elements along axis, NaN values are treated as zero.
+    fn nansum(&self, axis : usize, keep_dims : bool) -> NDArray<T>;
+
+    /// Mean of the elements along axis ignoring NaN values. The result is NaN if all the elements
+    /// are NaN. Panic if the axis is empty.
+    fn nanmean(&self, axis : usize, keep_dims : bool) -> NDArray<T>;
+
+    /// Minimum of the elements along axis ignoring NaN values. The result is NaN only if all the
+    /// elements are NaN.
+    fn nanmin(&self, axis : usize, keep_dims : bool) -> NDArray<T> where T : PartialOrd;
+
+    /// Maximum of the elements along axis ignoring NaN values. The result is NaN only if all the
+    /// elements are NaN.
+    fn nanmax(&self, axis : usize, keep_dims : bool) -> NDArray<T> where T : PartialOrd;
+
+    /// Index along axis of the first minimum ignoring NaN values. Panic if all the elements are
+    /// NaN.
+    fn nanargmin(&self, axis : usize, keep_dims : bool) -> NDArray<usize> where T : PartialOrd;
+
+    /// Index along axis of the first maximum ignoring NaN values. Panic if all the elements are
+    /// NaN.
+    fn nanargmax(&self, axis : usize, keep_dims : bool) -> NDArray<usize> where T : PartialOrd;
+}
+
+/// Iterator over the elements of the data along the reduced dimension.
+#[derive(Clone)]
+pub(crate) struct Lane<'a, T : 'a> {
+    data : &'a [T],
+    pos : usize,
+    stride : usize,
+    remaining : usize,
+}
+
+impl<'a, T : 'a> Iterator for Lane<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        if self.remaining == 0 {
+            return None;
+        }
+        let x = &self.data[self.pos];
+        self.pos = self.pos.wrapping_add(self.stride);
+        self.remaining -= 1;
+        Some(x)
+    }
+}
+
+/// Apply f to every lane along axis and collect the results in an NDArray. The lanes of large
+/// data are split across the thread pool when T is one of the RDS types.
+fn reduce_axis<T : 'static, U : Clone, R : NDData<T> + ?Sized, F : Fn(Lane<T>) -> U>(data : &R, axis : usize, keep_dims : bool, name : &str, f : F) -> NDArray<U> {
+    if axis >= data.dim() {
+        panic!("NDReducible::{}({}): axis is greater than data dimension ({} >= {})", name, axis, axis, data.dim());
+    }
+    let mut shape = data.shape().to_vec();
+    let mut strides = data.strides().to_vec();
+    let len = shape.remove(axis);
+    let stride = strides.remove(axis);
+    let size = shape.iter().fold(1, |acc, &x| acc * x);
+
+    let (source, offset) = (data.get_data(), data.offset());
+    // The lanes only borrow elements of type T and f produces either T or usize values
+    let chunks = unsafe { parallel::map_ranges_of::<T, _, _>(size, len, |range| {
+        let mut chunk = Vec::<U>::with_capacity(range.end - range.start);
+        let mut idx = parallel::ro_index(range.start, &shape[..]);
+        for _ in range {
+            chunk.push(f(Lane {
+                data : source,
+                pos : offset.wrapping_add(idx.to_pos(&shape[..], &strides[..])),
+                stride : stride,
+                remaining : len,
+            }));
+            idx.inc_ro(&shape[..]);
+        }
+        chunk
+    }) };
+    let mut result = Vec::<U>::with_capacity(size);
+    for chunk in chunks {
+        result.extend(chunk);
+    }
+
+    if keep_dims {
+        shape.insert(axis, 1);
+    }
+    NDArray {
+        strides : NDArray::<U>::compute_strides(&shape[..]),
+        shape : shape,
+        data : result.into_boxed_slice(),
+    }
+}
+
+/// Return the index and the value of the first element for which better is true against all the
+/// others. A NaN value is returned immediately unless skip_nan is true, in which case the first
+/// NaN value is only returned if all the elements are NaN.
+fn extremum<T : Reducible, F : Fn(&T, &T) -> bool>(lane : Lane<T>, skip_nan : bool, better : F) -> Option<(usize, T)> {
+    let mut best : Option<(usize, T)> = None;
+    let mut first_nan : Option<(usize, T)> = None;
+    for (i, &x) in lane.enumerate() {
+        if x.is_nan() {
+            if !skip_nan {
+                return Some((i, x));
+            }
+            if first_nan.is_none() {
+                first_nan = Some((i, x));
+            }
+            continue;
+        }
+        best = match best {
+            Some((j, b)) if !better(&x, &b) => Some((j, b)),
+            _ => Some((i, x)),
+        };
+    }
+    best.or(first_nan)
+}
+
+impl<T : Reducible, R : NDData<T> + ?Sized> NDReducible<T> for R {
+
+    fn sum(&self, axis : usize, keep_dims : bool) -> NDArray<T> {
+        reduce_axis(self, axis, keep_dims, "sum", |lane| lane.fold(T::zero(), |acc, &x| acc + x))
+    }
+
+    fn product(&self, axis : usize, keep_dims : bool) -> NDArray<T> {
+        reduce_axis(self, axis, keep_dims, "product", |lane| lane.fold(T::one(), |acc, &x| acc * x))
+    }
+
+    fn mean(&self, axis : usize, keep_dims : bool) -> NDArray<T> {
+        reduce_axis(self, axis, keep_dims, "mean", |lane| {
+            if lane.remaining == 0 {
+                panic!("NDReducible::mean({}): the axis is empty", axis);
+            }
+            T::mean(lane.cloned())
+        })
+    }
+
+    fn min(&self, axis : usize, keep_dims : bool) -> NDArray<T> where T : PartialOrd {
+        reduce_axis(self, axis, keep_dims, "min", |lane| match extremum(lane, false, |a, b| a < b) {
+            Some((_, x)) => x,
+            None => panic!("NDReducible::min({}): the axis is empty", axis)
+        })
+    }
+
+    fn max(&self, axis : usize, keep_dims : bool) -> NDArray<T> where T : PartialOrd {
+        reduce_axis(self, axis, keep_dims, "max", |lane| match extremum(lane, false, |a, b| a > b) {
+            Some((_, x)) => x,
+            None => panic!("NDReducible::max({}): the axis is empty", axis)
+        })
+    }
+
+    fn argmin(&self, axis : usize, keep_dims : bool) -> NDArray<usize> where T : PartialOrd {
+        reduce_axis(self, axis, keep_dims, "argmin", |lane| match extremum(lane, false, |a, b| a < b) {
+            Some((i, _)) => i,
+            None => panic!("NDReducible::argmin({}): the axis is empty", axis)
+        })
+    }
+
+    fn argmax(&self, axis : usize, keep_dims : bool) -> NDArray<usize> where T : PartialOrd {
+        reduce_axis(self, axis, keep_dims, "argmax", |lane| match extremum(lane, false, |a, b| a > b) {
+            Some((i, _)) => i,
+            None => panic!("NDReducible::argmax({}): the axis is empty", axis)
+        })
+    }
+
+    fn nansum(&self, axis : usize, keep_dims : bool) -> NDArray<T> {
+        reduce_axis(self, axis, keep_dims, "nansum", |lane| lane.filter(|x| !x.is_nan()).fold(T::zero(), |acc, &x| acc + x))
+    }
+
+    fn nanmean(&self, axis : usize, keep_dims : bool) -> NDArray<T> {
+        reduce_axis(self, axis, keep_dims, "nanmean", |lane| {
+            if lane.remaining == 0 {
+                panic!("NDReducible::nanmean({}): the axis is empty", axis);
+            }
+            T::mean(lane.filter(|x| !x.is_nan()).cloned())
+        })
+    }
+
+    fn nanmin(&self, axis : usize, keep_dims : bool) -> NDArray<T> where T : PartialOrd {
+        reduce_axis(self, axis, keep_dims, "nanmin", |lane| match extremum(lane, true, |a, b| a < b) {
+            Some((_, x)) => x,
+            None => panic!("NDReducible::nanmin({}): the axis is empty", axis)
+        })
+    }
+
+    fn nanmax(&self, axis : usize, keep_dims : bool) -> NDArray<T> where T : PartialOrd {
+        reduce_axis(self, axis, keep_dims, "nanmax", |lane| match extremum(lane, true, |a, b| a > b) {
+            Some((_, x)) => x,
+            None => panic!("NDReducible::nanmax({}): the axis is empty", axis)
+        })
+    }
+
+    fn nanargmin(&self, axis : usize, keep_dims : bool) -> NDArray<usize> where T : PartialOrd {
+        reduce_axis(self, axis, keep_dims, "nanargmin", |lane| match extremum(lane, true, |a, b| a < b) {
+            Some((_, x)) if x.is_nan() => panic!("NDReducible::nanargmin({}): all the elements are NaN", axis),
+            Some((i, _)) => i,
+            None => panic!("NDReducible::nanargmin({}): the axis is empty", axis)
+        })
+    }
+
+    fn nanargmax(&self, axis : usize, keep_dims : bool) -> NDArray<usize> where T : PartialOrd {
+        reduce_axis(self, axis, keep_dims, "nanargmax", |lane| match extremum(lane, true, |a, b| a > b) {
+            Some((_, x)) if x.is_nan() => panic!("NDReducible::nanargmax({}): all the elements are NaN", axis),
+            Some((i, _)) => i,
+            None => panic!("NDReducible::nanargmax({}): the axis is empty", axis)
+        })
+    }
+}
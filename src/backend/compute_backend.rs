@@ -4,6 +4,7 @@ use std::fmt::Display;
 use std::mem::{transmute, size_of};
 
 use array::{NDData, NDDataMut, NDArray, NDSlice, NDSliceMut};
+use error::Error;
 
 pub trait SizedBuffer {
 
@@ -19,17 +20,17 @@ pub trait SizedBufferMut : SizedBuffer {
 
 pub trait ComputeBackend {
 
-    fn init(&mut self) -> Result<(),String>;
+    fn init(&mut self) -> Result<(), Error>;
 
-    fn create_array(&mut self, size : usize) -> Result<u32,String>;
+    fn create_array(&mut self, size : usize) -> Result<u32, Error>;
 
-    fn set_array(&self, id : u32, array : &SizedBuffer) -> Result<(),String>;
+    fn set_array(&self, id : u32, array : &SizedBuffer) -> Result<(), Error>;
 
-    fn get_array(&self, id : u32, array : &mut SizedBufferMut) -> Result<(),String>;
+    fn get_array(&self, id : u32, array : &mut SizedBufferMut) -> Result<(), Error>;
 
-    fn delete_array(&mut self, id : u32) -> Result<(),String>;
+    fn delete_array(&mut self, id : u32) -> Result<(), Error>;
 
-    fn finalize(&mut self) -> Result<(),String>;
+    fn finalize(&mut self) -> Result<(), Error>;
 }
 
 pub struct ArrayRegistry<T : Clone> {
@@ -6,6 +6,7 @@ use std::ptr;
 use std::mem::{transmute, size_of};
 
 use backend::compute_backend::{SizedBuffer, SizedBufferMut, ComputeBackend, ArrayRegistry};
+use error::Error;
 
 pub type CLPlatformId = *const libc::c_void;
 pub type CLDeviceId = *const libc::c_void;
@@ -110,7 +111,11 @@ impl CLPlatform {
         return Ok(String::from_utf8(buffer).unwrap());
     }
 
-    pub fn new(id : CLPlatformId) -> Result<CLPlatform, i32> {
+    pub fn new(id : CLPlatformId) -> Result<CLPlatform, Error> {
+        CLPlatform::query(id).map_err(|status| Error::Backend { message : "Failed to query OpenCL platform information".to_string(), status : Some(status) })
+    }
+
+    fn query(id : CLPlatformId) -> Result<CLPlatform, i32> {
         Ok(CLPlatform {
             id : id,
             name : try!(CLPlatform::get_info(id, CL_PLATFORM_NAME)),
@@ -187,7 +192,11 @@ impl CLDevice {
         return Ok(v);
     }
 
-    pub fn new(id : CLDeviceId) -> Result<CLDevice, i32> {
+    pub fn new(id : CLDeviceId) -> Result<CLDevice, Error> {
+        CLDevice::query(id).map_err(|status| Error::Backend { message : "Failed to query OpenCL device information".to_string(), status : Some(status) })
+    }
+
+    fn query(id : CLDeviceId) -> Result<CLDevice, i32> {
         let dim = try!(CLDevice::get_uint_info(id, CL_DEVICE_MAX_WORK_ITEM_DIMENSIONS));
         Ok(CLDevice {
             id : id,
@@ -284,82 +293,82 @@ impl fmt::Display for CLDevice {
 
 impl ComputeBackend for CLDevice {
 
-    fn init(&mut self) -> Result<(),String> {
+    fn init(&mut self) -> Result<(), Error> {
         let mut retcode = CL_SUCCESS;
 
         let context = unsafe { clCreateContext(ptr::null(), 1, &self.id, ptr::null(), ptr::null_mut(), &mut retcode) };
         if retcode != CL_SUCCESS {
-            return Err(format!("Failed to initialize OpenCL context for device {}", self.get_name()));
+            return Err(Error::Backend { message : format!("Failed to initialize OpenCL context for device {}", self.get_name()), status : Some(retcode) });
         }
         self.context = Some(context);
 
         let command_queue = unsafe { clCreateCommandQueue(context, self.id, 0, &mut retcode) };
         if retcode != CL_SUCCESS {
-            return Err(format!("Failed to initialize OpenCL command queue for device {}", self.get_name()));
+            return Err(Error::Backend { message : format!("Failed to initialize OpenCL command queue for device {}", self.get_name()), status : Some(retcode) });
         }
         self.command_queue = Some(command_queue);
 
         return Ok(());
     }
 
-    fn create_array(&mut self, size : usize) -> Result<u32,String> {
+    fn create_array(&mut self, size : usize) -> Result<u32, Error> {
         if let Some(context) = self.context {
             let mut retcode = CL_SUCCESS;
             let array = unsafe {clCreateBuffer(context, CL_MEM_READ_WRITE, size, ptr::null(), &mut retcode) };
             if retcode != CL_SUCCESS {
-                return Err(format!("Failed to allocate OpenCL array of size {} on device {}", size, self.get_name()));
+                return Err(Error::Backend { message : format!("Failed to allocate OpenCL array of size {} on device {}", size, self.get_name()), status : Some(retcode) });
             }
 
             return Ok(self.array_registry.register_array(array, size));
         }
-        return Err(format!("No OpenCL context for device {}", self.get_name()));
+        return Err(Error::Backend { message : format!("No OpenCL context for device {}", self.get_name()), status : None });
     }
     
-    fn set_array(&self, id : u32, array : &SizedBuffer) -> Result<(),String> {
+    fn set_array(&self, id : u32, array : &SizedBuffer) -> Result<(), Error> {
         if let Some(command_queue) = self.command_queue {
             let cl_mem = self.array_registry.get_array(id);
             if array.get_raw_size() != self.array_registry.get_array_size(id) {
-                return Err(format!("OpenCL array {} and SizedBuffer differ in size ({} != {})", id, self.array_registry.get_array_size(id), array.get_raw_size()));
+                return Err(Error::Backend { message : format!("Buffer of {} bytes can not be written to array {} of {} bytes", array.get_raw_size(), id, self.array_registry.get_array_size(id)), status : None });
             }
 
             let retcode = unsafe { clEnqueueWriteBuffer(command_queue, cl_mem, true as u32, 0, array.get_raw_size(), array.get_raw_ptr(), 0, ptr::null(), ptr::null()) };
             if retcode != CL_SUCCESS {
-                return Err(format!("Failed to read {} bytes in OpenCL array {}", array.get_raw_size(), id));
+                return Err(Error::Backend { message : format!("Failed to read {} bytes in OpenCL array {}", array.get_raw_size(), id), status : Some(retcode) });
             }
 
             return Ok(());
         }
-        return Err(format!("No OpenCL command queue for device {}", self.get_name()));
+        return Err(Error::Backend { message : format!("No OpenCL command queue for device {}", self.get_name()), status : None });
     }
 
-    fn get_array(&self, id : u32, array : &mut SizedBufferMut) -> Result<(),String> {
+    fn get_array(&self, id : u32, array : &mut SizedBufferMut) -> Result<(), Error> {
         if let Some(command_queue) = self.command_queue {
             let cl_mem = self.array_registry.get_array(id);
             if array.get_raw_size() != self.array_registry.get_array_size(id) {
-                return Err(format!("OpenCL array {} and SizedBuffer differ in size ({} != {})", id, self.array_registry.get_array_size(id), array.get_raw_size()));
+                return Err(Error::Backend { message : format!("Array {} of {} bytes can not be read into a buffer of {} bytes", id, self.array_registry.get_array_size(id), array.get_raw_size()), status : None });
             }
 
             let retcode = unsafe { clEnqueueReadBuffer(command_queue, cl_mem, true as u32, 0, array.get_raw_size(), array.get_raw_ptr_mut(), 0, ptr::null(), ptr::null()) };
             if retcode != CL_SUCCESS {
-                return Err(format!("Failed to write {} bytes in OpenCL array {}", array.get_raw_size(), id));
+                return Err(Error::Backend { message : format!("Failed to write {} bytes in OpenCL array {}", array.get_raw_size(), id), status : Some(retcode) });
             }
 
             return Ok(());
         }
-        return Err(format!("No OpenCL command queue for device {}", self.get_name()));
+        return Err(Error::Backend { message : format!("No OpenCL command queue for device {}", self.get_name()), status : None });
     }
 
-    fn delete_array(&mut self, id : u32) -> Result<(),String> {
+    fn delete_array(&mut self, id : u32) -> Result<(), Error> {
         let array = self.array_registry.get_array(id);
         self.array_registry.unregister_array(id);
         let retcode = unsafe { clReleaseMemObject(array) };
         if retcode != CL_SUCCESS {
-            return Err(format!("Failed to release OpenCL array {} on device {}", id, self.get_name()));
+            return Err(Error::Backend { message : format!("Failed to release OpenCL array {} on device {}", id, self.get_name()), status : Some(retcode) });
         }
         return Ok(());
     }
 
-    fn finalize(&mut self) -> Result<(),String> {
+    fn finalize(&mut self) -> Result<(), Error> {
         let mut retcode = CL_SUCCESS;
 
         if let Some(command_queue) = self.command_queue {
@@ -367,7 +376,7 @@ impl ComputeBackend for CLDevice {
         }
         self.command_queue = None;
         if retcode != CL_SUCCESS {
-            return Err(format!("Failed to release OpenCL command queue for device {}", self.get_name()));
+            return Err(Error::Backend { message : format!("Failed to release OpenCL command queue for device {}", self.get_name()), status : Some(retcode) });
         }
 
         if let Some(context) = self.context {
@@ -375,7 +384,7 @@ impl ComputeBackend for CLDevice {
         }
         self.context = None;
         if retcode != CL_SUCCESS {
-            return Err(format!("Failed to release OpenCL context for device {}", self.get_name()));
+            return Err(Error::Backend { message : format!("Failed to release OpenCL context for device {}", self.get_name()), status : Some(retcode) });
         }
 
         return Ok(());
@@ -383,17 +392,17 @@ impl ComputeBackend for CLDevice {
 }
 
 impl OpenCL {
-    pub fn get_platform_list() -> Result<Vec<CLPlatform>, i32> {
+    pub fn get_platform_list() -> Result<Vec<CLPlatform>, Error> {
         let mut num_platforms = 0u32;
         let retcode = unsafe { clGetPlatformIDs(0, ptr::null_mut(), &mut num_platforms) };
         if retcode != CL_SUCCESS {
-            return Err(retcode);
+            return Err(Error::Backend { message : "Failed to count OpenCL platforms".to_string(), status : Some(retcode) });
         }
 
         let mut platform_ids : Vec<CLPlatformId> = repeat(ptr::null()).take(num_platforms as usize).collect();
         let retcode = unsafe { clGetPlatformIDs(num_platforms, platform_ids.as_mut_ptr(), ptr::null_mut()) };
         if retcode != CL_SUCCESS {
-            return Err(retcode);
+            return Err(Error::Backend { message : "Failed to list OpenCL platforms".to_string(), status : Some(retcode) });
         }
 
         let mut platforms = Vec::<CLPlatform>::with_capacity(num_platforms as usize);
@@ -404,17 +413,17 @@ impl OpenCL {
         return Ok(platforms);
     }
 
-    pub fn get_device_list(platform : &CLPlatform) -> Result<Vec<CLDevice>, i32> {
+    pub fn get_device_list(platform : &CLPlatform) -> Result<Vec<CLDevice>, Error> {
         let mut num_devices = 0u32;
         let retcode = unsafe { clGetDeviceIDs(platform.get_id(), CL_DEVICE_TYPE_ALL, 0, ptr::null_mut(), &mut num_devices) };
         if retcode != CL_SUCCESS {
-            return Err(retcode);
+            return Err(Error::Backend { message : format!("Failed to count OpenCL devices of platform {}", platform.get_name()), status : Some(retcode) });
         }
 
         let mut device_ids : Vec<CLDeviceId> = repeat(ptr::null()).take(num_devices as usize).collect();
         let retcode = unsafe { clGetDeviceIDs(platform.get_id(), CL_DEVICE_TYPE_ALL, num_devices, device_ids.as_mut_ptr(), ptr::null_mut()) };
         if retcode != CL_SUCCESS {
-            return Err(retcode);
+            return Err(Error::Backend { message : format!("Failed to list OpenCL devices of platform {}", platform.get_name()), status : Some(retcode) });
         }
 
         let mut devices = Vec::<CLDevice>::with_capacity(num_devices as usize);
@@ -0,0 +1,652 @@
+//! Pure Rust implementation of the cblas functions used by the `Blas` trait. The functions have
+//! the same signatures and semantics as their cblas counterparts so that they can replace the
+//! external library when the `rust-blas` feature is enabled.
+
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+use types::complex::{c32, c64, Complex};
+use super::{CBLAS_ROW_MAJOR, CBLAS_NO_TRANS, CBLAS_TRANS, CBLAS_CONJ_TRANS, CBLAS_UPPER, CBLAS_UNIT, CBLAS_LEFT};
+
+/// Size of the square blocks processed at once by gemm.
+const BLOCK_SIZE : usize = 64;
+
+/// Element type on which the fallback operates.
+trait Element : Copy + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self> + Div<Output = Self> {
+    /// Real type of the norms.
+    type Real : Copy + PartialOrd + Add<Output = Self::Real> + Mul<Output = Self::Real> + Div<Output = Self::Real>;
+
+    fn zero() -> Self;
+
+    fn one() -> Self;
+
+    fn is_zero(self) -> bool;
+
+    /// Return |re| + |im|, the absolute value used by asum.
+    fn abs1(self) -> Self::Real;
+
+    /// Return [|re|, |im|], the magnitudes accumulated by nrm2.
+    fn abs_parts(self) -> [Self::Real; 2];
+
+    fn real_zero() -> Self::Real;
+
+    fn real_one() -> Self::Real;
+
+    fn sqrt(x : Self::Real) -> Self::Real;
+
+    /// Return the conjugate, which is the value itself for real types.
+    fn conj(self) -> Self;
+
+    /// Return the real part as an element.
+    fn real(self) -> Self;
+}
+
+impl Element for f32 {
+    type Real = f32;
+    fn zero() -> f32 { 0.0 }
+    fn one() -> f32 { 1.0 }
+    fn is_zero(self) -> bool { self == 0.0 }
+    fn abs1(self) -> f32 { self.abs() }
+    fn abs_parts(self) -> [f32; 2] { [self.abs(), 0.0] }
+    fn real_zero() -> f32 { 0.0 }
+    fn real_one() -> f32 { 1.0 }
+    fn sqrt(x : f32) -> f32 { x.sqrt() }
+    fn conj(self) -> f32 { self }
+    fn real(self) -> f32 { self }
+}
+
+impl Element for f64 {
+    type Real = f64;
+    fn zero() -> f64 { 0.0 }
+    fn one() -> f64 { 1.0 }
+    fn is_zero(self) -> bool { self == 0.0 }
+    fn abs1(self) -> f64 { self.abs() }
+    fn abs_parts(self) -> [f64; 2] { [self.abs(), 0.0] }
+    fn real_zero() -> f64 { 0.0 }
+    fn real_one() -> f64 { 1.0 }
+    fn sqrt(x : f64) -> f64 { x.sqrt() }
+    fn conj(self) -> f64 { self }
+    fn real(self) -> f64 { self }
+}
+
+impl Element for c32 {
+    type Real = f32;
+    fn zero() -> c32 { c32::new(0.0, 0.0) }
+    fn one() -> c32 { c32::new(1.0, 0.0) }
+    fn is_zero(self) -> bool { self.re == 0.0 && self.im == 0.0 }
+    fn abs1(self) -> f32 { self.re.abs() + self.im.abs() }
+    fn abs_parts(self) -> [f32; 2] { [self.re.abs(), self.im.abs()] }
+    fn real_zero() -> f32 { 0.0 }
+    fn real_one() -> f32 { 1.0 }
+    fn sqrt(x : f32) -> f32 { x.sqrt() }
+    fn conj(self) -> c32 { Complex::conj(self) }
+    fn real(self) -> c32 { c32::new(self.re, 0.0) }
+}
+
+impl Element for c64 {
+    type Real = f64;
+    fn zero() -> c64 { c64::new(0.0, 0.0) }
+    fn one() -> c64 { c64::new(1.0, 0.0) }
+    fn is_zero(self) -> bool { self.re == 0.0 && self.im == 0.0 }
+    fn abs1(self) -> f64 { self.re.abs() + self.im.abs() }
+    fn abs_parts(self) -> [f64; 2] { [self.re.abs(), self.im.abs()] }
+    fn real_zero() -> f64 { 0.0 }
+    fn real_one() -> f64 { 1.0 }
+    fn sqrt(x : f64) -> f64 { x.sqrt() }
+    fn conj(self) -> c64 { Complex::conj(self) }
+    fn real(self) -> c64 { c64::new(self.re, 0.0) }
+}
+
+/// Return the offset of the element i of a vector of length n with increment inc. As in blas, a
+/// negative increment starts from the end of the vector.
+fn pos(i : isize, n : isize, inc : isize) -> isize {
+    if inc >= 0 { i * inc } else { (n - 1 - i) * -inc }
+}
+
+/// Return the row and column strides of op(a) for a matrix stored with leading dimension ld.
+fn op_strides(layout : i32, trans : i32, ld : isize) -> (isize, isize) {
+    let strides = if layout == CBLAS_ROW_MAJOR { (ld, 1) } else { (1, ld) };
+    if trans == CBLAS_NO_TRANS { strides } else { (strides.1, strides.0) }
+}
+
+unsafe fn asum<T : Element>(n : isize, x : *const T, incx : isize) -> T::Real {
+    let mut asum = T::real_zero();
+    if incx <= 0 {
+        return asum;
+    }
+    for i in 0..n {
+        asum = asum + (*x.offset(i * incx)).abs1();
+    }
+    asum
+}
+
+unsafe fn nrm2<T : Element>(n : isize, x : *const T, incx : isize) -> T::Real {
+    // Accumulate sum(|x_i|^2) as scale^2 * ssq, as in the reference dlassq, so that the squares
+    // of large or small elements neither overflow nor underflow
+    let mut scale = T::real_zero();
+    let mut ssq = T::real_one();
+    if incx <= 0 {
+        return scale;
+    }
+    for i in 0..n {
+        for &v in (*x.offset(i * incx)).abs_parts().iter() {
+            if v > T::real_zero() {
+                if scale < v {
+                    let r = scale / v;
+                    ssq = T::real_one() + ssq * r * r;
+                    scale = v;
+                }
+                else {
+                    let r = v / scale;
+                    ssq = ssq + r * r;
+                }
+            }
+        }
+    }
+    scale * T::sqrt(ssq)
+}
+
+unsafe fn scal<T : Element>(n : isize, a : T, x : *mut T, incx : isize) {
+    if incx <= 0 {
+        return;
+    }
+    for i in 0..n {
+        *x.offset(i * incx) = a * *x.offset(i * incx);
+    }
+}
+
+unsafe fn axpy<T : Element>(n : isize, a : T, x : *const T, incx : isize, y : *mut T, incy : isize) {
+    for i in 0..n {
+        let yi = y.offset(pos(i, n, incy));
+        *yi = *yi + a * *x.offset(pos(i, n, incx));
+    }
+}
+
+unsafe fn dot<T : Element>(n : isize, x : *const T, incx : isize, y : *const T, incy : isize) -> T {
+    let mut dot = T::zero();
+    for i in 0..n {
+        dot = dot + *x.offset(pos(i, n, incx)) * *y.offset(pos(i, n, incy));
+    }
+    dot
+}
+
+unsafe fn conj_dot<T : Element>(n : isize, x : *const T, incx : isize, y : *const T, incy : isize) -> T {
+    let mut dot = T::zero();
+    for i in 0..n {
+        dot = dot + (*x.offset(pos(i, n, incx))).conj() * *y.offset(pos(i, n, incy));
+    }
+    dot
+}
+
+unsafe fn swap<T : Element>(n : isize, x : *mut T, incx : isize, y : *mut T, incy : isize) {
+    for i in 0..n {
+        let (xi, yi) = (x.offset(pos(i, n, incx)), y.offset(pos(i, n, incy)));
+        let tmp = *xi;
+        *xi = *yi;
+        *yi = tmp;
+    }
+}
+
+unsafe fn copy<T : Element>(n : isize, x : *const T, incx : isize, y : *mut T, incy : isize) {
+    for i in 0..n {
+        *y.offset(pos(i, n, incy)) = *x.offset(pos(i, n, incx));
+    }
+}
+
+/// Apply the rotation [[h11, h12], [h21, h22]] to the pairs (x_i, y_i).
+unsafe fn rot<T : Element>(n : isize, x : *mut T, incx : isize, y : *mut T, incy : isize, h : [T; 4]) {
+    for i in 0..n {
+        let (xi, yi) = (x.offset(pos(i, n, incx)), y.offset(pos(i, n, incy)));
+        let (a, b) = (*xi, *yi);
+        *xi = h[0] * a + h[1] * b;
+        *yi = h[2] * a + h[3] * b;
+    }
+}
+
+/// Return the rotation described by the parameters of rotm, or None if it is the identity.
+unsafe fn rotm_matrix<T : Element + PartialEq + From<i8>>(param : *const T) -> Option<[T; 4]> {
+    let flag = *param;
+    let (h11, h21, h12, h22) = (*param.offset(1), *param.offset(2), *param.offset(3), *param.offset(4));
+    if flag == T::from(-2) {
+        None
+    }
+    else if flag == T::from(-1) {
+        Some([h11, h12, h21, h22])
+    }
+    else if flag == T::from(0) {
+        Some([T::from(1), h12, h21, T::from(1)])
+    }
+    else {
+        Some([h11, T::from(1), T::from(-1), h22])
+    }
+}
+
+/// Return the position of the first element of maximum |re| + |im|.
+unsafe fn iamax<T : Element>(n : isize, x : *const T, incx : isize) -> usize {
+    let mut imax = 0;
+    if n <= 0 || incx <= 0 {
+        return imax;
+    }
+    let mut max = (*x).abs1();
+    for i in 1..n {
+        let value = (*x.offset(i * incx)).abs1();
+        if value > max {
+            imax = i as usize;
+            max = value;
+        }
+    }
+    imax
+}
+
+/// Construct a real Givens rotation as the reference blas srotg and drotg.
+fn rotg<T : Copy + PartialOrd + Neg<Output = T> + Add<Output = T> + Mul<Output = T> + Div<Output = T> + From<i8>>(a : &mut T, b : &mut T, abs : fn(T) -> T, sqrt : fn(T) -> T) -> (T, T) {
+    let (zero, one) = (T::from(0), T::from(1));
+    let roe = if abs(*a) > abs(*b) { *a } else { *b };
+    let scale = abs(*a) + abs(*b);
+    if scale == zero {
+        *a = zero;
+        *b = zero;
+        return (one, zero);
+    }
+    let (sa, sb) = (*a / scale, *b / scale);
+    let mut r = scale * sqrt(sa * sa + sb * sb);
+    if roe < zero {
+        r = -r;
+    }
+    let (c, s) = (*a / r, *b / r);
+    let mut z = one;
+    if abs(*a) > abs(*b) {
+        z = s;
+    }
+    if abs(*b) >= abs(*a) && c != zero {
+        z = one / c;
+    }
+    *a = r;
+    *b = z;
+    (c, s)
+}
+
+/// Construct a complex Givens rotation as the reference blas crotg and zrotg.
+macro_rules! complex_rotg {
+    ($a:expr, $b:expr, $c:expr, $s:expr, $t:ident) => {{
+        let (abs_a, abs_b) = ((*$a).abs(), (*$b).abs());
+        if abs_a == 0.0 {
+            *$c = 0.0;
+            *$s = $t::new(1.0, 0.0);
+            *$a = *$b;
+            return;
+        }
+        let scale = abs_a + abs_b;
+        let norm = scale * ((abs_a / scale) * (abs_a / scale) + (abs_b / scale) * (abs_b / scale)).sqrt();
+        let alpha = $t::new((*$a).re / abs_a, (*$a).im / abs_a);
+        *$c = abs_a / norm;
+        *$s = alpha * Complex::conj(*$b) * $t::new(1.0 / norm, 0.0);
+        *$a = alpha * $t::new(norm, 0.0);
+    }}
+}
+
+/// Scale the m x n matrix c of strides (rs, cs) by beta, setting it to zero if beta is zero.
+unsafe fn scale_matrix<T : Element>(m : isize, n : isize, beta : T, c : *mut T, rs : isize, cs : isize) {
+    for i in 0..m {
+        for j in 0..n {
+            let cij = c.offset(i * rs + j * cs);
+            *cij = if beta.is_zero() { T::zero() } else { beta * *cij };
+        }
+    }
+}
+
+unsafe fn gemv<T : Element>(layout : i32, trans : i32, m : isize, n : isize, alpha : T, a : *const T, lda : isize, x : *const T, incx : isize, beta : T, y : *mut T, incy : isize) {
+    let (rs, cs) = op_strides(layout, trans, lda);
+    let (rows, cols) = if trans == CBLAS_NO_TRANS { (m, n) } else { (n, m) };
+    for i in 0..rows {
+        let yi = y.offset(pos(i, rows, incy));
+        let mut acc = T::zero();
+        for j in 0..cols {
+            let aij = *a.offset(i * rs + j * cs);
+            let aij = if trans == CBLAS_CONJ_TRANS { aij.conj() } else { aij };
+            acc = acc + aij * *x.offset(pos(j, cols, incx));
+        }
+        *yi = if beta.is_zero() { alpha * acc } else { alpha * acc + beta * *yi };
+    }
+}
+
+unsafe fn ger<T : Element>(layout : i32, m : isize, n : isize, alpha : T, x : *const T, incx : isize, y : *const T, incy : isize, a : *mut T, lda : isize, conj : bool) {
+    let (rs, cs) = op_strides(layout, CBLAS_NO_TRANS, lda);
+    for i in 0..m {
+        let axi = alpha * *x.offset(pos(i, m, incx));
+        for j in 0..n {
+            let yj = *y.offset(pos(j, n, incy));
+            let yj = if conj { yj.conj() } else { yj };
+            let aij = a.offset(i * rs + j * cs);
+            *aij = *aij + axi * yj;
+        }
+    }
+}
+
+/// Compute y = alpha * a * x + beta * y for a symmetric, or Hermitian if hermitian is true, a of
+/// which only the uplo triangle is stored.
+unsafe fn symv<T : Element>(layout : i32, uplo : i32, n : isize, alpha : T, a : *const T, lda : isize, x : *const T, incx : isize, beta : T, y : *mut T, incy : isize, hermitian : bool) {
+    let (rs, cs) = op_strides(layout, CBLAS_NO_TRANS, lda);
+    let mut ax = vec![T::zero(); n as usize];
+    for i in 0..n {
+        for j in 0..n {
+            let stored = if uplo == CBLAS_UPPER { i <= j } else { i >= j };
+            let aij = if stored { *a.offset(i * rs + j * cs) } else { *a.offset(j * rs + i * cs) };
+            let aij = if !hermitian { aij } else if i == j { aij.real() } else if stored { aij } else { aij.conj() };
+            ax[i as usize] = ax[i as usize] + aij * *x.offset(pos(j, n, incx));
+        }
+    }
+    for i in 0..n {
+        let yi = y.offset(pos(i, n, incy));
+        *yi = if beta.is_zero() { alpha * ax[i as usize] } else { alpha * ax[i as usize] + beta * *yi };
+    }
+}
+
+/// Return the element (i, j) of op(a) where a is a triangular matrix of which only the uplo
+/// triangle is stored.
+unsafe fn get_triangular<T : Element>(layout : i32, uplo : i32, trans : i32, diag : i32, a : *const T, lda : isize, i : isize, j : isize) -> T {
+    let (r, c) = if trans == CBLAS_NO_TRANS { (i, j) } else { (j, i) };
+    if r == c && diag == CBLAS_UNIT {
+        return T::one();
+    }
+    let stored = if uplo == CBLAS_UPPER { r <= c } else { r >= c };
+    if !stored {
+        return T::zero();
+    }
+    let (rs, cs) = op_strides(layout, CBLAS_NO_TRANS, lda);
+    let arc = *a.offset(r * rs + c * cs);
+    if trans == CBLAS_CONJ_TRANS { arc.conj() } else { arc }
+}
+
+unsafe fn trmv<T : Element>(layout : i32, uplo : i32, trans : i32, diag : i32, n : isize, a : *const T, lda : isize, x : *mut T, incx : isize) {
+    let mut ax = vec![T::zero(); n as usize];
+    for i in 0..n {
+        for j in 0..n {
+            ax[i as usize] = ax[i as usize] + get_triangular(layout, uplo, trans, diag, a, lda, i, j) * *x.offset(pos(j, n, incx));
+        }
+    }
+    for i in 0..n {
+        *x.offset(pos(i, n, incx)) = ax[i as usize];
+    }
+}
+
+unsafe fn trsv<T : Element>(layout : i32, uplo : i32, trans : i32, diag : i32, n : isize, a : *const T, lda : isize, x : *mut T, incx : isize) {
+    // op(a) is lower triangular if exactly one of a being lower and op transposing holds
+    let lower = (uplo == CBLAS_UPPER) == (trans != CBLAS_NO_TRANS);
+    for k in 0..n {
+        let i = if lower { k } else { n - 1 - k };
+        let mut xi = *x.offset(pos(i, n, incx));
+        let js = if lower { 0..i } else { (i + 1)..n };
+        for j in js {
+            xi = xi - get_triangular(layout, uplo, trans, diag, a, lda, i, j) * *x.offset(pos(j, n, incx));
+        }
+        *x.offset(pos(i, n, incx)) = xi / get_triangular(layout, uplo, trans, diag, a, lda, i, i);
+    }
+}
+
+unsafe fn gemm<T : Element>(layout : i32, transa : i32, transb : i32, m : isize, n : isize, k : isize, alpha : T, a : *const T, lda : isize, b : *const T, ldb : isize, beta : T, c : *mut T, ldc : isize) {
+    let (ars, acs) = op_strides(layout, transa, lda);
+    let (brs, bcs) = op_strides(layout, transb, ldb);
+    let (crs, ccs) = op_strides(layout, CBLAS_NO_TRANS, ldc);
+    scale_matrix(m, n, beta, c, crs, ccs);
+    let bs = BLOCK_SIZE as isize;
+    // Iterate over blocks so that the blocks of a, b and c in use stay in cache
+    for i0 in (0..m).step_by(BLOCK_SIZE) {
+        for l0 in (0..k).step_by(BLOCK_SIZE) {
+            for j0 in (0..n).step_by(BLOCK_SIZE) {
+                for i in i0..(i0 + bs).min(m) {
+                    for l in l0..(l0 + bs).min(k) {
+                        let ail = *a.offset(i * ars + l * acs);
+                        let ail = alpha * if transa == CBLAS_CONJ_TRANS { ail.conj() } else { ail };
+                        for j in j0..(j0 + bs).min(n) {
+                            let cij = c.offset(i * crs + j * ccs);
+                            let blj = *b.offset(l * brs + j * bcs);
+                            let blj = if transb == CBLAS_CONJ_TRANS { blj.conj() } else { blj };
+                            *cij = *cij + ail * blj;
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Return the element (i, j) of a symmetric a of which only the uplo triangle is stored.
+unsafe fn get_symmetric<T : Element>(layout : i32, uplo : i32, a : *const T, lda : isize, i : isize, j : isize) -> T {
+    let (rs, cs) = op_strides(layout, CBLAS_NO_TRANS, lda);
+    let stored = if uplo == CBLAS_UPPER { i <= j } else { i >= j };
+    if stored { *a.offset(i * rs + j * cs) } else { *a.offset(j * rs + i * cs) }
+}
+
+/// Return the element (i, j) of op(a) for a general a.
+unsafe fn get_op<T : Element>(layout : i32, trans : i32, a : *const T, lda : isize, i : isize, j : isize) -> T {
+    let (rs, cs) = op_strides(layout, trans, lda);
+    let aij = *a.offset(i * rs + j * cs);
+    if trans == CBLAS_CONJ_TRANS { aij.conj() } else { aij }
+}
+
+unsafe fn symm<T : Element>(layout : i32, side : i32, uplo : i32, m : isize, n : isize, alpha : T, a : *const T, lda : isize, b : *const T, ldb : isize, beta : T, c : *mut T, ldc : isize) {
+    let (crs, ccs) = op_strides(layout, CBLAS_NO_TRANS, ldc);
+    for i in 0..m {
+        for j in 0..n {
+            let mut acc = T::zero();
+            if side == CBLAS_LEFT {
+                for l in 0..m {
+                    acc = acc + get_symmetric(layout, uplo, a, lda, i, l) * get_op(layout, CBLAS_NO_TRANS, b, ldb, l, j);
+                }
+            }
+            else {
+                for l in 0..n {
+                    acc = acc + get_op(layout, CBLAS_NO_TRANS, b, ldb, i, l) * get_symmetric(layout, uplo, a, lda, l, j);
+                }
+            }
+            let cij = c.offset(i * crs + j * ccs);
+            *cij = if beta.is_zero() { alpha * acc } else { alpha * acc + beta * *cij };
+        }
+    }
+}
+
+/// Compute the uplo triangle of c = alpha * op(a) * op2(b) + beta * c, adding
+/// alpha * op(b) * op2(a) if b is given, where op2 is the transposition, or the conjugate
+/// transposition if hermitian is true, of op. Used by syrk, syr2k and herk.
+unsafe fn rank_k<T : Element>(layout : i32, uplo : i32, trans : i32, n : isize, k : isize, alpha : T, a : *const T, lda : isize, b : Option<(*const T, isize)>, beta : T, c : *mut T, ldc : isize, hermitian : bool) {
+    let (crs, ccs) = op_strides(layout, CBLAS_NO_TRANS, ldc);
+    let transpose = if hermitian { CBLAS_CONJ_TRANS } else { CBLAS_TRANS };
+    let (op1, op2) = if trans == CBLAS_NO_TRANS { (CBLAS_NO_TRANS, transpose) } else { (transpose, CBLAS_NO_TRANS) };
+    for i in 0..n {
+        for j in 0..n {
+            if (uplo == CBLAS_UPPER && i > j) || (uplo != CBLAS_UPPER && i < j) {
+                continue;
+            }
+            let mut acc = T::zero();
+            for l in 0..k {
+                match b {
+                    Some((b, ldb)) => {
+                        acc = acc + get_op(layout, op1, a, lda, i, l) * get_op(layout, op2, b, ldb, l, j);
+                        acc = acc + get_op(layout, op1, b, ldb, i, l) * get_op(layout, op2, a, lda, l, j);
+                    },
+                    None => acc = acc + get_op(layout, op1, a, lda, i, l) * get_op(layout, op2, a, lda, l, j),
+                }
+            }
+            let cij = c.offset(i * crs + j * ccs);
+            *cij = if beta.is_zero() { alpha * acc } else { alpha * acc + beta * *cij };
+            if hermitian && i == j {
+                *cij = (*cij).real();
+            }
+        }
+    }
+}
+
+unsafe fn trmm<T : Element>(layout : i32, side : i32, uplo : i32, trans : i32, diag : i32, m : isize, n : isize, alpha : T, a : *const T, lda : isize, b : *mut T, ldb : isize) {
+    let (brs, bcs) = op_strides(layout, CBLAS_NO_TRANS, ldb);
+    let mut result = vec![T::zero(); (m * n) as usize];
+    for i in 0..m {
+        for j in 0..n {
+            let mut acc = T::zero();
+            if side == CBLAS_LEFT {
+                for l in 0..m {
+                    acc = acc + get_triangular(layout, uplo, trans, diag, a, lda, i, l) * *b.offset(l * brs + j * bcs);
+                }
+            }
+            else {
+                for l in 0..n {
+                    acc = acc + *b.offset(i * brs + l * bcs) * get_triangular(layout, uplo, trans, diag, a, lda, l, j);
+                }
+            }
+            result[(i * n + j) as usize] = alpha * acc;
+        }
+    }
+    for i in 0..m {
+        for j in 0..n {
+            *b.offset(i * brs + j * bcs) = result[(i * n + j) as usize];
+        }
+    }
+}
+
+unsafe fn trsm<T : Element>(layout : i32, side : i32, uplo : i32, trans : i32, diag : i32, m : isize, n : isize, alpha : T, a : *const T, lda : isize, b : *mut T, ldb : isize) {
+    let (brs, bcs) = op_strides(layout, CBLAS_NO_TRANS, ldb);
+    scale_matrix(m, n, alpha, b, brs, bcs);
+    // op(a) is lower triangular if exactly one of a being lower and op transposing holds
+    let lower = (uplo == CBLAS_UPPER) == (trans != CBLAS_NO_TRANS);
+    if side == CBLAS_LEFT {
+        // Substitution down, or up, each column of b
+        for j in 0..n {
+            for k in 0..m {
+                let i = if lower { k } else { m - 1 - k };
+                let mut xij = *b.offset(i * brs + j * bcs);
+                let ls = if lower { 0..i } else { (i + 1)..m };
+                for l in ls {
+                    xij = xij - get_triangular(layout, uplo, trans, diag, a, lda, i, l) * *b.offset(l * brs + j * bcs);
+                }
+                *b.offset(i * brs + j * bcs) = xij / get_triangular(layout, uplo, trans, diag, a, lda, i, i);
+            }
+        }
+    }
+    else {
+        // Substitution along each row of b, starting from the first column if op(a) is upper
+        for i in 0..m {
+            for k in 0..n {
+                let j = if lower { n - 1 - k } else { k };
+                let mut xij = *b.offset(i * brs + j * bcs);
+                let ls = if lower { (j + 1)..n } else { 0..j };
+                for l in ls {
+                    xij = xij - *b.offset(i * brs + l * bcs) * get_triangular(layout, uplo, trans, diag, a, lda, l, j);
+                }
+                *b.offset(i * brs + j * bcs) = xij / get_triangular(layout, uplo, trans, diag, a, lda, j, j);
+            }
+        }
+    }
+}
+
+pub unsafe fn cblas_sasum(n : isize, x : *const f32, incx : isize) -> f32 { asum(n, x, incx) }
+pub unsafe fn cblas_dasum(n : isize, x : *const f64, incx : isize) -> f64 { asum(n, x, incx) }
+pub unsafe fn cblas_scasum(n : isize, x : *const c32, incx : isize) -> f32 { asum(n, x, incx) }
+pub unsafe fn cblas_dzasum(n : isize, x : *const c64, incx : isize) -> f64 { asum(n, x, incx) }
+
+pub unsafe fn cblas_snrm2(n : isize, x : *const f32, incx : isize) -> f32 { nrm2(n, x, incx) }
+pub unsafe fn cblas_dnrm2(n : isize, x : *const f64, incx : isize) -> f64 { nrm2(n, x, incx) }
+pub unsafe fn cblas_scnrm2(n : isize, x : *const c32, incx : isize) -> f32 { nrm2(n, x, incx) }
+pub unsafe fn cblas_dznrm2(n : isize, x : *const c64, incx : isize) -> f64 { nrm2(n, x, incx) }
+
+pub unsafe fn cblas_sscal(n : isize, a : f32, x : *mut f32, incx : isize) { scal(n, a, x, incx) }
+pub unsafe fn cblas_dscal(n : isize, a : f64, x : *mut f64, incx : isize) { scal(n, a, x, incx) }
+pub unsafe fn cblas_cscal(n : isize, a : *const c32, x : *mut c32, incx : isize) { scal(n, *a, x, incx) }
+pub unsafe fn cblas_zscal(n : isize, a : *const c64, x : *mut c64, incx : isize) { scal(n, *a, x, incx) }
+
+pub unsafe fn cblas_saxpy(n : isize, a : f32, x : *const f32, incx : isize, y : *mut f32, incy : isize) { axpy(n, a, x, incx, y, incy) }
+pub unsafe fn cblas_daxpy(n : isize, a : f64, x : *const f64, incx : isize, y : *mut f64, incy : isize) { axpy(n, a, x, incx, y, incy) }
+pub unsafe fn cblas_caxpy(n : isize, a : *const c32, x : *const c32, incx : isize, y : *mut c32, incy : isize) { axpy(n, *a, x, incx, y, incy) }
+pub unsafe fn cblas_zaxpy(n : isize, a : *const c64, x : *const c64, incx : isize, y : *mut c64, incy : isize) { axpy(n, *a, x, incx, y, incy) }
+
+pub unsafe fn cblas_sdot(n : isize, x : *const f32, incx : isize, y : *const f32, incy : isize) -> f32 { dot(n, x, incx, y, incy) }
+pub unsafe fn cblas_ddot(n : isize, x : *const f64, incx : isize, y : *const f64, incy : isize) -> f64 { dot(n, x, incx, y, incy) }
+pub unsafe fn cblas_cdotu_sub(n : isize, x : *const c32, incx : isize, y : *const c32, incy : isize, dotu : *mut c32) { *dotu = dot(n, x, incx, y, incy) }
+pub unsafe fn cblas_zdotu_sub(n : isize, x : *const c64, incx : isize, y : *const c64, incy : isize, dotu : *mut c64) { *dotu = dot(n, x, incx, y, incy) }
+
+pub unsafe fn cblas_sgemv(layout : i32, trans : i32, m : isize, n : isize, alpha : f32, a : *const f32, lda : isize, x : *const f32, incx : isize, beta : f32, y : *mut f32, incy : isize) { gemv(layout, trans, m, n, alpha, a, lda, x, incx, beta, y, incy) }
+pub unsafe fn cblas_dgemv(layout : i32, trans : i32, m : isize, n : isize, alpha : f64, a : *const f64, lda : isize, x : *const f64, incx : isize, beta : f64, y : *mut f64, incy : isize) { gemv(layout, trans, m, n, alpha, a, lda, x, incx, beta, y, incy) }
+pub unsafe fn cblas_cgemv(layout : i32, trans : i32, m : isize, n : isize, alpha : *const c32, a : *const c32, lda : isize, x : *const c32, incx : isize, beta : *const c32, y : *mut c32, incy : isize) { gemv(layout, trans, m, n, *alpha, a, lda, x, incx, *beta, y, incy) }
+pub unsafe fn cblas_zgemv(layout : i32, trans : i32, m : isize, n : isize, alpha : *const c64, a : *const c64, lda : isize, x : *const c64, incx : isize, beta : *const c64, y : *mut c64, incy : isize) { gemv(layout, trans, m, n, *alpha, a, lda, x, incx, *beta, y, incy) }
+
+pub unsafe fn cblas_sgemm(layout : i32, transa : i32, transb : i32, m : isize, n : isize, k : isize, alpha : f32, a : *const f32, lda : isize, b : *const f32, ldb : isize, beta : f32, c : *mut f32, ldc : isize) { gemm(layout, transa, transb, m, n, k, alpha, a, lda, b, ldb, beta, c, ldc) }
+pub unsafe fn cblas_dgemm(layout : i32, transa : i32, transb : i32, m : isize, n : isize, k : isize, alpha : f64, a : *const f64, lda : isize, b : *const f64, ldb : isize, beta : f64, c : *mut f64, ldc : isize) { gemm(layout, transa, transb, m, n, k, alpha, a, lda, b, ldb, beta, c, ldc) }
+pub unsafe fn cblas_cgemm(layout : i32, transa : i32, transb : i32, m : isize, n : isize, k : isize, alpha : *const c32, a : *const c32, lda : isize, b : *const c32, ldb : isize, beta : *const c32, c : *mut c32, ldc : isize) { gemm(layout, transa, transb, m, n, k, *alpha, a, lda, b, ldb, *beta, c, ldc) }
+pub unsafe fn cblas_zgemm(layout : i32, transa : i32, transb : i32, m : isize, n : isize, k : isize, alpha : *const c64, a : *const c64, lda : isize, b : *const c64, ldb : isize, beta : *const c64, c : *mut c64, ldc : isize) { gemm(layout, transa, transb, m, n, k, *alpha, a, lda, b, ldb, *beta, c, ldc) }
+
+pub unsafe fn cblas_cdotc_sub(n : isize, x : *const c32, incx : isize, y : *const c32, incy : isize, dotc : *mut c32) { *dotc = conj_dot(n, x, incx, y, incy) }
+pub unsafe fn cblas_zdotc_sub(n : isize, x : *const c64, incx : isize, y : *const c64, incy : isize, dotc : *mut c64) { *dotc = conj_dot(n, x, incx, y, incy) }
+
+pub unsafe fn cblas_sswap(n : isize, x : *mut f32, incx : isize, y : *mut f32, incy : isize) { swap(n, x, incx, y, incy) }
+pub unsafe fn cblas_dswap(n : isize, x : *mut f64, incx : isize, y : *mut f64, incy : isize) { swap(n, x, incx, y, incy) }
+pub unsafe fn cblas_cswap(n : isize, x : *mut c32, incx : isize, y : *mut c32, incy : isize) { swap(n, x, incx, y, incy) }
+pub unsafe fn cblas_zswap(n : isize, x : *mut c64, incx : isize, y : *mut c64, incy : isize) { swap(n, x, incx, y, incy) }
+
+pub unsafe fn cblas_scopy(n : isize, x : *const f32, incx : isize, y : *mut f32, incy : isize) { copy(n, x, incx, y, incy) }
+pub unsafe fn cblas_dcopy(n : isize, x : *const f64, incx : isize, y : *mut f64, incy : isize) { copy(n, x, incx, y, incy) }
+pub unsafe fn cblas_ccopy(n : isize, x : *const c32, incx : isize, y : *mut c32, incy : isize) { copy(n, x, incx, y, incy) }
+pub unsafe fn cblas_zcopy(n : isize, x : *const c64, incx : isize, y : *mut c64, incy : isize) { copy(n, x, incx, y, incy) }
+
+pub unsafe fn cblas_srot(n : isize, x : *mut f32, incx : isize, y : *mut f32, incy : isize, c : f32, s : f32) { rot(n, x, incx, y, incy, [c, s, -s, c]) }
+pub unsafe fn cblas_drot(n : isize, x : *mut f64, incx : isize, y : *mut f64, incy : isize, c : f64, s : f64) { rot(n, x, incx, y, incy, [c, s, -s, c]) }
+
+pub unsafe fn cblas_srotm(n : isize, x : *mut f32, incx : isize, y : *mut f32, incy : isize, param : *const f32) { if let Some(h) = rotm_matrix(param) { rot(n, x, incx, y, incy, h) } }
+pub unsafe fn cblas_drotm(n : isize, x : *mut f64, incx : isize, y : *mut f64, incy : isize, param : *const f64) { if let Some(h) = rotm_matrix(param) { rot(n, x, incx, y, incy, h) } }
+
+pub unsafe fn cblas_srotg(a : *mut f32, b : *mut f32, c : *mut f32, s : *mut f32) { let (rc, rs) = rotg(&mut *a, &mut *b, f32::abs, f32::sqrt); *c = rc; *s = rs; }
+pub unsafe fn cblas_drotg(a : *mut f64, b : *mut f64, c : *mut f64, s : *mut f64) { let (rc, rs) = rotg(&mut *a, &mut *b, f64::abs, f64::sqrt); *c = rc; *s = rs; }
+pub unsafe fn cblas_crotg(a : *mut c32, b : *const c32, c : *mut f32, s : *mut c32) { complex_rotg!(a, b, c, s, c32) }
+pub unsafe fn cblas_zrotg(a : *mut c64, b : *const c64, c : *mut f64, s : *mut c64) { complex_rotg!(a, b, c, s, c64) }
+
+pub unsafe fn cblas_isamax(n : isize, x : *const f32, incx : isize) -> usize { iamax(n, x, incx) }
+pub unsafe fn cblas_idamax(n : isize, x : *const f64, incx : isize) -> usize { iamax(n, x, incx) }
+pub unsafe fn cblas_icamax(n : isize, x : *const c32, incx : isize) -> usize { iamax(n, x, incx) }
+pub unsafe fn cblas_izamax(n : isize, x : *const c64, incx : isize) -> usize { iamax(n, x, incx) }
+
+pub unsafe fn cblas_sger(layout : i32, m : isize, n : isize, alpha : f32, x : *const f32, incx : isize, y : *const f32, incy : isize, a : *mut f32, lda : isize) { ger(layout, m, n, alpha, x, incx, y, incy, a, lda, false) }
+pub unsafe fn cblas_dger(layout : i32, m : isize, n : isize, alpha : f64, x : *const f64, incx : isize, y : *const f64, incy : isize, a : *mut f64, lda : isize) { ger(layout, m, n, alpha, x, incx, y, incy, a, lda, false) }
+pub unsafe fn cblas_cgeru(layout : i32, m : isize, n : isize, alpha : *const c32, x : *const c32, incx : isize, y : *const c32, incy : isize, a : *mut c32, lda : isize) { ger(layout, m, n, *alpha, x, incx, y, incy, a, lda, false) }
+pub unsafe fn cblas_zgeru(layout : i32, m : isize, n : isize, alpha : *const c64, x : *const c64, incx : isize, y : *const c64, incy : isize, a : *mut c64, lda : isize) { ger(layout, m, n, *alpha, x, incx, y, incy, a, lda, false) }
+pub unsafe fn cblas_cgerc(layout : i32, m : isize, n : isize, alpha : *const c32, x : *const c32, incx : isize, y : *const c32, incy : isize, a : *mut c32, lda : isize) { ger(layout, m, n, *alpha, x, incx, y, incy, a, lda, true) }
+pub unsafe fn cblas_zgerc(layout : i32, m : isize, n : isize, alpha : *const c64, x : *const c64, incx : isize, y : *const c64, incy : isize, a : *mut c64, lda : isize) { ger(layout, m, n, *alpha, x, incx, y, incy, a, lda, true) }
+
+pub unsafe fn cblas_ssymv(layout : i32, uplo : i32, n : isize, alpha : f32, a : *const f32, lda : isize, x : *const f32, incx : isize, beta : f32, y : *mut f32, incy : isize) { symv(layout, uplo, n, alpha, a, lda, x, incx, beta, y, incy, false) }
+pub unsafe fn cblas_dsymv(layout : i32, uplo : i32, n : isize, alpha : f64, a : *const f64, lda : isize, x : *const f64, incx : isize, beta : f64, y : *mut f64, incy : isize) { symv(layout, uplo, n, alpha, a, lda, x, incx, beta, y, incy, false) }
+pub unsafe fn cblas_chemv(layout : i32, uplo : i32, n : isize, alpha : *const c32, a : *const c32, lda : isize, x : *const c32, incx : isize, beta : *const c32, y : *mut c32, incy : isize) { symv(layout, uplo, n, *alpha, a, lda, x, incx, *beta, y, incy, true) }
+pub unsafe fn cblas_zhemv(layout : i32, uplo : i32, n : isize, alpha : *const c64, a : *const c64, lda : isize, x : *const c64, incx : isize, beta : *const c64, y : *mut c64, incy : isize) { symv(layout, uplo, n, *alpha, a, lda, x, incx, *beta, y, incy, true) }
+
+pub unsafe fn cblas_strmv(layout : i32, uplo : i32, trans : i32, diag : i32, n : isize, a : *const f32, lda : isize, x : *mut f32, incx : isize) { trmv(layout, uplo, trans, diag, n, a, lda, x, incx) }
+pub unsafe fn cblas_dtrmv(layout : i32, uplo : i32, trans : i32, diag : i32, n : isize, a : *const f64, lda : isize, x : *mut f64, incx : isize) { trmv(layout, uplo, trans, diag, n, a, lda, x, incx) }
+pub unsafe fn cblas_ctrmv(layout : i32, uplo : i32, trans : i32, diag : i32, n : isize, a : *const c32, lda : isize, x : *mut c32, incx : isize) { trmv(layout, uplo, trans, diag, n, a, lda, x, incx) }
+pub unsafe fn cblas_ztrmv(layout : i32, uplo : i32, trans : i32, diag : i32, n : isize, a : *const c64, lda : isize, x : *mut c64, incx : isize) { trmv(layout, uplo, trans, diag, n, a, lda, x, incx) }
+
+pub unsafe fn cblas_strsv(layout : i32, uplo : i32, trans : i32, diag : i32, n : isize, a : *const f32, lda : isize, x : *mut f32, incx : isize) { trsv(layout, uplo, trans, diag, n, a, lda, x, incx) }
+pub unsafe fn cblas_dtrsv(layout : i32, uplo : i32, trans : i32, diag : i32, n : isize, a : *const f64, lda : isize, x : *mut f64, incx : isize) { trsv(layout, uplo, trans, diag, n, a, lda, x, incx) }
+pub unsafe fn cblas_ctrsv(layout : i32, uplo : i32, trans : i32, diag : i32, n : isize, a : *const c32, lda : isize, x : *mut c32, incx : isize) { trsv(layout, uplo, trans, diag, n, a, lda, x, incx) }
+pub unsafe fn cblas_ztrsv(layout : i32, uplo : i32, trans : i32, diag : i32, n : isize, a : *const c64, lda : isize, x : *mut c64, incx : isize) { trsv(layout, uplo, trans, diag, n, a, lda, x, incx) }
+
+pub unsafe fn cblas_ssymm(layout : i32, side : i32, uplo : i32, m : isize, n : isize, alpha : f32, a : *const f32, lda : isize, b : *const f32, ldb : isize, beta : f32, c : *mut f32, ldc : isize) { symm(layout, side, uplo, m, n, alpha, a, lda, b, ldb, beta, c, ldc) }
+pub unsafe fn cblas_dsymm(layout : i32, side : i32, uplo : i32, m : isize, n : isize, alpha : f64, a : *const f64, lda : isize, b : *const f64, ldb : isize, beta : f64, c : *mut f64, ldc : isize) { symm(layout, side, uplo, m, n, alpha, a, lda, b, ldb, beta, c, ldc) }
+pub unsafe fn cblas_csymm(layout : i32, side : i32, uplo : i32, m : isize, n : isize, alpha : *const c32, a : *const c32, lda : isize, b : *const c32, ldb : isize, beta : *const c32, c : *mut c32, ldc : isize) { symm(layout, side, uplo, m, n, *alpha, a, lda, b, ldb, *beta, c, ldc) }
+pub unsafe fn cblas_zsymm(layout : i32, side : i32, uplo : i32, m : isize, n : isize, alpha : *const c64, a : *const c64, lda : isize, b : *const c64, ldb : isize, beta : *const c64, c : *mut c64, ldc : isize) { symm(layout, side, uplo, m, n, *alpha, a, lda, b, ldb, *beta, c, ldc) }
+
+pub unsafe fn cblas_ssyrk(layout : i32, uplo : i32, trans : i32, n : isize, k : isize, alpha : f32, a : *const f32, lda : isize, beta : f32, c : *mut f32, ldc : isize) { rank_k(layout, uplo, trans, n, k, alpha, a, lda, None, beta, c, ldc, false) }
+pub unsafe fn cblas_dsyrk(layout : i32, uplo : i32, trans : i32, n : isize, k : isize, alpha : f64, a : *const f64, lda : isize, beta : f64, c : *mut f64, ldc : isize) { rank_k(layout, uplo, trans, n, k, alpha, a, lda, None, beta, c, ldc, false) }
+pub unsafe fn cblas_csyrk(layout : i32, uplo : i32, trans : i32, n : isize, k : isize, alpha : *const c32, a : *const c32, lda : isize, beta : *const c32, c : *mut c32, ldc : isize) { rank_k(layout, uplo, trans, n, k, *alpha, a, lda, None, *beta, c, ldc, false) }
+pub unsafe fn cblas_zsyrk(layout : i32, uplo : i32, trans : i32, n : isize, k : isize, alpha : *const c64, a : *const c64, lda : isize, beta : *const c64, c : *mut c64, ldc : isize) { rank_k(layout, uplo, trans, n, k, *alpha, a, lda, None, *beta, c, ldc, false) }
+
+pub unsafe fn cblas_ssyr2k(layout : i32, uplo : i32, trans : i32, n : isize, k : isize, alpha : f32, a : *const f32, lda : isize, b : *const f32, ldb : isize, beta : f32, c : *mut f32, ldc : isize) { rank_k(layout, uplo, trans, n, k, alpha, a, lda, Some((b, ldb)), beta, c, ldc, false) }
+pub unsafe fn cblas_dsyr2k(layout : i32, uplo : i32, trans : i32, n : isize, k : isize, alpha : f64, a : *const f64, lda : isize, b : *const f64, ldb : isize, beta : f64, c : *mut f64, ldc : isize) { rank_k(layout, uplo, trans, n, k, alpha, a, lda, Some((b, ldb)), beta, c, ldc, false) }
+pub unsafe fn cblas_csyr2k(layout : i32, uplo : i32, trans : i32, n : isize, k : isize, alpha : *const c32, a : *const c32, lda : isize, b : *const c32, ldb : isize, beta : *const c32, c : *mut c32, ldc : isize) { rank_k(layout, uplo, trans, n, k, *alpha, a, lda, Some((b, ldb)), *beta, c, ldc, false) }
+pub unsafe fn cblas_zsyr2k(layout : i32, uplo : i32, trans : i32, n : isize, k : isize, alpha : *const c64, a : *const c64, lda : isize, b : *const c64, ldb : isize, beta : *const c64, c : *mut c64, ldc : isize) { rank_k(layout, uplo, trans, n, k, *alpha, a, lda, Some((b, ldb)), *beta, c, ldc, false) }
+
+pub unsafe fn cblas_cherk(layout : i32, uplo : i32, trans : i32, n : isize, k : isize, alpha : f32, a : *const c32, lda : isize, beta : f32, c : *mut c32, ldc : isize) { rank_k(layout, uplo, trans, n, k, c32::new(alpha, 0.0), a, lda, None, c32::new(beta, 0.0), c, ldc, true) }
+pub unsafe fn cblas_zherk(layout : i32, uplo : i32, trans : i32, n : isize, k : isize, alpha : f64, a : *const c64, lda : isize, beta : f64, c : *mut c64, ldc : isize) { rank_k(layout, uplo, trans, n, k, c64::new(alpha, 0.0), a, lda, None, c64::new(beta, 0.0), c, ldc, true) }
+
+pub unsafe fn cblas_strmm(layout : i32, side : i32, uplo : i32, transa : i32, diag : i32, m : isize, n : isize, alpha : f32, a : *const f32, lda : isize, b : *mut f32, ldb : isize) { trmm(layout, side, uplo, transa, diag, m, n, alpha, a, lda, b, ldb) }
+pub unsafe fn cblas_dtrmm(layout : i32, side : i32, uplo : i32, transa : i32, diag : i32, m : isize, n : isize, alpha : f64, a : *const f64, lda : isize, b : *mut f64, ldb : isize) { trmm(layout, side, uplo, transa, diag, m, n, alpha, a, lda, b, ldb) }
+pub unsafe fn cblas_ctrmm(layout : i32, side : i32, uplo : i32, transa : i32, diag : i32, m : isize, n : isize, alpha : *const c32, a : *const c32, lda : isize, b : *mut c32, ldb : isize) { trmm(layout, side, uplo, transa, diag, m, n, *alpha, a, lda, b, ldb) }
+pub unsafe fn cblas_ztrmm(layout : i32, side : i32, uplo : i32, transa : i32, diag : i32, m : isize, n : isize, alpha : *const c64, a : *const c64, lda : isize, b : *mut c64, ldb : isize) { trmm(layout, side, uplo, transa, diag, m, n, *alpha, a, lda, b, ldb) }
+
+pub unsafe fn cblas_strsm(layout : i32, side : i32, uplo : i32, transa : i32, diag : i32, m : isize, n : isize, alpha : f32, a : *const f32, lda : isize, b : *mut f32, ldb : isize) { trsm(layout, side, uplo, transa, diag, m, n, alpha, a, lda, b, ldb) }
+pub unsafe fn cblas_dtrsm(layout : i32, side : i32, uplo : i32, transa : i32, diag : i32, m : isize, n : isize, alpha : f64, a : *const f64, lda : isize, b : *mut f64, ldb : isize) { trsm(layout, side, uplo, transa, diag, m, n, alpha, a, lda, b, ldb) }
+pub unsafe fn cblas_ctrsm(layout : i32, side : i32, uplo : i32, transa : i32, diag : i32, m : isize, n : isize, alpha : *const c32, a : *const c32, lda : isize, b : *mut c32, ldb : isize) { trsm(layout, side, uplo, transa, diag, m, n, *alpha, a, lda, b, ldb) }
+pub unsafe fn cblas_ztrsm(layout : i32, side : i32, uplo : i32, transa : i32, diag : i32, m : isize, n : isize, alpha : *const c64, a : *const c64, lda : isize, b : *mut c64, ldb : isize) { trsm(layout, side, uplo, transa, diag, m, n, *alpha, a, lda, b, ldb) }
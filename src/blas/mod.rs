@@ -1,18 +1,98 @@
 extern crate libc;
 
+use std::cmp::max;
 use std::fmt::Display;
-use std::ops::{Add, AddAssign, Sub, SubAssign, Mul, MulAssign};
+use std::iter::repeat;
 
-use types::complex::{c32, c64};
-use array::{NDData, NDDataMut, NDArray, NDSlice, NDSliceMut};
+use types::complex::{c32, c64, Complex};
+use array::{NDData, NDDataMut, NDArray, NDView, NDViewable, NDViewableMut, NDRange};
+use array::ndindex::NDIndex;
+use error::Error;
 
 const CBLAS_ROW_MAJOR : libc::c_int = 101;
-#[allow(dead_code)]
 const CBLAS_COL_MAJOR : libc::c_int = 102;
 const CBLAS_NO_TRANS : libc::c_int = 111;
-#[allow(dead_code)]
 const CBLAS_TRANS : libc::c_int = 112;
+const CBLAS_CONJ_TRANS : libc::c_int = 113;
+const CBLAS_UPPER : libc::c_int = 121;
+const CBLAS_LOWER : libc::c_int = 122;
+const CBLAS_NON_UNIT : libc::c_int = 131;
+const CBLAS_UNIT : libc::c_int = 132;
+const CBLAS_LEFT : libc::c_int = 141;
+const CBLAS_RIGHT : libc::c_int = 142;
+
+/// Which triangle of a symmetric, Hermitian or triangular matrix is referenced.
+#[derive(Clone,Copy,Debug,PartialEq)]
+pub enum Uplo {
+    Upper,
+    Lower,
+}
+
+/// Whether a triangular matrix has an implicit unit diagonal.
+#[derive(Clone,Copy,Debug,PartialEq)]
+pub enum Diag {
+    NonUnit,
+    Unit,
+}
+
+/// Side of the product on which a symmetric or triangular matrix is applied.
+#[derive(Clone,Copy,Debug,PartialEq)]
+pub enum Side {
+    Left,
+    Right,
+}
+
+/// Operation applied to a matrix before the product.
+#[derive(Clone,Copy,Debug,PartialEq)]
+pub enum Transpose {
+    NoTrans,
+    Trans,
+    ConjTrans,
+}
+
+impl Uplo {
+    fn to_cblas(self) -> libc::c_int {
+        match self {
+            Uplo::Upper => CBLAS_UPPER,
+            Uplo::Lower => CBLAS_LOWER,
+        }
+    }
+}
+
+impl Side {
+    fn to_cblas(self) -> libc::c_int {
+        match self {
+            Side::Left => CBLAS_LEFT,
+            Side::Right => CBLAS_RIGHT,
+        }
+    }
+}
 
+impl Diag {
+    fn to_cblas(self) -> libc::c_int {
+        match self {
+            Diag::NonUnit => CBLAS_NON_UNIT,
+            Diag::Unit => CBLAS_UNIT,
+        }
+    }
+}
+
+impl Transpose {
+    fn to_cblas(self) -> libc::c_int {
+        match self {
+            Transpose::NoTrans => CBLAS_NO_TRANS,
+            Transpose::Trans => CBLAS_TRANS,
+            Transpose::ConjTrans => CBLAS_CONJ_TRANS,
+        }
+    }
+}
+
+#[cfg(feature = "rust-blas")]
+mod fallback;
+#[cfg(feature = "rust-blas")]
+use self::fallback::*;
+
+#[cfg(not(feature = "rust-blas"))]
 #[link(name = "blas")]
 extern {
     fn cblas_sasum (n : isize, x : *const libc::c_float, incx : isize) -> libc::c_float;
@@ -39,16 +119,94 @@ extern {
     fn cblas_ddot (n : isize, x : *const libc::c_double, incx : isize, y : *const libc::c_double, incy : isize) -> libc::c_double;
     fn cblas_cdotu_sub (n : isize, x : *const c32, incx : isize, y : *const c32, incy : isize, dotu : *mut c32) -> libc::c_void;
     fn cblas_zdotu_sub (n : isize, x : *const c64, incx : isize, y : *const c64, incy : isize, dotu : *mut c64) -> libc::c_void;
+    fn cblas_cdotc_sub (n : isize, x : *const c32, incx : isize, y : *const c32, incy : isize, dotc : *mut c32) -> libc::c_void;
+    fn cblas_zdotc_sub (n : isize, x : *const c64, incx : isize, y : *const c64, incy : isize, dotc : *mut c64) -> libc::c_void;
+
+    fn cblas_sswap (n : isize, x : *mut libc::c_float, incx : isize, y : *mut libc::c_float, incy : isize) -> libc::c_void;
+    fn cblas_dswap (n : isize, x : *mut libc::c_double, incx : isize, y : *mut libc::c_double, incy : isize) -> libc::c_void;
+    fn cblas_cswap (n : isize, x : *mut c32, incx : isize, y : *mut c32, incy : isize) -> libc::c_void;
+    fn cblas_zswap (n : isize, x : *mut c64, incx : isize, y : *mut c64, incy : isize) -> libc::c_void;
+
+    fn cblas_scopy (n : isize, x : *const libc::c_float, incx : isize, y : *mut libc::c_float, incy : isize) -> libc::c_void;
+    fn cblas_dcopy (n : isize, x : *const libc::c_double, incx : isize, y : *mut libc::c_double, incy : isize) -> libc::c_void;
+    fn cblas_ccopy (n : isize, x : *const c32, incx : isize, y : *mut c32, incy : isize) -> libc::c_void;
+    fn cblas_zcopy (n : isize, x : *const c64, incx : isize, y : *mut c64, incy : isize) -> libc::c_void;
+
+    fn cblas_srot (n : isize, x : *mut libc::c_float, incx : isize, y : *mut libc::c_float, incy : isize, c : libc::c_float, s : libc::c_float) -> libc::c_void;
+    fn cblas_drot (n : isize, x : *mut libc::c_double, incx : isize, y : *mut libc::c_double, incy : isize, c : libc::c_double, s : libc::c_double) -> libc::c_void;
+
+    fn cblas_srotg (a : *mut libc::c_float, b : *mut libc::c_float, c : *mut libc::c_float, s : *mut libc::c_float) -> libc::c_void;
+    fn cblas_drotg (a : *mut libc::c_double, b : *mut libc::c_double, c : *mut libc::c_double, s : *mut libc::c_double) -> libc::c_void;
+    fn cblas_crotg (a : *mut c32, b : *const c32, c : *mut libc::c_float, s : *mut c32) -> libc::c_void;
+    fn cblas_zrotg (a : *mut c64, b : *const c64, c : *mut libc::c_double, s : *mut c64) -> libc::c_void;
+
+    fn cblas_srotm (n : isize, x : *mut libc::c_float, incx : isize, y : *mut libc::c_float, incy : isize, param : *const libc::c_float) -> libc::c_void;
+    fn cblas_drotm (n : isize, x : *mut libc::c_double, incx : isize, y : *mut libc::c_double, incy : isize, param : *const libc::c_double) -> libc::c_void;
+
+    fn cblas_isamax (n : isize, x : *const libc::c_float, incx : isize) -> libc::size_t;
+    fn cblas_idamax (n : isize, x : *const libc::c_double, incx : isize) -> libc::size_t;
+    fn cblas_icamax (n : isize, x : *const c32, incx : isize) -> libc::size_t;
+    fn cblas_izamax (n : isize, x : *const c64, incx : isize) -> libc::size_t;
 
     fn cblas_sgemv (layout : libc::c_int, trans : libc::c_int, m : isize, n : isize, alpha : libc::c_float, a : *const libc::c_float, lda : isize, x : *const libc::c_float, incx : isize, beta :libc::c_float, y : *mut libc::c_float, incy : isize) -> libc::c_void;
     fn cblas_dgemv (layout : libc::c_int, trans : libc::c_int, m : isize, n : isize, alpha : libc::c_double, a : *const libc::c_double, lda : isize, x : *const libc::c_double, incx : isize, beta :libc::c_double, y : *mut libc::c_double, incy : isize) -> libc::c_void;
     fn cblas_cgemv (layout : libc::c_int, trans : libc::c_int, m : isize, n : isize, alpha : *const c32, a : *const c32, lda : isize, x : *const c32, incx : isize, beta : *const c32, y : *mut c32, incy : isize) -> libc::c_void;
     fn cblas_zgemv (layout : libc::c_int, trans : libc::c_int, m : isize, n : isize, alpha : *const c64, a : *const c64, lda : isize, x : *const c64, incx : isize, beta : *const c64, y : *mut c64, incy : isize) -> libc::c_void;
 
+    fn cblas_sger (layout : libc::c_int, m : isize, n : isize, alpha : libc::c_float, x : *const libc::c_float, incx : isize, y : *const libc::c_float, incy : isize, a : *mut libc::c_float, lda : isize) -> libc::c_void;
+    fn cblas_dger (layout : libc::c_int, m : isize, n : isize, alpha : libc::c_double, x : *const libc::c_double, incx : isize, y : *const libc::c_double, incy : isize, a : *mut libc::c_double, lda : isize) -> libc::c_void;
+    fn cblas_cgeru (layout : libc::c_int, m : isize, n : isize, alpha : *const c32, x : *const c32, incx : isize, y : *const c32, incy : isize, a : *mut c32, lda : isize) -> libc::c_void;
+    fn cblas_zgeru (layout : libc::c_int, m : isize, n : isize, alpha : *const c64, x : *const c64, incx : isize, y : *const c64, incy : isize, a : *mut c64, lda : isize) -> libc::c_void;
+    fn cblas_cgerc (layout : libc::c_int, m : isize, n : isize, alpha : *const c32, x : *const c32, incx : isize, y : *const c32, incy : isize, a : *mut c32, lda : isize) -> libc::c_void;
+    fn cblas_zgerc (layout : libc::c_int, m : isize, n : isize, alpha : *const c64, x : *const c64, incx : isize, y : *const c64, incy : isize, a : *mut c64, lda : isize) -> libc::c_void;
+
+    fn cblas_ssymv (layout : libc::c_int, uplo : libc::c_int, n : isize, alpha : libc::c_float, a : *const libc::c_float, lda : isize, x : *const libc::c_float, incx : isize, beta : libc::c_float, y : *mut libc::c_float, incy : isize) -> libc::c_void;
+    fn cblas_dsymv (layout : libc::c_int, uplo : libc::c_int, n : isize, alpha : libc::c_double, a : *const libc::c_double, lda : isize, x : *const libc::c_double, incx : isize, beta : libc::c_double, y : *mut libc::c_double, incy : isize) -> libc::c_void;
+    fn cblas_chemv (layout : libc::c_int, uplo : libc::c_int, n : isize, alpha : *const c32, a : *const c32, lda : isize, x : *const c32, incx : isize, beta : *const c32, y : *mut c32, incy : isize) -> libc::c_void;
+    fn cblas_zhemv (layout : libc::c_int, uplo : libc::c_int, n : isize, alpha : *const c64, a : *const c64, lda : isize, x : *const c64, incx : isize, beta : *const c64, y : *mut c64, incy : isize) -> libc::c_void;
+
+    fn cblas_strmv (layout : libc::c_int, uplo : libc::c_int, trans : libc::c_int, diag : libc::c_int, n : isize, a : *const libc::c_float, lda : isize, x : *mut libc::c_float, incx : isize) -> libc::c_void;
+    fn cblas_dtrmv (layout : libc::c_int, uplo : libc::c_int, trans : libc::c_int, diag : libc::c_int, n : isize, a : *const libc::c_double, lda : isize, x : *mut libc::c_double, incx : isize) -> libc::c_void;
+    fn cblas_ctrmv (layout : libc::c_int, uplo : libc::c_int, trans : libc::c_int, diag : libc::c_int, n : isize, a : *const c32, lda : isize, x : *mut c32, incx : isize) -> libc::c_void;
+    fn cblas_ztrmv (layout : libc::c_int, uplo : libc::c_int, trans : libc::c_int, diag : libc::c_int, n : isize, a : *const c64, lda : isize, x : *mut c64, incx : isize) -> libc::c_void;
+
+    fn cblas_strsv (layout : libc::c_int, uplo : libc::c_int, trans : libc::c_int, diag : libc::c_int, n : isize, a : *const libc::c_float, lda : isize, x : *mut libc::c_float, incx : isize) -> libc::c_void;
+    fn cblas_dtrsv (layout : libc::c_int, uplo : libc::c_int, trans : libc::c_int, diag : libc::c_int, n : isize, a : *const libc::c_double, lda : isize, x : *mut libc::c_double, incx : isize) -> libc::c_void;
+    fn cblas_ctrsv (layout : libc::c_int, uplo : libc::c_int, trans : libc::c_int, diag : libc::c_int, n : isize, a : *const c32, lda : isize, x : *mut c32, incx : isize) -> libc::c_void;
+    fn cblas_ztrsv (layout : libc::c_int, uplo : libc::c_int, trans : libc::c_int, diag : libc::c_int, n : isize, a : *const c64, lda : isize, x : *mut c64, incx : isize) -> libc::c_void;
+
     fn cblas_sgemm (layout : libc::c_int, transa : libc::c_int, transb : libc::c_int, m : isize, n : isize, k : isize, alpha : libc::c_float, a : *const libc::c_float, lda : isize, b : *const libc::c_float, ldb : isize, beta : libc::c_float, c : *mut libc::c_float, ldc : isize) -> libc::c_void;
     fn cblas_dgemm (layout : libc::c_int, transa : libc::c_int, transb : libc::c_int, m : isize, n : isize, k : isize, alpha : libc::c_double, a : *const libc::c_double, lda : isize, b : *const libc::c_double, ldb : isize, beta : libc::c_double, c : *mut libc::c_double, ldc : isize) -> libc::c_void;
     fn cblas_cgemm (layout : libc::c_int, transa : libc::c_int, transb : libc::c_int, m : isize, n : isize, k : isize, alpha : *const c32, a : *const c32, lda : isize, b : *const c32, ldb : isize, beta : *const c32, c : *mut c32, ldc : isize) -> libc::c_void;
     fn cblas_zgemm (layout : libc::c_int, transa : libc::c_int, transb : libc::c_int, m : isize, n : isize, k : isize, alpha : *const c64, a : *const c64, lda : isize, b : *const c64, ldb : isize, beta : *const c64, c : *mut c64, ldc : isize) -> libc::c_void;
+
+    fn cblas_ssymm (layout : libc::c_int, side : libc::c_int, uplo : libc::c_int, m : isize, n : isize, alpha : libc::c_float, a : *const libc::c_float, lda : isize, b : *const libc::c_float, ldb : isize, beta : libc::c_float, c : *mut libc::c_float, ldc : isize) -> libc::c_void;
+    fn cblas_dsymm (layout : libc::c_int, side : libc::c_int, uplo : libc::c_int, m : isize, n : isize, alpha : libc::c_double, a : *const libc::c_double, lda : isize, b : *const libc::c_double, ldb : isize, beta : libc::c_double, c : *mut libc::c_double, ldc : isize) -> libc::c_void;
+    fn cblas_csymm (layout : libc::c_int, side : libc::c_int, uplo : libc::c_int, m : isize, n : isize, alpha : *const c32, a : *const c32, lda : isize, b : *const c32, ldb : isize, beta : *const c32, c : *mut c32, ldc : isize) -> libc::c_void;
+    fn cblas_zsymm (layout : libc::c_int, side : libc::c_int, uplo : libc::c_int, m : isize, n : isize, alpha : *const c64, a : *const c64, lda : isize, b : *const c64, ldb : isize, beta : *const c64, c : *mut c64, ldc : isize) -> libc::c_void;
+
+    fn cblas_ssyrk (layout : libc::c_int, uplo : libc::c_int, trans : libc::c_int, n : isize, k : isize, alpha : libc::c_float, a : *const libc::c_float, lda : isize, beta : libc::c_float, c : *mut libc::c_float, ldc : isize) -> libc::c_void;
+    fn cblas_dsyrk (layout : libc::c_int, uplo : libc::c_int, trans : libc::c_int, n : isize, k : isize, alpha : libc::c_double, a : *const libc::c_double, lda : isize, beta : libc::c_double, c : *mut libc::c_double, ldc : isize) -> libc::c_void;
+    fn cblas_csyrk (layout : libc::c_int, uplo : libc::c_int, trans : libc::c_int, n : isize, k : isize, alpha : *const c32, a : *const c32, lda : isize, beta : *const c32, c : *mut c32, ldc : isize) -> libc::c_void;
+    fn cblas_zsyrk (layout : libc::c_int, uplo : libc::c_int, trans : libc::c_int, n : isize, k : isize, alpha : *const c64, a : *const c64, lda : isize, beta : *const c64, c : *mut c64, ldc : isize) -> libc::c_void;
+
+    fn cblas_ssyr2k (layout : libc::c_int, uplo : libc::c_int, trans : libc::c_int, n : isize, k : isize, alpha : libc::c_float, a : *const libc::c_float, lda : isize, b : *const libc::c_float, ldb : isize, beta : libc::c_float, c : *mut libc::c_float, ldc : isize) -> libc::c_void;
+    fn cblas_dsyr2k (layout : libc::c_int, uplo : libc::c_int, trans : libc::c_int, n : isize, k : isize, alpha : libc::c_double, a : *const libc::c_double, lda : isize, b : *const libc::c_double, ldb : isize, beta : libc::c_double, c : *mut libc::c_double, ldc : isize) -> libc::c_void;
+    fn cblas_csyr2k (layout : libc::c_int, uplo : libc::c_int, trans : libc::c_int, n : isize, k : isize, alpha : *const c32, a : *const c32, lda : isize, b : *const c32, ldb : isize, beta : *const c32, c : *mut c32, ldc : isize) -> libc::c_void;
+    fn cblas_zsyr2k (layout : libc::c_int, uplo : libc::c_int, trans : libc::c_int, n : isize, k : isize, alpha : *const c64, a : *const c64, lda : isize, b : *const c64, ldb : isize, beta : *const c64, c : *mut c64, ldc : isize) -> libc::c_void;
+
+    fn cblas_cherk (layout : libc::c_int, uplo : libc::c_int, trans : libc::c_int, n : isize, k : isize, alpha : libc::c_float, a : *const c32, lda : isize, beta : libc::c_float, c : *mut c32, ldc : isize) -> libc::c_void;
+    fn cblas_zherk (layout : libc::c_int, uplo : libc::c_int, trans : libc::c_int, n : isize, k : isize, alpha : libc::c_double, a : *const c64, lda : isize, beta : libc::c_double, c : *mut c64, ldc : isize) -> libc::c_void;
+
+    fn cblas_strmm (layout : libc::c_int, side : libc::c_int, uplo : libc::c_int, transa : libc::c_int, diag : libc::c_int, m : isize, n : isize, alpha : libc::c_float, a : *const libc::c_float, lda : isize, b : *mut libc::c_float, ldb : isize) -> libc::c_void;
+    fn cblas_dtrmm (layout : libc::c_int, side : libc::c_int, uplo : libc::c_int, transa : libc::c_int, diag : libc::c_int, m : isize, n : isize, alpha : libc::c_double, a : *const libc::c_double, lda : isize, b : *mut libc::c_double, ldb : isize) -> libc::c_void;
+    fn cblas_ctrmm (layout : libc::c_int, side : libc::c_int, uplo : libc::c_int, transa : libc::c_int, diag : libc::c_int, m : isize, n : isize, alpha : *const c32, a : *const c32, lda : isize, b : *mut c32, ldb : isize) -> libc::c_void;
+    fn cblas_ztrmm (layout : libc::c_int, side : libc::c_int, uplo : libc::c_int, transa : libc::c_int, diag : libc::c_int, m : isize, n : isize, alpha : *const c64, a : *const c64, lda : isize, b : *mut c64, ldb : isize) -> libc::c_void;
+
+    fn cblas_strsm (layout : libc::c_int, side : libc::c_int, uplo : libc::c_int, transa : libc::c_int, diag : libc::c_int, m : isize, n : isize, alpha : libc::c_float, a : *const libc::c_float, lda : isize, b : *mut libc::c_float, ldb : isize) -> libc::c_void;
+    fn cblas_dtrsm (layout : libc::c_int, side : libc::c_int, uplo : libc::c_int, transa : libc::c_int, diag : libc::c_int, m : isize, n : isize, alpha : libc::c_double, a : *const libc::c_double, lda : isize, b : *mut libc::c_double, ldb : isize) -> libc::c_void;
+    fn cblas_ctrsm (layout : libc::c_int, side : libc::c_int, uplo : libc::c_int, transa : libc::c_int, diag : libc::c_int, m : isize, n : isize, alpha : *const c32, a : *const c32, lda : isize, b : *mut c32, ldb : isize) -> libc::c_void;
+    fn cblas_ztrsm (layout : libc::c_int, side : libc::c_int, uplo : libc::c_int, transa : libc::c_int, diag : libc::c_int, m : isize, n : isize, alpha : *const c64, a : *const c64, lda : isize, b : *mut c64, ldb : isize) -> libc::c_void;
 }
 
 /// A trait representing N-dimensional array on which blas functions can be applied.
@@ -65,30 +223,641 @@ pub trait Blas<T : Clone + Display> : NDDataMut<T> {
 
     /// Compute y += a * x where y is this array, x is a N-dimensional array of the same size as y 
     /// and a is a scalar.
-    fn axpy(&mut self, a : T, x : &NDData<T>);
+    fn axpy(&mut self, a : T, x : &NDData<T>) {
+        if let Err(e) = self.try_axpy(a, x) {
+            panic!("Blas::axpy(): {}", e);
+        }
+    }
+
+    /// Fallible version of axpy: return an error if x is not of the same shape as y.
+    fn try_axpy(&mut self, a : T, x : &NDData<T>) -> Result<(), Error>;
 
     /// Compute the dot product of this array and x.
-    fn dot(&self, x : &NDData<T>) -> T;
+    fn dot(&self, x : &NDData<T>) -> T {
+        match self.try_dot(x) {
+            Ok(dot) => dot,
+            Err(e) => panic!("Blas::dot(): {}", e),
+        }
+    }
+
+    /// Fallible version of dot: return an error if this array and x are not one dimensional arrays 
+    /// of the same length.
+    fn try_dot(&self, x : &NDData<T>) -> Result<T, Error>;
+
+    /// Compute the dot product of the conjugate of this array and x. For real types this is the
+    /// same as dot.
+    fn dotc(&self, x : &NDData<T>) -> T {
+        match self.try_dotc(x) {
+            Ok(dotc) => dotc,
+            Err(e) => panic!("Blas::dotc(): {}", e),
+        }
+    }
+
+    /// Fallible version of dotc: return an error if this array and x are not one dimensional
+    /// arrays of the same length.
+    fn try_dotc(&self, x : &NDData<T>) -> Result<T, Error>;
+
+    /// Exchange the elements of this array with the elements of x.
+    fn swap(&mut self, x : &mut NDDataMut<T>) {
+        if let Err(e) = self.try_swap(x) {
+            panic!("Blas::swap(): {}", e);
+        }
+    }
+
+    /// Fallible version of swap: return an error if x is not of the same shape as this array.
+    fn try_swap(&mut self, x : &mut NDDataMut<T>) -> Result<(), Error>;
+
+    /// Copy the elements of x into this array.
+    fn copy(&mut self, x : &NDData<T>) {
+        if let Err(e) = self.try_copy(x) {
+            panic!("Blas::copy(): {}", e);
+        }
+    }
+
+    /// Fallible version of copy: return an error if x is not of the same shape as this array.
+    fn try_copy(&mut self, x : &NDData<T>) -> Result<(), Error>;
+
+    /// Apply the plane rotation (c, s) to the pairs of elements of this array x and of y:
+    /// x = c * x + s * y and y = conj(c) * y - conj(s) * x.
+    fn rot(&mut self, y : &mut NDDataMut<T>, c : T, s : T) {
+        if let Err(e) = self.try_rot(y, c, s) {
+            panic!("Blas::rot(): {}", e);
+        }
+    }
+
+    /// Fallible version of rot: return an error if y is not of the same shape as this array.
+    fn try_rot(&mut self, y : &mut NDDataMut<T>, c : T, s : T) -> Result<(), Error>;
+
+    /// Construct the plane rotation (c, s) which zeroes the second element b of this two
+    /// elements array [a, b] and return it. The first element is overwritten with r, the
+    /// element which is left after the rotation. For real types the second element is
+    /// overwritten with the value z from which c and s can be reconstructed, for complex types
+    /// it is left unchanged.
+    fn rotg(&mut self) -> (T, T) {
+        match self.try_rotg() {
+            Ok(rotation) => rotation,
+            Err(e) => panic!("Blas::rotg(): {}", e),
+        }
+    }
+
+    /// Fallible version of rotg: return an error if this array is not a one dimensional array of
+    /// two elements.
+    fn try_rotg(&mut self) -> Result<(T, T), Error>;
+
+    /// Apply the modified plane rotation described by param = [flag, h11, h21, h12, h22] to the
+    /// pairs of elements of this array x and of y: x = h11 * x + h12 * y and
+    /// y = h21 * x + h22 * y. Depending on the flag, some elements of the rotation are implied
+    /// as in blas. Only defined for real types.
+    fn rotm(&mut self, y : &mut NDDataMut<T>, param : &[T]) {
+        if let Err(e) = self.try_rotm(y, param) {
+            panic!("Blas::rotm(): {}", e);
+        }
+    }
+
+    /// Fallible version of rotm: return an error if y is not of the same shape as this array, if
+    /// param doesn't have five elements or if T is a complex type.
+    fn try_rotm(&mut self, y : &mut NDDataMut<T>, param : &[T]) -> Result<(), Error>;
+
+    /// Return the index of the first element of maximum absolute value, using |re| + |im| for
+    /// complex types, or None if the array is empty.
+    fn iamax(&self) -> Option<Vec<usize>>;
 
     /// Compute y = alpha * a * x + beta * y where y is this array, x is a one dimensional array, a 
     /// a two dimensional array and alpha and beta are scalars.
     /// Automatically determine whether a need to be transposed.
-    fn gemv(&mut self, alpha : T, a : &NDData<T>, x : &NDData<T>, beta : T);
+    fn gemv(&mut self, alpha : T, a : &NDData<T>, x : &NDData<T>, beta : T) {
+        if let Err(e) = self.try_gemv(alpha, a, x, beta) {
+            panic!("Blas::gemv(): {}", e);
+        }
+    }
+
+    /// Fallible version of gemv: return an error if the dimensions or the shapes of a, x and y 
+    /// don't match.
+    fn try_gemv(&mut self, alpha : T, a : &NDData<T>, x : &NDData<T>, beta : T) -> Result<(), Error>;
+
+    /// Compute y = alpha * op(a) * x + beta * y where y is this array, x is a one dimensional
+    /// array, a a two dimensional array and op is given by trans. Unlike gemv, which can't
+    /// distinguish a square matrix from its transpose, the operation is never inferred.
+    fn gemv_trans(&mut self, alpha : T, a : &NDData<T>, trans : Transpose, x : &NDData<T>, beta : T) {
+        if let Err(e) = self.try_gemv_trans(alpha, a, trans, x, beta) {
+            panic!("Blas::gemv_trans(): {}", e);
+        }
+    }
+
+    /// Fallible version of gemv_trans: return an error if the shapes of op(a), x and y don't
+    /// match.
+    fn try_gemv_trans(&mut self, alpha : T, a : &NDData<T>, trans : Transpose, x : &NDData<T>, beta : T) -> Result<(), Error>;
+
+    /// Compute the rank-1 update a += alpha * x * y^T where a is this two dimensional array, x and
+    /// y are one dimensional arrays and alpha is a scalar. For complex types this is blas geru.
+    fn ger(&mut self, alpha : T, x : &NDData<T>, y : &NDData<T>) {
+        if let Err(e) = self.try_ger(alpha, x, y) {
+            panic!("Blas::ger(): {}", e);
+        }
+    }
+
+    /// Fallible version of ger: return an error if this array is not of shape [x.len, y.len].
+    fn try_ger(&mut self, alpha : T, x : &NDData<T>, y : &NDData<T>) -> Result<(), Error>;
+
+    /// Compute the rank-1 update a += alpha * x * y^H where a is this two dimensional array, x and
+    /// y are one dimensional arrays and alpha is a scalar. For real types this is the same as ger.
+    fn gerc(&mut self, alpha : T, x : &NDData<T>, y : &NDData<T>) {
+        if let Err(e) = self.try_gerc(alpha, x, y) {
+            panic!("Blas::gerc(): {}", e);
+        }
+    }
+
+    /// Fallible version of gerc: return an error if this array is not of shape [x.len, y.len].
+    fn try_gerc(&mut self, alpha : T, x : &NDData<T>, y : &NDData<T>) -> Result<(), Error>;
+
+    /// Compute y = alpha * a * x + beta * y where y is this array and a is a symmetric matrix of
+    /// which only the uplo triangle is referenced. Only defined for real types, see hemv for
+    /// complex types.
+    fn symv(&mut self, alpha : T, a : &NDData<T>, uplo : Uplo, x : &NDData<T>, beta : T) {
+        if let Err(e) = self.try_symv(alpha, a, uplo, x, beta) {
+            panic!("Blas::symv(): {}", e);
+        }
+    }
+
+    /// Fallible version of symv: return an error if a is not square, if x and y don't match its
+    /// size or if T is a complex type.
+    fn try_symv(&mut self, alpha : T, a : &NDData<T>, uplo : Uplo, x : &NDData<T>, beta : T) -> Result<(), Error>;
+
+    /// Compute y = alpha * a * x + beta * y where y is this array and a is a Hermitian matrix of
+    /// which only the uplo triangle is referenced. For real types this is the same as symv.
+    fn hemv(&mut self, alpha : T, a : &NDData<T>, uplo : Uplo, x : &NDData<T>, beta : T) {
+        if let Err(e) = self.try_hemv(alpha, a, uplo, x, beta) {
+            panic!("Blas::hemv(): {}", e);
+        }
+    }
+
+    /// Fallible version of hemv: return an error if a is not square or if x and y don't match its
+    /// size.
+    fn try_hemv(&mut self, alpha : T, a : &NDData<T>, uplo : Uplo, x : &NDData<T>, beta : T) -> Result<(), Error>;
+
+    /// Compute x = op(a) * x where x is this array and a is a triangular matrix of which only the
+    /// uplo triangle is referenced.
+    fn trmv(&mut self, a : &NDData<T>, uplo : Uplo, trans : Transpose, diag : Diag) {
+        if let Err(e) = self.try_trmv(a, uplo, trans, diag) {
+            panic!("Blas::trmv(): {}", e);
+        }
+    }
+
+    /// Fallible version of trmv: return an error if a is not square or if x doesn't match its
+    /// size.
+    fn try_trmv(&mut self, a : &NDData<T>, uplo : Uplo, trans : Transpose, diag : Diag) -> Result<(), Error>;
+
+    /// Solve op(a) * x = b where b is this array, which is overwritten by x, and a is a triangular
+    /// matrix of which only the uplo triangle is referenced.
+    fn trsv(&mut self, a : &NDData<T>, uplo : Uplo, trans : Transpose, diag : Diag) {
+        if let Err(e) = self.try_trsv(a, uplo, trans, diag) {
+            panic!("Blas::trsv(): {}", e);
+        }
+    }
+
+    /// Fallible version of trsv: return an error if a is not square or if b doesn't match its
+    /// size.
+    fn try_trsv(&mut self, a : &NDData<T>, uplo : Uplo, trans : Transpose, diag : Diag) -> Result<(), Error>;
 
     /// Compute y = alpha * a * x + beta * y where y is this array, a and b are two dimensional 
     /// arrays and alpha and beta are scalars.
     /// Automatically determine whether a and b need to be transposed.
-    fn gemm(&mut self, alpha : T, a : &NDData<T>, b : &NDData<T>, beta : T);
+    fn gemm(&mut self, alpha : T, a : &NDData<T>, b : &NDData<T>, beta : T) {
+        if let Err(e) = self.try_gemm(alpha, a, b, beta) {
+            panic!("Blas::gemm(): {}", e);
+        }
+    }
+
+    /// Fallible version of gemm: return an error if the dimensions or the shapes of a, b and c 
+    /// don't match.
+    fn try_gemm(&mut self, alpha : T, a : &NDData<T>, b : &NDData<T>, beta : T) -> Result<(), Error>;
+
+    /// Compute c = alpha * op(a) * op(b) + beta * c where c is this array, a and b are two
+    /// dimensional arrays and op is given by transa and transb. Unlike gemm, which can't
+    /// distinguish a square matrix from its transpose, the operations are never inferred.
+    fn gemm_trans(&mut self, alpha : T, a : &NDData<T>, transa : Transpose, b : &NDData<T>, transb : Transpose, beta : T) {
+        if let Err(e) = self.try_gemm_trans(alpha, a, transa, b, transb, beta) {
+            panic!("Blas::gemm_trans(): {}", e);
+        }
+    }
+
+    /// Fallible version of gemm_trans: return an error if the shapes of op(a), op(b) and c don't
+    /// match.
+    fn try_gemm_trans(&mut self, alpha : T, a : &NDData<T>, transa : Transpose, b : &NDData<T>, transb : Transpose, beta : T) -> Result<(), Error>;
+
+    /// Compute c[i] = alpha * a[i] * b[i] + beta * c[i] for each matrix i of the three
+    /// dimensional array c of shape [batch, m, n], where a is of shape [batch, m, k] and b of
+    /// shape [batch, k, n]. Either a or b can instead be a single two dimensional matrix which is
+    /// then used for every element of the batch. Each product is computed by a separate gemm
+    /// call, as the OpenCL backend doesn't provide a batched gemm yet.
+    fn batched_gemm(&mut self, alpha : T, a : &NDData<T>, b : &NDData<T>, beta : T) {
+        if let Err(e) = self.try_batched_gemm(alpha, a, b, beta) {
+            panic!("Blas::batched_gemm(): {}", e);
+        }
+    }
+
+    /// Fallible version of batched_gemm: return an error if the dimensions, the batch sizes or
+    /// the matrix shapes of a, b and c don't match.
+    fn try_batched_gemm(&mut self, alpha : T, a : &NDData<T>, b : &NDData<T>, beta : T) -> Result<(), Error>;
+
+    /// Compute c = alpha * a * b + beta * c if side is Left, or c = alpha * b * a + beta * c if
+    /// side is Right, where c is this array and a is a symmetric matrix of which only the uplo
+    /// triangle is referenced.
+    fn symm(&mut self, alpha : T, a : &NDData<T>, side : Side, uplo : Uplo, b : &NDData<T>, beta : T) {
+        if let Err(e) = self.try_symm(alpha, a, side, uplo, b, beta) {
+            panic!("Blas::symm(): {}", e);
+        }
+    }
+
+    /// Fallible version of symm: return an error if a is not square or if the shapes of a, b and
+    /// c don't match.
+    fn try_symm(&mut self, alpha : T, a : &NDData<T>, side : Side, uplo : Uplo, b : &NDData<T>, beta : T) -> Result<(), Error>;
+
+    /// Compute the rank-k update c = alpha * a * a^T + beta * c if trans is NoTrans, or
+    /// c = alpha * a^T * a + beta * c otherwise, where c is this symmetric array of which only
+    /// the uplo triangle is updated.
+    fn syrk(&mut self, alpha : T, a : &NDData<T>, uplo : Uplo, trans : Transpose, beta : T) {
+        if let Err(e) = self.try_syrk(alpha, a, uplo, trans, beta) {
+            panic!("Blas::syrk(): {}", e);
+        }
+    }
+
+    /// Fallible version of syrk: return an error if c is not square or if op(a) doesn't have as
+    /// many rows as c.
+    fn try_syrk(&mut self, alpha : T, a : &NDData<T>, uplo : Uplo, trans : Transpose, beta : T) -> Result<(), Error>;
+
+    /// Compute the rank-2k update c = alpha * (a * b^T + b * a^T) + beta * c if trans is
+    /// NoTrans, or c = alpha * (a^T * b + b^T * a) + beta * c otherwise, where c is this
+    /// symmetric array of which only the uplo triangle is updated.
+    fn syr2k(&mut self, alpha : T, a : &NDData<T>, b : &NDData<T>, uplo : Uplo, trans : Transpose, beta : T) {
+        if let Err(e) = self.try_syr2k(alpha, a, b, uplo, trans, beta) {
+            panic!("Blas::syr2k(): {}", e);
+        }
+    }
+
+    /// Fallible version of syr2k: return an error if c is not square, if op(a) doesn't have as
+    /// many rows as c or if b is not of the same shape as a.
+    fn try_syr2k(&mut self, alpha : T, a : &NDData<T>, b : &NDData<T>, uplo : Uplo, trans : Transpose, beta : T) -> Result<(), Error>;
+
+    /// Compute the rank-k update c = alpha * a * a^H + beta * c if trans is NoTrans, or
+    /// c = alpha * a^H * a + beta * c otherwise, where c is this Hermitian array of which only
+    /// the uplo triangle is updated. alpha and beta must be real for the result to be Hermitian.
+    /// For real types this is the same as syrk.
+    fn herk(&mut self, alpha : T, a : &NDData<T>, uplo : Uplo, trans : Transpose, beta : T) {
+        if let Err(e) = self.try_herk(alpha, a, uplo, trans, beta) {
+            panic!("Blas::herk(): {}", e);
+        }
+    }
+
+    /// Fallible version of herk: return an error if c is not square, if op(a) doesn't have as
+    /// many rows as c or if alpha or beta has a non zero imaginary part.
+    fn try_herk(&mut self, alpha : T, a : &NDData<T>, uplo : Uplo, trans : Transpose, beta : T) -> Result<(), Error>;
+
+    /// Compute b = alpha * op(a) * b if side is Left, or b = alpha * b * op(a) if side is Right,
+    /// where b is this array and a is a triangular matrix of which only the uplo triangle is
+    /// referenced.
+    fn trmm(&mut self, alpha : T, a : &NDData<T>, side : Side, uplo : Uplo, trans : Transpose, diag : Diag) {
+        if let Err(e) = self.try_trmm(alpha, a, side, uplo, trans, diag) {
+            panic!("Blas::trmm(): {}", e);
+        }
+    }
+
+    /// Fallible version of trmm: return an error if a is not square or doesn't match the shape
+    /// of b.
+    fn try_trmm(&mut self, alpha : T, a : &NDData<T>, side : Side, uplo : Uplo, trans : Transpose, diag : Diag) -> Result<(), Error>;
+
+    /// Solve op(a) * x = alpha * b if side is Left, or x * op(a) = alpha * b if side is Right,
+    /// where b is this array, which is overwritten by x, and a is a triangular matrix of which
+    /// only the uplo triangle is referenced.
+    fn trsm(&mut self, alpha : T, a : &NDData<T>, side : Side, uplo : Uplo, trans : Transpose, diag : Diag) {
+        if let Err(e) = self.try_trsm(alpha, a, side, uplo, trans, diag) {
+            panic!("Blas::trsm(): {}", e);
+        }
+    }
+
+    /// Fallible version of trsm: return an error if a is not square or doesn't match the shape
+    /// of b.
+    fn try_trsm(&mut self, alpha : T, a : &NDData<T>, side : Side, uplo : Uplo, trans : Transpose, diag : Diag) -> Result<(), Error>;
+}
+
+/// Check that the shape of x is the same as the shape of y.
+fn check_shape(y : &[usize], x : &[usize]) -> Result<(), Error> {
+    if y.len() != x.len() {
+        return Err(Error::DimensionMismatch { expected : y.len(), found : x.len() });
+    }
+    if y != x {
+        return Err(Error::ShapeMismatch { expected : y.to_vec(), found : x.to_vec() });
+    }
+    Ok(())
+}
+
+/// Check that x has the same shape as y.
+fn check_axpy<T>(y : &NDData<T>, x : &NDData<T>) -> Result<(), Error> {
+    check_shape(y.shape(), x.shape())
+}
+
+/// Check that y and x are one dimensional and of the same length.
+fn check_dot<T>(y : &NDData<T>, x : &NDData<T>) -> Result<(), Error> {
+    if y.dim() != 1 {
+        return Err(Error::DimensionMismatch { expected : 1, found : y.dim() });
+    }
+    check_axpy(y, x)
+}
+
+/// Check the dimensions of y, a and x and return the transposition to apply to a.
+fn check_gemv<T>(y : &NDData<T>, a : &NDData<T>, x : &NDData<T>) -> Result<Transpose, Error> {
+    if y.dim() != 1 {
+        return Err(Error::DimensionMismatch { expected : 1, found : y.dim() });
+    }
+    if a.dim() != 2 {
+        return Err(Error::DimensionMismatch { expected : 2, found : a.dim() });
+    }
+    if x.dim() != 1 {
+        return Err(Error::DimensionMismatch { expected : 1, found : x.dim() });
+    }
+    match get_mv_trans(a.shape()[0], a.shape()[1], x.shape()[0], y.shape()[0]) {
+        Some(trans) => Ok(trans),
+        None => Err(Error::ShapeMismatch { expected : vec![y.shape()[0], x.shape()[0]], found : a.shape().to_vec() }),
+    }
 }
 
-fn get_mv_trans(m : usize, n : usize, xs : usize, ys : usize) -> libc::c_int {
+/// Check that y and x are one dimensional and that op(a) is of shape [y.len, x.len].
+fn check_gemv_trans<T>(y : &NDData<T>, a : &NDData<T>, trans : Transpose, x : &NDData<T>) -> Result<(), Error> {
+    for v in &[y, x] {
+        if v.dim() != 1 {
+            return Err(Error::DimensionMismatch { expected : 1, found : v.dim() });
+        }
+    }
+    let (m, n) = get_op_shape(a, trans)?;
+    check_shape(&[n], x.shape())?;
+    check_shape(&[m], y.shape())
+}
+
+/// Check the dimensions of c, a and b and return the transpositions to apply to a and b together 
+/// with the inner dimension of the product.
+fn check_gemm<T>(c : &NDData<T>, a : &NDData<T>, b : &NDData<T>) -> Result<(libc::c_int, libc::c_int, usize), Error> {
+    for m in &[c, a, b] {
+        if m.dim() != 2 {
+            return Err(Error::DimensionMismatch { expected : 2, found : m.dim() });
+        }
+    }
+    let (transa, transb, k) = get_mm_trans(a.shape()[0], a.shape()[1], b.shape()[0], b.shape()[1], c.shape()[0], c.shape()[1]);
+    if transa == 0 {
+        return Err(Error::ShapeMismatch { expected : vec![a.shape()[0], b.shape()[1]], found : c.shape().to_vec() });
+    }
+    Ok((transa, transb, k))
+}
+
+/// Check that a is of shape [x.len, y.len].
+fn check_ger<T>(a : &NDData<T>, x : &NDData<T>, y : &NDData<T>) -> Result<(), Error> {
+    for v in &[x, y] {
+        if v.dim() != 1 {
+            return Err(Error::DimensionMismatch { expected : 1, found : v.dim() });
+        }
+    }
+    check_shape(&[x.shape()[0], y.shape()[0]], a.shape())
+}
+
+/// Check that a is a square matrix and that x and y are vectors of its size.
+fn check_square_mv<T>(a : &NDData<T>, x : &NDData<T>, y : &NDData<T>) -> Result<(), Error> {
+    if a.dim() != 2 {
+        return Err(Error::DimensionMismatch { expected : 2, found : a.dim() });
+    }
+    let n = a.shape()[0];
+    check_shape(&[n, n], a.shape())?;
+    check_shape(&[n], x.shape())?;
+    check_shape(&[n], y.shape())
+}
+
+/// Return a, or a copy of a if it can't be described to blas, together with its layout as
+/// returned by get_matrix.
+fn get_matrix_or_copy<'a, T : Clone>(a : &'a NDData<T>, copy : &'a mut Option<NDArray<T>>) -> (&'a NDData<T>, (usize, bool, isize)) {
+    match get_matrix(a) {
+        Some(layout) => (a, layout),
+        None => {
+            *copy = Some(NDArray::<T>::copy(a));
+            let a = copy.as_ref().unwrap();
+            (a, get_matrix(a).unwrap())
+        }
+    }
+}
+
+/// Check that a is two dimensional and return the shape of op(a).
+fn get_op_shape<T>(a : &NDData<T>, trans : Transpose) -> Result<(usize, usize), Error> {
+    if a.dim() != 2 {
+        return Err(Error::DimensionMismatch { expected : 2, found : a.dim() });
+    }
+    if trans == Transpose::NoTrans {
+        return Ok((a.shape()[0], a.shape()[1]));
+    }
+    Ok((a.shape()[1], a.shape()[0]))
+}
+
+/// Check that c is of shape [op(a).rows, op(b).cols] and that the inner dimensions of op(a) and
+/// op(b) match, then return that inner dimension.
+fn check_gemm_trans<T>(c : &NDData<T>, a : &NDData<T>, transa : Transpose, b : &NDData<T>, transb : Transpose) -> Result<usize, Error> {
+    let (m, k) = get_op_shape(a, transa)?;
+    let (kb, n) = get_op_shape(b, transb)?;
+    check_shape(&[k, n], &[kb, n])?;
+    check_shape(&[m, n], c.shape())?;
+    Ok(k)
+}
+
+/// Check that c is a batch of matrices of shape [batch, m, n] and that a and b are either batches
+/// of the same size or single matrices whose shapes are [m, k] and [k, n], then return the batch
+/// size.
+fn check_batched_gemm<T>(c : &NDData<T>, a : &NDData<T>, b : &NDData<T>) -> Result<usize, Error> {
+    if c.dim() != 3 {
+        return Err(Error::DimensionMismatch { expected : 3, found : c.dim() });
+    }
+    let batch = c.shape()[0];
+    for m in &[a, b] {
+        if m.dim() == 3 {
+            check_shape(&[batch, m.shape()[1], m.shape()[2]], m.shape())?;
+        }
+        else if m.dim() != 2 {
+            return Err(Error::DimensionMismatch { expected : 3, found : m.dim() });
+        }
+    }
+    let (ma, ka) = (a.shape()[a.dim() - 2], a.shape()[a.dim() - 1]);
+    let (kb, nb) = (b.shape()[b.dim() - 2], b.shape()[b.dim() - 1]);
+    check_shape(&[ka, nb], &[kb, nb])?;
+    check_shape(&[batch, ma, nb], c.shape())?;
+    Ok(batch)
+}
+
+/// Return the matrix i of the batch a, or a itself if it is a single matrix.
+fn get_batch_matrix<'a, T>(a : &'a NDData<T>, i : usize) -> NDView<'a, T> {
+    if a.dim() == 3 {
+        return a.view(&[NDRange::index(i), NDRange::all(), NDRange::all()]);
+    }
+    a.view(&[NDRange::all(), NDRange::all()])
+}
+
+/// Check that a is a square matrix of the size of the side dimension of the two dimensional c.
+fn check_side<T>(c : &NDData<T>, a : &NDData<T>, side : Side) -> Result<(), Error> {
+    if c.dim() != 2 {
+        return Err(Error::DimensionMismatch { expected : 2, found : c.dim() });
+    }
+    let n = if side == Side::Left { c.shape()[0] } else { c.shape()[1] };
+    check_shape(&[n, n], a.shape())
+}
+
+/// Check that c is a square matrix and that op(a) has as many rows as c, then return the number
+/// of columns of op(a).
+fn check_rank_k<T>(c : &NDData<T>, a : &NDData<T>, trans : Transpose) -> Result<usize, Error> {
+    if c.dim() != 2 {
+        return Err(Error::DimensionMismatch { expected : 2, found : c.dim() });
+    }
+    let n = c.shape()[0];
+    check_shape(&[n, n], c.shape())?;
+    let (rows, k) = get_op_shape(a, trans)?;
+    check_shape(&[n, k], &[rows, k])?;
+    Ok(k)
+}
+
+/// Return a, or a row-major copy of a if it isn't stored in row-major order, together with the
+/// position of its first element and its leading dimension.
+fn get_row_major<'a, T : Clone>(a : &'a NDData<T>, copy : &'a mut Option<NDArray<T>>) -> (&'a NDData<T>, usize, isize) {
+    if let Some((pos, false, ld)) = get_matrix(a) {
+        return (a, pos, ld);
+    }
+    *copy = Some(NDArray::<T>::copy(a));
+    let a = copy.as_ref().unwrap();
+    let (pos, _, ld) = get_matrix(a).unwrap();
+    (a, pos, ld)
+}
+
+/// Check that x is a one dimensional array of two elements.
+fn check_rotg<T>(x : &NDData<T>) -> Result<(), Error> {
+    if x.dim() != 1 {
+        return Err(Error::DimensionMismatch { expected : 1, found : x.dim() });
+    }
+    check_shape(&[2], x.shape())
+}
+
+/// Check that the parameters of a modified rotation have five elements.
+fn check_rotm<T>(param : &[T]) -> Result<(), Error> {
+    if param.len() != 5 {
+        return Err(Error::ShapeMismatch { expected : vec![5], found : vec![param.len()] });
+    }
+    Ok(())
+}
+
+fn flip_trans(trans : libc::c_int) -> libc::c_int {
+    if trans == CBLAS_NO_TRANS {
+        return CBLAS_TRANS;
+    }
+    return CBLAS_NO_TRANS;
+}
+
+fn get_lowest(first : usize, n : usize, inc : isize) -> usize {
+    if inc < 0 {
+        return first - (n - 1) * (-inc) as usize;
+    }
+    return first;
+}
+
+/// Return the position of the lowest element of a one dimensional x in its underlying storage 
+/// array, as expected by blas, and its increment.
+fn get_vector<T>(x : &NDData<T>) -> (usize, isize) {
+    let inc = x.strides()[0] as isize;
+    return (get_lowest(x.offset(), x.shape()[0], inc), inc);
+}
+
+/// Split x in lines along its last dimension and return the length of those lines together with, 
+/// for each line, the position of its lowest element and its increment. If flatten is true and x 
+/// is contiguous, x is returned as a single line.
+fn get_lines<T, D : ?Sized + NDData<T>>(x : &D, flatten : bool) -> (usize, Vec<(usize, isize)>) {
+    if x.dim() == 0 || (flatten && x.is_contiguous()) {
+        return (x.size(), vec![(x.offset(), 1)]);
+    }
+
+    let last = x.dim() - 1;
+    let n = x.shape()[last];
+    let inc = x.strides()[last] as isize;
+    let mut lines = Vec::<(usize, isize)>::new();
+    let mut idx : Vec<usize> = repeat(0usize).take(last).collect();
+    loop {
+        let first = x.offset().wrapping_add(idx.to_pos(&x.shape()[..last], &x.strides()[..last]));
+        lines.push((get_lowest(first, n, inc), inc));
+        idx.inc_ro(&x.shape()[..last]);
+        if idx.is_zero() {
+            break;
+        }
+    }
+    return (n, lines);
+}
+
+/// Split y and x, which must have the same shape, in lines along their last dimension and return
+/// the length of those lines together with the pairs of lines as returned by get_lines.
+fn get_line_pairs<T, Y : ?Sized + NDData<T>, X : ?Sized + NDData<T>>(y : &Y, x : &X) -> (usize, Vec<((usize, isize), (usize, isize))>) {
+    let flatten = y.is_contiguous() && x.is_contiguous();
+    let (n, ylines) = get_lines(y, flatten);
+    let (_, xlines) = get_lines(x, flatten);
+    return (n, ylines.into_iter().zip(xlines.into_iter()).collect());
+}
+
+/// Return the position of the element i of a line of length n starting at the lowest position 
+/// pos with an increment inc.
+fn get_line_element(pos : usize, n : usize, inc : isize, i : usize) -> usize {
+    if inc < 0 {
+        return pos + (n - 1 - i) * (-inc) as usize;
+    }
+    return pos + i * inc as usize;
+}
+
+/// Return the index of an element of maximum absolute value of x. iamax returns the position of 
+/// the maximum of a line in memory order, as blas, while abs computes the absolute value used to 
+/// compare the maxima of the different lines.
+fn get_iamax<T, F, A>(x : &NDData<T>, iamax : F, abs : A) -> Option<Vec<usize>> 
+    where F : Fn(usize, *const T, isize) -> usize, A : Fn(&T) -> f64 {
+    if x.size() == 0 {
+        return None;
+    }
+    let (n, lines) = get_lines(x, true);
+    let mut best : Option<(usize, f64)> = None;
+    for (l, &(pos, inc)) in lines.iter().enumerate() {
+        let j = iamax(n, unsafe { x.get_data().as_ptr().offset(pos as isize) }, inc.abs());
+        let value = abs(&x.get_data()[pos + j * inc.abs() as usize]);
+        let j = if inc < 0 { n - 1 - j } else { j };
+        match best {
+            Some((_, max)) if max >= value => {},
+            _ => best = Some((l * n + j, value)),
+        }
+    }
+    // Convert the row-major position into an index
+    let mut flat = best.unwrap().0;
+    let mut idx : Vec<usize> = repeat(0usize).take(x.dim()).collect();
+    for i in (0..x.dim()).rev() {
+        idx[i] = flat % x.shape()[i];
+        flat /= x.shape()[i];
+    }
+    return Some(idx);
+}
+
+/// Return the position of the first element of a two dimensional a, whether a is stored in 
+/// column-major order and its leading dimension. Return None if a can't be described to blas.
+fn get_matrix<T>(a : &NDData<T>) -> Option<(usize, bool, isize)> {
+    let (m, n) = (a.shape()[0], a.shape()[1]);
+    let (s0, s1) = (a.strides()[0] as isize, a.strides()[1] as isize);
+    if (n <= 1 || s1 == 1) && (m <= 1 || s0 >= n as isize) {
+        return Some((a.offset(), false, if m <= 1 { max(n, 1) as isize } else { s0 }));
+    }
+    if (m <= 1 || s0 == 1) && (n <= 1 || s1 >= m as isize) {
+        return Some((a.offset(), true, if n <= 1 { max(m, 1) as isize } else { s1 }));
+    }
+    return None;
+}
+
+fn get_mv_trans(m : usize, n : usize, xs : usize, ys : usize) -> Option<Transpose> {
     if m == ys && n == xs {
-        return CBLAS_NO_TRANS;
+        return Some(Transpose::NoTrans);
     }
     else if m == xs && n == ys {
-        return CBLAS_TRANS;
+        return Some(Transpose::Trans);
     }
-    return 0;
+    return None;
 }
 
 fn get_mm_trans(am : usize, an : usize, bm : usize, bn : usize, cm : usize, cn : usize) -> (libc::c_int,libc::c_int,usize) {
@@ -110,483 +879,1657 @@ fn get_mm_trans(am : usize, an : usize, bm : usize, bn : usize, cm : usize, cn :
 impl<R> Blas<f32> for R where R : NDDataMut<f32> {
 
     fn asum(&self) -> f32 {
-        unsafe {
-            cblas_sasum(self.size() as isize, self.get_data().as_ptr(), 1) as f32
+        let (n, lines) = get_lines(self, true);
+        let mut asum = 0.0f32;
+        for (pos, inc) in lines {
+            asum += unsafe { cblas_sasum(n as isize, self.get_data().as_ptr().offset(pos as isize), inc.abs()) };
         }
+        return asum;
     }
 
     fn nrm2(&self) -> f32 {
-        unsafe {
-            cblas_snrm2(self.size() as isize, self.get_data().as_ptr(), 1) as f32
+        let (n, lines) = get_lines(self, true);
+        // Combine the norms of the lines with hypot so that large norms do not overflow
+        let mut norm = 0.0f32;
+        for &(pos, inc) in &lines {
+            let nrm2 = unsafe { cblas_snrm2(n as isize, self.get_data().as_ptr().offset(pos as isize), inc.abs()) };
+            if lines.len() == 1 {
+                return nrm2;
+            }
+            norm = norm.hypot(nrm2);
         }
+        return norm;
     }
 
     fn scal(&mut self, a : f32) {
+        let (n, lines) = get_lines(self, true);
+        for (pos, inc) in lines {
+            unsafe {
+                cblas_sscal(n as isize, a, self.get_data_mut().as_mut_ptr().offset(pos as isize), inc.abs());
+            }
+        }
+    }
+
+    fn try_axpy(&mut self, a : f32, x : &NDData<f32>) -> Result<(), Error> {
+        check_axpy(self, x)?;
+
+        let flatten = self.is_contiguous() && x.is_contiguous();
+        let (n, ylines) = get_lines(self, flatten);
+        let (_, xlines) = get_lines(x, flatten);
+        for (&(ypos, incy), &(xpos, incx)) in ylines.iter().zip(xlines.iter()) {
+            unsafe {
+                cblas_saxpy(n as isize, a, x.get_data().as_ptr().offset(xpos as isize), incx, self.get_data_mut().as_mut_ptr().offset(ypos as isize), incy);
+            }
+        }
+        Ok(())
+    }
+
+    fn try_dot(&self, x : &NDData<f32>) -> Result<f32, Error> {
+        check_dot(self, x)?;
+        let (xpos, incx) = get_vector(x);
+        let (ypos, incy) = get_vector(self);
         unsafe {
-            cblas_sscal(self.size() as isize, a, self.get_data_mut().as_mut_ptr(), 1);
+            Ok(cblas_sdot(self.size() as isize, x.get_data().as_ptr().offset(xpos as isize), incx, self.get_data().as_ptr().offset(ypos as isize), incy) as f32)
         }
     }
 
-    fn axpy(&mut self, a : f32, x : &NDData<f32>) {
-        if self.dim() != x.dim() {
-            panic!("Blas::axpy(): x is not of the same dimension ({} != {})",  x.dim(), self.dim());
+    fn try_dotc(&self, x : &NDData<f32>) -> Result<f32, Error> {
+        self.try_dot(x)
+    }
+
+    fn try_swap(&mut self, x : &mut NDDataMut<f32>) -> Result<(), Error> {
+        check_shape(self.shape(), x.shape())?;
+        let (n, lines) = get_line_pairs(self, x);
+        for ((ypos, incy), (xpos, incx)) in lines {
+            unsafe {
+                cblas_sswap(n as isize, x.get_data_mut().as_mut_ptr().offset(xpos as isize), incx, self.get_data_mut().as_mut_ptr().offset(ypos as isize), incy);
+            }
         }
+        Ok(())
+    }
 
-        if self.shape() != x.shape() {
-            panic!("Blas::axpy(): x is not of the same shape ({:?} != {:?})",  x.shape(), self.shape());
+    fn try_copy(&mut self, x : &NDData<f32>) -> Result<(), Error> {
+        check_axpy(self, x)?;
+        let (n, lines) = get_line_pairs(self, x);
+        for ((ypos, incy), (xpos, incx)) in lines {
+            unsafe {
+                cblas_scopy(n as isize, x.get_data().as_ptr().offset(xpos as isize), incx, self.get_data_mut().as_mut_ptr().offset(ypos as isize), incy);
+            }
         }
+        Ok(())
+    }
 
+    fn try_rot(&mut self, y : &mut NDDataMut<f32>, c : f32, s : f32) -> Result<(), Error> {
+        check_shape(self.shape(), y.shape())?;
+        let (n, lines) = get_line_pairs(self, y);
+        for ((xpos, incx), (ypos, incy)) in lines {
+            unsafe {
+                cblas_srot(n as isize, self.get_data_mut().as_mut_ptr().offset(xpos as isize), incx, y.get_data_mut().as_mut_ptr().offset(ypos as isize), incy, c, s);
+            }
+        }
+        Ok(())
+    }
+
+    fn try_rotg(&mut self) -> Result<(f32, f32), Error> {
+        check_rotg(self)?;
+        let (mut a, mut b) = (*self.idx(&[0]), *self.idx(&[1]));
+        let (mut c, mut s) = (0.0, 0.0);
         unsafe {
-            cblas_saxpy(self.size() as isize, a, x.get_data().as_ptr(), 1, self.get_data_mut().as_mut_ptr(), 1);
+            cblas_srotg(&mut a, &mut b, &mut c, &mut s);
         }
+        *self.idx_mut(&[0]) = a;
+        *self.idx_mut(&[1]) = b;
+        Ok((c, s))
     }
 
-    fn dot(&self, x : &NDData<f32>) -> f32 {
-        if self.dim() != 1 || x.dim() != 1{
-            panic!("Blas::dot(): x or self is not in 1 dimension ({} != 1 || {} != 1)",  x.dim(), self.dim());
+    fn try_rotm(&mut self, y : &mut NDDataMut<f32>, param : &[f32]) -> Result<(), Error> {
+        check_shape(self.shape(), y.shape())?;
+        check_rotm(param)?;
+        let (n, lines) = get_line_pairs(self, y);
+        for ((xpos, incx), (ypos, incy)) in lines {
+            unsafe {
+                cblas_srotm(n as isize, self.get_data_mut().as_mut_ptr().offset(xpos as isize), incx, y.get_data_mut().as_mut_ptr().offset(ypos as isize), incy, param.as_ptr());
+            }
         }
+        Ok(())
+    }
+
+    fn iamax(&self) -> Option<Vec<usize>> {
+        get_iamax(self, |n, x, inc| unsafe { cblas_isamax(n as isize, x, inc) }, |x| x.abs() as f64)
+    }
+
+    fn try_gemv(&mut self, alpha : f32, a : &NDData<f32>, x : &NDData<f32>, beta : f32) -> Result<(), Error> {
+        let trans = check_gemv(self, a, x)?;
+        self.try_gemv_trans(alpha, a, trans, x, beta)
+    }
+
+    #[allow(unused_assignments)]
+    fn try_gemv_trans(&mut self, alpha : f32, a : &NDData<f32>, trans : Transpose, x : &NDData<f32>, beta : f32) -> Result<(), Error> {
+        check_gemv_trans(self, a, trans, x)?;
+
+        let a_copy;
+        let (a, (apos, a_col_major, lda)) = match get_matrix(a) {
+            Some(layout) => (a, layout),
+            None => {
+                a_copy = NDArray::<f32>::copy(a);
+                (&a_copy as &NDData<f32>, get_matrix(&a_copy).unwrap())
+            }
+        };
+        let layout = if a_col_major { CBLAS_COL_MAJOR } else { CBLAS_ROW_MAJOR };
+        let (xpos, incx) = get_vector(x);
+        let (ypos, incy) = get_vector(self);
+
         unsafe {
-            cblas_sdot(self.size() as isize, x.get_data().as_ptr(), 1, self.get_data().as_ptr(), 1) as f32
+            cblas_sgemv(layout, trans.to_cblas(), a.shape()[0] as isize, a.shape()[1] as isize, alpha, a.get_data().as_ptr().offset(apos as isize), lda, x.get_data().as_ptr().offset(xpos as isize), incx, beta, self.get_data_mut().as_mut_ptr().offset(ypos as isize), incy);
         }
+        Ok(())
     }
 
-    #[allow(unused_assignments)]
-    fn gemv(&mut self, alpha : f32, a : &NDData<f32>, x : &NDData<f32>, beta : f32) {
-        if self.dim() != 1 || a.dim() != 2 {
-            panic!("Blas::gemv(): self is not in 1 dimension or a is not in 2 dimension({} != 1 || {} != 2)", self.dim(), a.dim());
+    fn try_ger(&mut self, alpha : f32, x : &NDData<f32>, y : &NDData<f32>) -> Result<(), Error> {
+        check_ger(self, x, y)?;
+        match get_matrix(self) {
+            Some((apos, a_col_major, lda)) => {
+                let layout = if a_col_major { CBLAS_COL_MAJOR } else { CBLAS_ROW_MAJOR };
+                let (xpos, incx) = get_vector(x);
+                let (ypos, incy) = get_vector(y);
+                unsafe {
+                    cblas_sger(layout, self.shape()[0] as isize, self.shape()[1] as isize, alpha, x.get_data().as_ptr().offset(xpos as isize), incx, y.get_data().as_ptr().offset(ypos as isize), incy, self.get_data_mut().as_mut_ptr().offset(apos as isize), lda);
+                }
+            },
+            None => {
+                let mut a = NDArray::<f32>::copy(self);
+                a.try_ger(alpha, x, y)?;
+                self.try_assign(&a)?;
+            }
         }
+        Ok(())
+    }
+
+    fn try_gerc(&mut self, alpha : f32, x : &NDData<f32>, y : &NDData<f32>) -> Result<(), Error> {
+        self.try_ger(alpha, x, y)
+    }
 
-        let trans = get_mv_trans(a.shape()[0], a.shape()[1], x.shape()[0], self.shape()[0]);
-        if trans == 0 {
-            panic!("Blas::gemv(): a dimensions ({:?} do not match y ({:?}) and x ({:?})", a.shape(), self.shape(), x.shape());
+    fn try_symv(&mut self, alpha : f32, a : &NDData<f32>, uplo : Uplo, x : &NDData<f32>, beta : f32) -> Result<(), Error> {
+        check_square_mv(a, x, self)?;
+        let mut a_copy = None;
+        let (a, (apos, a_col_major, lda)) = get_matrix_or_copy(a, &mut a_copy);
+        let layout = if a_col_major { CBLAS_COL_MAJOR } else { CBLAS_ROW_MAJOR };
+        let (xpos, incx) = get_vector(x);
+        let (ypos, incy) = get_vector(self);
+        unsafe {
+            cblas_ssymv(layout, uplo.to_cblas(), a.shape()[0] as isize, alpha, a.get_data().as_ptr().offset(apos as isize), lda, x.get_data().as_ptr().offset(xpos as isize), incx, beta, self.get_data_mut().as_mut_ptr().offset(ypos as isize), incy);
         }
+        Ok(())
+    }
+
+    fn try_hemv(&mut self, alpha : f32, a : &NDData<f32>, uplo : Uplo, x : &NDData<f32>, beta : f32) -> Result<(), Error> {
+        self.try_symv(alpha, a, uplo, x, beta)
+    }
 
+    fn try_trmv(&mut self, a : &NDData<f32>, uplo : Uplo, trans : Transpose, diag : Diag) -> Result<(), Error> {
+        check_square_mv(a, self, self)?;
+        let mut a_copy = None;
+        let (a, (apos, a_col_major, lda)) = get_matrix_or_copy(a, &mut a_copy);
+        let layout = if a_col_major { CBLAS_COL_MAJOR } else { CBLAS_ROW_MAJOR };
+        let (xpos, incx) = get_vector(self);
         unsafe {
-            cblas_sgemv(CBLAS_ROW_MAJOR, trans, a.shape()[0] as isize, a.shape()[1] as isize, alpha, a.get_data().as_ptr(), a.strides()[0] as isize, x.get_data().as_ptr(), 1, beta, self.get_data_mut().as_mut_ptr(), 1);
+            cblas_strmv(layout, uplo.to_cblas(), trans.to_cblas(), diag.to_cblas(), a.shape()[0] as isize, a.get_data().as_ptr().offset(apos as isize), lda, self.get_data_mut().as_mut_ptr().offset(xpos as isize), incx);
         }
+        Ok(())
+    }
+
+    fn try_trsv(&mut self, a : &NDData<f32>, uplo : Uplo, trans : Transpose, diag : Diag) -> Result<(), Error> {
+        check_square_mv(a, self, self)?;
+        let mut a_copy = None;
+        let (a, (apos, a_col_major, lda)) = get_matrix_or_copy(a, &mut a_copy);
+        let layout = if a_col_major { CBLAS_COL_MAJOR } else { CBLAS_ROW_MAJOR };
+        let (xpos, incx) = get_vector(self);
+        unsafe {
+            cblas_strsv(layout, uplo.to_cblas(), trans.to_cblas(), diag.to_cblas(), a.shape()[0] as isize, a.get_data().as_ptr().offset(apos as isize), lda, self.get_data_mut().as_mut_ptr().offset(xpos as isize), incx);
+        }
+        Ok(())
     }
 
     #[allow(unused_assignments)]
-    fn gemm(&mut self, alpha : f32, a : &NDData<f32>, b : &NDData<f32>, beta : f32) {
-        if self.dim() != 2 || a.dim() != 2 || b.dim() != 2 {
-            panic!("Blas::gemm(): a, b or c is not in 2 dimension({} != 2 || {} != 2 || {} != 2)", a.dim(), b.dim(), self.dim());
+    fn try_gemm(&mut self, alpha : f32, a : &NDData<f32>, b : &NDData<f32>, beta : f32) -> Result<(), Error> {
+        let (transa, transb, k) = check_gemm(self, a, b)?;
+
+        let a_copy;
+        let (a, (apos, a_col_major, lda)) = match get_matrix(a) {
+            Some(layout) => (a, layout),
+            None => {
+                a_copy = NDArray::<f32>::copy(a);
+                (&a_copy as &NDData<f32>, get_matrix(&a_copy).unwrap())
+            }
+        };
+        let b_copy;
+        let (b, (bpos, b_col_major, ldb)) = match get_matrix(b) {
+            Some(layout) => (b, layout),
+            None => {
+                b_copy = NDArray::<f32>::copy(b);
+                (&b_copy as &NDData<f32>, get_matrix(&b_copy).unwrap())
+            }
+        };
+
+        match get_matrix(self) {
+            Some((cpos, c_col_major, ldc)) => {
+                let layout = if c_col_major { CBLAS_COL_MAJOR } else { CBLAS_ROW_MAJOR };
+                let transa = if a_col_major != c_col_major { flip_trans(transa) } else { transa };
+                let transb = if b_col_major != c_col_major { flip_trans(transb) } else { transb };
+                unsafe {
+                    cblas_sgemm(layout, transa, transb, self.shape()[0] as isize, self.shape()[1] as isize, k as isize, alpha, a.get_data().as_ptr().offset(apos as isize), lda, b.get_data().as_ptr().offset(bpos as isize), ldb, beta, self.get_data_mut().as_mut_ptr().offset(cpos as isize), ldc);
+                }
+            },
+            None => {
+                let mut c = NDArray::<f32>::copy(self);
+                c.try_gemm(alpha, a, b, beta)?;
+                self.try_assign(&c)?;
+            }
         }
+        Ok(())
+    }
 
-        let (transa, transb, k) = get_mm_trans(a.shape()[0], a.shape()[1], b.shape()[0], b.shape()[1], self.shape()[0], self.shape()[1]);
-        if transa == 0 {
-            panic!("Blas::gemm(): a dimensions ({:?} do not match b ({:?}) and c ({:?})", a.shape(), b.shape(), self.shape());
+    fn try_gemm_trans(&mut self, alpha : f32, a : &NDData<f32>, transa : Transpose, b : &NDData<f32>, transb : Transpose, beta : f32) -> Result<(), Error> {
+        let k = check_gemm_trans(self, a, transa, b, transb)?;
+        match get_matrix(self) {
+            Some((cpos, false, ldc)) => {
+                let (mut a_copy, mut b_copy) = (None, None);
+                let (a, apos, lda) = get_row_major(a, &mut a_copy);
+                let (b, bpos, ldb) = get_row_major(b, &mut b_copy);
+                unsafe {
+                    cblas_sgemm(CBLAS_ROW_MAJOR, transa.to_cblas(), transb.to_cblas(), self.shape()[0] as isize, self.shape()[1] as isize, k as isize, alpha, a.get_data().as_ptr().offset(apos as isize), lda, b.get_data().as_ptr().offset(bpos as isize), ldb, beta, self.get_data_mut().as_mut_ptr().offset(cpos as isize), ldc);
+                }
+            },
+            _ => {
+                // Compute on a row-major copy of c
+                let mut c = NDArray::<f32>::copy(self);
+                c.try_gemm_trans(alpha, a, transa, b, transb, beta)?;
+                self.try_assign(&c)?;
+            }
         }
+        Ok(())
+    }
 
-        unsafe {
-            cblas_sgemm(CBLAS_ROW_MAJOR, transa, transb, self.shape()[0] as isize, self.shape()[1] as isize, k as isize, alpha, a.get_data().as_ptr(), a.strides()[0] as isize, b.get_data().as_ptr(), b.strides()[0] as isize, beta, self.get_data_mut().as_mut_ptr(), self.strides()[0] as isize);
+    fn try_batched_gemm(&mut self, alpha : f32, a : &NDData<f32>, b : &NDData<f32>, beta : f32) -> Result<(), Error> {
+        let batch = check_batched_gemm(self, a, b)?;
+        for i in 0..batch {
+            let (ai, bi) = (get_batch_matrix(a, i), get_batch_matrix(b, i));
+            self.view_mut(&[NDRange::index(i), NDRange::all(), NDRange::all()]).try_gemm_trans(alpha, &ai, Transpose::NoTrans, &bi, Transpose::NoTrans, beta)?;
+        }
+        Ok(())
+    }
+
+    fn try_symm(&mut self, alpha : f32, a : &NDData<f32>, side : Side, uplo : Uplo, b : &NDData<f32>, beta : f32) -> Result<(), Error> {
+        check_side(self, a, side)?;
+        check_axpy(self, b)?;
+        match get_matrix(self) {
+            Some((cpos, false, ldc)) => {
+                let (mut a_copy, mut b_copy) = (None, None);
+                let (a, apos, lda) = get_row_major(a, &mut a_copy);
+                let (b, bpos, ldb) = get_row_major(b, &mut b_copy);
+                unsafe {
+                    cblas_ssymm(CBLAS_ROW_MAJOR, side.to_cblas(), uplo.to_cblas(), self.shape()[0] as isize, self.shape()[1] as isize, alpha, a.get_data().as_ptr().offset(apos as isize), lda, b.get_data().as_ptr().offset(bpos as isize), ldb, beta, self.get_data_mut().as_mut_ptr().offset(cpos as isize), ldc);
+                }
+            },
+            _ => {
+                // Compute on a row-major copy of c
+                let mut c = NDArray::<f32>::copy(self);
+                c.try_symm(alpha, a, side, uplo, b, beta)?;
+                self.try_assign(&c)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn try_syrk(&mut self, alpha : f32, a : &NDData<f32>, uplo : Uplo, trans : Transpose, beta : f32) -> Result<(), Error> {
+        let k = check_rank_k(self, a, trans)?;
+        match get_matrix(self) {
+            Some((cpos, false, ldc)) => {
+                let mut a_copy = None;
+                let (a, apos, lda) = get_row_major(a, &mut a_copy);
+                let trans = if trans == Transpose::NoTrans { CBLAS_NO_TRANS } else { CBLAS_TRANS };
+                unsafe {
+                    cblas_ssyrk(CBLAS_ROW_MAJOR, uplo.to_cblas(), trans, self.shape()[0] as isize, k as isize, alpha, a.get_data().as_ptr().offset(apos as isize), lda, beta, self.get_data_mut().as_mut_ptr().offset(cpos as isize), ldc);
+                }
+            },
+            _ => {
+                // Compute on a row-major copy of c
+                let mut c = NDArray::<f32>::copy(self);
+                c.try_syrk(alpha, a, uplo, trans, beta)?;
+                self.try_assign(&c)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn try_syr2k(&mut self, alpha : f32, a : &NDData<f32>, b : &NDData<f32>, uplo : Uplo, trans : Transpose, beta : f32) -> Result<(), Error> {
+        let k = check_rank_k(self, a, trans)?;
+        check_axpy(a, b)?;
+        match get_matrix(self) {
+            Some((cpos, false, ldc)) => {
+                let (mut a_copy, mut b_copy) = (None, None);
+                let (a, apos, lda) = get_row_major(a, &mut a_copy);
+                let (b, bpos, ldb) = get_row_major(b, &mut b_copy);
+                let trans = if trans == Transpose::NoTrans { CBLAS_NO_TRANS } else { CBLAS_TRANS };
+                unsafe {
+                    cblas_ssyr2k(CBLAS_ROW_MAJOR, uplo.to_cblas(), trans, self.shape()[0] as isize, k as isize, alpha, a.get_data().as_ptr().offset(apos as isize), lda, b.get_data().as_ptr().offset(bpos as isize), ldb, beta, self.get_data_mut().as_mut_ptr().offset(cpos as isize), ldc);
+                }
+            },
+            _ => {
+                // Compute on a row-major copy of c
+                let mut c = NDArray::<f32>::copy(self);
+                c.try_syr2k(alpha, a, b, uplo, trans, beta)?;
+                self.try_assign(&c)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn try_herk(&mut self, alpha : f32, a : &NDData<f32>, uplo : Uplo, trans : Transpose, beta : f32) -> Result<(), Error> {
+        self.try_syrk(alpha, a, uplo, trans, beta)
+    }
+
+    fn try_trmm(&mut self, alpha : f32, a : &NDData<f32>, side : Side, uplo : Uplo, trans : Transpose, diag : Diag) -> Result<(), Error> {
+        check_side(self, a, side)?;
+        match get_matrix(self) {
+            Some((cpos, false, ldc)) => {
+                let mut a_copy = None;
+                let (a, apos, lda) = get_row_major(a, &mut a_copy);
+                unsafe {
+                    cblas_strmm(CBLAS_ROW_MAJOR, side.to_cblas(), uplo.to_cblas(), trans.to_cblas(), diag.to_cblas(), self.shape()[0] as isize, self.shape()[1] as isize, alpha, a.get_data().as_ptr().offset(apos as isize), lda, self.get_data_mut().as_mut_ptr().offset(cpos as isize), ldc);
+                }
+            },
+            _ => {
+                // Compute on a row-major copy of c
+                let mut c = NDArray::<f32>::copy(self);
+                c.try_trmm(alpha, a, side, uplo, trans, diag)?;
+                self.try_assign(&c)?;
+            }
         }
+        Ok(())
+    }
+
+    fn try_trsm(&mut self, alpha : f32, a : &NDData<f32>, side : Side, uplo : Uplo, trans : Transpose, diag : Diag) -> Result<(), Error> {
+        check_side(self, a, side)?;
+        match get_matrix(self) {
+            Some((cpos, false, ldc)) => {
+                let mut a_copy = None;
+                let (a, apos, lda) = get_row_major(a, &mut a_copy);
+                unsafe {
+                    cblas_strsm(CBLAS_ROW_MAJOR, side.to_cblas(), uplo.to_cblas(), trans.to_cblas(), diag.to_cblas(), self.shape()[0] as isize, self.shape()[1] as isize, alpha, a.get_data().as_ptr().offset(apos as isize), lda, self.get_data_mut().as_mut_ptr().offset(cpos as isize), ldc);
+                }
+            },
+            _ => {
+                // Compute on a row-major copy of c
+                let mut c = NDArray::<f32>::copy(self);
+                c.try_trsm(alpha, a, side, uplo, trans, diag)?;
+                self.try_assign(&c)?;
+            }
+        }
+        Ok(())
     }
 }
 
 impl<R> Blas<f64> for R where R : NDDataMut<f64> {
 
     fn asum(&self) -> f64 {
-        unsafe {
-            cblas_dasum(self.size() as isize, self.get_data().as_ptr(), 1) as f64
+        let (n, lines) = get_lines(self, true);
+        let mut asum = 0.0f64;
+        for (pos, inc) in lines {
+            asum += unsafe { cblas_dasum(n as isize, self.get_data().as_ptr().offset(pos as isize), inc.abs()) };
         }
+        return asum;
     }
 
     fn nrm2(&self) -> f64 {
-        unsafe {
-            cblas_dnrm2(self.size() as isize, self.get_data().as_ptr(), 1) as f64
+        let (n, lines) = get_lines(self, true);
+        // Combine the norms of the lines with hypot so that large norms do not overflow
+        let mut norm = 0.0f64;
+        for &(pos, inc) in &lines {
+            let nrm2 = unsafe { cblas_dnrm2(n as isize, self.get_data().as_ptr().offset(pos as isize), inc.abs()) };
+            if lines.len() == 1 {
+                return nrm2;
+            }
+            norm = norm.hypot(nrm2);
         }
+        return norm;
     }
 
     fn scal(&mut self, a : f64) {
-        unsafe {
-            cblas_dscal(self.size() as isize, a, self.get_data_mut().as_mut_ptr(), 1);
+        let (n, lines) = get_lines(self, true);
+        for (pos, inc) in lines {
+            unsafe {
+                cblas_dscal(n as isize, a, self.get_data_mut().as_mut_ptr().offset(pos as isize), inc.abs());
+            }
         }
     }
 
-    fn axpy(&mut self, a : f64, x : &NDData<f64>) {
-        if self.dim() != x.dim() {
-            panic!("Blas::axpy(): x is not of the same dimension ({} != {})",  x.dim(), self.dim());
-        }
+    fn try_axpy(&mut self, a : f64, x : &NDData<f64>) -> Result<(), Error> {
+        check_axpy(self, x)?;
 
-        if self.shape() != x.shape() {
-            panic!("Blas::axpy(): x is not of the same shape ({:?} != {:?})",  x.shape(), self.shape());
+        let flatten = self.is_contiguous() && x.is_contiguous();
+        let (n, ylines) = get_lines(self, flatten);
+        let (_, xlines) = get_lines(x, flatten);
+        for (&(ypos, incy), &(xpos, incx)) in ylines.iter().zip(xlines.iter()) {
+            unsafe {
+                cblas_daxpy(n as isize, a, x.get_data().as_ptr().offset(xpos as isize), incx, self.get_data_mut().as_mut_ptr().offset(ypos as isize), incy);
+            }
         }
+        Ok(())
+    }
 
+    fn try_dot(&self, x : &NDData<f64>) -> Result<f64, Error> {
+        check_dot(self, x)?;
+        let (xpos, incx) = get_vector(x);
+        let (ypos, incy) = get_vector(self);
         unsafe {
-            cblas_daxpy(self.size() as isize, a, x.get_data().as_ptr(), 1, self.get_data_mut().as_mut_ptr(), 1);
+            Ok(cblas_ddot(self.size() as isize, x.get_data().as_ptr().offset(xpos as isize), incx, self.get_data().as_ptr().offset(ypos as isize), incy) as f64)
         }
     }
 
-    fn dot(&self, x : &NDData<f64>) -> f64 {
-        if self.dim() != 1 || x.dim() != 1{
-            panic!("Blas::dot(): x or self is not in 1 dimension ({} != 1 || {} != 1)",  x.dim(), self.dim());
-        }
-        unsafe {
-            cblas_ddot(self.size() as isize, x.get_data().as_ptr(), 1, self.get_data().as_ptr(), 1) as f64
+    fn try_dotc(&self, x : &NDData<f64>) -> Result<f64, Error> {
+        self.try_dot(x)
+    }
+
+    fn try_swap(&mut self, x : &mut NDDataMut<f64>) -> Result<(), Error> {
+        check_shape(self.shape(), x.shape())?;
+        let (n, lines) = get_line_pairs(self, x);
+        for ((ypos, incy), (xpos, incx)) in lines {
+            unsafe {
+                cblas_dswap(n as isize, x.get_data_mut().as_mut_ptr().offset(xpos as isize), incx, self.get_data_mut().as_mut_ptr().offset(ypos as isize), incy);
+            }
         }
+        Ok(())
     }
 
-    #[allow(unused_assignments)]
-    fn gemv(&mut self, alpha : f64, a : &NDData<f64>, x : &NDData<f64>, beta : f64) {
-        if self.dim() != 1 || a.dim() != 2 {
-            panic!("Blas::gemv(): self is not in 1 dimension or a is not in 2 dimension({} != 1 || {} != 2)", self.dim(), x.dim());
+    fn try_copy(&mut self, x : &NDData<f64>) -> Result<(), Error> {
+        check_axpy(self, x)?;
+        let (n, lines) = get_line_pairs(self, x);
+        for ((ypos, incy), (xpos, incx)) in lines {
+            unsafe {
+                cblas_dcopy(n as isize, x.get_data().as_ptr().offset(xpos as isize), incx, self.get_data_mut().as_mut_ptr().offset(ypos as isize), incy);
+            }
         }
+        Ok(())
+    }
 
-        let trans = get_mv_trans(a.shape()[0], a.shape()[1], x.shape()[0], self.shape()[0]);
-        if trans == 0 {
-            panic!("Blas::gemv(): a dimensions ({:?} do not match y ({:?}) and x ({:?})", a.shape(), self.shape(), x.shape());
+    fn try_rot(&mut self, y : &mut NDDataMut<f64>, c : f64, s : f64) -> Result<(), Error> {
+        check_shape(self.shape(), y.shape())?;
+        let (n, lines) = get_line_pairs(self, y);
+        for ((xpos, incx), (ypos, incy)) in lines {
+            unsafe {
+                cblas_drot(n as isize, self.get_data_mut().as_mut_ptr().offset(xpos as isize), incx, y.get_data_mut().as_mut_ptr().offset(ypos as isize), incy, c, s);
+            }
         }
+        Ok(())
+    }
 
+    fn try_rotg(&mut self) -> Result<(f64, f64), Error> {
+        check_rotg(self)?;
+        let (mut a, mut b) = (*self.idx(&[0]), *self.idx(&[1]));
+        let (mut c, mut s) = (0.0, 0.0);
         unsafe {
-            cblas_dgemv(CBLAS_ROW_MAJOR, trans, a.shape()[0] as isize, a.shape()[1] as isize, alpha, a.get_data().as_ptr(), a.strides()[0] as isize, x.get_data().as_ptr(), 1, beta, self.get_data_mut().as_mut_ptr(), 1);
+            cblas_drotg(&mut a, &mut b, &mut c, &mut s);
         }
+        *self.idx_mut(&[0]) = a;
+        *self.idx_mut(&[1]) = b;
+        Ok((c, s))
     }
 
-    #[allow(unused_assignments)]
-    fn gemm(&mut self, alpha : f64, a : &NDData<f64>, b : &NDData<f64>, beta : f64) {
-        if self.dim() != 2 || a.dim() != 2 || b.dim() != 2 {
-            panic!("Blas::gemm(): a, b or c is not in 2 dimension({} != 2 || {} != 2 || {} != 2)", a.dim(), b.dim(), self.dim());
+    fn try_rotm(&mut self, y : &mut NDDataMut<f64>, param : &[f64]) -> Result<(), Error> {
+        check_shape(self.shape(), y.shape())?;
+        check_rotm(param)?;
+        let (n, lines) = get_line_pairs(self, y);
+        for ((xpos, incx), (ypos, incy)) in lines {
+            unsafe {
+                cblas_drotm(n as isize, self.get_data_mut().as_mut_ptr().offset(xpos as isize), incx, y.get_data_mut().as_mut_ptr().offset(ypos as isize), incy, param.as_ptr());
+            }
         }
+        Ok(())
+    }
 
-        let (transa, transb, k) = get_mm_trans(a.shape()[0], a.shape()[1], b.shape()[0], b.shape()[1], self.shape()[0], self.shape()[1]);
-        if transa == 0 {
-            panic!("Blas::gemm(): a dimensions ({:?} do not match b ({:?}) and c ({:?})", a.shape(), b.shape(), self.shape());
-        }
+    fn iamax(&self) -> Option<Vec<usize>> {
+        get_iamax(self, |n, x, inc| unsafe { cblas_idamax(n as isize, x, inc) }, |x| x.abs() as f64)
+    }
+
+    fn try_gemv(&mut self, alpha : f64, a : &NDData<f64>, x : &NDData<f64>, beta : f64) -> Result<(), Error> {
+        let trans = check_gemv(self, a, x)?;
+        self.try_gemv_trans(alpha, a, trans, x, beta)
+    }
+
+    #[allow(unused_assignments)]
+    fn try_gemv_trans(&mut self, alpha : f64, a : &NDData<f64>, trans : Transpose, x : &NDData<f64>, beta : f64) -> Result<(), Error> {
+        check_gemv_trans(self, a, trans, x)?;
+
+        let a_copy;
+        let (a, (apos, a_col_major, lda)) = match get_matrix(a) {
+            Some(layout) => (a, layout),
+            None => {
+                a_copy = NDArray::<f64>::copy(a);
+                (&a_copy as &NDData<f64>, get_matrix(&a_copy).unwrap())
+            }
+        };
+        let layout = if a_col_major { CBLAS_COL_MAJOR } else { CBLAS_ROW_MAJOR };
+        let (xpos, incx) = get_vector(x);
+        let (ypos, incy) = get_vector(self);
 
         unsafe {
-            cblas_dgemm(CBLAS_ROW_MAJOR, transa, transb, self.shape()[0] as isize, self.shape()[1] as isize, k as isize, alpha, a.get_data().as_ptr(), a.strides()[0] as isize, b.get_data().as_ptr(), b.strides()[0] as isize, beta, self.get_data_mut().as_mut_ptr(), self.strides()[0] as isize);
+            cblas_dgemv(layout, trans.to_cblas(), a.shape()[0] as isize, a.shape()[1] as isize, alpha, a.get_data().as_ptr().offset(apos as isize), lda, x.get_data().as_ptr().offset(xpos as isize), incx, beta, self.get_data_mut().as_mut_ptr().offset(ypos as isize), incy);
         }
+        Ok(())
     }
-}
 
-impl<R> Blas<c32> for R where R : NDDataMut<c32> {
+    fn try_ger(&mut self, alpha : f64, x : &NDData<f64>, y : &NDData<f64>) -> Result<(), Error> {
+        check_ger(self, x, y)?;
+        match get_matrix(self) {
+            Some((apos, a_col_major, lda)) => {
+                let layout = if a_col_major { CBLAS_COL_MAJOR } else { CBLAS_ROW_MAJOR };
+                let (xpos, incx) = get_vector(x);
+                let (ypos, incy) = get_vector(y);
+                unsafe {
+                    cblas_dger(layout, self.shape()[0] as isize, self.shape()[1] as isize, alpha, x.get_data().as_ptr().offset(xpos as isize), incx, y.get_data().as_ptr().offset(ypos as isize), incy, self.get_data_mut().as_mut_ptr().offset(apos as isize), lda);
+                }
+            },
+            None => {
+                let mut a = NDArray::<f64>::copy(self);
+                a.try_ger(alpha, x, y)?;
+                self.try_assign(&a)?;
+            }
+        }
+        Ok(())
+    }
 
-    fn asum(&self) -> c32 {
+    fn try_gerc(&mut self, alpha : f64, x : &NDData<f64>, y : &NDData<f64>) -> Result<(), Error> {
+        self.try_ger(alpha, x, y)
+    }
+
+    fn try_symv(&mut self, alpha : f64, a : &NDData<f64>, uplo : Uplo, x : &NDData<f64>, beta : f64) -> Result<(), Error> {
+        check_square_mv(a, x, self)?;
+        let mut a_copy = None;
+        let (a, (apos, a_col_major, lda)) = get_matrix_or_copy(a, &mut a_copy);
+        let layout = if a_col_major { CBLAS_COL_MAJOR } else { CBLAS_ROW_MAJOR };
+        let (xpos, incx) = get_vector(x);
+        let (ypos, incy) = get_vector(self);
         unsafe {
-            c32::new(cblas_scasum(self.size() as isize, self.get_data().as_ptr(), 1),0.0)
+            cblas_dsymv(layout, uplo.to_cblas(), a.shape()[0] as isize, alpha, a.get_data().as_ptr().offset(apos as isize), lda, x.get_data().as_ptr().offset(xpos as isize), incx, beta, self.get_data_mut().as_mut_ptr().offset(ypos as isize), incy);
         }
+        Ok(())
     }
 
-    fn nrm2(&self) -> c32 {
+    fn try_hemv(&mut self, alpha : f64, a : &NDData<f64>, uplo : Uplo, x : &NDData<f64>, beta : f64) -> Result<(), Error> {
+        self.try_symv(alpha, a, uplo, x, beta)
+    }
+
+    fn try_trmv(&mut self, a : &NDData<f64>, uplo : Uplo, trans : Transpose, diag : Diag) -> Result<(), Error> {
+        check_square_mv(a, self, self)?;
+        let mut a_copy = None;
+        let (a, (apos, a_col_major, lda)) = get_matrix_or_copy(a, &mut a_copy);
+        let layout = if a_col_major { CBLAS_COL_MAJOR } else { CBLAS_ROW_MAJOR };
+        let (xpos, incx) = get_vector(self);
         unsafe {
-            c32::new(cblas_scnrm2(self.size() as isize, self.get_data().as_ptr(), 1),0.0)
+            cblas_dtrmv(layout, uplo.to_cblas(), trans.to_cblas(), diag.to_cblas(), a.shape()[0] as isize, a.get_data().as_ptr().offset(apos as isize), lda, self.get_data_mut().as_mut_ptr().offset(xpos as isize), incx);
         }
+        Ok(())
     }
 
-    fn scal(&mut self, a : c32) {
+    fn try_trsv(&mut self, a : &NDData<f64>, uplo : Uplo, trans : Transpose, diag : Diag) -> Result<(), Error> {
+        check_square_mv(a, self, self)?;
+        let mut a_copy = None;
+        let (a, (apos, a_col_major, lda)) = get_matrix_or_copy(a, &mut a_copy);
+        let layout = if a_col_major { CBLAS_COL_MAJOR } else { CBLAS_ROW_MAJOR };
+        let (xpos, incx) = get_vector(self);
         unsafe {
-            cblas_cscal(self.size() as isize, &a, self.get_data_mut().as_mut_ptr(), 1);
+            cblas_dtrsv(layout, uplo.to_cblas(), trans.to_cblas(), diag.to_cblas(), a.shape()[0] as isize, a.get_data().as_ptr().offset(apos as isize), lda, self.get_data_mut().as_mut_ptr().offset(xpos as isize), incx);
         }
+        Ok(())
     }
 
-    fn axpy(&mut self, a : c32, x : &NDData<c32>) {
-        if self.dim() != x.dim() {
-            panic!("Blas::axpy(): x is not of the same dimension ({} != {})",  x.dim(), self.dim());
+    #[allow(unused_assignments)]
+    fn try_gemm(&mut self, alpha : f64, a : &NDData<f64>, b : &NDData<f64>, beta : f64) -> Result<(), Error> {
+        let (transa, transb, k) = check_gemm(self, a, b)?;
+
+        let a_copy;
+        let (a, (apos, a_col_major, lda)) = match get_matrix(a) {
+            Some(layout) => (a, layout),
+            None => {
+                a_copy = NDArray::<f64>::copy(a);
+                (&a_copy as &NDData<f64>, get_matrix(&a_copy).unwrap())
+            }
+        };
+        let b_copy;
+        let (b, (bpos, b_col_major, ldb)) = match get_matrix(b) {
+            Some(layout) => (b, layout),
+            None => {
+                b_copy = NDArray::<f64>::copy(b);
+                (&b_copy as &NDData<f64>, get_matrix(&b_copy).unwrap())
+            }
+        };
+
+        match get_matrix(self) {
+            Some((cpos, c_col_major, ldc)) => {
+                let layout = if c_col_major { CBLAS_COL_MAJOR } else { CBLAS_ROW_MAJOR };
+                let transa = if a_col_major != c_col_major { flip_trans(transa) } else { transa };
+                let transb = if b_col_major != c_col_major { flip_trans(transb) } else { transb };
+                unsafe {
+                    cblas_dgemm(layout, transa, transb, self.shape()[0] as isize, self.shape()[1] as isize, k as isize, alpha, a.get_data().as_ptr().offset(apos as isize), lda, b.get_data().as_ptr().offset(bpos as isize), ldb, beta, self.get_data_mut().as_mut_ptr().offset(cpos as isize), ldc);
+                }
+            },
+            None => {
+                let mut c = NDArray::<f64>::copy(self);
+                c.try_gemm(alpha, a, b, beta)?;
+                self.try_assign(&c)?;
+            }
         }
+        Ok(())
+    }
 
-        if self.shape() != x.shape() {
-            panic!("Blas::axpy(): x is not of the same shape ({:?} != {:?})",  x.shape(), self.shape());
+    fn try_gemm_trans(&mut self, alpha : f64, a : &NDData<f64>, transa : Transpose, b : &NDData<f64>, transb : Transpose, beta : f64) -> Result<(), Error> {
+        let k = check_gemm_trans(self, a, transa, b, transb)?;
+        match get_matrix(self) {
+            Some((cpos, false, ldc)) => {
+                let (mut a_copy, mut b_copy) = (None, None);
+                let (a, apos, lda) = get_row_major(a, &mut a_copy);
+                let (b, bpos, ldb) = get_row_major(b, &mut b_copy);
+                unsafe {
+                    cblas_dgemm(CBLAS_ROW_MAJOR, transa.to_cblas(), transb.to_cblas(), self.shape()[0] as isize, self.shape()[1] as isize, k as isize, alpha, a.get_data().as_ptr().offset(apos as isize), lda, b.get_data().as_ptr().offset(bpos as isize), ldb, beta, self.get_data_mut().as_mut_ptr().offset(cpos as isize), ldc);
+                }
+            },
+            _ => {
+                // Compute on a row-major copy of c
+                let mut c = NDArray::<f64>::copy(self);
+                c.try_gemm_trans(alpha, a, transa, b, transb, beta)?;
+                self.try_assign(&c)?;
+            }
         }
+        Ok(())
+    }
 
-        unsafe {
-            cblas_caxpy(self.size() as isize, &a, x.get_data().as_ptr(), 1, self.get_data_mut().as_mut_ptr(), 1);
+    fn try_batched_gemm(&mut self, alpha : f64, a : &NDData<f64>, b : &NDData<f64>, beta : f64) -> Result<(), Error> {
+        let batch = check_batched_gemm(self, a, b)?;
+        for i in 0..batch {
+            let (ai, bi) = (get_batch_matrix(a, i), get_batch_matrix(b, i));
+            self.view_mut(&[NDRange::index(i), NDRange::all(), NDRange::all()]).try_gemm_trans(alpha, &ai, Transpose::NoTrans, &bi, Transpose::NoTrans, beta)?;
         }
+        Ok(())
     }
 
-    fn dot(&self, x : &NDData<c32>) -> c32 {
-        if self.dim() != 1 || x.dim() != 1{
-            panic!("Blas::dot(): x or self is not in 1 dimension ({} != 1 || {} != 1)",  x.dim(), self.dim());
+    fn try_symm(&mut self, alpha : f64, a : &NDData<f64>, side : Side, uplo : Uplo, b : &NDData<f64>, beta : f64) -> Result<(), Error> {
+        check_side(self, a, side)?;
+        check_axpy(self, b)?;
+        match get_matrix(self) {
+            Some((cpos, false, ldc)) => {
+                let (mut a_copy, mut b_copy) = (None, None);
+                let (a, apos, lda) = get_row_major(a, &mut a_copy);
+                let (b, bpos, ldb) = get_row_major(b, &mut b_copy);
+                unsafe {
+                    cblas_dsymm(CBLAS_ROW_MAJOR, side.to_cblas(), uplo.to_cblas(), self.shape()[0] as isize, self.shape()[1] as isize, alpha, a.get_data().as_ptr().offset(apos as isize), lda, b.get_data().as_ptr().offset(bpos as isize), ldb, beta, self.get_data_mut().as_mut_ptr().offset(cpos as isize), ldc);
+                }
+            },
+            _ => {
+                // Compute on a row-major copy of c
+                let mut c = NDArray::<f64>::copy(self);
+                c.try_symm(alpha, a, side, uplo, b, beta)?;
+                self.try_assign(&c)?;
+            }
         }
-        let mut dotu = c32::new(0.0,0.0);
-        unsafe {
-            cblas_cdotu_sub(self.size() as isize, x.get_data().as_ptr(), 1, self.get_data().as_ptr(), 1, &mut dotu);
+        Ok(())
+    }
+
+    fn try_syrk(&mut self, alpha : f64, a : &NDData<f64>, uplo : Uplo, trans : Transpose, beta : f64) -> Result<(), Error> {
+        let k = check_rank_k(self, a, trans)?;
+        match get_matrix(self) {
+            Some((cpos, false, ldc)) => {
+                let mut a_copy = None;
+                let (a, apos, lda) = get_row_major(a, &mut a_copy);
+                let trans = if trans == Transpose::NoTrans { CBLAS_NO_TRANS } else { CBLAS_TRANS };
+                unsafe {
+                    cblas_dsyrk(CBLAS_ROW_MAJOR, uplo.to_cblas(), trans, self.shape()[0] as isize, k as isize, alpha, a.get_data().as_ptr().offset(apos as isize), lda, beta, self.get_data_mut().as_mut_ptr().offset(cpos as isize), ldc);
+                }
+            },
+            _ => {
+                // Compute on a row-major copy of c
+                let mut c = NDArray::<f64>::copy(self);
+                c.try_syrk(alpha, a, uplo, trans, beta)?;
+                self.try_assign(&c)?;
+            }
         }
-        return dotu;
+        Ok(())
     }
 
-    #[allow(unused_assignments)]
-    fn gemv(&mut self, alpha : c32, a : &NDData<c32>, x : &NDData<c32>, beta : c32) {
-        if self.dim() != 1 || a.dim() != 2 {
-            panic!("Blas::gemv(): self is not in 1 dimension or a is not in 2 dimension({} != 1 || {} != 2)", self.dim(), x.dim());
+    fn try_syr2k(&mut self, alpha : f64, a : &NDData<f64>, b : &NDData<f64>, uplo : Uplo, trans : Transpose, beta : f64) -> Result<(), Error> {
+        let k = check_rank_k(self, a, trans)?;
+        check_axpy(a, b)?;
+        match get_matrix(self) {
+            Some((cpos, false, ldc)) => {
+                let (mut a_copy, mut b_copy) = (None, None);
+                let (a, apos, lda) = get_row_major(a, &mut a_copy);
+                let (b, bpos, ldb) = get_row_major(b, &mut b_copy);
+                let trans = if trans == Transpose::NoTrans { CBLAS_NO_TRANS } else { CBLAS_TRANS };
+                unsafe {
+                    cblas_dsyr2k(CBLAS_ROW_MAJOR, uplo.to_cblas(), trans, self.shape()[0] as isize, k as isize, alpha, a.get_data().as_ptr().offset(apos as isize), lda, b.get_data().as_ptr().offset(bpos as isize), ldb, beta, self.get_data_mut().as_mut_ptr().offset(cpos as isize), ldc);
+                }
+            },
+            _ => {
+                // Compute on a row-major copy of c
+                let mut c = NDArray::<f64>::copy(self);
+                c.try_syr2k(alpha, a, b, uplo, trans, beta)?;
+                self.try_assign(&c)?;
+            }
         }
+        Ok(())
+    }
+
+    fn try_herk(&mut self, alpha : f64, a : &NDData<f64>, uplo : Uplo, trans : Transpose, beta : f64) -> Result<(), Error> {
+        self.try_syrk(alpha, a, uplo, trans, beta)
+    }
 
-        let trans = get_mv_trans(a.shape()[0], a.shape()[1], x.shape()[0], self.shape()[0]);
-        if trans == 0 {
-            panic!("Blas::gemv(): a dimensions ({:?} do not match y ({:?}) and x ({:?})", a.shape(), self.shape(), x.shape());
+    fn try_trmm(&mut self, alpha : f64, a : &NDData<f64>, side : Side, uplo : Uplo, trans : Transpose, diag : Diag) -> Result<(), Error> {
+        check_side(self, a, side)?;
+        match get_matrix(self) {
+            Some((cpos, false, ldc)) => {
+                let mut a_copy = None;
+                let (a, apos, lda) = get_row_major(a, &mut a_copy);
+                unsafe {
+                    cblas_dtrmm(CBLAS_ROW_MAJOR, side.to_cblas(), uplo.to_cblas(), trans.to_cblas(), diag.to_cblas(), self.shape()[0] as isize, self.shape()[1] as isize, alpha, a.get_data().as_ptr().offset(apos as isize), lda, self.get_data_mut().as_mut_ptr().offset(cpos as isize), ldc);
+                }
+            },
+            _ => {
+                // Compute on a row-major copy of c
+                let mut c = NDArray::<f64>::copy(self);
+                c.try_trmm(alpha, a, side, uplo, trans, diag)?;
+                self.try_assign(&c)?;
+            }
         }
+        Ok(())
+    }
 
-        unsafe {
-            cblas_cgemv(CBLAS_ROW_MAJOR, trans, a.shape()[0] as isize, a.shape()[1] as isize, &alpha, a.get_data().as_ptr(), a.strides()[0] as isize, x.get_data().as_ptr(), 1, &beta, self.get_data_mut().as_mut_ptr(), 1);
+    fn try_trsm(&mut self, alpha : f64, a : &NDData<f64>, side : Side, uplo : Uplo, trans : Transpose, diag : Diag) -> Result<(), Error> {
+        check_side(self, a, side)?;
+        match get_matrix(self) {
+            Some((cpos, false, ldc)) => {
+                let mut a_copy = None;
+                let (a, apos, lda) = get_row_major(a, &mut a_copy);
+                unsafe {
+                    cblas_dtrsm(CBLAS_ROW_MAJOR, side.to_cblas(), uplo.to_cblas(), trans.to_cblas(), diag.to_cblas(), self.shape()[0] as isize, self.shape()[1] as isize, alpha, a.get_data().as_ptr().offset(apos as isize), lda, self.get_data_mut().as_mut_ptr().offset(cpos as isize), ldc);
+                }
+            },
+            _ => {
+                // Compute on a row-major copy of c
+                let mut c = NDArray::<f64>::copy(self);
+                c.try_trsm(alpha, a, side, uplo, trans, diag)?;
+                self.try_assign(&c)?;
+            }
         }
+        Ok(())
     }
+}
 
-    #[allow(unused_assignments)]
-    fn gemm(&mut self, alpha : c32, a : &NDData<c32>, b : &NDData<c32>, beta : c32) {
-        if self.dim() != 2 || a.dim() != 2 || b.dim() != 2 {
-            panic!("Blas::gemm(): a, b or c is not in 2 dimension({} != 2 || {} != 2 || {} != 2)", a.dim(), b.dim(), self.dim());
+impl<R> Blas<c32> for R where R : NDDataMut<c32> {
+
+    fn asum(&self) -> c32 {
+        let (n, lines) = get_lines(self, true);
+        let mut asum = 0.0f32;
+        for (pos, inc) in lines {
+            asum += unsafe { cblas_scasum(n as isize, self.get_data().as_ptr().offset(pos as isize), inc.abs()) };
         }
+        return c32::new(asum, 0.0);
+    }
 
-        let (transa, transb, k) = get_mm_trans(a.shape()[0], a.shape()[1], b.shape()[0], b.shape()[1], self.shape()[0], self.shape()[1]);
-        if transa == 0 {
-            panic!("Blas::gemm(): a dimensions ({:?} do not match b ({:?}) and c ({:?})", a.shape(), b.shape(), self.shape());
+    fn nrm2(&self) -> c32 {
+        let (n, lines) = get_lines(self, true);
+        // Combine the norms of the lines with hypot so that large norms do not overflow
+        let mut norm = 0.0f32;
+        for &(pos, inc) in &lines {
+            let nrm2 = unsafe { cblas_scnrm2(n as isize, self.get_data().as_ptr().offset(pos as isize), inc.abs()) };
+            if lines.len() == 1 {
+                return c32::new(nrm2, 0.0);
+            }
+            norm = norm.hypot(nrm2);
         }
+        return c32::new(norm, 0.0);
+    }
 
-        unsafe {
-            cblas_cgemm(CBLAS_ROW_MAJOR, transa, transb, self.shape()[0] as isize, self.shape()[1] as isize, k as isize, &alpha, a.get_data().as_ptr(), a.strides()[0] as isize, b.get_data().as_ptr(), b.strides()[0] as isize, &beta, self.get_data_mut().as_mut_ptr(), self.strides()[0] as isize);
+    fn scal(&mut self, a : c32) {
+        let (n, lines) = get_lines(self, true);
+        for (pos, inc) in lines {
+            unsafe {
+                cblas_cscal(n as isize, &a, self.get_data_mut().as_mut_ptr().offset(pos as isize), inc.abs());
+            }
         }
     }
-}
 
-impl<R> Blas<c64> for R where R : NDDataMut<c64> {
+    fn try_axpy(&mut self, a : c32, x : &NDData<c32>) -> Result<(), Error> {
+        check_axpy(self, x)?;
 
-    fn asum(&self) -> c64 {
-        unsafe {
-            c64::new(cblas_dzasum(self.size() as isize, self.get_data().as_ptr(), 1),0.0)
+        let flatten = self.is_contiguous() && x.is_contiguous();
+        let (n, ylines) = get_lines(self, flatten);
+        let (_, xlines) = get_lines(x, flatten);
+        for (&(ypos, incy), &(xpos, incx)) in ylines.iter().zip(xlines.iter()) {
+            unsafe {
+                cblas_caxpy(n as isize, &a, x.get_data().as_ptr().offset(xpos as isize), incx, self.get_data_mut().as_mut_ptr().offset(ypos as isize), incy);
+            }
         }
+        Ok(())
     }
 
-    fn nrm2(&self) -> c64 {
+    fn try_dot(&self, x : &NDData<c32>) -> Result<c32, Error> {
+        check_dot(self, x)?;
+        let (xpos, incx) = get_vector(x);
+        let (ypos, incy) = get_vector(self);
+        let mut dotu = c32::new(0.0,0.0);
         unsafe {
-            c64::new(cblas_dznrm2(self.size() as isize, self.get_data().as_ptr(), 1),0.0)
+            cblas_cdotu_sub(self.size() as isize, x.get_data().as_ptr().offset(xpos as isize), incx, self.get_data().as_ptr().offset(ypos as isize), incy, &mut dotu);
         }
+        return Ok(dotu);
     }
 
-    fn scal(&mut self, a : c64) {
+    fn try_dotc(&self, x : &NDData<c32>) -> Result<c32, Error> {
+        check_dot(self, x)?;
+        let (xpos, incx) = get_vector(x);
+        let (ypos, incy) = get_vector(self);
+        let mut dotc = c32::new(0.0,0.0);
         unsafe {
-            cblas_zscal(self.size() as isize, &a, self.get_data_mut().as_mut_ptr(), 1);
+            cblas_cdotc_sub(self.size() as isize, self.get_data().as_ptr().offset(ypos as isize), incy, x.get_data().as_ptr().offset(xpos as isize), incx, &mut dotc);
         }
+        return Ok(dotc);
     }
 
-    fn axpy(&mut self, a : c64, x : &NDData<c64>) {
-        if self.dim() != x.dim() {
-            panic!("Blas::axpy(): x is not of the same dimension ({} != {})",  x.dim(), self.dim());
+    fn try_swap(&mut self, x : &mut NDDataMut<c32>) -> Result<(), Error> {
+        check_shape(self.shape(), x.shape())?;
+        let (n, lines) = get_line_pairs(self, x);
+        for ((ypos, incy), (xpos, incx)) in lines {
+            unsafe {
+                cblas_cswap(n as isize, x.get_data_mut().as_mut_ptr().offset(xpos as isize), incx, self.get_data_mut().as_mut_ptr().offset(ypos as isize), incy);
+            }
         }
+        Ok(())
+    }
 
-        if self.shape() != x.shape() {
-            panic!("Blas::axpy(): x is not of the same shape ({:?} != {:?})",  x.shape(), self.shape());
+    fn try_copy(&mut self, x : &NDData<c32>) -> Result<(), Error> {
+        check_axpy(self, x)?;
+        let (n, lines) = get_line_pairs(self, x);
+        for ((ypos, incy), (xpos, incx)) in lines {
+            unsafe {
+                cblas_ccopy(n as isize, x.get_data().as_ptr().offset(xpos as isize), incx, self.get_data_mut().as_mut_ptr().offset(ypos as isize), incy);
+            }
         }
+        Ok(())
+    }
 
-        unsafe {
-            cblas_zaxpy(self.size() as isize, &a, x.get_data().as_ptr(), 1, self.get_data_mut().as_mut_ptr(), 1);
+    fn try_rot(&mut self, y : &mut NDDataMut<c32>, c : c32, s : c32) -> Result<(), Error> {
+        check_shape(self.shape(), y.shape())?;
+        // cblas only provides rotations with real c and s for complex types
+        let (n, lines) = get_line_pairs(self, y);
+        let (cc, sc) = (c.conj(), s.conj());
+        for ((xpos, incx), (ypos, incy)) in lines {
+            for i in 0..n {
+                let (xi, yi) = (get_line_element(xpos, n, incx, i), get_line_element(ypos, n, incy, i));
+                let (xv, yv) = (self.get_data()[xi], y.get_data()[yi]);
+                self.get_data_mut()[xi] = c * xv + s * yv;
+                y.get_data_mut()[yi] = cc * yv - sc * xv;
+            }
         }
+        Ok(())
     }
 
-    fn dot(&self, x : &NDData<c64>) -> c64 {
-        if self.dim() != 1 || x.dim() != 1{
-            panic!("Blas::dot(): x or self is not in 1 dimension ({} != 1 || {} != 1)",  x.dim(), self.dim());
-        }
-        let mut dotu = c64::new(0.0,0.0);
+    fn try_rotg(&mut self) -> Result<(c32, c32), Error> {
+        check_rotg(self)?;
+        let (mut a, b) = (*self.idx(&[0]), *self.idx(&[1]));
+        let (mut c, mut s) = (0.0, c32::new(0.0, 0.0));
         unsafe {
-            cblas_zdotu_sub(self.size() as isize, x.get_data().as_ptr(), 1, self.get_data().as_ptr(), 1, &mut dotu);
+            cblas_crotg(&mut a, &b, &mut c, &mut s);
         }
-        return dotu;
+        *self.idx_mut(&[0]) = a;
+        Ok((c32::new(c, 0.0), s))
+    }
+
+    fn try_rotm(&mut self, _y : &mut NDDataMut<c32>, _param : &[c32]) -> Result<(), Error> {
+        Err(Error::UnsupportedType("c32 in rotm".to_string()))
+    }
+
+    fn iamax(&self) -> Option<Vec<usize>> {
+        get_iamax(self, |n, x, inc| unsafe { cblas_icamax(n as isize, x, inc) }, |x| (x.re.abs() + x.im.abs()) as f64)
+    }
+
+    fn try_gemv(&mut self, alpha : c32, a : &NDData<c32>, x : &NDData<c32>, beta : c32) -> Result<(), Error> {
+        let trans = check_gemv(self, a, x)?;
+        self.try_gemv_trans(alpha, a, trans, x, beta)
     }
 
     #[allow(unused_assignments)]
-    fn gemv(&mut self, alpha : c64, a : &NDData<c64>, x : &NDData<c64>, beta : c64) {
-        if self.dim() != 1 || a.dim() != 2 {
-            panic!("Blas::gemv(): self is not in 1 dimension or a is not in 2 dimension({} != 1 || {} != 2)", self.dim(), x.dim());
+    fn try_gemv_trans(&mut self, alpha : c32, a : &NDData<c32>, trans : Transpose, x : &NDData<c32>, beta : c32) -> Result<(), Error> {
+        check_gemv_trans(self, a, trans, x)?;
+
+        let a_copy;
+        let (a, (apos, a_col_major, lda)) = match get_matrix(a) {
+            Some(layout) => (a, layout),
+            None => {
+                a_copy = NDArray::<c32>::copy(a);
+                (&a_copy as &NDData<c32>, get_matrix(&a_copy).unwrap())
+            }
+        };
+        let layout = if a_col_major { CBLAS_COL_MAJOR } else { CBLAS_ROW_MAJOR };
+        let (xpos, incx) = get_vector(x);
+        let (ypos, incy) = get_vector(self);
+
+        unsafe {
+            cblas_cgemv(layout, trans.to_cblas(), a.shape()[0] as isize, a.shape()[1] as isize, &alpha, a.get_data().as_ptr().offset(apos as isize), lda, x.get_data().as_ptr().offset(xpos as isize), incx, &beta, self.get_data_mut().as_mut_ptr().offset(ypos as isize), incy);
         }
+        Ok(())
+    }
 
-        let trans = get_mv_trans(a.shape()[0], a.shape()[1], x.shape()[0], self.shape()[0]);
-        if trans == 0 {
-            panic!("Blas::gemv(): a dimensions ({:?} do not match y ({:?}) and x ({:?})", a.shape(), self.shape(), x.shape());
+    fn try_ger(&mut self, alpha : c32, x : &NDData<c32>, y : &NDData<c32>) -> Result<(), Error> {
+        check_ger(self, x, y)?;
+        match get_matrix(self) {
+            Some((apos, a_col_major, lda)) => {
+                let layout = if a_col_major { CBLAS_COL_MAJOR } else { CBLAS_ROW_MAJOR };
+                let (xpos, incx) = get_vector(x);
+                let (ypos, incy) = get_vector(y);
+                unsafe {
+                    cblas_cgeru(layout, self.shape()[0] as isize, self.shape()[1] as isize, &alpha, x.get_data().as_ptr().offset(xpos as isize), incx, y.get_data().as_ptr().offset(ypos as isize), incy, self.get_data_mut().as_mut_ptr().offset(apos as isize), lda);
+                }
+            },
+            None => {
+                let mut a = NDArray::<c32>::copy(self);
+                a.try_ger(alpha, x, y)?;
+                self.try_assign(&a)?;
+            }
         }
+        Ok(())
+    }
 
-        unsafe {
-            cblas_zgemv(CBLAS_ROW_MAJOR, trans, a.shape()[0] as isize, a.shape()[1] as isize, &alpha, a.get_data().as_ptr(), a.strides()[0] as isize, x.get_data().as_ptr(), 1, &beta, self.get_data_mut().as_mut_ptr(), 1);
+    fn try_gerc(&mut self, alpha : c32, x : &NDData<c32>, y : &NDData<c32>) -> Result<(), Error> {
+        check_ger(self, x, y)?;
+        match get_matrix(self) {
+            Some((apos, a_col_major, lda)) => {
+                let layout = if a_col_major { CBLAS_COL_MAJOR } else { CBLAS_ROW_MAJOR };
+                let (xpos, incx) = get_vector(x);
+                let (ypos, incy) = get_vector(y);
+                unsafe {
+                    cblas_cgerc(layout, self.shape()[0] as isize, self.shape()[1] as isize, &alpha, x.get_data().as_ptr().offset(xpos as isize), incx, y.get_data().as_ptr().offset(ypos as isize), incy, self.get_data_mut().as_mut_ptr().offset(apos as isize), lda);
+                }
+            },
+            None => {
+                let mut a = NDArray::<c32>::copy(self);
+                a.try_gerc(alpha, x, y)?;
+                self.try_assign(&a)?;
+            }
         }
+        Ok(())
     }
 
-    #[allow(unused_assignments)]
-    fn gemm(&mut self, alpha : c64, a : &NDData<c64>, b : &NDData<c64>, beta : c64) {
-        if self.dim() != 2 || a.dim() != 2 || b.dim() != 2 {
-            panic!("Blas::gemm(): a, b or c is not in 2 dimension({} != 2 || {} != 2 || {} != 2)", a.dim(), b.dim(), self.dim());
+    fn try_symv(&mut self, _alpha : c32, _a : &NDData<c32>, _uplo : Uplo, _x : &NDData<c32>, _beta : c32) -> Result<(), Error> {
+        Err(Error::UnsupportedType("c32 in symv".to_string()))
+    }
+
+    fn try_hemv(&mut self, alpha : c32, a : &NDData<c32>, uplo : Uplo, x : &NDData<c32>, beta : c32) -> Result<(), Error> {
+        check_square_mv(a, x, self)?;
+        let mut a_copy = None;
+        let (a, (apos, a_col_major, lda)) = get_matrix_or_copy(a, &mut a_copy);
+        let layout = if a_col_major { CBLAS_COL_MAJOR } else { CBLAS_ROW_MAJOR };
+        let (xpos, incx) = get_vector(x);
+        let (ypos, incy) = get_vector(self);
+        unsafe {
+            cblas_chemv(layout, uplo.to_cblas(), a.shape()[0] as isize, &alpha, a.get_data().as_ptr().offset(apos as isize), lda, x.get_data().as_ptr().offset(xpos as isize), incx, &beta, self.get_data_mut().as_mut_ptr().offset(ypos as isize), incy);
         }
+        Ok(())
+    }
 
-        let (transa, transb, k) = get_mm_trans(a.shape()[0], a.shape()[1], b.shape()[0], b.shape()[1], self.shape()[0], self.shape()[1]);
-        if transa == 0 {
-            panic!("Blas::gemm(): a dimensions ({:?} do not match b ({:?}) and c ({:?})", a.shape(), b.shape(), self.shape());
+    fn try_trmv(&mut self, a : &NDData<c32>, uplo : Uplo, trans : Transpose, diag : Diag) -> Result<(), Error> {
+        check_square_mv(a, self, self)?;
+        let mut a_copy = None;
+        let (a, (apos, a_col_major, lda)) = get_matrix_or_copy(a, &mut a_copy);
+        let layout = if a_col_major { CBLAS_COL_MAJOR } else { CBLAS_ROW_MAJOR };
+        let (xpos, incx) = get_vector(self);
+        unsafe {
+            cblas_ctrmv(layout, uplo.to_cblas(), trans.to_cblas(), diag.to_cblas(), a.shape()[0] as isize, a.get_data().as_ptr().offset(apos as isize), lda, self.get_data_mut().as_mut_ptr().offset(xpos as isize), incx);
         }
+        Ok(())
+    }
 
+    fn try_trsv(&mut self, a : &NDData<c32>, uplo : Uplo, trans : Transpose, diag : Diag) -> Result<(), Error> {
+        check_square_mv(a, self, self)?;
+        let mut a_copy = None;
+        let (a, (apos, a_col_major, lda)) = get_matrix_or_copy(a, &mut a_copy);
+        let layout = if a_col_major { CBLAS_COL_MAJOR } else { CBLAS_ROW_MAJOR };
+        let (xpos, incx) = get_vector(self);
         unsafe {
-            cblas_zgemm(CBLAS_ROW_MAJOR, transa, transb, self.shape()[0] as isize, self.shape()[1] as isize, k as isize, &alpha, a.get_data().as_ptr(), a.strides()[0] as isize, b.get_data().as_ptr(), b.strides()[0] as isize, &beta, self.get_data_mut().as_mut_ptr(), self.strides()[0] as isize);
+            cblas_ctrsv(layout, uplo.to_cblas(), trans.to_cblas(), diag.to_cblas(), a.shape()[0] as isize, a.get_data().as_ptr().offset(apos as isize), lda, self.get_data_mut().as_mut_ptr().offset(xpos as isize), incx);
         }
+        Ok(())
     }
-}
 
-/*
-==================== AddAssign ====================
-*/
+    #[allow(unused_assignments)]
+    fn try_gemm(&mut self, alpha : c32, a : &NDData<c32>, b : &NDData<c32>, beta : c32) -> Result<(), Error> {
+        let (transa, transb, k) = check_gemm(self, a, b)?;
+
+        let a_copy;
+        let (a, (apos, a_col_major, lda)) = match get_matrix(a) {
+            Some(layout) => (a, layout),
+            None => {
+                a_copy = NDArray::<c32>::copy(a);
+                (&a_copy as &NDData<c32>, get_matrix(&a_copy).unwrap())
+            }
+        };
+        let b_copy;
+        let (b, (bpos, b_col_major, ldb)) = match get_matrix(b) {
+            Some(layout) => (b, layout),
+            None => {
+                b_copy = NDArray::<c32>::copy(b);
+                (&b_copy as &NDData<c32>, get_matrix(&b_copy).unwrap())
+            }
+        };
+
+        match get_matrix(self) {
+            Some((cpos, c_col_major, ldc)) => {
+                let layout = if c_col_major { CBLAS_COL_MAJOR } else { CBLAS_ROW_MAJOR };
+                let transa = if a_col_major != c_col_major { flip_trans(transa) } else { transa };
+                let transb = if b_col_major != c_col_major { flip_trans(transb) } else { transb };
+                unsafe {
+                    cblas_cgemm(layout, transa, transb, self.shape()[0] as isize, self.shape()[1] as isize, k as isize, &alpha, a.get_data().as_ptr().offset(apos as isize), lda, b.get_data().as_ptr().offset(bpos as isize), ldb, &beta, self.get_data_mut().as_mut_ptr().offset(cpos as isize), ldc);
+                }
+            },
+            None => {
+                let mut c = NDArray::<c32>::copy(self);
+                c.try_gemm(alpha, a, b, beta)?;
+                self.try_assign(&c)?;
+            }
+        }
+        Ok(())
+    }
 
-impl<'a, T : Clone + Display + From<f32>, R : NDData<T> + Sized> AddAssign<&'a R> for NDArray<T> where NDArray<T> : Blas<T> {
+    fn try_gemm_trans(&mut self, alpha : c32, a : &NDData<c32>, transa : Transpose, b : &NDData<c32>, transb : Transpose, beta : c32) -> Result<(), Error> {
+        let k = check_gemm_trans(self, a, transa, b, transb)?;
+        match get_matrix(self) {
+            Some((cpos, false, ldc)) => {
+                let (mut a_copy, mut b_copy) = (None, None);
+                let (a, apos, lda) = get_row_major(a, &mut a_copy);
+                let (b, bpos, ldb) = get_row_major(b, &mut b_copy);
+                unsafe {
+                    cblas_cgemm(CBLAS_ROW_MAJOR, transa.to_cblas(), transb.to_cblas(), self.shape()[0] as isize, self.shape()[1] as isize, k as isize, &alpha, a.get_data().as_ptr().offset(apos as isize), lda, b.get_data().as_ptr().offset(bpos as isize), ldb, &beta, self.get_data_mut().as_mut_ptr().offset(cpos as isize), ldc);
+                }
+            },
+            _ => {
+                // Compute on a row-major copy of c
+                let mut c = NDArray::<c32>::copy(self);
+                c.try_gemm_trans(alpha, a, transa, b, transb, beta)?;
+                self.try_assign(&c)?;
+            }
+        }
+        Ok(())
+    }
 
-    fn add_assign(&mut self, rhs: &'a R) {
-        self.axpy(T::from(1.0f32), rhs);
+    fn try_batched_gemm(&mut self, alpha : c32, a : &NDData<c32>, b : &NDData<c32>, beta : c32) -> Result<(), Error> {
+        let batch = check_batched_gemm(self, a, b)?;
+        for i in 0..batch {
+            let (ai, bi) = (get_batch_matrix(a, i), get_batch_matrix(b, i));
+            self.view_mut(&[NDRange::index(i), NDRange::all(), NDRange::all()]).try_gemm_trans(alpha, &ai, Transpose::NoTrans, &bi, Transpose::NoTrans, beta)?;
+        }
+        Ok(())
     }
-}
 
-impl<'a, 'b, T : Clone + Display + From<f32>, R : NDData<T> + Sized> AddAssign<&'a R> for NDSliceMut<'b, T> where NDSliceMut<'b, T> : Blas<T> {
+    fn try_symm(&mut self, alpha : c32, a : &NDData<c32>, side : Side, uplo : Uplo, b : &NDData<c32>, beta : c32) -> Result<(), Error> {
+        check_side(self, a, side)?;
+        check_axpy(self, b)?;
+        match get_matrix(self) {
+            Some((cpos, false, ldc)) => {
+                let (mut a_copy, mut b_copy) = (None, None);
+                let (a, apos, lda) = get_row_major(a, &mut a_copy);
+                let (b, bpos, ldb) = get_row_major(b, &mut b_copy);
+                unsafe {
+                    cblas_csymm(CBLAS_ROW_MAJOR, side.to_cblas(), uplo.to_cblas(), self.shape()[0] as isize, self.shape()[1] as isize, &alpha, a.get_data().as_ptr().offset(apos as isize), lda, b.get_data().as_ptr().offset(bpos as isize), ldb, &beta, self.get_data_mut().as_mut_ptr().offset(cpos as isize), ldc);
+                }
+            },
+            _ => {
+                // Compute on a row-major copy of c
+                let mut c = NDArray::<c32>::copy(self);
+                c.try_symm(alpha, a, side, uplo, b, beta)?;
+                self.try_assign(&c)?;
+            }
+        }
+        Ok(())
+    }
 
-    fn add_assign(&mut self, rhs: &'a R) {
-        self.axpy(T::from(1.0f32), rhs);
+    fn try_syrk(&mut self, alpha : c32, a : &NDData<c32>, uplo : Uplo, trans : Transpose, beta : c32) -> Result<(), Error> {
+        let k = check_rank_k(self, a, trans)?;
+        match get_matrix(self) {
+            Some((cpos, false, ldc)) => {
+                let mut a_copy = None;
+                let (a, apos, lda) = get_row_major(a, &mut a_copy);
+                let trans = if trans == Transpose::NoTrans { CBLAS_NO_TRANS } else { CBLAS_TRANS };
+                unsafe {
+                    cblas_csyrk(CBLAS_ROW_MAJOR, uplo.to_cblas(), trans, self.shape()[0] as isize, k as isize, &alpha, a.get_data().as_ptr().offset(apos as isize), lda, &beta, self.get_data_mut().as_mut_ptr().offset(cpos as isize), ldc);
+                }
+            },
+            _ => {
+                // Compute on a row-major copy of c
+                let mut c = NDArray::<c32>::copy(self);
+                c.try_syrk(alpha, a, uplo, trans, beta)?;
+                self.try_assign(&c)?;
+            }
+        }
+        Ok(())
     }
-}
 
-/*
-==================== Add ====================
-*/
+    fn try_syr2k(&mut self, alpha : c32, a : &NDData<c32>, b : &NDData<c32>, uplo : Uplo, trans : Transpose, beta : c32) -> Result<(), Error> {
+        let k = check_rank_k(self, a, trans)?;
+        check_axpy(a, b)?;
+        match get_matrix(self) {
+            Some((cpos, false, ldc)) => {
+                let (mut a_copy, mut b_copy) = (None, None);
+                let (a, apos, lda) = get_row_major(a, &mut a_copy);
+                let (b, bpos, ldb) = get_row_major(b, &mut b_copy);
+                let trans = if trans == Transpose::NoTrans { CBLAS_NO_TRANS } else { CBLAS_TRANS };
+                unsafe {
+                    cblas_csyr2k(CBLAS_ROW_MAJOR, uplo.to_cblas(), trans, self.shape()[0] as isize, k as isize, &alpha, a.get_data().as_ptr().offset(apos as isize), lda, b.get_data().as_ptr().offset(bpos as isize), ldb, &beta, self.get_data_mut().as_mut_ptr().offset(cpos as isize), ldc);
+                }
+            },
+            _ => {
+                // Compute on a row-major copy of c
+                let mut c = NDArray::<c32>::copy(self);
+                c.try_syr2k(alpha, a, b, uplo, trans, beta)?;
+                self.try_assign(&c)?;
+            }
+        }
+        Ok(())
+    }
 
-impl<'a, 'b, T : Clone + Display + From<f32>, R : NDData<T> + Sized> Add<&'a R> for &'b NDArray<T> where NDArray<T> : Blas<T> {
-    type Output = NDArray<T>;
+    fn try_herk(&mut self, alpha : c32, a : &NDData<c32>, uplo : Uplo, trans : Transpose, beta : c32) -> Result<(), Error> {
+        let k = check_rank_k(self, a, trans)?;
+        if alpha.im != 0.0 || beta.im != 0.0 {
+            return Err(Error::InvalidArgument(format!("herk requires real alpha and beta, got {} and {}", alpha, beta)));
+        }
+        match get_matrix(self) {
+            Some((cpos, false, ldc)) => {
+                let mut a_copy = None;
+                let (a, apos, lda) = get_row_major(a, &mut a_copy);
+                let trans = if trans == Transpose::NoTrans { CBLAS_NO_TRANS } else { CBLAS_CONJ_TRANS };
+                unsafe {
+                    cblas_cherk(CBLAS_ROW_MAJOR, uplo.to_cblas(), trans, self.shape()[0] as isize, k as isize, alpha.re, a.get_data().as_ptr().offset(apos as isize), lda, beta.re, self.get_data_mut().as_mut_ptr().offset(cpos as isize), ldc);
+                }
+            },
+            _ => {
+                // Compute on a row-major copy of c
+                let mut c = NDArray::<c32>::copy(self);
+                c.try_herk(alpha, a, uplo, trans, beta)?;
+                self.try_assign(&c)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn try_trmm(&mut self, alpha : c32, a : &NDData<c32>, side : Side, uplo : Uplo, trans : Transpose, diag : Diag) -> Result<(), Error> {
+        check_side(self, a, side)?;
+        match get_matrix(self) {
+            Some((cpos, false, ldc)) => {
+                let mut a_copy = None;
+                let (a, apos, lda) = get_row_major(a, &mut a_copy);
+                unsafe {
+                    cblas_ctrmm(CBLAS_ROW_MAJOR, side.to_cblas(), uplo.to_cblas(), trans.to_cblas(), diag.to_cblas(), self.shape()[0] as isize, self.shape()[1] as isize, &alpha, a.get_data().as_ptr().offset(apos as isize), lda, self.get_data_mut().as_mut_ptr().offset(cpos as isize), ldc);
+                }
+            },
+            _ => {
+                // Compute on a row-major copy of c
+                let mut c = NDArray::<c32>::copy(self);
+                c.try_trmm(alpha, a, side, uplo, trans, diag)?;
+                self.try_assign(&c)?;
+            }
+        }
+        Ok(())
+    }
 
-    fn add(self, rhs: &'a R) -> NDArray<T> {
-        let mut res = NDArray::<T>::copy(self);
-        res += rhs;
-        return res;
+    fn try_trsm(&mut self, alpha : c32, a : &NDData<c32>, side : Side, uplo : Uplo, trans : Transpose, diag : Diag) -> Result<(), Error> {
+        check_side(self, a, side)?;
+        match get_matrix(self) {
+            Some((cpos, false, ldc)) => {
+                let mut a_copy = None;
+                let (a, apos, lda) = get_row_major(a, &mut a_copy);
+                unsafe {
+                    cblas_ctrsm(CBLAS_ROW_MAJOR, side.to_cblas(), uplo.to_cblas(), trans.to_cblas(), diag.to_cblas(), self.shape()[0] as isize, self.shape()[1] as isize, &alpha, a.get_data().as_ptr().offset(apos as isize), lda, self.get_data_mut().as_mut_ptr().offset(cpos as isize), ldc);
+                }
+            },
+            _ => {
+                // Compute on a row-major copy of c
+                let mut c = NDArray::<c32>::copy(self);
+                c.try_trsm(alpha, a, side, uplo, trans, diag)?;
+                self.try_assign(&c)?;
+            }
+        }
+        Ok(())
     }
 }
 
-impl<'a, 'b, 'c, T : Clone + Display + From<f32>, R : NDData<T> + Sized> Add<&'a R> for &'b NDSlice<'c, T> where NDArray<T> : Blas<T> {
-    type Output = NDArray<T>;
+impl<R> Blas<c64> for R where R : NDDataMut<c64> {
 
-    fn add(self, rhs: &'a R) -> NDArray<T> {
-        let mut res = NDArray::<T>::copy(self);
-        res += rhs;
-        return res;
+    fn asum(&self) -> c64 {
+        let (n, lines) = get_lines(self, true);
+        let mut asum = 0.0f64;
+        for (pos, inc) in lines {
+            asum += unsafe { cblas_dzasum(n as isize, self.get_data().as_ptr().offset(pos as isize), inc.abs()) };
+        }
+        return c64::new(asum, 0.0);
     }
-}
 
-impl<'a, 'b, 'c, T : Clone + Display + From<f32>, R : NDData<T> + Sized> Add<&'a R> for &'b NDSliceMut<'c, T> where NDArray<T> : Blas<T> {
-    type Output = NDArray<T>;
+    fn nrm2(&self) -> c64 {
+        let (n, lines) = get_lines(self, true);
+        // Combine the norms of the lines with hypot so that large norms do not overflow
+        let mut norm = 0.0f64;
+        for &(pos, inc) in &lines {
+            let nrm2 = unsafe { cblas_dznrm2(n as isize, self.get_data().as_ptr().offset(pos as isize), inc.abs()) };
+            if lines.len() == 1 {
+                return c64::new(nrm2, 0.0);
+            }
+            norm = norm.hypot(nrm2);
+        }
+        return c64::new(norm, 0.0);
+    }
 
-    fn add(self, rhs: &'a R) -> NDArray<T> {
-        let mut res = NDArray::<T>::copy(self);
-        res += rhs;
-        return res;
+    fn scal(&mut self, a : c64) {
+        let (n, lines) = get_lines(self, true);
+        for (pos, inc) in lines {
+            unsafe {
+                cblas_zscal(n as isize, &a, self.get_data_mut().as_mut_ptr().offset(pos as isize), inc.abs());
+            }
+        }
     }
-}
 
-/*
-==================== SubAssign ====================
-*/
+    fn try_axpy(&mut self, a : c64, x : &NDData<c64>) -> Result<(), Error> {
+        check_axpy(self, x)?;
 
-impl<'a, T : Clone + Display + From<f32>, R : NDData<T> + Sized> SubAssign<&'a R> for NDArray<T> where NDArray<T> : Blas<T> {
+        let flatten = self.is_contiguous() && x.is_contiguous();
+        let (n, ylines) = get_lines(self, flatten);
+        let (_, xlines) = get_lines(x, flatten);
+        for (&(ypos, incy), &(xpos, incx)) in ylines.iter().zip(xlines.iter()) {
+            unsafe {
+                cblas_zaxpy(n as isize, &a, x.get_data().as_ptr().offset(xpos as isize), incx, self.get_data_mut().as_mut_ptr().offset(ypos as isize), incy);
+            }
+        }
+        Ok(())
+    }
 
-    fn sub_assign(&mut self, rhs: &'a R) {
-        self.axpy(T::from(-1.0f32), rhs);
+    fn try_dot(&self, x : &NDData<c64>) -> Result<c64, Error> {
+        check_dot(self, x)?;
+        let (xpos, incx) = get_vector(x);
+        let (ypos, incy) = get_vector(self);
+        let mut dotu = c64::new(0.0,0.0);
+        unsafe {
+            cblas_zdotu_sub(self.size() as isize, x.get_data().as_ptr().offset(xpos as isize), incx, self.get_data().as_ptr().offset(ypos as isize), incy, &mut dotu);
+        }
+        return Ok(dotu);
     }
-}
 
-impl<'a, 'b, T : Clone + Display + From<f32>, R : NDData<T> + Sized> SubAssign<&'a R> for NDSliceMut<'b, T> where NDSliceMut<'b, T> : Blas<T> {
+    fn try_dotc(&self, x : &NDData<c64>) -> Result<c64, Error> {
+        check_dot(self, x)?;
+        let (xpos, incx) = get_vector(x);
+        let (ypos, incy) = get_vector(self);
+        let mut dotc = c64::new(0.0,0.0);
+        unsafe {
+            cblas_zdotc_sub(self.size() as isize, self.get_data().as_ptr().offset(ypos as isize), incy, x.get_data().as_ptr().offset(xpos as isize), incx, &mut dotc);
+        }
+        return Ok(dotc);
+    }
 
-    fn sub_assign(&mut self, rhs: &'a R) {
-        self.axpy(T::from(-1.0f32), rhs);
+    fn try_swap(&mut self, x : &mut NDDataMut<c64>) -> Result<(), Error> {
+        check_shape(self.shape(), x.shape())?;
+        let (n, lines) = get_line_pairs(self, x);
+        for ((ypos, incy), (xpos, incx)) in lines {
+            unsafe {
+                cblas_zswap(n as isize, x.get_data_mut().as_mut_ptr().offset(xpos as isize), incx, self.get_data_mut().as_mut_ptr().offset(ypos as isize), incy);
+            }
+        }
+        Ok(())
     }
-}
 
-/*
-==================== Sub ====================
-*/
+    fn try_copy(&mut self, x : &NDData<c64>) -> Result<(), Error> {
+        check_axpy(self, x)?;
+        let (n, lines) = get_line_pairs(self, x);
+        for ((ypos, incy), (xpos, incx)) in lines {
+            unsafe {
+                cblas_zcopy(n as isize, x.get_data().as_ptr().offset(xpos as isize), incx, self.get_data_mut().as_mut_ptr().offset(ypos as isize), incy);
+            }
+        }
+        Ok(())
+    }
 
-impl<'a, 'b, T : Clone + Display + From<f32>, R : NDData<T> + Sized> Sub<&'a R> for &'b NDArray<T> where NDArray<T> : Blas<T> {
-    type Output = NDArray<T>;
+    fn try_rot(&mut self, y : &mut NDDataMut<c64>, c : c64, s : c64) -> Result<(), Error> {
+        check_shape(self.shape(), y.shape())?;
+        // cblas only provides rotations with real c and s for complex types
+        let (n, lines) = get_line_pairs(self, y);
+        let (cc, sc) = (c.conj(), s.conj());
+        for ((xpos, incx), (ypos, incy)) in lines {
+            for i in 0..n {
+                let (xi, yi) = (get_line_element(xpos, n, incx, i), get_line_element(ypos, n, incy, i));
+                let (xv, yv) = (self.get_data()[xi], y.get_data()[yi]);
+                self.get_data_mut()[xi] = c * xv + s * yv;
+                y.get_data_mut()[yi] = cc * yv - sc * xv;
+            }
+        }
+        Ok(())
+    }
 
-    fn sub(self, rhs: &'a R) -> NDArray<T> {
-        let mut res = NDArray::<T>::copy(self);
-        res -= rhs;
-        return res;
+    fn try_rotg(&mut self) -> Result<(c64, c64), Error> {
+        check_rotg(self)?;
+        let (mut a, b) = (*self.idx(&[0]), *self.idx(&[1]));
+        let (mut c, mut s) = (0.0, c64::new(0.0, 0.0));
+        unsafe {
+            cblas_zrotg(&mut a, &b, &mut c, &mut s);
+        }
+        *self.idx_mut(&[0]) = a;
+        Ok((c64::new(c, 0.0), s))
     }
-}
 
-impl<'a, 'b, 'c, T : Clone + Display + From<f32>, R : NDData<T> + Sized> Sub<&'a R> for &'b NDSlice<'c, T> where NDArray<T> : Blas<T> {
-    type Output = NDArray<T>;
+    fn try_rotm(&mut self, _y : &mut NDDataMut<c64>, _param : &[c64]) -> Result<(), Error> {
+        Err(Error::UnsupportedType("c64 in rotm".to_string()))
+    }
 
-    fn sub(self, rhs: &'a R) -> NDArray<T> {
-        let mut res = NDArray::<T>::copy(self);
-        res -= rhs;
-        return res;
+    fn iamax(&self) -> Option<Vec<usize>> {
+        get_iamax(self, |n, x, inc| unsafe { cblas_izamax(n as isize, x, inc) }, |x| (x.re.abs() + x.im.abs()) as f64)
     }
-}
 
+    fn try_gemv(&mut self, alpha : c64, a : &NDData<c64>, x : &NDData<c64>, beta : c64) -> Result<(), Error> {
+        let trans = check_gemv(self, a, x)?;
+        self.try_gemv_trans(alpha, a, trans, x, beta)
+    }
 
-impl<'a, 'b, 'c, T : Clone + Display + From<f32>, R : NDData<T> + Sized> Sub<&'a R> for &'b NDSliceMut<'c, T> where NDArray<T> : Blas<T> {
-    type Output = NDArray<T>;
+    #[allow(unused_assignments)]
+    fn try_gemv_trans(&mut self, alpha : c64, a : &NDData<c64>, trans : Transpose, x : &NDData<c64>, beta : c64) -> Result<(), Error> {
+        check_gemv_trans(self, a, trans, x)?;
+
+        let a_copy;
+        let (a, (apos, a_col_major, lda)) = match get_matrix(a) {
+            Some(layout) => (a, layout),
+            None => {
+                a_copy = NDArray::<c64>::copy(a);
+                (&a_copy as &NDData<c64>, get_matrix(&a_copy).unwrap())
+            }
+        };
+        let layout = if a_col_major { CBLAS_COL_MAJOR } else { CBLAS_ROW_MAJOR };
+        let (xpos, incx) = get_vector(x);
+        let (ypos, incy) = get_vector(self);
 
-    fn sub(self, rhs: &'a R) -> NDArray<T> {
-        let mut res = NDArray::<T>::copy(self);
-        res -= rhs;
-        return res;
+        unsafe {
+            cblas_zgemv(layout, trans.to_cblas(), a.shape()[0] as isize, a.shape()[1] as isize, &alpha, a.get_data().as_ptr().offset(apos as isize), lda, x.get_data().as_ptr().offset(xpos as isize), incx, &beta, self.get_data_mut().as_mut_ptr().offset(ypos as isize), incy);
+        }
+        Ok(())
     }
-}
 
-/*
-==================== MulAssign ====================
-*/
-
-impl<'a, T : Clone + Display + From<f32>, I : NDData<T> + Sized> MulAssign<&'a I> for NDArray<T> where NDArray<T> : Blas<T> + NDData<T> {
+    fn try_ger(&mut self, alpha : c64, x : &NDData<c64>, y : &NDData<c64>) -> Result<(), Error> {
+        check_ger(self, x, y)?;
+        match get_matrix(self) {
+            Some((apos, a_col_major, lda)) => {
+                let layout = if a_col_major { CBLAS_COL_MAJOR } else { CBLAS_ROW_MAJOR };
+                let (xpos, incx) = get_vector(x);
+                let (ypos, incy) = get_vector(y);
+                unsafe {
+                    cblas_zgeru(layout, self.shape()[0] as isize, self.shape()[1] as isize, &alpha, x.get_data().as_ptr().offset(xpos as isize), incx, y.get_data().as_ptr().offset(ypos as isize), incy, self.get_data_mut().as_mut_ptr().offset(apos as isize), lda);
+                }
+            },
+            None => {
+                let mut a = NDArray::<c64>::copy(self);
+                a.try_ger(alpha, x, y)?;
+                self.try_assign(&a)?;
+            }
+        }
+        Ok(())
+    }
 
-    fn mul_assign(&mut self, rhs: &'a I) {
-        if self.dim() == 1 && rhs.dim() == 2 {
-            let x = NDArray::<T>::copy(self);
-            self.gemv(T::from(1.0), rhs, &x, T::from(0.0));
+    fn try_gerc(&mut self, alpha : c64, x : &NDData<c64>, y : &NDData<c64>) -> Result<(), Error> {
+        check_ger(self, x, y)?;
+        match get_matrix(self) {
+            Some((apos, a_col_major, lda)) => {
+                let layout = if a_col_major { CBLAS_COL_MAJOR } else { CBLAS_ROW_MAJOR };
+                let (xpos, incx) = get_vector(x);
+                let (ypos, incy) = get_vector(y);
+                unsafe {
+                    cblas_zgerc(layout, self.shape()[0] as isize, self.shape()[1] as isize, &alpha, x.get_data().as_ptr().offset(xpos as isize), incx, y.get_data().as_ptr().offset(ypos as isize), incy, self.get_data_mut().as_mut_ptr().offset(apos as isize), lda);
+                }
+            },
+            None => {
+                let mut a = NDArray::<c64>::copy(self);
+                a.try_gerc(alpha, x, y)?;
+                self.try_assign(&a)?;
+            }
         }
-        else if self.dim() == 2 && rhs.dim() == 2 {
-            let a = NDArray::<T>::copy(self);
-            self.gemm(T::from(1.0), &a, rhs, T::from(0.0));
+        Ok(())
+    }
+
+    fn try_symv(&mut self, _alpha : c64, _a : &NDData<c64>, _uplo : Uplo, _x : &NDData<c64>, _beta : c64) -> Result<(), Error> {
+        Err(Error::UnsupportedType("c64 in symv".to_string()))
+    }
+
+    fn try_hemv(&mut self, alpha : c64, a : &NDData<c64>, uplo : Uplo, x : &NDData<c64>, beta : c64) -> Result<(), Error> {
+        check_square_mv(a, x, self)?;
+        let mut a_copy = None;
+        let (a, (apos, a_col_major, lda)) = get_matrix_or_copy(a, &mut a_copy);
+        let layout = if a_col_major { CBLAS_COL_MAJOR } else { CBLAS_ROW_MAJOR };
+        let (xpos, incx) = get_vector(x);
+        let (ypos, incy) = get_vector(self);
+        unsafe {
+            cblas_zhemv(layout, uplo.to_cblas(), a.shape()[0] as isize, &alpha, a.get_data().as_ptr().offset(apos as isize), lda, x.get_data().as_ptr().offset(xpos as isize), incx, &beta, self.get_data_mut().as_mut_ptr().offset(ypos as isize), incy);
         }
-        else {
-            panic!("MulAssign could not find a blas operation between NDData of dimension {} and {}", self.dim(), rhs.dim());
+        Ok(())
+    }
+
+    fn try_trmv(&mut self, a : &NDData<c64>, uplo : Uplo, trans : Transpose, diag : Diag) -> Result<(), Error> {
+        check_square_mv(a, self, self)?;
+        let mut a_copy = None;
+        let (a, (apos, a_col_major, lda)) = get_matrix_or_copy(a, &mut a_copy);
+        let layout = if a_col_major { CBLAS_COL_MAJOR } else { CBLAS_ROW_MAJOR };
+        let (xpos, incx) = get_vector(self);
+        unsafe {
+            cblas_ztrmv(layout, uplo.to_cblas(), trans.to_cblas(), diag.to_cblas(), a.shape()[0] as isize, a.get_data().as_ptr().offset(apos as isize), lda, self.get_data_mut().as_mut_ptr().offset(xpos as isize), incx);
         }
+        Ok(())
     }
-}
 
-impl<'a, 'b, T : Clone + Display + From<f32>, I : NDData<T> + Sized> MulAssign<&'b I> for NDSliceMut<'a, T> where NDSliceMut<'a, T> : Blas<T> + NDData<T> {
+    fn try_trsv(&mut self, a : &NDData<c64>, uplo : Uplo, trans : Transpose, diag : Diag) -> Result<(), Error> {
+        check_square_mv(a, self, self)?;
+        let mut a_copy = None;
+        let (a, (apos, a_col_major, lda)) = get_matrix_or_copy(a, &mut a_copy);
+        let layout = if a_col_major { CBLAS_COL_MAJOR } else { CBLAS_ROW_MAJOR };
+        let (xpos, incx) = get_vector(self);
+        unsafe {
+            cblas_ztrsv(layout, uplo.to_cblas(), trans.to_cblas(), diag.to_cblas(), a.shape()[0] as isize, a.get_data().as_ptr().offset(apos as isize), lda, self.get_data_mut().as_mut_ptr().offset(xpos as isize), incx);
+        }
+        Ok(())
+    }
 
-    fn mul_assign(&mut self, rhs: &'b I) {
-        if self.dim() == 1 && rhs.dim() == 2 {
-            let x = NDArray::<T>::copy(self);
-            self.gemv(T::from(1.0), rhs, &x, T::from(0.0));
+    #[allow(unused_assignments)]
+    fn try_gemm(&mut self, alpha : c64, a : &NDData<c64>, b : &NDData<c64>, beta : c64) -> Result<(), Error> {
+        let (transa, transb, k) = check_gemm(self, a, b)?;
+
+        let a_copy;
+        let (a, (apos, a_col_major, lda)) = match get_matrix(a) {
+            Some(layout) => (a, layout),
+            None => {
+                a_copy = NDArray::<c64>::copy(a);
+                (&a_copy as &NDData<c64>, get_matrix(&a_copy).unwrap())
+            }
+        };
+        let b_copy;
+        let (b, (bpos, b_col_major, ldb)) = match get_matrix(b) {
+            Some(layout) => (b, layout),
+            None => {
+                b_copy = NDArray::<c64>::copy(b);
+                (&b_copy as &NDData<c64>, get_matrix(&b_copy).unwrap())
+            }
+        };
+
+        match get_matrix(self) {
+            Some((cpos, c_col_major, ldc)) => {
+                let layout = if c_col_major { CBLAS_COL_MAJOR } else { CBLAS_ROW_MAJOR };
+                let transa = if a_col_major != c_col_major { flip_trans(transa) } else { transa };
+                let transb = if b_col_major != c_col_major { flip_trans(transb) } else { transb };
+                unsafe {
+                    cblas_zgemm(layout, transa, transb, self.shape()[0] as isize, self.shape()[1] as isize, k as isize, &alpha, a.get_data().as_ptr().offset(apos as isize), lda, b.get_data().as_ptr().offset(bpos as isize), ldb, &beta, self.get_data_mut().as_mut_ptr().offset(cpos as isize), ldc);
+                }
+            },
+            None => {
+                let mut c = NDArray::<c64>::copy(self);
+                c.try_gemm(alpha, a, b, beta)?;
+                self.try_assign(&c)?;
+            }
         }
-        else if self.dim() == 2 && rhs.dim() == 2 {
-            let a = NDArray::<T>::copy(self);
-            self.gemm(T::from(1.0), &a, rhs, T::from(0.0));
+        Ok(())
+    }
+
+    fn try_gemm_trans(&mut self, alpha : c64, a : &NDData<c64>, transa : Transpose, b : &NDData<c64>, transb : Transpose, beta : c64) -> Result<(), Error> {
+        let k = check_gemm_trans(self, a, transa, b, transb)?;
+        match get_matrix(self) {
+            Some((cpos, false, ldc)) => {
+                let (mut a_copy, mut b_copy) = (None, None);
+                let (a, apos, lda) = get_row_major(a, &mut a_copy);
+                let (b, bpos, ldb) = get_row_major(b, &mut b_copy);
+                unsafe {
+                    cblas_zgemm(CBLAS_ROW_MAJOR, transa.to_cblas(), transb.to_cblas(), self.shape()[0] as isize, self.shape()[1] as isize, k as isize, &alpha, a.get_data().as_ptr().offset(apos as isize), lda, b.get_data().as_ptr().offset(bpos as isize), ldb, &beta, self.get_data_mut().as_mut_ptr().offset(cpos as isize), ldc);
+                }
+            },
+            _ => {
+                // Compute on a row-major copy of c
+                let mut c = NDArray::<c64>::copy(self);
+                c.try_gemm_trans(alpha, a, transa, b, transb, beta)?;
+                self.try_assign(&c)?;
+            }
         }
-        else {
-            panic!("MulAssign could not find a blas operation between NDData of dimension {} and {}", self.dim(), rhs.dim());
+        Ok(())
+    }
+
+    fn try_batched_gemm(&mut self, alpha : c64, a : &NDData<c64>, b : &NDData<c64>, beta : c64) -> Result<(), Error> {
+        let batch = check_batched_gemm(self, a, b)?;
+        for i in 0..batch {
+            let (ai, bi) = (get_batch_matrix(a, i), get_batch_matrix(b, i));
+            self.view_mut(&[NDRange::index(i), NDRange::all(), NDRange::all()]).try_gemm_trans(alpha, &ai, Transpose::NoTrans, &bi, Transpose::NoTrans, beta)?;
         }
+        Ok(())
     }
-}
 
-/*
-==================== Mul ====================
-*/
+    fn try_symm(&mut self, alpha : c64, a : &NDData<c64>, side : Side, uplo : Uplo, b : &NDData<c64>, beta : c64) -> Result<(), Error> {
+        check_side(self, a, side)?;
+        check_axpy(self, b)?;
+        match get_matrix(self) {
+            Some((cpos, false, ldc)) => {
+                let (mut a_copy, mut b_copy) = (None, None);
+                let (a, apos, lda) = get_row_major(a, &mut a_copy);
+                let (b, bpos, ldb) = get_row_major(b, &mut b_copy);
+                unsafe {
+                    cblas_zsymm(CBLAS_ROW_MAJOR, side.to_cblas(), uplo.to_cblas(), self.shape()[0] as isize, self.shape()[1] as isize, &alpha, a.get_data().as_ptr().offset(apos as isize), lda, b.get_data().as_ptr().offset(bpos as isize), ldb, &beta, self.get_data_mut().as_mut_ptr().offset(cpos as isize), ldc);
+                }
+            },
+            _ => {
+                // Compute on a row-major copy of c
+                let mut c = NDArray::<c64>::copy(self);
+                c.try_symm(alpha, a, side, uplo, b, beta)?;
+                self.try_assign(&c)?;
+            }
+        }
+        Ok(())
+    }
 
-impl<'a, 'b, T : Clone + Display + From<f32>, R : NDData<T> + Sized> Mul<&'a R> for &'b NDArray<T> where NDArray<T> : Blas<T> {
-    type Output = NDArray<T>;
+    fn try_syrk(&mut self, alpha : c64, a : &NDData<c64>, uplo : Uplo, trans : Transpose, beta : c64) -> Result<(), Error> {
+        let k = check_rank_k(self, a, trans)?;
+        match get_matrix(self) {
+            Some((cpos, false, ldc)) => {
+                let mut a_copy = None;
+                let (a, apos, lda) = get_row_major(a, &mut a_copy);
+                let trans = if trans == Transpose::NoTrans { CBLAS_NO_TRANS } else { CBLAS_TRANS };
+                unsafe {
+                    cblas_zsyrk(CBLAS_ROW_MAJOR, uplo.to_cblas(), trans, self.shape()[0] as isize, k as isize, &alpha, a.get_data().as_ptr().offset(apos as isize), lda, &beta, self.get_data_mut().as_mut_ptr().offset(cpos as isize), ldc);
+                }
+            },
+            _ => {
+                // Compute on a row-major copy of c
+                let mut c = NDArray::<c64>::copy(self);
+                c.try_syrk(alpha, a, uplo, trans, beta)?;
+                self.try_assign(&c)?;
+            }
+        }
+        Ok(())
+    }
 
-    fn mul(self, rhs: &'a R) -> NDArray<T> {
-        let mut res = NDArray::<T>::copy(self);
-        res *= rhs;
-        return res;
+    fn try_syr2k(&mut self, alpha : c64, a : &NDData<c64>, b : &NDData<c64>, uplo : Uplo, trans : Transpose, beta : c64) -> Result<(), Error> {
+        let k = check_rank_k(self, a, trans)?;
+        check_axpy(a, b)?;
+        match get_matrix(self) {
+            Some((cpos, false, ldc)) => {
+                let (mut a_copy, mut b_copy) = (None, None);
+                let (a, apos, lda) = get_row_major(a, &mut a_copy);
+                let (b, bpos, ldb) = get_row_major(b, &mut b_copy);
+                let trans = if trans == Transpose::NoTrans { CBLAS_NO_TRANS } else { CBLAS_TRANS };
+                unsafe {
+                    cblas_zsyr2k(CBLAS_ROW_MAJOR, uplo.to_cblas(), trans, self.shape()[0] as isize, k as isize, &alpha, a.get_data().as_ptr().offset(apos as isize), lda, b.get_data().as_ptr().offset(bpos as isize), ldb, &beta, self.get_data_mut().as_mut_ptr().offset(cpos as isize), ldc);
+                }
+            },
+            _ => {
+                // Compute on a row-major copy of c
+                let mut c = NDArray::<c64>::copy(self);
+                c.try_syr2k(alpha, a, b, uplo, trans, beta)?;
+                self.try_assign(&c)?;
+            }
+        }
+        Ok(())
     }
-}
 
-impl<'a, 'b, 'c, T : Clone + Display + From<f32>, R : NDData<T> + Sized> Mul<&'a R> for &'b NDSlice<'c, T> where NDArray<T> : Blas<T> {
-    type Output = NDArray<T>;
+    fn try_herk(&mut self, alpha : c64, a : &NDData<c64>, uplo : Uplo, trans : Transpose, beta : c64) -> Result<(), Error> {
+        let k = check_rank_k(self, a, trans)?;
+        if alpha.im != 0.0 || beta.im != 0.0 {
+            return Err(Error::InvalidArgument(format!("herk requires real alpha and beta, got {} and {}", alpha, beta)));
+        }
+        match get_matrix(self) {
+            Some((cpos, false, ldc)) => {
+                let mut a_copy = None;
+                let (a, apos, lda) = get_row_major(a, &mut a_copy);
+                let trans = if trans == Transpose::NoTrans { CBLAS_NO_TRANS } else { CBLAS_CONJ_TRANS };
+                unsafe {
+                    cblas_zherk(CBLAS_ROW_MAJOR, uplo.to_cblas(), trans, self.shape()[0] as isize, k as isize, alpha.re, a.get_data().as_ptr().offset(apos as isize), lda, beta.re, self.get_data_mut().as_mut_ptr().offset(cpos as isize), ldc);
+                }
+            },
+            _ => {
+                // Compute on a row-major copy of c
+                let mut c = NDArray::<c64>::copy(self);
+                c.try_herk(alpha, a, uplo, trans, beta)?;
+                self.try_assign(&c)?;
+            }
+        }
+        Ok(())
+    }
 
-    fn mul(self, rhs: &'a R) -> NDArray<T> {
-        let mut res = NDArray::<T>::copy(self);
-        res *= rhs;
-        return res;
+    fn try_trmm(&mut self, alpha : c64, a : &NDData<c64>, side : Side, uplo : Uplo, trans : Transpose, diag : Diag) -> Result<(), Error> {
+        check_side(self, a, side)?;
+        match get_matrix(self) {
+            Some((cpos, false, ldc)) => {
+                let mut a_copy = None;
+                let (a, apos, lda) = get_row_major(a, &mut a_copy);
+                unsafe {
+                    cblas_ztrmm(CBLAS_ROW_MAJOR, side.to_cblas(), uplo.to_cblas(), trans.to_cblas(), diag.to_cblas(), self.shape()[0] as isize, self.shape()[1] as isize, &alpha, a.get_data().as_ptr().offset(apos as isize), lda, self.get_data_mut().as_mut_ptr().offset(cpos as isize), ldc);
+                }
+            },
+            _ => {
+                // Compute on a row-major copy of c
+                let mut c = NDArray::<c64>::copy(self);
+                c.try_trmm(alpha, a, side, uplo, trans, diag)?;
+                self.try_assign(&c)?;
+            }
+        }
+        Ok(())
     }
-}
-impl<'a, 'b, 'c, T : Clone + Display + From<f32>, R : NDData<T> + Sized> Mul<&'a R> for &'b NDSliceMut<'c, T> where NDArray<T> : Blas<T> {
-    type Output = NDArray<T>;
 
-    fn mul(self, rhs: &'a R) -> NDArray<T> {
-        let mut res = NDArray::<T>::copy(self);
-        res *= rhs;
-        return res;
+    fn try_trsm(&mut self, alpha : c64, a : &NDData<c64>, side : Side, uplo : Uplo, trans : Transpose, diag : Diag) -> Result<(), Error> {
+        check_side(self, a, side)?;
+        match get_matrix(self) {
+            Some((cpos, false, ldc)) => {
+                let mut a_copy = None;
+                let (a, apos, lda) = get_row_major(a, &mut a_copy);
+                unsafe {
+                    cblas_ztrsm(CBLAS_ROW_MAJOR, side.to_cblas(), uplo.to_cblas(), trans.to_cblas(), diag.to_cblas(), self.shape()[0] as isize, self.shape()[1] as isize, &alpha, a.get_data().as_ptr().offset(apos as isize), lda, self.get_data_mut().as_mut_ptr().offset(cpos as isize), ldc);
+                }
+            },
+            _ => {
+                // Compute on a row-major copy of c
+                let mut c = NDArray::<c64>::copy(self);
+                c.try_trsm(alpha, a, side, uplo, trans, diag)?;
+                self.try_assign(&c)?;
+            }
+        }
+        Ok(())
     }
 }
@@ -0,0 +1,97 @@
+use std::error;
+use std::fmt;
+use std::io;
+
+/// Error type returned by the fallible operations of RDS.
+#[derive(Debug)]
+pub enum Error {
+    /// An I/O error occurred while opening, reading or writing a file.
+    Io(io::Error),
+    /// A value or a string could not be parsed. The message describes the value and its location
+    /// while source is the underlying parsing or decoding error, when there is one.
+    Parse {
+        message : String,
+        source : Option<Box<error::Error + Send + Sync>>,
+    },
+    /// The content of a file does not follow the expected format.
+    Format(String),
+    /// Some data does not have the expected shape.
+    ShapeMismatch {
+        expected : Vec<usize>,
+        found : Vec<usize>,
+    },
+    /// Some data or an index does not have the expected number of dimensions.
+    DimensionMismatch {
+        expected : usize,
+        found : usize,
+    },
+    /// An index is outside of the valid range of axis, whose length is len.
+    IndexOutOfBounds {
+        axis : usize,
+        index : usize,
+        len : usize,
+    },
+    /// An axis is greater than the dimension of the data.
+    InvalidAxis {
+        axis : usize,
+        dim : usize,
+    },
+    /// A range along axis is empty: start is not before end.
+    EmptyRange {
+        axis : usize,
+        start : usize,
+        end : usize,
+    },
+    /// A data type is not supported by the operation or the file format.
+    UnsupportedType(String),
+    /// A scalar argument is outside of the values accepted by the operation.
+    InvalidArgument(String),
+    /// A LAPACK routine failed. info is its return code: negative when an argument is invalid,
+    /// positive on a numerical failure such as a singular or not positive definite matrix.
+    Lapack {
+        routine : String,
+        info : i32,
+    },
+    /// A compute backend operation failed. status is the status code returned by the backend API
+    /// when there is one, for example an OpenCL error code.
+    Backend {
+        message : String,
+        status : Option<i32>,
+    },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f : &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Error::Io(ref e) => write!(f, "I/O error: {}", e),
+            Error::Parse { ref message, .. } => write!(f, "Parse error: {}", message),
+            Error::Format(ref s) => write!(f, "Format error: {}", s),
+            Error::ShapeMismatch { ref expected, ref found } => write!(f, "Shape mismatch: expected {:?}, found {:?}", expected, found),
+            Error::DimensionMismatch { expected, found } => write!(f, "Dimension mismatch: expected {}, found {}", expected, found),
+            Error::IndexOutOfBounds { axis, index, len } => write!(f, "Index {} is out of bounds for axis {} of length {}", index, axis, len),
+            Error::InvalidAxis { axis, dim } => write!(f, "Axis {} is invalid for data of dimension {}", axis, dim),
+            Error::EmptyRange { axis, start, end } => write!(f, "Range {}..{} is empty for axis {}", start, end, axis),
+            Error::UnsupportedType(ref s) => write!(f, "Unsupported type: {}", s),
+            Error::InvalidArgument(ref s) => write!(f, "Invalid argument: {}", s),
+            Error::Lapack { ref routine, info } => write!(f, "LAPACK routine {} failed with info {}", routine, info),
+            Error::Backend { ref message, status : Some(status) } => write!(f, "Backend error: {} (status {})", message, status),
+            Error::Backend { ref message, status : None } => write!(f, "Backend error: {}", message),
+        }
+    }
+}
+
+impl error::Error for Error {
+    fn source(&self) -> Option<&(error::Error + 'static)> {
+        match *self {
+            Error::Io(ref e) => Some(e),
+            Error::Parse { source : Some(ref e), .. } => Some(&**e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(e : io::Error) -> Error {
+        Error::Io(e)
+    }
+}
@@ -8,12 +8,16 @@ pub mod types;
 /// using the `NDSliceable` and `NDSliceableMut` trait which returns `NDSlice` and `NDSliceMut` 
 /// respectively. Those slice can also be sliced into sub slice.
 ///
+/// Arbitrary strided views, selecting a range of indices with an optional (possibly negative) 
+/// step in every dimension, can be borrowed using the `NDViewable` and `NDViewableMut` traits 
+/// which return `NDView` and `NDViewMut` respectively.
+///
 /// Those N-dimensional structures implement two basic traits, `NDData` and `NDDataMut`, which give 
 /// access to their data.
 /// 
 /// The `NDIndex` trait provides helper functions to manipulate N-dimensional indexes.
 ///
-/// The CSV and Numpy sub module allow to load and store N-dimensional arrays.
+/// The CSV, Numpy, HDF5 and MATLAB sub module allow to load and store N-dimensional arrays.
 ///
 /// # Examples
 /// 
@@ -96,6 +100,167 @@ pub mod types;
 /// }
 /// ```
 ///
+/// ## NDView
+///
+/// Views select parts of the data which are not contiguous, without copying them:
+///
+/// ```
+/// use rds::array::{NDData, NDDataMut, NDArray, NDRange, NDViewable, NDViewableMut};
+///
+/// let mut array = NDArray::<f32>::from_slice(&[3, 3], &[0.0, 1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0]);
+///
+/// // The second column of the matrix
+/// let column = array.view(&[NDRange::all(), NDRange::index(1)]);
+/// assert!(column == NDArray::<f32>::from_slice(&[3], &[1.0, 4.0, 7.0]));
+///
+/// // Every other row in reverse order
+/// let rows = array.view(&[NDRange::all().step(-2)]);
+/// assert!(rows.shape() == &[2, 3]);
+/// assert!(rows[&[0, 0]] == 6.0 && rows[&[1, 0]] == 0.0);
+///
+/// // Mutable views can be assigned
+/// array.view_mut(&[NDRange::new(1, 3), NDRange::index(2)]).assign(&NDArray::<f32>::new(&[2], 0.0));
+/// assert!(array[&[1, 2]] == 0.0 && array[&[2, 2]] == 0.0);
+///
+/// // Axes can be permuted without copying, to_contiguous() copies the result in row-major order
+/// let cube = NDArray::<f32>::new(&[2, 3, 4], 1.0);
+/// assert!(cube.permute_axes(&[2, 0, 1]).shape() == &[4, 2, 3]);
+/// assert!(cube.swap_axes(0, 1).shape() == &[3, 2, 4]);
+/// let transposed = cube.transposed().to_contiguous();
+/// assert!(transposed.shape() == &[4, 3, 2] && transposed.is_contiguous());
+/// ```
+///
+/// ## Iterators
+///
+/// The elements of any NDData can be iterated in row-major or column-major order, with or without 
+/// their index, and sub-views can be iterated along any dimension:
+///
+/// ```
+/// use rds::array::{NDData, NDArray};
+/// use rds::array::iter::{NDIterable, NDIterableMut};
+///
+/// let mut matrix = NDArray::<u32>::from_slice(&[2, 3], &[1, 2, 3, 4, 5, 6]);
+/// assert!(matrix.iter_co().cloned().collect::<Vec<u32>>() == vec![1, 4, 2, 5, 3, 6]);
+///
+/// for (idx, x) in matrix.iter_mut().enumerate() {
+///     *x += idx as u32;
+/// }
+///
+/// // Sum of each column
+/// let sums : Vec<u32> = matrix.axis_iter(1).map(|column| column.iter().sum()).collect();
+/// assert!(sums == vec![8, 12, 16]);
+/// ```
+///
+/// ## Reductions
+///
+/// Sums, products, means and extrema can be computed along any dimension. With keep_dims the 
+/// result can be broadcasted against the data:
+///
+/// ```
+/// use rds::array::{NDData, NDArray};
+/// use rds::array::reduce::NDReducible;
+///
+/// let signals = NDArray::<f64>::from_slice(&[2, 4], &[1.0, 2.0, 3.0, 6.0, 0.0, ::std::f64::NAN, 4.0, 2.0]);
+///
+/// // Per channel statistics, ignoring the missing samples
+/// assert!(signals.nanmean(1, false) == NDArray::<f64>::from_slice(&[2], &[3.0, 2.0]));
+/// assert!(signals.nanargmax(1, false) == NDArray::<usize>::from_slice(&[2], &[3, 2]));
+///
+/// // Remove the mean of each channel
+/// let centered = &signals - &signals.nanmean(1, true);
+/// assert!(centered[&[0, 0]] == -2.0 && centered[&[1, 3]] == 0.0);
+/// ```
+///
+/// ## Concatenation
+///
+/// Arrays can be joined along an existing dimension with `concatenate` or along a new one with 
+/// `stack`, for example to assemble signals into a channels by samples matrix:
+///
+/// ```
+/// use rds::array::{NDData, NDArray};
+/// use rds::array::join::{concatenate, stack};
+///
+/// let channel1 = NDArray::<f32>::from_slice(&[3], &[1.0, 2.0, 3.0]);
+/// let channel2 = NDArray::<f32>::from_slice(&[3], &[4.0, 5.0, 6.0]);
+/// let channels = stack(&[&channel1, &channel2], 0);
+/// assert!(channels.shape() == &[2, 3]);
+/// let longer = concatenate(&[&channels, &channels], 1);
+/// assert!(longer.shape() == &[2, 6]);
+/// ```
+///
+/// ## Closures
+///
+/// Arbitrary functions can be applied element-wise with `map`, `map_inplace` and `zip_map`, the 
+/// result can be of a different type:
+///
+/// ```
+/// use rds::array::{NDData, NDArray};
+/// use rds::array::map::{NDMappable, NDMappableMut};
+///
+/// let mut array = NDArray::<f64>::from_slice(&[3], &[1.0, 10.0, 100.0]);
+/// array.map_inplace(|x| *x = x.log10());
+/// assert!(array.map(|&x| x as u8) == NDArray::<u8>::from_slice(&[3], &[0, 1, 2]));
+/// assert!(array.fold(0.0, |acc, &x| acc + x) == 3.0);
+/// ```
+///
+/// ## Masks and indexing
+///
+/// Element-wise comparisons produce boolean masks which can select or assign elements, while 
+/// `take` and `put` gather and scatter sub-arrays by index:
+///
+/// ```
+/// use rds::array::{NDData, NDArray};
+/// use rds::array::mask::{NDComparable, NDSelectable, NDSelectableMut};
+///
+/// let mut samples = NDArray::<f32>::from_slice(&[2, 3], &[0.1, 5.0, 0.3, 7.0, 0.2, 0.4]);
+/// assert!(samples.select(&samples.gt(1.0)) == NDArray::<f32>::from_slice(&[2], &[5.0, 7.0]));
+///
+/// let spikes = samples.gt(1.0);
+/// samples.masked_assign(&spikes, 0.0);
+/// assert!(samples.take(&[2, 0], 1) == NDArray::<f32>::from_slice(&[2, 2], &[0.3, 0.1, 0.4, 0.0]));
+/// ```
+///
+/// ## Printing
+///
+/// NDArray, slices and views implement `Display` and `Debug` with numpy like nested brackets and 
+/// aligned columns. The precision of the format is applied to every element and arrays of more 
+/// than 1000 elements are elided unless the alternate flag (`{:#}`) is used:
+///
+/// ```
+/// use rds::array::NDArray;
+///
+/// let array = NDArray::<f64>::from_slice(&[2, 2], &[1.0, -2.0, 0.5, 10.0]);
+/// assert!(format!("{:.1}", array) == "[[ 1.0, -2.0],\n [ 0.5, 10.0]]");
+/// ```
+///
+/// ## Arithmetic
+///
+/// The `+`, `-`, `*`, `/` and `%` operators work element-wise between references to any `NDData` 
+/// of any RDS type and broadcast their operands following the numpy rules. The right hand side can 
+/// also be a scalar and `-` negates an array. For `f32`, `f64`, `c32` and `c64`, additions, 
+/// subtractions and scaling are computed with Blas when possible:
+///
+/// ```
+/// use rds::array::{NDData, NDArray};
+///
+/// let matrix = NDArray::<f32>::from_slice(&[2, 3], &[1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+/// let bias = NDArray::<f32>::from_slice(&[3], &[1.0, 0.0, -1.0]);
+/// let scale = NDArray::<f32>::from_slice(&[2, 1], &[2.0, 0.5]);
+///
+/// // The bias row is added to every row and every row is scaled by its own factor
+/// let result = &(&matrix + &bias) * &scale;
+/// assert!(result == NDArray::<f32>::from_slice(&[2, 3], &[4.0, 4.0, 4.0, 2.5, 2.5, 2.5]));
+///
+/// // In place operations broadcast their right hand side
+/// let mut centered = NDArray::<f32>::copy(&matrix);
+/// centered -= &NDArray::<f32>::from_slice(&[3], &[2.5, 3.5, 4.5]);
+/// assert!(centered == NDArray::<f32>::from_slice(&[2, 3], &[-1.5, -1.5, -1.5, 1.5, 1.5, 1.5]));
+///
+/// // Integer arrays and scalar operands
+/// let counts = NDArray::<u16>::from_slice(&[4], &[7, 8, 9, 10]);
+/// assert!(&(&counts * 2) % 3 == NDArray::<u16>::from_slice(&[4], &[2, 1, 0, 2]));
+/// ```
+///
 /// ## CSV
 ///
 /// Here is how to load an array from a csv file, modify it then save it.
@@ -139,6 +304,55 @@ pub mod types;
 /// }
 /// numpy_file.write_data(&array);
 /// ```
+///
+/// Several named arrays can be stored together in a numpy archive, optionally deflated.
+///
+/// ```no_run
+/// use rds::array::NDArray;
+/// use rds::array::npz::NpzFile;
+/// 
+/// let mut npz_file = NpzFile::new("data.npz");
+/// npz_file.compression = true;
+/// npz_file.write_data("ones", &NDArray::<f64>::new(&[10, 10], 1.0)).unwrap();
+/// npz_file.write_data("zeros", &NDArray::<i32>::new(&[5], 0)).unwrap();
+/// npz_file.close().unwrap();
+/// for name in npz_file.members().unwrap() {
+///     let array : NDArray<f64> = npz_file.read_array(&name).unwrap();
+///     println!("{}: {}", name, array);
+/// }
+/// ```
+///
+/// ## HDF5
+///
+/// The groups and datasets of an HDF5 file are designated by their path from the root group.
+///
+/// ```no_run
+/// use rds::array::NDArray;
+/// use rds::array::hdf5::HDF5File;
+/// 
+/// let hdf5_file = HDF5File::new("data.h5");
+/// for name in hdf5_file.datasets("/measures").unwrap() {
+///     let array : NDArray<f64> = hdf5_file.read_dataset(&format!("/measures/{}", name)).unwrap();
+///     println!("{}: {}", name, array);
+/// }
+/// ```
+///
+/// Written datasets can be chunked and compressed, and groups and datasets can carry attributes.
+/// A new file is created, which is completed when it is closed.
+///
+/// ```no_run
+/// use rds::array::NDArray;
+/// use rds::array::hdf5::HDF5File;
+/// 
+/// let array = NDArray::<f64>::new(&[100, 100], 1.0);
+/// let mut hdf5_file = HDF5File::new("results.h5");
+/// hdf5_file.chunk_shape = Some(vec![10, 100]);
+/// hdf5_file.compression = Some(6);
+/// hdf5_file.write_data("/results/ones", &array).unwrap();
+/// hdf5_file.write_string_attribute("/results", "unit", "volt").unwrap();
+/// hdf5_file.write_attribute("/results/ones", "scale", 0.5f64).unwrap();
+/// hdf5_file.close().unwrap();
+/// ```
 pub mod array;
 
 /// Module containing Blas bindings and overloaded operation for `NDData`.
@@ -151,10 +365,16 @@ pub mod array;
 /// The convention is that the calling array is always the output array. This means for example 
 /// that a matrix vector multiplication should be called on the vector. Whether a matrix need a 
 /// translation or not is infered by the framework. If there are more than one solution, the one 
-/// requiring the less translations is taken.
+/// requiring the less translations is taken. The symmetric, Hermitian and triangular routines 
+/// instead take explicit `Uplo`, `Transpose` and `Diag` flags.
 ///
-/// The operators are overloaded to use those Blas functions, however they only operate on 
-/// references making the syntax a bit weird.
+/// The arithmetic operators are element-wise (see the `array::ops` module): matrix products are 
+/// computed by calling `gemv` and `gemm` explicitly.
+///
+/// The functions are provided by the system cblas library by default. Enabling the `rust-blas`
+/// feature replaces it with a pure Rust implementation which does not require linking to any
+/// library. Combined with `--no-default-features`, which disables the LAPACK based `linalg`
+/// module, RDS can then be built without any system dependency.
 ///
 /// # Examples
 ///
@@ -183,7 +403,7 @@ pub mod array;
 /// let vec1 = NDArray::<f32>::from_slice(&[2], &[2.0, 0.0]);
 /// let mut vec2 = NDArray::<f32>::copy(&vec1);
 /// let rot90 = NDArray::<f32>::from_slice(&[2,2], &[0.0, -1.0, 1.0, 0.0]);
-/// vec2 *= &rot90;
+/// vec2.gemv(1.0, &rot90, &vec1, 0.0);
 /// assert!(vec1.dot(&vec2) == 0.0);
 /// assert!(vec2 == NDArray::<f32>::from_slice(&[2], &[0.0, 2.0]));
 /// ```
@@ -195,17 +415,100 @@ pub mod array;
 /// use rds::blas::Blas;
 /// 
 /// let rot90 = NDArray::<f32>::from_slice(&[2,2], &[0.0, -1.0, 1.0, 0.0]);
-/// let rot180 = &rot90 * &rot90;
+/// let mut rot180 = NDArray::<f32>::new(&[2,2], 0.0);
+/// rot180.gemm(1.0, &rot90, &rot90, 0.0);
 /// let identity = NDArray::<f32>::from_slice(&[2,2], &[1.0, 0.0, 0.0, 1.0]);
-/// let mut transformed = NDArray::<f32>::copy(&identity);
-/// transformed *= &rot90;
-/// transformed *= &rot180;
-/// transformed *= &rot90;
+/// let mut transformed = NDArray::<f32>::new(&[2,2], 0.0);
+/// transformed.gemm(1.0, &rot180, &rot180, 0.0);
 /// assert!(transformed == identity);
 /// ```
 pub mod blas;
 
+/// Module containing LAPACK bindings to solve linear systems and decompose matrices.
+///
+/// The `Linalg` trait is implemented for two dimensional f32, f64, c32 and c64 data. Solvers 
+/// return a new NDArray while decompositions return a structure holding their factors.
+///
+/// ```
+/// use rds::array::{NDData, NDArray};
+/// use rds::linalg::Linalg;
+///
+/// let a = NDArray::<f64>::from_slice(&[2, 2], &[4.0, 1.0, 1.0, 3.0]);
+/// let b = NDArray::<f64>::from_slice(&[2], &[1.0, 2.0]);
+/// let x = a.solve(&b).unwrap();
+/// assert!((4.0 * x[&[0]] + x[&[1]] - 1.0).abs() < 1e-12);
+/// assert!((x[&[0]] + 3.0 * x[&[1]] - 2.0).abs() < 1e-12);
+///
+/// let cholesky = a.cholesky().unwrap();
+/// assert!(cholesky.l[&[0, 0]] == 2.0 && cholesky.l[&[0, 1]] == 0.0);
+///
+/// let eigen = a.eigh().unwrap();
+/// assert!(eigen.values.shape() == &[2] && eigen.values[&[0]] < eigen.values[&[1]]);
+/// ```
+#[cfg(feature = "lapack")]
+pub mod linalg;
+
+/// Module controlling how the array operations are split across threads.
+///
+/// The arithmetic operators, reductions and contractions of arrays of RDS types with at least 
+/// `PARALLEL_THRESHOLD` elements are transparently executed on a shared pool of worker threads. 
+/// Maps of arbitrary element types stay on the calling thread unless the `par_` variants are 
+/// used, which require the elements and the closure to be shareable between threads. 
+/// By default one thread per available core is used, which can be changed for the whole program 
+/// or only for the operations called from the current thread.
+///
+/// ```
+/// use rds::array::NDArray;
+/// use rds::array::map::NDMappable;
+/// use rds::parallel;
+///
+/// parallel::set_num_threads(4);
+/// parallel::set_local_num_threads(Some(1));
+/// let array = NDArray::<f64>::new(&[512, 512], 1.0);
+/// let doubled = array.par_map(|x| 2.0 * x);
+/// assert!(doubled == NDArray::<f64>::new(&[512, 512], 2.0));
+/// parallel::set_local_num_threads(None);
+/// assert!(parallel::num_threads() == 4);
+/// ```
+pub mod parallel;
+
 pub mod backend;
 
+/// Module containing the error type returned by the fallible operations.
+///
+/// Every fallible function of RDS returns an `Error` which allows to distinguish I/O failures, 
+/// parsing errors, malformed files, shape mismatches, unsupported types and compute backend 
+/// failures. I/O errors are available as the source of the error.
+///
+/// ```no_run
+/// use rds::Error;
+/// use rds::array::numpy::NumpyFile;
+///
+/// match NumpyFile::new("data.npy").read_array::<f64>() {
+///     Ok(array) => println!("{}", array),
+///     Err(Error::Io(e)) => println!("Could not read the file: {}", e),
+///     Err(Error::UnsupportedType(dtype)) => println!("Unsupported dtype {}", dtype),
+///     Err(e) => println!("Invalid numpy file: {}", e),
+/// }
+/// ```
+///
+/// The array operations and the `Blas` methods which panic on invalid shapes or indices have a 
+/// `try_*` counterpart returning a structured error instead.
+///
+/// ```
+/// use rds::Error;
+/// use rds::array::{NDArray, NDData};
+///
+/// let mut array = NDArray::<f32>::new(&[2, 3], 0.0);
+/// match array.try_reshape(&[4, 2]) {
+///     Err(Error::ShapeMismatch { expected, found }) => println!("Can't reshape {:?} to {:?}", expected, found),
+///     _ => unreachable!(),
+/// }
+/// assert!(array.try_idx(&[2, 0]).is_err());
+/// ```
+pub mod error;
+
+pub use error::Error;
+
 #[cfg(test)]
 mod tests;
@@ -0,0 +1,582 @@
+extern crate libc;
+
+use std::cmp::{max, min};
+use std::fmt::Display;
+
+use types::complex::{c32, c64};
+use array::{NDData, NDDataMut, NDArray};
+use error::Error;
+
+#[link(name = "lapack")]
+extern {
+    fn sgesv_ (n : *const libc::c_int, nrhs : *const libc::c_int, a : *mut libc::c_float, lda : *const libc::c_int, ipiv : *mut libc::c_int, b : *mut libc::c_float, ldb : *const libc::c_int, info : *mut libc::c_int) -> libc::c_void;
+    fn dgesv_ (n : *const libc::c_int, nrhs : *const libc::c_int, a : *mut libc::c_double, lda : *const libc::c_int, ipiv : *mut libc::c_int, b : *mut libc::c_double, ldb : *const libc::c_int, info : *mut libc::c_int) -> libc::c_void;
+    fn cgesv_ (n : *const libc::c_int, nrhs : *const libc::c_int, a : *mut c32, lda : *const libc::c_int, ipiv : *mut libc::c_int, b : *mut c32, ldb : *const libc::c_int, info : *mut libc::c_int) -> libc::c_void;
+    fn zgesv_ (n : *const libc::c_int, nrhs : *const libc::c_int, a : *mut c64, lda : *const libc::c_int, ipiv : *mut libc::c_int, b : *mut c64, ldb : *const libc::c_int, info : *mut libc::c_int) -> libc::c_void;
+
+    fn sgetrf_ (m : *const libc::c_int, n : *const libc::c_int, a : *mut libc::c_float, lda : *const libc::c_int, ipiv : *mut libc::c_int, info : *mut libc::c_int) -> libc::c_void;
+    fn dgetrf_ (m : *const libc::c_int, n : *const libc::c_int, a : *mut libc::c_double, lda : *const libc::c_int, ipiv : *mut libc::c_int, info : *mut libc::c_int) -> libc::c_void;
+    fn cgetrf_ (m : *const libc::c_int, n : *const libc::c_int, a : *mut c32, lda : *const libc::c_int, ipiv : *mut libc::c_int, info : *mut libc::c_int) -> libc::c_void;
+    fn zgetrf_ (m : *const libc::c_int, n : *const libc::c_int, a : *mut c64, lda : *const libc::c_int, ipiv : *mut libc::c_int, info : *mut libc::c_int) -> libc::c_void;
+
+    fn spotrf_ (uplo : *const libc::c_char, n : *const libc::c_int, a : *mut libc::c_float, lda : *const libc::c_int, info : *mut libc::c_int) -> libc::c_void;
+    fn dpotrf_ (uplo : *const libc::c_char, n : *const libc::c_int, a : *mut libc::c_double, lda : *const libc::c_int, info : *mut libc::c_int) -> libc::c_void;
+    fn cpotrf_ (uplo : *const libc::c_char, n : *const libc::c_int, a : *mut c32, lda : *const libc::c_int, info : *mut libc::c_int) -> libc::c_void;
+    fn zpotrf_ (uplo : *const libc::c_char, n : *const libc::c_int, a : *mut c64, lda : *const libc::c_int, info : *mut libc::c_int) -> libc::c_void;
+
+    fn sgeqrf_ (m : *const libc::c_int, n : *const libc::c_int, a : *mut libc::c_float, lda : *const libc::c_int, tau : *mut libc::c_float, work : *mut libc::c_float, lwork : *const libc::c_int, info : *mut libc::c_int) -> libc::c_void;
+    fn dgeqrf_ (m : *const libc::c_int, n : *const libc::c_int, a : *mut libc::c_double, lda : *const libc::c_int, tau : *mut libc::c_double, work : *mut libc::c_double, lwork : *const libc::c_int, info : *mut libc::c_int) -> libc::c_void;
+    fn cgeqrf_ (m : *const libc::c_int, n : *const libc::c_int, a : *mut c32, lda : *const libc::c_int, tau : *mut c32, work : *mut c32, lwork : *const libc::c_int, info : *mut libc::c_int) -> libc::c_void;
+    fn zgeqrf_ (m : *const libc::c_int, n : *const libc::c_int, a : *mut c64, lda : *const libc::c_int, tau : *mut c64, work : *mut c64, lwork : *const libc::c_int, info : *mut libc::c_int) -> libc::c_void;
+
+    fn sorgqr_ (m : *const libc::c_int, n : *const libc::c_int, k : *const libc::c_int, a : *mut libc::c_float, lda : *const libc::c_int, tau : *const libc::c_float, work : *mut libc::c_float, lwork : *const libc::c_int, info : *mut libc::c_int) -> libc::c_void;
+    fn dorgqr_ (m : *const libc::c_int, n : *const libc::c_int, k : *const libc::c_int, a : *mut libc::c_double, lda : *const libc::c_int, tau : *const libc::c_double, work : *mut libc::c_double, lwork : *const libc::c_int, info : *mut libc::c_int) -> libc::c_void;
+    fn cungqr_ (m : *const libc::c_int, n : *const libc::c_int, k : *const libc::c_int, a : *mut c32, lda : *const libc::c_int, tau : *const c32, work : *mut c32, lwork : *const libc::c_int, info : *mut libc::c_int) -> libc::c_void;
+    fn zungqr_ (m : *const libc::c_int, n : *const libc::c_int, k : *const libc::c_int, a : *mut c64, lda : *const libc::c_int, tau : *const c64, work : *mut c64, lwork : *const libc::c_int, info : *mut libc::c_int) -> libc::c_void;
+
+    fn sgesdd_ (jobz : *const libc::c_char, m : *const libc::c_int, n : *const libc::c_int, a : *mut libc::c_float, lda : *const libc::c_int, s : *mut libc::c_float, u : *mut libc::c_float, ldu : *const libc::c_int, vt : *mut libc::c_float, ldvt : *const libc::c_int, work : *mut libc::c_float, lwork : *const libc::c_int, iwork : *mut libc::c_int, info : *mut libc::c_int) -> libc::c_void;
+    fn dgesdd_ (jobz : *const libc::c_char, m : *const libc::c_int, n : *const libc::c_int, a : *mut libc::c_double, lda : *const libc::c_int, s : *mut libc::c_double, u : *mut libc::c_double, ldu : *const libc::c_int, vt : *mut libc::c_double, ldvt : *const libc::c_int, work : *mut libc::c_double, lwork : *const libc::c_int, iwork : *mut libc::c_int, info : *mut libc::c_int) -> libc::c_void;
+    fn cgesdd_ (jobz : *const libc::c_char, m : *const libc::c_int, n : *const libc::c_int, a : *mut c32, lda : *const libc::c_int, s : *mut libc::c_float, u : *mut c32, ldu : *const libc::c_int, vt : *mut c32, ldvt : *const libc::c_int, work : *mut c32, lwork : *const libc::c_int, rwork : *mut libc::c_float, iwork : *mut libc::c_int, info : *mut libc::c_int) -> libc::c_void;
+    fn zgesdd_ (jobz : *const libc::c_char, m : *const libc::c_int, n : *const libc::c_int, a : *mut c64, lda : *const libc::c_int, s : *mut libc::c_double, u : *mut c64, ldu : *const libc::c_int, vt : *mut c64, ldvt : *const libc::c_int, work : *mut c64, lwork : *const libc::c_int, rwork : *mut libc::c_double, iwork : *mut libc::c_int, info : *mut libc::c_int) -> libc::c_void;
+
+    fn ssyevd_ (jobz : *const libc::c_char, uplo : *const libc::c_char, n : *const libc::c_int, a : *mut libc::c_float, lda : *const libc::c_int, w : *mut libc::c_float, work : *mut libc::c_float, lwork : *const libc::c_int, iwork : *mut libc::c_int, liwork : *const libc::c_int, info : *mut libc::c_int) -> libc::c_void;
+    fn dsyevd_ (jobz : *const libc::c_char, uplo : *const libc::c_char, n : *const libc::c_int, a : *mut libc::c_double, lda : *const libc::c_int, w : *mut libc::c_double, work : *mut libc::c_double, lwork : *const libc::c_int, iwork : *mut libc::c_int, liwork : *const libc::c_int, info : *mut libc::c_int) -> libc::c_void;
+    fn cheevd_ (jobz : *const libc::c_char, uplo : *const libc::c_char, n : *const libc::c_int, a : *mut c32, lda : *const libc::c_int, w : *mut libc::c_float, work : *mut c32, lwork : *const libc::c_int, rwork : *mut libc::c_float, lrwork : *const libc::c_int, iwork : *mut libc::c_int, liwork : *const libc::c_int, info : *mut libc::c_int) -> libc::c_void;
+    fn zheevd_ (jobz : *const libc::c_char, uplo : *const libc::c_char, n : *const libc::c_int, a : *mut c64, lda : *const libc::c_int, w : *mut libc::c_double, work : *mut c64, lwork : *const libc::c_int, rwork : *mut libc::c_double, lrwork : *const libc::c_int, iwork : *mut libc::c_int, liwork : *const libc::c_int, info : *mut libc::c_int) -> libc::c_void;
+
+    fn sgeev_ (jobvl : *const libc::c_char, jobvr : *const libc::c_char, n : *const libc::c_int, a : *mut libc::c_float, lda : *const libc::c_int, wr : *mut libc::c_float, wi : *mut libc::c_float, vl : *mut libc::c_float, ldvl : *const libc::c_int, vr : *mut libc::c_float, ldvr : *const libc::c_int, work : *mut libc::c_float, lwork : *const libc::c_int, info : *mut libc::c_int) -> libc::c_void;
+    fn dgeev_ (jobvl : *const libc::c_char, jobvr : *const libc::c_char, n : *const libc::c_int, a : *mut libc::c_double, lda : *const libc::c_int, wr : *mut libc::c_double, wi : *mut libc::c_double, vl : *mut libc::c_double, ldvl : *const libc::c_int, vr : *mut libc::c_double, ldvr : *const libc::c_int, work : *mut libc::c_double, lwork : *const libc::c_int, info : *mut libc::c_int) -> libc::c_void;
+    fn cgeev_ (jobvl : *const libc::c_char, jobvr : *const libc::c_char, n : *const libc::c_int, a : *mut c32, lda : *const libc::c_int, w : *mut c32, vl : *mut c32, ldvl : *const libc::c_int, vr : *mut c32, ldvr : *const libc::c_int, work : *mut c32, lwork : *const libc::c_int, rwork : *mut libc::c_float, info : *mut libc::c_int) -> libc::c_void;
+    fn zgeev_ (jobvl : *const libc::c_char, jobvr : *const libc::c_char, n : *const libc::c_int, a : *mut c64, lda : *const libc::c_int, w : *mut c64, vl : *mut c64, ldvl : *const libc::c_int, vr : *mut c64, ldvr : *const libc::c_int, work : *mut c64, lwork : *const libc::c_int, rwork : *mut libc::c_double, info : *mut libc::c_int) -> libc::c_void;
+}
+
+/// LU decomposition with partial pivoting P A = L U of a m x n matrix A, with k = min(m, n).
+pub struct LU<T> {
+    /// The m x k lower trapezoidal factor, with a unit diagonal.
+    pub l : NDArray<T>,
+    /// The k x n upper trapezoidal factor.
+    pub u : NDArray<T>,
+    /// The row permutation P: row i of L U is row permutation[i] of A.
+    pub permutation : Vec<usize>,
+}
+
+/// Cholesky decomposition A = L L^H of a hermitian positive definite matrix A.
+pub struct Cholesky<T> {
+    /// The lower triangular factor.
+    pub l : NDArray<T>,
+}
+
+/// Reduced QR decomposition A = Q R of a m x n matrix A, with k = min(m, n).
+pub struct QR<T> {
+    /// The m x k factor with orthonormal columns.
+    pub q : NDArray<T>,
+    /// The k x n upper trapezoidal factor.
+    pub r : NDArray<T>,
+}
+
+/// Reduced singular value decomposition A = U diag(S) VT of a m x n matrix A, with k = min(m, n).
+pub struct SVD<T, R> {
+    /// The m x k left singular vectors, as columns.
+    pub u : NDArray<T>,
+    /// The k singular values in descending order.
+    pub s : NDArray<R>,
+    /// The k x n right singular vectors, as rows.
+    pub vt : NDArray<T>,
+}
+
+/// Eigendecomposition of a symmetric or hermitian matrix.
+pub struct SymmetricEigen<T, R> {
+    /// The real eigenvalues in ascending order.
+    pub values : NDArray<R>,
+    /// The orthonormal eigenvectors, as columns.
+    pub vectors : NDArray<T>,
+}
+
+/// Eigendecomposition of a general square matrix.
+pub struct Eigen<C> {
+    /// The complex eigenvalues. Complex conjugate pairs of real matrices are consecutive, the
+    /// eigenvalue with the positive imaginary part first.
+    pub values : NDArray<C>,
+    /// The right eigenvectors, as columns normalized to a euclidian norm of 1.
+    pub vectors : NDArray<C>,
+}
+
+/// A trait representing two dimensional arrays on which LAPACK solvers and decompositions can be
+/// applied. The data is copied in column-major order before being handed to LAPACK, the matrix
+/// itself is left unmodified.
+pub trait Linalg<T> {
+
+    /// Type of the singular values and of the eigenvalues of hermitian matrices.
+    type Real;
+
+    /// Type of the eigenvalues and eigenvectors of general matrices.
+    type Complex;
+
+    /// Solve the linear system A X = B where A is this square matrix and B a one or two
+    /// dimensional array with as many rows as A. X has the same shape as B. Uses ?gesv.
+    fn solve(&self, b : &NDData<T>) -> Result<NDArray<T>, Error>;
+
+    /// Compute the LU decomposition with partial pivoting of this matrix. The decomposition is
+    /// returned even if U is singular. Uses ?getrf.
+    fn lu(&self) -> Result<LU<T>, Error>;
+
+    /// Compute the Cholesky decomposition of this hermitian positive definite matrix. Only the
+    /// lower triangle of the matrix is read. Uses ?potrf.
+    fn cholesky(&self) -> Result<Cholesky<T>, Error>;
+
+    /// Compute the reduced QR decomposition of this matrix. Uses ?geqrf and ?orgqr or ?ungqr.
+    fn qr(&self) -> Result<QR<T>, Error>;
+
+    /// Compute the reduced singular value decomposition of this matrix. Uses ?gesdd.
+    fn svd(&self) -> Result<SVD<T, Self::Real>, Error>;
+
+    /// Compute the eigenvalues and eigenvectors of this symmetric or hermitian matrix. Only the
+    /// lower triangle of the matrix is read. Uses ?syevd or ?heevd.
+    fn eigh(&self) -> Result<SymmetricEigen<T, Self::Real>, Error>;
+
+    /// Compute the eigenvalues and right eigenvectors of this general square matrix. Uses ?geev.
+    fn eig(&self) -> Result<Eigen<Self::Complex>, Error>;
+}
+
+/// Return the leading dimension of a column-major matrix with m rows, as expected by LAPACK.
+fn ld(m : usize) -> libc::c_int {
+    max(m, 1) as libc::c_int
+}
+
+/// Return the number of rows and columns of a, which needs to be two dimensional.
+fn check_matrix<T>(a : &NDData<T>) -> Result<(usize, usize), Error> {
+    if a.dim() != 2 {
+        return Err(Error::DimensionMismatch { expected : 2, found : a.dim() });
+    }
+    Ok((a.shape()[0], a.shape()[1]))
+}
+
+/// Return the order of a, which needs to be a square matrix.
+fn check_square<T>(a : &NDData<T>) -> Result<usize, Error> {
+    let (m, n) = check_matrix(a)?;
+    if m != n {
+        return Err(Error::ShapeMismatch { expected : vec![m, m], found : vec![m, n] });
+    }
+    Ok(n)
+}
+
+/// Check that b is a one or two dimensional right hand side for a system of order n.
+fn check_rhs<T>(b : &NDData<T>, n : usize) -> Result<(), Error> {
+    if b.dim() != 1 && b.dim() != 2 {
+        return Err(Error::DimensionMismatch { expected : 2, found : b.dim() });
+    }
+    if b.shape()[0] != n {
+        let mut expected = b.shape().to_vec();
+        expected[0] = n;
+        return Err(Error::ShapeMismatch { expected : expected, found : b.shape().to_vec() });
+    }
+    Ok(())
+}
+
+fn check_info(routine : &str, info : libc::c_int) -> Result<(), Error> {
+    if info != 0 {
+        return Err(Error::Lapack { routine : routine.trim_end_matches('_').to_string(), info : info });
+    }
+    Ok(())
+}
+
+/// Copy a one or two dimensional a in a column-major buffer and return it together with its
+/// number of rows and columns. One dimensional data is treated as a column vector.
+fn to_col_major<T : Clone>(a : &NDData<T>) -> (Vec<T>, usize, usize) {
+    let (m, n) = if a.dim() == 1 { (a.shape()[0], 1) } else { (a.shape()[0], a.shape()[1]) };
+    let mut buffer = Vec::<T>::with_capacity(m * n);
+    for j in 0..n {
+        for i in 0..m {
+            let value = if a.dim() == 1 { a.idx(&[i]) } else { a.idx(&[i, j]) };
+            buffer.push(value.clone());
+        }
+    }
+    (buffer, m, n)
+}
+
+/// Copy the leading m x n block of a column-major buffer of leading dimension ld in a new NDArray.
+fn from_col_major<T : Clone>(buffer : &[T], m : usize, n : usize, ld : libc::c_int) -> NDArray<T> {
+    let mut data = Vec::<T>::with_capacity(m * n);
+    for i in 0..m {
+        for j in 0..n {
+            data.push(buffer[i + j * ld as usize].clone());
+        }
+    }
+    NDArray::from_slice(&[m, n], &data[..])
+}
+
+/// Set the elements above the diagonal of a to zero and, if given, the diagonal to diagonal.
+fn tril<T : Clone + Display>(a : &mut NDArray<T>, zero : T, diagonal : Option<T>) {
+    for i in 0..a.shape()[0] {
+        for j in i..a.shape()[1] {
+            if j > i {
+                *a.idx_mut(&[i, j]) = zero.clone();
+            }
+            else if let Some(ref d) = diagonal {
+                *a.idx_mut(&[i, j]) = d.clone();
+            }
+        }
+    }
+}
+
+/// Set the elements below the diagonal of a to zero.
+fn triu<T : Clone + Display>(a : &mut NDArray<T>, zero : T) {
+    for i in 0..a.shape()[0] {
+        for j in 0..min(i, a.shape()[1]) {
+            *a.idx_mut(&[i, j]) = zero.clone();
+        }
+    }
+}
+
+/// Convert the row interchanges returned by ?getrf into a row permutation of m rows.
+fn ipiv_to_permutation(ipiv : &[libc::c_int], m : usize) -> Vec<usize> {
+    let mut permutation : Vec<usize> = (0..m).collect();
+    for (i, &p) in ipiv.iter().enumerate() {
+        permutation.swap(i, p as usize - 1);
+    }
+    permutation
+}
+
+const JOB_NONE : libc::c_char = b'N' as libc::c_char;
+const JOB_VECTORS : libc::c_char = b'V' as libc::c_char;
+const JOB_SMALL : libc::c_char = b'S' as libc::c_char;
+const LOWER : libc::c_char = b'L' as libc::c_char;
+
+// solve, lu and cholesky are identical for real and complex types except for the zero and one
+// constants filling the triangular factors.
+macro_rules! linalg_factorizations {
+    ($t:ident, $zero:expr, $one:expr, $gesv:ident, $getrf:ident, $potrf:ident) => {
+
+    fn solve(&self, b : &NDData<$t>) -> Result<NDArray<$t>, Error> {
+        let n = check_square(self)?;
+        check_rhs(b, n)?;
+        let (mut a, _, _) = to_col_major(self);
+        let (mut x, _, nrhs) = to_col_major(b);
+        let mut ipiv = vec![0 as libc::c_int; n];
+        let mut info = 0;
+        unsafe {
+            $gesv(&(n as libc::c_int), &(nrhs as libc::c_int), a.as_mut_ptr(), &ld(n), ipiv.as_mut_ptr(), x.as_mut_ptr(), &ld(n), &mut info);
+        }
+        check_info(stringify!($gesv), info)?;
+        let mut solution = from_col_major(&x, n, nrhs, ld(n));
+        solution.try_reshape(b.shape())?;
+        Ok(solution)
+    }
+
+    fn lu(&self) -> Result<LU<$t>, Error> {
+        let (m, n) = check_matrix(self)?;
+        let k = min(m, n);
+        let (mut a, _, _) = to_col_major(self);
+        let mut ipiv = vec![0 as libc::c_int; k];
+        let mut info = 0;
+        unsafe {
+            $getrf(&(m as libc::c_int), &(n as libc::c_int), a.as_mut_ptr(), &ld(m), ipiv.as_mut_ptr(), &mut info);
+        }
+        if info < 0 {
+            check_info(stringify!($getrf), info)?;
+        }
+        let mut l = from_col_major(&a, m, k, ld(m));
+        tril(&mut l, $zero, Some($one));
+        let mut u = from_col_major(&a, k, n, ld(m));
+        triu(&mut u, $zero);
+        Ok(LU { l : l, u : u, permutation : ipiv_to_permutation(&ipiv, m) })
+    }
+
+    fn cholesky(&self) -> Result<Cholesky<$t>, Error> {
+        let n = check_square(self)?;
+        let (mut a, _, _) = to_col_major(self);
+        let mut info = 0;
+        unsafe {
+            $potrf(&LOWER, &(n as libc::c_int), a.as_mut_ptr(), &ld(n), &mut info);
+        }
+        check_info(stringify!($potrf), info)?;
+        let mut l = from_col_major(&a, n, n, ld(n));
+        tril(&mut l, $zero, None);
+        Ok(Cholesky { l : l })
+    }
+
+    }
+}
+
+macro_rules! real_linalg {
+    ($t:ident, $c:ident, $gesv:ident, $getrf:ident, $potrf:ident, $geqrf:ident, $orgqr:ident, $gesdd:ident, $syevd:ident, $geev:ident) => {
+
+impl<R> Linalg<$t> for R where R : NDData<$t> {
+
+    type Real = $t;
+
+    type Complex = $c;
+
+    linalg_factorizations!($t, 0.0, 1.0, $gesv, $getrf, $potrf);
+
+    fn qr(&self) -> Result<QR<$t>, Error> {
+        let (m, n) = check_matrix(self)?;
+        let k = min(m, n);
+        let (mut a, _, _) = to_col_major(self);
+        let mut tau = vec![0.0 as $t; max(k, 1)];
+        let mut work = vec![0.0 as $t; 1];
+        let mut info = 0;
+        unsafe {
+            $geqrf(&(m as libc::c_int), &(n as libc::c_int), a.as_mut_ptr(), &ld(m), tau.as_mut_ptr(), work.as_mut_ptr(), &-1, &mut info);
+        }
+        check_info(stringify!($geqrf), info)?;
+        let lwork = max(work[0] as libc::c_int, max(n, 1) as libc::c_int);
+        work = vec![0.0 as $t; lwork as usize];
+        unsafe {
+            $geqrf(&(m as libc::c_int), &(n as libc::c_int), a.as_mut_ptr(), &ld(m), tau.as_mut_ptr(), work.as_mut_ptr(), &lwork, &mut info);
+        }
+        check_info(stringify!($geqrf), info)?;
+        let mut r = from_col_major(&a, k, n, ld(m));
+        triu(&mut r, 0.0);
+
+        unsafe {
+            $orgqr(&(m as libc::c_int), &(k as libc::c_int), &(k as libc::c_int), a.as_mut_ptr(), &ld(m), tau.as_ptr(), work.as_mut_ptr(), &-1, &mut info);
+        }
+        check_info(stringify!($orgqr), info)?;
+        let lwork = max(work[0] as libc::c_int, max(k, 1) as libc::c_int);
+        work = vec![0.0 as $t; lwork as usize];
+        unsafe {
+            $orgqr(&(m as libc::c_int), &(k as libc::c_int), &(k as libc::c_int), a.as_mut_ptr(), &ld(m), tau.as_ptr(), work.as_mut_ptr(), &lwork, &mut info);
+        }
+        check_info(stringify!($orgqr), info)?;
+        Ok(QR { q : from_col_major(&a, m, k, ld(m)), r : r })
+    }
+
+    fn svd(&self) -> Result<SVD<$t, $t>, Error> {
+        let (m, n) = check_matrix(self)?;
+        let k = min(m, n);
+        let (mut a, _, _) = to_col_major(self);
+        let mut s = vec![0.0 as $t; k];
+        let mut u = vec![0.0 as $t; m * k];
+        let mut vt = vec![0.0 as $t; max(k, 1) * n];
+        let mut iwork = vec![0 as libc::c_int; max(8 * k, 1)];
+        let mut work = vec![0.0 as $t; 1];
+        let mut info = 0;
+        unsafe {
+            $gesdd(&JOB_SMALL, &(m as libc::c_int), &(n as libc::c_int), a.as_mut_ptr(), &ld(m), s.as_mut_ptr(), u.as_mut_ptr(), &ld(m), vt.as_mut_ptr(), &ld(k), work.as_mut_ptr(), &-1, iwork.as_mut_ptr(), &mut info);
+        }
+        check_info(stringify!($gesdd), info)?;
+        let lwork = max(work[0] as libc::c_int, 1);
+        work = vec![0.0 as $t; lwork as usize];
+        unsafe {
+            $gesdd(&JOB_SMALL, &(m as libc::c_int), &(n as libc::c_int), a.as_mut_ptr(), &ld(m), s.as_mut_ptr(), u.as_mut_ptr(), &ld(m), vt.as_mut_ptr(), &ld(k), work.as_mut_ptr(), &lwork, iwork.as_mut_ptr(), &mut info);
+        }
+        check_info(stringify!($gesdd), info)?;
+        Ok(SVD {
+            u : from_col_major(&u, m, k, ld(m)),
+            s : NDArray::from_slice(&[k], &s[..]),
+            vt : from_col_major(&vt, k, n, ld(k)),
+        })
+    }
+
+    fn eigh(&self) -> Result<SymmetricEigen<$t, $t>, Error> {
+        let n = check_square(self)?;
+        let (mut a, _, _) = to_col_major(self);
+        let mut w = vec![0.0 as $t; n];
+        let mut work = vec![0.0 as $t; 1];
+        let mut iwork = vec![0 as libc::c_int; 1];
+        let mut info = 0;
+        unsafe {
+            $syevd(&JOB_VECTORS, &LOWER, &(n as libc::c_int), a.as_mut_ptr(), &ld(n), w.as_mut_ptr(), work.as_mut_ptr(), &-1, iwork.as_mut_ptr(), &-1, &mut info);
+        }
+        check_info(stringify!($syevd), info)?;
+        let lwork = max(work[0] as libc::c_int, 1);
+        let liwork = max(iwork[0], 1);
+        work = vec![0.0 as $t; lwork as usize];
+        iwork = vec![0 as libc::c_int; liwork as usize];
+        unsafe {
+            $syevd(&JOB_VECTORS, &LOWER, &(n as libc::c_int), a.as_mut_ptr(), &ld(n), w.as_mut_ptr(), work.as_mut_ptr(), &lwork, iwork.as_mut_ptr(), &liwork, &mut info);
+        }
+        check_info(stringify!($syevd), info)?;
+        Ok(SymmetricEigen {
+            values : NDArray::from_slice(&[n], &w[..]),
+            vectors : from_col_major(&a, n, n, ld(n)),
+        })
+    }
+
+    fn eig(&self) -> Result<Eigen<$c>, Error> {
+        let n = check_square(self)?;
+        let (mut a, _, _) = to_col_major(self);
+        let mut wr = vec![0.0 as $t; n];
+        let mut wi = vec![0.0 as $t; n];
+        let mut vl = vec![0.0 as $t; 1];
+        let mut vr = vec![0.0 as $t; n * n];
+        let mut work = vec![0.0 as $t; 1];
+        let mut info = 0;
+        unsafe {
+            $geev(&JOB_NONE, &JOB_VECTORS, &(n as libc::c_int), a.as_mut_ptr(), &ld(n), wr.as_mut_ptr(), wi.as_mut_ptr(), vl.as_mut_ptr(), &1, vr.as_mut_ptr(), &ld(n), work.as_mut_ptr(), &-1, &mut info);
+        }
+        check_info(stringify!($geev), info)?;
+        let lwork = max(work[0] as libc::c_int, max(4 * n, 1) as libc::c_int);
+        work = vec![0.0 as $t; lwork as usize];
+        unsafe {
+            $geev(&JOB_NONE, &JOB_VECTORS, &(n as libc::c_int), a.as_mut_ptr(), &ld(n), wr.as_mut_ptr(), wi.as_mut_ptr(), vl.as_mut_ptr(), &1, vr.as_mut_ptr(), &ld(n), work.as_mut_ptr(), &lwork, &mut info);
+        }
+        check_info(stringify!($geev), info)?;
+
+        // Complex conjugate pairs are stored as the real and imaginary parts of the first vector
+        let mut values = Vec::<$c>::with_capacity(n);
+        let mut vectors = vec![$c::new(0.0, 0.0); n * n];
+        let mut j = 0;
+        while j < n {
+            if wi[j] == 0.0 {
+                values.push($c::new(wr[j], 0.0));
+                for i in 0..n {
+                    vectors[i + j * n] = $c::new(vr[i + j * n], 0.0);
+                }
+                j += 1;
+            }
+            else {
+                values.push($c::new(wr[j], wi[j]));
+                values.push($c::new(wr[j + 1], wi[j + 1]));
+                for i in 0..n {
+                    vectors[i + j * n] = $c::new(vr[i + j * n], vr[i + (j + 1) * n]);
+                    vectors[i + (j + 1) * n] = $c::new(vr[i + j * n], -vr[i + (j + 1) * n]);
+                }
+                j += 2;
+            }
+        }
+        Ok(Eigen {
+            values : NDArray::from_slice(&[n], &values[..]),
+            vectors : from_col_major(&vectors, n, n, ld(n)),
+        })
+    }
+}
+
+    }
+}
+
+macro_rules! complex_linalg {
+    ($t:ident, $r:ident, $gesv:ident, $getrf:ident, $potrf:ident, $geqrf:ident, $ungqr:ident, $gesdd:ident, $heevd:ident, $geev:ident) => {
+
+impl<R> Linalg<$t> for R where R : NDData<$t> {
+
+    type Real = $r;
+
+    type Complex = $t;
+
+    linalg_factorizations!($t, $t::new(0.0, 0.0), $t::new(1.0, 0.0), $gesv, $getrf, $potrf);
+
+    fn qr(&self) -> Result<QR<$t>, Error> {
+        let (m, n) = check_matrix(self)?;
+        let k = min(m, n);
+        let (mut a, _, _) = to_col_major(self);
+        let mut tau = vec![$t::new(0.0, 0.0); max(k, 1)];
+        let mut work = vec![$t::new(0.0, 0.0); 1];
+        let mut info = 0;
+        unsafe {
+            $geqrf(&(m as libc::c_int), &(n as libc::c_int), a.as_mut_ptr(), &ld(m), tau.as_mut_ptr(), work.as_mut_ptr(), &-1, &mut info);
+        }
+        check_info(stringify!($geqrf), info)?;
+        let lwork = max(work[0].re as libc::c_int, max(n, 1) as libc::c_int);
+        work = vec![$t::new(0.0, 0.0); lwork as usize];
+        unsafe {
+            $geqrf(&(m as libc::c_int), &(n as libc::c_int), a.as_mut_ptr(), &ld(m), tau.as_mut_ptr(), work.as_mut_ptr(), &lwork, &mut info);
+        }
+        check_info(stringify!($geqrf), info)?;
+        let mut r = from_col_major(&a, k, n, ld(m));
+        triu(&mut r, $t::new(0.0, 0.0));
+
+        unsafe {
+            $ungqr(&(m as libc::c_int), &(k as libc::c_int), &(k as libc::c_int), a.as_mut_ptr(), &ld(m), tau.as_ptr(), work.as_mut_ptr(), &-1, &mut info);
+        }
+        check_info(stringify!($ungqr), info)?;
+        let lwork = max(work[0].re as libc::c_int, max(k, 1) as libc::c_int);
+        work = vec![$t::new(0.0, 0.0); lwork as usize];
+        unsafe {
+            $ungqr(&(m as libc::c_int), &(k as libc::c_int), &(k as libc::c_int), a.as_mut_ptr(), &ld(m), tau.as_ptr(), work.as_mut_ptr(), &lwork, &mut info);
+        }
+        check_info(stringify!($ungqr), info)?;
+        Ok(QR { q : from_col_major(&a, m, k, ld(m)), r : r })
+    }
+
+    fn svd(&self) -> Result<SVD<$t, $r>, Error> {
+        let (m, n) = check_matrix(self)?;
+        let k = min(m, n);
+        let (mut a, _, _) = to_col_major(self);
+        let mut s = vec![0.0 as $r; k];
+        let mut u = vec![$t::new(0.0, 0.0); m * k];
+        let mut vt = vec![$t::new(0.0, 0.0); max(k, 1) * n];
+        let lrwork = max(k * max(5 * k + 7, 2 * max(m, n) + 2 * k + 1), 1);
+        let mut rwork = vec![0.0 as $r; lrwork];
+        let mut iwork = vec![0 as libc::c_int; max(8 * k, 1)];
+        let mut work = vec![$t::new(0.0, 0.0); 1];
+        let mut info = 0;
+        unsafe {
+            $gesdd(&JOB_SMALL, &(m as libc::c_int), &(n as libc::c_int), a.as_mut_ptr(), &ld(m), s.as_mut_ptr(), u.as_mut_ptr(), &ld(m), vt.as_mut_ptr(), &ld(k), work.as_mut_ptr(), &-1, rwork.as_mut_ptr(), iwork.as_mut_ptr(), &mut info);
+        }
+        check_info(stringify!($gesdd), info)?;
+        let lwork = max(work[0].re as libc::c_int, 1);
+        work = vec![$t::new(0.0, 0.0); lwork as usize];
+        unsafe {
+            $gesdd(&JOB_SMALL, &(m as libc::c_int), &(n as libc::c_int), a.as_mut_ptr(), &ld(m), s.as_mut_ptr(), u.as_mut_ptr(), &ld(m), vt.as_mut_ptr(), &ld(k), work.as_mut_ptr(), &lwork, rwork.as_mut_ptr(), iwork.as_mut_ptr(), &mut info);
+        }
+        check_info(stringify!($gesdd), info)?;
+        Ok(SVD {
+            u : from_col_major(&u, m, k, ld(m)),
+            s : NDArray::from_slice(&[k], &s[..]),
+            vt : from_col_major(&vt, k, n, ld(k)),
+        })
+    }
+
+    fn eigh(&self) -> Result<SymmetricEigen<$t, $r>, Error> {
+        let n = check_square(self)?;
+        let (mut a, _, _) = to_col_major(self);
+        let mut w = vec![0.0 as $r; n];
+        let mut work = vec![$t::new(0.0, 0.0); 1];
+        let mut rwork = vec![0.0 as $r; 1];
+        let mut iwork = vec![0 as libc::c_int; 1];
+        let mut info = 0;
+        unsafe {
+            $heevd(&JOB_VECTORS, &LOWER, &(n as libc::c_int), a.as_mut_ptr(), &ld(n), w.as_mut_ptr(), work.as_mut_ptr(), &-1, rwork.as_mut_ptr(), &-1, iwork.as_mut_ptr(), &-1, &mut info);
+        }
+        check_info(stringify!($heevd), info)?;
+        let lwork = max(work[0].re as libc::c_int, 1);
+        let lrwork = max(rwork[0] as libc::c_int, 1);
+        let liwork = max(iwork[0], 1);
+        work = vec![$t::new(0.0, 0.0); lwork as usize];
+        rwork = vec![0.0 as $r; lrwork as usize];
+        iwork = vec![0 as libc::c_int; liwork as usize];
+        unsafe {
+            $heevd(&JOB_VECTORS, &LOWER, &(n as libc::c_int), a.as_mut_ptr(), &ld(n), w.as_mut_ptr(), work.as_mut_ptr(), &lwork, rwork.as_mut_ptr(), &lrwork, iwork.as_mut_ptr(), &liwork, &mut info);
+        }
+        check_info(stringify!($heevd), info)?;
+        Ok(SymmetricEigen {
+            values : NDArray::from_slice(&[n], &w[..]),
+            vectors : from_col_major(&a, n, n, ld(n)),
+        })
+    }
+
+    fn eig(&self) -> Result<Eigen<$t>, Error> {
+        let n = check_square(self)?;
+        let (mut a, _, _) = to_col_major(self);
+        let mut w = vec![$t::new(0.0, 0.0); n];
+        let mut vl = vec![$t::new(0.0, 0.0); 1];
+        let mut vr = vec![$t::new(0.0, 0.0); n * n];
+        let mut rwork = vec![0.0 as $r; max(2 * n, 1)];
+        let mut work = vec![$t::new(0.0, 0.0); 1];
+        let mut info = 0;
+        unsafe {
+            $geev(&JOB_NONE, &JOB_VECTORS, &(n as libc::c_int), a.as_mut_ptr(), &ld(n), w.as_mut_ptr(), vl.as_mut_ptr(), &1, vr.as_mut_ptr(), &ld(n), work.as_mut_ptr(), &-1, rwork.as_mut_ptr(), &mut info);
+        }
+        check_info(stringify!($geev), info)?;
+        let lwork = max(work[0].re as libc::c_int, max(2 * n, 1) as libc::c_int);
+        work = vec![$t::new(0.0, 0.0); lwork as usize];
+        unsafe {
+            $geev(&JOB_NONE, &JOB_VECTORS, &(n as libc::c_int), a.as_mut_ptr(), &ld(n), w.as_mut_ptr(), vl.as_mut_ptr(), &1, vr.as_mut_ptr(), &ld(n), work.as_mut_ptr(), &lwork, rwork.as_mut_ptr(), &mut info);
+        }
+        check_info(stringify!($geev), info)?;
+        Ok(Eigen {
+            values : NDArray::from_slice(&[n], &w[..]),
+            vectors : from_col_major(&vr, n, n, ld(n)),
+        })
+    }
+}
+
+    }
+}
+
+real_linalg!(f32, c32, sgesv_, sgetrf_, spotrf_, sgeqrf_, sorgqr_, sgesdd_, ssyevd_, sgeev_);
+real_linalg!(f64, c64, dgesv_, dgetrf_, dpotrf_, dgeqrf_, dorgqr_, dgesdd_, dsyevd_, dgeev_);
+complex_linalg!(c32, f32, cgesv_, cgetrf_, cpotrf_, cgeqrf_, cungqr_, cgesdd_, cheevd_, cgeev_);
+complex_linalg!(c64, f64, zgesv_, zgetrf_, zpotrf_, zgeqrf_, zungqr_, zgesdd_, zheevd_, zgeev_);
@@ -0,0 +1,278 @@
+use std::any::{Any, TypeId};
+use std::cell::Cell;
+use std::cmp::min;
+use std::mem;
+use std::ops::Range;
+use std::panic::{self, AssertUnwindSafe};
+use std::process;
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::{channel, Sender, Receiver};
+use std::thread;
+
+use types::complex::{c32, c64};
+use array::{NDData, NDView};
+use array::ndindex::NDIndex;
+
+/// Number of elements below which the operations are always executed on the calling thread.
+pub const PARALLEL_THRESHOLD : usize = 1 << 16;
+
+type Job = Box<FnOnce() + Send + 'static>;
+
+/// Worker threads sharing a single job queue. The pool only grows, when more threads than
+/// currently available are requested.
+struct ThreadPool {
+    sender : Sender<Job>,
+    receiver : Arc<Mutex<Receiver<Job>>>,
+    workers : usize,
+}
+
+static NUM_THREADS : AtomicUsize = AtomicUsize::new(0);
+static POOL : Mutex<Option<ThreadPool>> = Mutex::new(None);
+
+thread_local! {
+    static LOCAL_NUM_THREADS : Cell<Option<usize>> = Cell::new(None);
+    static IN_WORKER : Cell<bool> = Cell::new(false);
+}
+
+impl ThreadPool {
+
+    fn new() -> ThreadPool {
+        let (sender, receiver) = channel::<Job>();
+        ThreadPool {
+            sender : sender,
+            receiver : Arc::new(Mutex::new(receiver)),
+            workers : 0,
+        }
+    }
+
+    fn grow(&mut self, workers : usize) {
+        while self.workers < workers {
+            let receiver = self.receiver.clone();
+            thread::spawn(move || {
+                IN_WORKER.with(|w| w.set(true));
+                loop {
+                    let job = match receiver.lock().unwrap().recv() {
+                        Ok(job) => job,
+                        Err(_) => break,
+                    };
+                    job();
+                }
+            });
+            self.workers += 1;
+        }
+    }
+}
+
+/// Set the number of threads used by the operations of every thread which did not override it
+/// with `set_local_num_threads`. A value of 0, the default, uses one thread per available core.
+pub fn set_num_threads(n : usize) {
+    NUM_THREADS.store(n, Ordering::Relaxed);
+}
+
+/// Override the number of threads used by the operations called from the current thread. None
+/// restores the global setting.
+pub fn set_local_num_threads(n : Option<usize>) {
+    LOCAL_NUM_THREADS.with(|local| local.set(n));
+}
+
+/// Return the number of threads the operations called from the current thread are split across.
+/// Operations called from within a worker thread are never split further.
+pub fn num_threads() -> usize {
+    if IN_WORKER.with(|w| w.get()) {
+        return 1;
+    }
+    let n = LOCAL_NUM_THREADS.with(|local| local.get()).unwrap_or_else(|| NUM_THREADS.load(Ordering::Relaxed));
+    if n == 0 {
+        return thread::available_parallelism().map(|n| n.get()).unwrap_or(1);
+    }
+    n
+}
+
+/// Return true if an operation over size elements would be split across several threads.
+pub fn is_parallel(size : usize) -> bool {
+    size >= PARALLEL_THRESHOLD && num_threads() > 1
+}
+
+/// Split the range 0..size in one contiguous range per thread, or in a single range if the
+/// operation should not be parallelized. Each item of the range accounts for weight elements,
+/// for example the length of the reduced axis of a reduction.
+pub fn split(size : usize, weight : usize) -> Vec<Range<usize>> {
+    if !is_parallel(size.saturating_mul(weight)) {
+        return vec![0..size];
+    }
+    let threads = min(num_threads(), size);
+    let chunk = (size + threads - 1) / threads;
+    (0..threads).map(|i| i * chunk..min((i + 1) * chunk, size)).filter(|r| r.start < r.end).collect()
+}
+
+/// Completion reports of the jobs sent to the thread pool. The jobs borrow from the stack frame
+/// of `execute`, so the reports are awaited when the Completion is dropped, including while
+/// unwinding, before these borrows are released.
+struct Completion {
+    done : Receiver<thread::Result<()>>,
+    pending : usize,
+    panicked : Option<Box<Any + Send>>,
+}
+
+impl Completion {
+
+    /// Wait for every pending job. A job dropped without reporting, which leaves no way of
+    /// knowing whether it still borrows from the caller, aborts the process.
+    fn wait(&mut self) {
+        while self.pending > 0 {
+            match self.done.recv() {
+                Ok(result) => {
+                    self.pending -= 1;
+                    if let Err(e) = result {
+                        self.panicked = Some(e);
+                    }
+                },
+                Err(_) => process::abort()
+            }
+        }
+    }
+}
+
+impl Drop for Completion {
+
+    fn drop(&mut self) {
+        self.wait();
+    }
+}
+
+/// Execute the jobs on the thread pool and wait for all of them to complete. A panic in one of
+/// the jobs is propagated to the caller once every job has completed.
+fn execute<'a>(jobs : Vec<Box<FnOnce() + Send + 'a>>) {
+    let (done_sender, done) = channel::<thread::Result<()>>();
+    let mut completion = Completion {
+        done : done,
+        pending : 0,
+        panicked : None,
+    };
+    {
+        // Dropped before completion, even while unwinding, so a job dropped without running
+        // disconnects the channel instead of blocking the wait.
+        let done_sender = done_sender;
+        let mut pool = POOL.lock().unwrap_or_else(|e| e.into_inner());
+        if pool.is_none() {
+            *pool = Some(ThreadPool::new());
+        }
+        let pool = pool.as_mut().unwrap();
+        pool.grow(jobs.len());
+        for job in jobs {
+            let done_sender = done_sender.clone();
+            let job : Box<FnOnce() + Send + 'a> = Box::new(move || {
+                let _ = done_sender.send(panic::catch_unwind(AssertUnwindSafe(job)));
+            });
+            // The jobs borrow from the caller's stack frame, which outlives them because
+            // completion waits for every job sent, even if this function unwinds.
+            let job : Job = unsafe { mem::transmute(job) };
+            if pool.sender.send(job).is_err() {
+                panic!("parallel::execute(): the thread pool is disconnected");
+            }
+            completion.pending += 1;
+        }
+    }
+    completion.wait();
+    if let Some(e) = completion.panicked.take() {
+        panic::resume_unwind(e);
+    }
+}
+
+/// Apply f to the ranges returned by `split(size, weight)` in parallel and return the results in
+/// the order of the ranges.
+pub fn map_ranges<U : Send, F : Fn(Range<usize>) -> U + Sync>(size : usize, weight : usize, f : F) -> Vec<U> {
+    let ranges = split(size, weight);
+    if ranges.len() == 1 {
+        return vec![f(0..size)];
+    }
+    let mut results : Vec<Option<U>> = ranges.iter().map(|_| None).collect();
+    {
+        let f = &f;
+        let jobs = results.iter_mut().zip(ranges).map(|(result, range)| {
+            Box::new(move || *result = Some(f(range))) as Box<FnOnce() + Send>
+        }).collect();
+        execute(jobs);
+    }
+    results.into_iter().map(|r| r.unwrap()).collect()
+}
+
+/// Return true if T is one of the RDS types or usize, all of which are Send and Sync.
+fn is_shareable<T : 'static>() -> bool {
+    let t = TypeId::of::<T>();
+    [TypeId::of::<u8>(), TypeId::of::<u16>(), TypeId::of::<u32>(), TypeId::of::<u64>(),
+     TypeId::of::<i8>(), TypeId::of::<i16>(), TypeId::of::<i32>(), TypeId::of::<i64>(),
+     TypeId::of::<f32>(), TypeId::of::<f64>(), TypeId::of::<c32>(), TypeId::of::<c64>(),
+     TypeId::of::<usize>()].contains(&t)
+}
+
+/// Wrapper sending or sharing a value with the thread pool regardless of its type.
+struct Unchecked<T>(T);
+
+unsafe impl<T> Send for Unchecked<T> {}
+unsafe impl<T> Sync for Unchecked<T> {}
+
+/// Same as map_ranges for the generic operations on elements of type T, whose signatures do not
+/// require T to be Send or Sync. The ranges are only split across the thread pool if T is one of
+/// the RDS types, otherwise f is applied to the whole range on the calling thread.
+///
+/// Unsafe because f and its results are sent to other threads unchecked: they must only be Send
+/// and Sync because of values of type T or usize they hold or borrow.
+pub(crate) unsafe fn map_ranges_of<T : 'static, U, F : Fn(Range<usize>) -> U>(size : usize, weight : usize, f : F) -> Vec<U> {
+    if !is_shareable::<T>() {
+        return vec![f(0..size)];
+    }
+    let f = Unchecked(&f);
+    map_ranges(size, weight, |range| Unchecked((f.0)(range))).into_iter().map(|result| result.0).collect()
+}
+
+/// Split data in the chunks given by `split(data.len(), 1)` and apply f in parallel to each of
+/// them together with the position of its first element.
+pub fn for_each_chunk_mut<T : Send, F : Fn(usize, &mut [T]) + Sync>(data : &mut [T], f : F) {
+    let ranges = split(data.len(), 1);
+    if ranges.len() == 1 {
+        return f(0, data);
+    }
+    let f = &f;
+    let mut jobs = Vec::<Box<FnOnce() + Send>>::with_capacity(ranges.len());
+    let mut rest = data;
+    for range in ranges {
+        let (chunk, tail) = { rest }.split_at_mut(range.end - range.start);
+        rest = tail;
+        jobs.push(Box::new(move || f(range.start, chunk)));
+    }
+    execute(jobs);
+}
+
+/// Return the index of the element at position pos in the row-major order of shape.
+pub fn ro_index(pos : usize, shape : &[usize]) -> Vec<usize> {
+    let mut idx = vec![0; shape.len()];
+    let mut pos = pos;
+    for i in (0..shape.len()).rev() {
+        if shape[i] > 0 {
+            idx[i] = pos % shape[i];
+            pos /= shape[i];
+        }
+    }
+    idx
+}
+
+/// Apply f to every element of data and return the results in row-major order, in parallel if
+/// data is large enough.
+pub fn map_elements<T : Sync, U : Send, F : Fn(&T) -> U + Sync>(data : &NDView<T>, f : F) -> Vec<U> {
+    let chunks = map_ranges(data.size(), 1, |range| {
+        let mut idx = ro_index(range.start, data.shape());
+        let mut chunk = Vec::<U>::with_capacity(range.end - range.start);
+        for _ in range {
+            chunk.push(f(data.idx(&idx[..])));
+            idx.inc_ro(data.shape());
+        }
+        chunk
+    });
+    let mut result = Vec::<U>::with_capacity(data.size());
+    for chunk in chunks {
+        result.extend(chunk);
+    }
+    result
+}
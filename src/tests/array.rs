@@ -1,4 +1,5 @@
-use array::{NDArray, NDData, NDDataMut, NDSliceable, NDSliceableMut};
+use array::{NDArray, NDData, NDDataMut, NDSliceable, NDSliceableMut, NDViewable, NDViewableMut, NDRange};
+use error::Error;
 
 #[test]
 fn indexing() {
@@ -357,3 +358,250 @@ fn insert_split() {
     assert!(array3d.split(2, 3) == NDArray::<f64>::new(&[3, 3, 2], 1.0));
     assert!(array3d == NDArray::<f64>::new(&[3, 3, 3], 0.0));
 }
+
+#[test]
+fn view() {
+    let mut array = NDArray::<f64>::new(&[4, 5], 0.0);
+    for i in 0..4 {
+        for j in 0..5 {
+            array[&[i, j]] = (i * 3 + j * 5) as f64;
+        }
+    }
+
+    let column = array.view(&[NDRange::all(), NDRange::index(2)]);
+    assert!(column.shape() == &[4]);
+    for i in 0..4 {
+        assert!(column[&[i]] == (i * 3 + 10) as f64);
+    }
+
+    let stepped = array.view(&[NDRange::new(1, 4).step(2), NDRange::new(0, 5).step(3)]);
+    assert!(stepped.shape() == &[2, 2]);
+    for i in 0..2 {
+        for j in 0..2 {
+            assert!(stepped[&[i, j]] == ((1 + i * 2) * 3 + j * 3 * 5) as f64);
+        }
+    }
+
+    let reversed = array.view(&[NDRange::all().step(-1), NDRange::new(1, 5).step(-2)]);
+    assert!(reversed.shape() == &[4, 2]);
+    for i in 0..4 {
+        for j in 0..2 {
+            assert!(reversed[&[i, j]] == ((3 - i) * 3 + (4 - j * 2) * 5) as f64);
+        }
+    }
+
+    let nested = reversed.view(&[NDRange::index(1)]);
+    assert!(nested == NDArray::<f64>::from_slice(&[2], &[26.0, 16.0]));
+}
+
+#[test]
+fn view_of_slice() {
+    let mut array = NDArray::<f64>::new(&[3, 3, 3], 0.0);
+    for i in 0..3 {
+        for j in 0..3 {
+            for k in 0..3 {
+                array[&[i, j, k]] = (i * 3 + j * 5 + k * 7) as f64;
+            }
+        }
+    }
+    let slice = array.slice(&[1]);
+    let diagonal = slice.view(&[NDRange::all().step(-1), NDRange::index(1)]);
+    for i in 0..3 {
+        assert!(diagonal[&[i]] == (3 + (2 - i) * 5 + 7) as f64);
+    }
+}
+
+#[test]
+fn view_mut() {
+    let mut array = NDArray::<f64>::new(&[4, 4], 0.0);
+    array.view_mut(&[NDRange::all(), NDRange::index(1)]).assign(&NDArray::<f64>::new(&[4], 1.0));
+    {
+        let mut reversed = array.view_mut(&[NDRange::index(3), NDRange::all().step(-1)]);
+        for i in 0..4 {
+            reversed[&[i]] = i as f64;
+        }
+    }
+    for i in 0..3 {
+        for j in 0..4 {
+            assert!(array[&[i, j]] == if j == 1 { 1.0 } else { 0.0 });
+        }
+    }
+    for j in 0..4 {
+        assert!(array[&[3, j]] == (3 - j) as f64);
+    }
+}
+
+#[test]
+fn view_copy() {
+    let mut array = NDArray::<f64>::new(&[3, 4], 0.0);
+    for i in 0..3 {
+        for j in 0..4 {
+            array[&[i, j]] = (i * 3 + j * 5) as f64;
+        }
+    }
+    let view = array.view(&[NDRange::all().step(-1), NDRange::new(1, 3)]);
+    let copy = NDArray::copy(&view);
+    assert!(copy.strides() == &[2, 1]);
+    assert!(copy == view);
+    let casted = NDArray::<i32>::cast(&view);
+    for i in 0..3 {
+        for j in 0..2 {
+            assert!(casted[&[i, j]] == ((2 - i) * 3 + (j + 1) * 5) as i32);
+        }
+    }
+}
+
+#[test]
+#[should_panic]
+fn view_outofbound() {
+    let array = NDArray::<f64>::new(&[3, 3], 0.0);
+    array.view(&[NDRange::new(1, 4)]);
+}
+
+#[test]
+#[should_panic]
+fn view_zero_step() {
+    let array = NDArray::<f64>::new(&[3, 3], 0.0);
+    array.view(&[NDRange::all().step(0)]);
+}
+
+#[test]
+fn view_empty() {
+    let array = NDArray::<f64>::new(&[3, 4], 1.0);
+    let empty = array.view(&[NDRange::new(2, 2)]);
+    assert!(empty.shape() == &[0, 4]);
+    assert!(empty.size() == 0);
+    let reversed = array.view(&[NDRange::all(), NDRange::new(4, 4).step(-1)]);
+    assert!(reversed.shape() == &[3, 0]);
+    assert!(NDArray::copy(&reversed).size() == 0);
+
+    let rows = NDArray::<f64>::new(&[0, 5], 0.0);
+    let view = rows.view(&[NDRange::all(), NDRange::new(1, 3)]);
+    assert!(view.shape() == &[0, 2]);
+    assert!(rows.view(&[]).shape() == &[0, 5]);
+}
+
+#[test]
+#[should_panic]
+fn view_start_after_end() {
+    let array = NDArray::<f64>::new(&[3, 3], 0.0);
+    array.view(&[NDRange::new(2, 1)]);
+}
+
+#[test]
+fn permute_axes() {
+    let mut array = NDArray::<f64>::new(&[2, 3, 4], 0.0);
+    for i in 0..2 {
+        for j in 0..3 {
+            for k in 0..4 {
+                array[&[i, j, k]] = (i * 100 + j * 10 + k) as f64;
+            }
+        }
+    }
+    {
+        let permuted = array.permute_axes(&[1, 2, 0]);
+        assert!(permuted.shape() == &[3, 4, 2]);
+        assert!(!permuted.is_contiguous());
+        for i in 0..2 {
+            for j in 0..3 {
+                for k in 0..4 {
+                    assert!(permuted[&[j, k, i]] == array[&[i, j, k]]);
+                }
+            }
+        }
+        let contiguous = permuted.to_contiguous();
+        assert!(contiguous.is_contiguous());
+        assert!(contiguous == permuted);
+        let swapped = array.swap_axes(0, 2);
+        assert!(swapped.shape() == &[4, 3, 2]);
+        assert!(swapped[&[3, 1, 0]] == 13.0);
+        assert!(swapped == array.transposed());
+    }
+    {
+        let mut swapped = array.swap_axes_mut(1, 2);
+        swapped[&[1, 3, 2]] = -1.0;
+    }
+    assert!(array[&[1, 2, 3]] == -1.0);
+}
+
+#[test]
+fn transposed_non_square() {
+    let array = NDArray::<i32>::from_slice(&[2, 3], &[1, 2, 3, 4, 5, 6]);
+    let transposed = array.transposed();
+    assert!(transposed == NDArray::<i32>::from_slice(&[3, 2], &[1, 4, 2, 5, 3, 6]));
+    let view = array.view(&[NDRange::all(), NDRange::new(1, 3)]);
+    assert!(view.transposed() == NDArray::<i32>::from_slice(&[2, 2], &[2, 5, 3, 6]));
+    let mut array = NDArray::<i32>::from_slice(&[2, 1, 3], &[1, 2, 3, 4, 5, 6]);
+    array.transpose();
+    assert!(array == NDArray::<i32>::from_slice(&[3, 1, 2], &[1, 4, 2, 5, 3, 6]));
+}
+
+#[test]
+#[should_panic]
+fn permute_axes_invalid() {
+    let array = NDArray::<f64>::new(&[2, 3, 4], 0.0);
+    array.permute_axes(&[0, 2, 2]);
+}
+
+#[test]
+fn try_idx() {
+    let array = NDArray::<f64>::new(&[3, 3, 3], 1.0);
+    assert!(*array.try_idx(&[1, 2, 0]).unwrap() == 1.0);
+    match array.try_idx(&[1, 2]) {
+        Err(Error::DimensionMismatch { expected : 3, found : 2 }) => {},
+        _ => panic!("expected a dimension mismatch"),
+    }
+    match array.try_idx(&[1, 4, 1]) {
+        Err(Error::IndexOutOfBounds { axis : 1, index : 4, len : 3 }) => {},
+        _ => panic!("expected an index out of bounds"),
+    }
+}
+
+#[test]
+fn try_reshape_assign() {
+    let mut array = NDArray::<f64>::new(&[4, 3], 0.0);
+    match array.try_reshape(&[5, 2]) {
+        Err(Error::ShapeMismatch { expected, found }) => {
+            assert!(expected == vec![4, 3]);
+            assert!(found == vec![5, 2]);
+        },
+        _ => panic!("expected a shape mismatch"),
+    }
+    assert!(array.shape() == &[4, 3]);
+    array.try_reshape(&[2, 6]).unwrap();
+    assert!(array.shape() == &[2, 6]);
+    let other = NDArray::<f64>::new(&[6, 2], 1.0);
+    assert!(array.try_assign(&other).is_err());
+    array.try_assign(&NDArray::<f64>::new(&[2, 6], 1.0)).unwrap();
+    assert!(array == NDArray::<f64>::new(&[2, 6], 1.0));
+}
+
+#[test]
+fn try_insert_extract_remove_split() {
+    let mut array = NDArray::<f64>::new(&[3, 4], 0.0);
+    let original = NDArray::<f64>::copy(&array);
+    match array.try_insert(2, 0, &NDArray::<f64>::new(&[3, 1], 1.0)) {
+        Err(Error::InvalidAxis { axis : 2, dim : 2 }) => {},
+        _ => panic!("expected an invalid axis"),
+    }
+    match array.try_insert(1, 0, &NDArray::<f64>::new(&[2, 1], 1.0)) {
+        Err(Error::ShapeMismatch { expected, found }) => {
+            assert!(expected == vec![3, 1]);
+            assert!(found == vec![2, 1]);
+        },
+        _ => panic!("expected a shape mismatch"),
+    }
+    match array.try_extract(&[1, 2], &[1, 4]) {
+        Err(Error::EmptyRange { axis : 0, start : 1, end : 1 }) => {},
+        _ => panic!("expected an empty range"),
+    }
+    match array.try_remove(1, 2, 5) {
+        Err(Error::IndexOutOfBounds { axis : 1, index : 5, len : 4 }) => {},
+        _ => panic!("expected an index out of bounds"),
+    }
+    assert!(array.try_split(0, 3).is_err());
+    assert!(array == original);
+    let splitted = array.try_split(1, 3).unwrap();
+    assert!(splitted.shape() == &[3, 1]);
+    assert!(array.shape() == &[3, 3]);
+}
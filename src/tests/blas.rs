@@ -1,9 +1,10 @@
 use std::f32;
 use std::f64;
 
-use types::complex::{c32, c64};
-use array::{NDDataMut, NDArray};
-use blas::Blas;
+use types::complex::{c32, c64, Complex};
+use array::{NDDataMut, NDArray, NDViewable, NDViewableMut, NDRange};
+use blas::{Blas, Uplo, Diag, Side, Transpose};
+use error::Error;
 
 #[test]
 fn asum_f32() {
@@ -69,6 +70,25 @@ fn nrm2_c64() {
     assert!(array2.nrm2() == c64::new(25.0f64.sqrt(), 0.0));
 }
 
+#[test]
+fn nrm2_scaling() {
+    let mut array1 = NDArray::<f64>::new(&[2], 3e200);
+    array1[&[1]] = 4e200;
+    assert!((array1.nrm2() / 5e200 - 1.0).abs() < 1e-15);
+    let mut array2 = NDArray::<f64>::new(&[2], 3e-200);
+    array2[&[1]] = 4e-200;
+    assert!((array2.nrm2() / 5e-200 - 1.0).abs() < 1e-15);
+    let mut array3 = NDArray::<f32>::new(&[3], 1e-30);
+    array3[&[0]] = 3e30;
+    array3[&[1]] = 4e30;
+    assert!((array3.nrm2() / 5e30 - 1.0).abs() < 1e-6);
+    let array4 = NDArray::<c64>::new(&[4], c64::new(1e300, -1e300));
+    assert!((array4.nrm2().re / 8f64.sqrt() / 1e300 - 1.0).abs() < 1e-15);
+    let mut array5 = NDArray::<f64>::new(&[2, 4, 3], 1e300);
+    let view = array5.view_mut(&[NDRange::all(), NDRange::all(), NDRange::new(0, 2)]);
+    assert!((view.nrm2() / 4.0 / 1e300 - 1.0).abs() < 1e-15);
+}
+
 #[test]
 fn scal_f32() {
     let mut array1 = NDArray::<f32>::new(&[5], 0.0);
@@ -512,13 +532,436 @@ fn ops_overloading() {
     let matrix1 = NDArray::<f64>::from_slice(&[3,3], &[1.0, 1.0, 0.0, 0.0, 1.0, 1.0, 1.0, 0.0, 1.0]);
     let mut matrix2 = NDArray::<f64>::copy(&matrix1);
     matrix2.transpose();
-    let matrix3 = &matrix2 * &matrix1;
+    let mut matrix3 = NDArray::<f64>::new(&[3,3], 0.0);
+    matrix3.gemm(1.0, &matrix2, &matrix1, 0.0);
     array1 += &array3;
     assert!(array1 == NDArray::<f64>::from_slice(&[3], &[7.0, 14.0, 21.0]));
     array1 -= &array2;
     assert!(array1 == NDArray::<f64>::from_slice(&[3], &[4.0, 8.0, 12.0]));
-    array1 *= &matrix3;
+    let x = NDArray::<f64>::copy(&array1);
+    array1.gemv(1.0, &matrix3, &x, 0.0);
     assert!(array1 == NDArray::<f64>::from_slice(&[3], &[28.0, 32.0, 36.0]));
     let array4 = &array1 + &array2;
     assert!(array4 == NDArray::<f64>::from_slice(&[3], &[31.0, 38.0, 45.0]));
 }
+
+#[test]
+fn level1_view() {
+    let mut array = NDArray::<f64>::new(&[4, 4], 0.0);
+    for i in 0..4 {
+        for j in 0..4 {
+            array[&[i, j]] = (i * 4 + j) as f64;
+        }
+    }
+    assert!(array.view_mut(&[NDRange::all(), NDRange::new(1, 3)]).asum() == 60.0);
+    assert!((array.view_mut(&[NDRange::all(), NDRange::index(0)]).nrm2() - 224.0f64.sqrt()).abs() < 1e-12);
+    let column = NDArray::copy(&array.view(&[NDRange::all(), NDRange::index(0)]));
+    let reversed_row = NDArray::copy(&array.view(&[NDRange::index(0), NDRange::all().step(-1)]));
+    assert!(array.view_mut(&[NDRange::all().step(-1), NDRange::index(0)]).dot(&reversed_row) == column.dot(&NDArray::<f64>::from_slice(&[4], &[0.0, 1.0, 2.0, 3.0])));
+    array.view_mut(&[NDRange::all().step(-2), NDRange::all().step(2)]).scal(-1.0);
+    array.view_mut(&[NDRange::all(), NDRange::index(3)]).axpy(2.0, &reversed_row);
+    for i in 0..4 {
+        for j in 0..4 {
+            let mut expected = (i * 4 + j) as f64;
+            if i % 2 == 1 && j % 2 == 0 {
+                expected = -expected;
+            }
+            if j == 3 {
+                expected += 2.0 * (3 - i) as f64;
+            }
+            assert!(array[&[i, j]] == expected);
+        }
+    }
+}
+
+#[test]
+fn gemm_view() {
+    let mut a = NDArray::<f32>::new(&[3, 4], 0.0);
+    for i in 0..3 {
+        for j in 0..4 {
+            a[&[i, j]] = (i * 4 + j) as f32;
+        }
+    }
+    let identity = NDArray::<f32>::from_slice(&[2, 2], &[1.0, 0.0, 0.0, 1.0]);
+    let mut c = NDArray::<f32>::new(&[4, 4], 0.0);
+    let inner = NDArray::copy(&a.view(&[NDRange::new(1, 3), NDRange::new(1, 3)]));
+    c.view_mut(&[NDRange::new(0, 2), NDRange::new(2, 4)]).gemm(1.0, &a.view(&[NDRange::new(1, 3), NDRange::new(1, 3)]), &identity, 0.0);
+    assert!(c.view(&[NDRange::new(0, 2), NDRange::new(2, 4)]) == inner);
+    c.view_mut(&[NDRange::new(2, 4), NDRange::new(0, 2)]).gemm(1.0, &a.view(&[NDRange::all().step(-2), NDRange::new(1, 3)]), &identity, 0.0);
+    assert!(c.view(&[NDRange::index(2), NDRange::new(0, 2)]) == NDArray::<f32>::from_slice(&[2], &[9.0, 10.0]));
+    assert!(c.view(&[NDRange::index(3), NDRange::new(0, 2)]) == NDArray::<f32>::from_slice(&[2], &[1.0, 2.0]));
+    let mut y = NDArray::<f32>::new(&[3], 0.0);
+    y.view_mut(&[NDRange::all().step(-1)]).gemv(1.0, &a.view(&[NDRange::all(), NDRange::all().step(3)]), &NDArray::<f32>::new(&[2], 1.0), 0.0);
+    assert!(y == NDArray::<f32>::from_slice(&[3], &[19.0, 11.0, 3.0]));
+}
+
+#[test]
+fn gemm_transposed() {
+    let a = NDArray::<f64>::from_slice(&[2, 3], &[1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+    let mut c = NDArray::<f64>::new(&[3, 3], 0.0);
+    c.gemm(1.0, &a.transposed(), &a, 0.0);
+    assert!(c == NDArray::<f64>::from_slice(&[3, 3], &[17.0, 22.0, 27.0, 22.0, 29.0, 36.0, 27.0, 36.0, 45.0]));
+    let mut c = NDArray::<f64>::new(&[2, 2], 0.0);
+    c.transposed_mut().gemm(1.0, &a, &a.transposed(), 0.0);
+    assert!(c == NDArray::<f64>::from_slice(&[2, 2], &[14.0, 32.0, 32.0, 77.0]));
+}
+
+#[test]
+fn gemm_large() {
+    // Larger than a block of the rust-blas gemm
+    let (m, k, n) = (70, 130, 65);
+    let mut a = NDArray::<f64>::new(&[m, k], 0.0);
+    let mut b = NDArray::<f64>::new(&[k, n], 0.0);
+    for i in 0..m {
+        for l in 0..k {
+            a[&[i, l]] = ((i * 7 + l * 3) % 11) as f64 - 5.0;
+        }
+    }
+    for l in 0..k {
+        for j in 0..n {
+            b[&[l, j]] = ((l * 5 + j) % 13) as f64 - 6.0;
+        }
+    }
+    let mut c = NDArray::<f64>::new(&[m, n], 1.0);
+    c.gemm(2.0, &a, &b, 0.5);
+    for i in 0..m {
+        for j in 0..n {
+            let mut expected = 0.5;
+            for l in 0..k {
+                expected += 2.0 * a[&[i, l]] * b[&[l, j]];
+            }
+            assert!(c[&[i, j]] == expected);
+        }
+    }
+}
+
+#[test]
+fn try_shape_errors() {
+    let mut y = NDArray::<f64>::new(&[3], 0.0);
+    let x = NDArray::<f64>::new(&[4], 1.0);
+    let a = NDArray::<f64>::new(&[3, 3], 1.0);
+    match y.try_axpy(1.0, &x) {
+        Err(Error::ShapeMismatch { expected, found }) => {
+            assert!(expected == vec![3]);
+            assert!(found == vec![4]);
+        },
+        _ => panic!("expected a shape mismatch"),
+    }
+    assert!(y.try_dot(&x).is_err());
+    match y.try_gemv(1.0, &a, &x, 0.0) {
+        Err(Error::ShapeMismatch { expected, found }) => {
+            assert!(expected == vec![3, 4]);
+            assert!(found == vec![3, 3]);
+        },
+        _ => panic!("expected a shape mismatch"),
+    }
+    match y.try_gemm(1.0, &a, &a, 0.0) {
+        Err(Error::DimensionMismatch { expected : 2, found : 1 }) => {},
+        _ => panic!("expected a dimension mismatch"),
+    }
+    assert!(y == NDArray::<f64>::new(&[3], 0.0));
+    y.try_gemv(1.0, &a, &NDArray::<f64>::new(&[3], 1.0), 0.0).unwrap();
+    assert!(y == NDArray::<f64>::new(&[3], 3.0));
+    let mut c = NDArray::<f64>::new(&[3, 3], 0.0);
+    c.try_gemm(1.0, &a, &a, 0.0).unwrap();
+    assert!(c.try_dot(&a).is_err());
+}
+
+#[test]
+fn dotc() {
+    let x = NDArray::<c64>::from_slice(&[2], &[c64::new(1.0, 2.0), c64::new(0.0, -1.0)]);
+    let y = NDArray::<c64>::from_slice(&[2], &[c64::new(3.0, 1.0), c64::new(2.0, 2.0)]);
+    assert!(x.dot(&y) == c64::new(3.0, 5.0));
+    assert!(x.dotc(&y) == c64::new(3.0, -3.0));
+    assert!(x.dotc(&x) == c64::new(6.0, 0.0));
+    let a = NDArray::<f32>::from_slice(&[3], &[1.0, 2.0, 3.0]);
+    assert!(a.dotc(&a.view(&[NDRange::all().step(-1)])) == 10.0);
+    assert!(a.try_dotc(&NDArray::<f32>::new(&[2], 1.0)).is_err());
+}
+
+#[test]
+fn swap_copy() {
+    let mut a = NDArray::<f64>::from_slice(&[2, 2], &[1.0, 2.0, 3.0, 4.0]);
+    let mut b = NDArray::<f64>::from_slice(&[2, 2], &[5.0, 6.0, 7.0, 8.0]);
+    a.swap(&mut b);
+    assert!(a == NDArray::<f64>::from_slice(&[2, 2], &[5.0, 6.0, 7.0, 8.0]));
+    assert!(b == NDArray::<f64>::from_slice(&[2, 2], &[1.0, 2.0, 3.0, 4.0]));
+    a.transposed_mut().swap(&mut b);
+    assert!(a == NDArray::<f64>::from_slice(&[2, 2], &[1.0, 3.0, 2.0, 4.0]));
+    assert!(b == NDArray::<f64>::from_slice(&[2, 2], &[5.0, 7.0, 6.0, 8.0]));
+    assert!(a.try_swap(&mut NDArray::<f64>::new(&[4], 0.0)).is_err());
+
+    let mut c = NDArray::<c32>::new(&[3], c32::new(0.0, 0.0));
+    let d = NDArray::<c32>::from_slice(&[3], &[c32::new(1.0, 1.0), c32::new(2.0, 0.0), c32::new(0.0, 3.0)]);
+    c.view_mut(&[NDRange::all().step(-1)]).copy(&d);
+    assert!(c == NDArray::<c32>::from_slice(&[3], &[c32::new(0.0, 3.0), c32::new(2.0, 0.0), c32::new(1.0, 1.0)]));
+    match c.try_copy(&NDArray::<c32>::new(&[2], c32::new(0.0, 0.0))) {
+        Err(Error::ShapeMismatch { expected, found }) => {
+            assert!(expected == vec![3]);
+            assert!(found == vec![2]);
+        },
+        _ => panic!("expected a shape mismatch"),
+    }
+}
+
+#[test]
+fn rot_rotg() {
+    let mut ab = NDArray::<f64>::from_slice(&[2], &[3.0, 4.0]);
+    let (c, s) = ab.rotg();
+    assert!((c - 0.6).abs() < 1e-12 && (s - 0.8).abs() < 1e-12);
+    assert!((ab[&[0]] - 5.0).abs() < 1e-12);
+    let mut x = NDArray::<f64>::from_slice(&[2], &[3.0, 1.0]);
+    let mut y = NDArray::<f64>::from_slice(&[2], &[4.0, 2.0]);
+    x.rot(&mut y, c, s);
+    assert!((x[&[0]] - 5.0).abs() < 1e-12 && y[&[0]].abs() < 1e-12);
+    assert!((x[&[1]] - 2.2).abs() < 1e-12 && (y[&[1]] - 0.4).abs() < 1e-12);
+    assert!(x.try_rotg().is_ok());
+    assert!(NDArray::<f64>::new(&[3], 1.0).try_rotg().is_err());
+
+    let mut ab = NDArray::<c64>::from_slice(&[2], &[c64::new(0.0, 3.0), c64::new(4.0, 0.0)]);
+    let (c, s) = ab.rotg();
+    let mut x = NDArray::<c64>::from_slice(&[1], &[c64::new(0.0, 3.0)]);
+    let mut y = NDArray::<c64>::from_slice(&[1], &[c64::new(4.0, 0.0)]);
+    x.rot(&mut y, c, s);
+    assert!((x[&[0]] - ab[&[0]]).abs() < 1e-12 && y[&[0]].abs() < 1e-12);
+    assert!((ab[&[0]].abs() - 5.0).abs() < 1e-12 && ab[&[1]] == c64::new(4.0, 0.0));
+}
+
+#[test]
+fn rotm() {
+    let mut x = NDArray::<f32>::from_slice(&[2], &[1.0, 2.0]);
+    let mut y = NDArray::<f32>::from_slice(&[2], &[3.0, 4.0]);
+    x.rotm(&mut y, &[-1.0, 1.0, 2.0, 3.0, 4.0]);
+    assert!(x == NDArray::<f32>::from_slice(&[2], &[10.0, 14.0]));
+    assert!(y == NDArray::<f32>::from_slice(&[2], &[14.0, 20.0]));
+    x.rotm(&mut y, &[0.0, 9.0, 0.5, 0.0, 9.0]);
+    assert!(x == NDArray::<f32>::from_slice(&[2], &[10.0, 14.0]));
+    assert!(y == NDArray::<f32>::from_slice(&[2], &[19.0, 27.0]));
+    x.rotm(&mut y, &[1.0, 0.0, 9.0, 9.0, 1.0]);
+    assert!(x == NDArray::<f32>::from_slice(&[2], &[19.0, 27.0]));
+    assert!(y == NDArray::<f32>::from_slice(&[2], &[9.0, 13.0]));
+    x.rotm(&mut y, &[-2.0, 9.0, 9.0, 9.0, 9.0]);
+    assert!(x == NDArray::<f32>::from_slice(&[2], &[19.0, 27.0]));
+    assert!(x.try_rotm(&mut y, &[-2.0]).is_err());
+    let mut z = NDArray::<c32>::new(&[2], c32::new(1.0, 0.0));
+    match z.try_rotm(&mut NDArray::<c32>::new(&[2], c32::new(1.0, 0.0)), &[c32::new(-2.0, 0.0); 5]) {
+        Err(Error::UnsupportedType(_)) => {},
+        _ => panic!("expected an unsupported type"),
+    }
+}
+
+#[test]
+fn iamax() {
+    let mut a = NDArray::<f32>::from_slice(&[2, 3], &[1.0, -7.0, 3.0, 7.0, 0.0, -2.0]);
+    assert!(a.iamax() == Some(vec![0, 1]));
+    assert!(a.transposed_mut().iamax() == Some(vec![0, 1]));
+    assert!(a.view_mut(&[NDRange::all(), NDRange::all().step(-1)]).iamax() == Some(vec![0, 1]));
+    let b = NDArray::<c64>::from_slice(&[3], &[c64::new(3.0, 0.0), c64::new(-2.0, 2.0), c64::new(0.0, 4.0)]);
+    assert!(b.iamax() == Some(vec![1]));
+    assert!(NDArray::<f64>::new(&[0], 1.0).iamax() == None);
+}
+
+#[test]
+fn ger() {
+    let x = NDArray::<f64>::from_slice(&[2], &[1.0, 2.0]);
+    let y = NDArray::<f64>::from_slice(&[3], &[1.0, 0.0, -1.0]);
+    let mut a = NDArray::<f64>::new(&[2, 3], 1.0);
+    a.ger(2.0, &x, &y);
+    assert!(a == NDArray::<f64>::from_slice(&[2, 3], &[3.0, 1.0, -1.0, 5.0, 1.0, -3.0]));
+    let mut at = NDArray::<f64>::new(&[3, 2], 0.0);
+    at.transposed_mut().ger(1.0, &x, &y);
+    assert!(at == NDArray::<f64>::from_slice(&[3, 2], &[1.0, 2.0, 0.0, 0.0, -1.0, -2.0]));
+    match a.try_ger(1.0, &y, &x) {
+        Err(Error::ShapeMismatch { expected, found }) => {
+            assert!(expected == vec![3, 2]);
+            assert!(found == vec![2, 3]);
+        },
+        _ => panic!("expected a shape mismatch"),
+    }
+
+    let x = NDArray::<c32>::from_slice(&[1], &[c32::new(1.0, 1.0)]);
+    let y = NDArray::<c32>::from_slice(&[2], &[c32::new(0.0, 1.0), c32::new(2.0, 0.0)]);
+    let mut a = NDArray::<c32>::new(&[1, 2], c32::new(0.0, 0.0));
+    a.ger(c32::new(1.0, 0.0), &x, &y);
+    assert!(a == NDArray::<c32>::from_slice(&[1, 2], &[c32::new(-1.0, 1.0), c32::new(2.0, 2.0)]));
+    let mut a = NDArray::<c32>::new(&[1, 2], c32::new(0.0, 0.0));
+    a.gerc(c32::new(1.0, 0.0), &x, &y);
+    assert!(a == NDArray::<c32>::from_slice(&[1, 2], &[c32::new(1.0, -1.0), c32::new(2.0, 2.0)]));
+}
+
+#[test]
+fn symv_hemv() {
+    // Only the referenced triangle holds meaningful values
+    let upper = NDArray::<f32>::from_slice(&[2, 2], &[1.0, 2.0, 99.0, 3.0]);
+    let lower = NDArray::<f32>::from_slice(&[2, 2], &[1.0, 99.0, 2.0, 3.0]);
+    let x = NDArray::<f32>::from_slice(&[2], &[1.0, 1.0]);
+    let mut y = NDArray::<f32>::new(&[2], 1.0);
+    y.symv(1.0, &upper, Uplo::Upper, &x, 2.0);
+    assert!(y == NDArray::<f32>::from_slice(&[2], &[5.0, 7.0]));
+    y.symv(1.0, &lower, Uplo::Lower, &x, 0.0);
+    assert!(y == NDArray::<f32>::from_slice(&[2], &[3.0, 5.0]));
+    y.hemv(1.0, &lower.transposed(), Uplo::Upper, &x, 0.0);
+    assert!(y == NDArray::<f32>::from_slice(&[2], &[3.0, 5.0]));
+    assert!(y.try_symv(1.0, &NDArray::<f32>::new(&[2, 3], 1.0), Uplo::Upper, &x, 0.0).is_err());
+
+    let a = NDArray::<c64>::from_slice(&[2, 2], &[c64::new(2.0, 0.0), c64::new(0.0, -1.0), c64::new(9.0, 9.0), c64::new(3.0, 0.0)]);
+    let x = NDArray::<c64>::from_slice(&[2], &[c64::new(1.0, 0.0), c64::new(0.0, 1.0)]);
+    let mut y = NDArray::<c64>::new(&[2], c64::new(0.0, 0.0));
+    y.hemv(c64::new(1.0, 0.0), &a, Uplo::Upper, &x, c64::new(0.0, 0.0));
+    assert!(y == NDArray::<c64>::from_slice(&[2], &[c64::new(3.0, 0.0), c64::new(0.0, 4.0)]));
+    match y.try_symv(c64::new(1.0, 0.0), &a, Uplo::Upper, &x, c64::new(0.0, 0.0)) {
+        Err(Error::UnsupportedType(_)) => {},
+        _ => panic!("expected an unsupported type"),
+    }
+}
+
+#[test]
+fn trmv_trsv() {
+    let a = NDArray::<f64>::from_slice(&[3, 3], &[2.0, 1.0, 1.0, 99.0, 4.0, 2.0, 99.0, 99.0, 8.0]);
+    let b = NDArray::<f64>::from_slice(&[3], &[1.0, 2.0, 3.0]);
+    let mut x = NDArray::<f64>::copy(&b);
+    x.trmv(&a, Uplo::Upper, Transpose::NoTrans, Diag::NonUnit);
+    assert!(x == NDArray::<f64>::from_slice(&[3], &[7.0, 14.0, 24.0]));
+    x.trsv(&a, Uplo::Upper, Transpose::NoTrans, Diag::NonUnit);
+    assert!(x == b);
+    x.trmv(&a, Uplo::Upper, Transpose::Trans, Diag::Unit);
+    assert!(x == NDArray::<f64>::from_slice(&[3], &[1.0, 3.0, 8.0]));
+    x.trsv(&a.transposed(), Uplo::Lower, Transpose::NoTrans, Diag::Unit);
+    assert!(x == b);
+    x.view_mut(&[NDRange::all().step(-1)]).trmv(&a, Uplo::Lower, Transpose::NoTrans, Diag::NonUnit);
+    assert!(x == NDArray::<f64>::from_slice(&[3], &[503.0, 305.0, 6.0]));
+    assert!(x.try_trsv(&NDArray::<f64>::new(&[2, 2], 1.0), Uplo::Upper, Transpose::NoTrans, Diag::Unit).is_err());
+
+    let a = NDArray::<c32>::from_slice(&[2, 2], &[c32::new(1.0, 1.0), c32::new(0.0, 2.0), c32::new(0.0, 0.0), c32::new(2.0, 0.0)]);
+    let mut x = NDArray::<c32>::from_slice(&[2], &[c32::new(1.0, 0.0), c32::new(1.0, 0.0)]);
+    x.trmv(&a, Uplo::Upper, Transpose::ConjTrans, Diag::NonUnit);
+    assert!(x == NDArray::<c32>::from_slice(&[2], &[c32::new(1.0, -1.0), c32::new(2.0, -2.0)]));
+    x.trsv(&a, Uplo::Upper, Transpose::ConjTrans, Diag::NonUnit);
+    assert!(x == NDArray::<c32>::from_slice(&[2], &[c32::new(1.0, 0.0), c32::new(1.0, 0.0)]));
+}
+
+#[test]
+fn gemm_trans() {
+    let a = NDArray::<f32>::from_slice(&[2, 2], &[1.0, 2.0, 3.0, 4.0]);
+    let b = NDArray::<f32>::from_slice(&[2, 2], &[1.0, 0.0, 0.0, 1.0]);
+    let mut c = NDArray::<f32>::new(&[2, 2], 0.0);
+    c.gemm_trans(1.0, &a, Transpose::Trans, &b, Transpose::NoTrans, 0.0);
+    assert!(c == NDArray::<f32>::from_slice(&[2, 2], &[1.0, 3.0, 2.0, 4.0]));
+    // Writing through a transposed view stores the result column-major
+    let mut c = NDArray::<f32>::new(&[2, 2], 0.0);
+    c.transposed_mut().gemm_trans(1.0, &a, Transpose::Trans, &b, Transpose::NoTrans, 0.0);
+    assert!(c == a);
+    assert!(c.try_gemm_trans(1.0, &a, Transpose::NoTrans, &NDArray::<f32>::new(&[3, 2], 1.0), Transpose::NoTrans, 0.0).is_err());
+
+    let a = NDArray::<c64>::from_slice(&[2, 2], &[c64::new(0.0, 1.0), c64::new(0.0, 0.0), c64::new(0.0, 0.0), c64::new(1.0, 0.0)]);
+    let b = NDArray::<c64>::from_slice(&[2, 2], &[c64::new(1.0, 0.0), c64::new(0.0, 0.0), c64::new(0.0, 0.0), c64::new(1.0, 0.0)]);
+    let mut c = NDArray::<c64>::new(&[2, 2], c64::new(0.0, 0.0));
+    c.gemm_trans(c64::new(1.0, 0.0), &a, Transpose::ConjTrans, &b, Transpose::NoTrans, c64::new(0.0, 0.0));
+    assert!(c == NDArray::<c64>::from_slice(&[2, 2], &[c64::new(0.0, -1.0), c64::new(0.0, 0.0), c64::new(0.0, 0.0), c64::new(1.0, 0.0)]));
+}
+
+#[test]
+fn gemv_trans() {
+    let a = NDArray::<f32>::from_slice(&[2, 2], &[1.0, 2.0, 3.0, 4.0]);
+    let x = NDArray::<f32>::from_slice(&[2], &[1.0, 1.0]);
+    let mut y = NDArray::<f32>::new(&[2], 0.0);
+    y.gemv_trans(1.0, &a, Transpose::NoTrans, &x, 0.0);
+    assert!(y == NDArray::<f32>::from_slice(&[2], &[3.0, 7.0]));
+    y.gemv_trans(1.0, &a, Transpose::Trans, &x, 0.0);
+    assert!(y == NDArray::<f32>::from_slice(&[2], &[4.0, 6.0]));
+    // A transposed view of a is stored column-major
+    y.gemv_trans(1.0, &a.transposed(), Transpose::Trans, &x, 0.0);
+    assert!(y == NDArray::<f32>::from_slice(&[2], &[3.0, 7.0]));
+    let b = NDArray::<f32>::new(&[2, 3], 1.0);
+    assert!(y.try_gemv_trans(1.0, &b, Transpose::NoTrans, &x, 0.0).is_err());
+    y.gemv_trans(1.0, &b, Transpose::NoTrans, &NDArray::<f32>::new(&[3], 1.0), 0.0);
+    assert!(y == NDArray::<f32>::from_slice(&[2], &[3.0, 3.0]));
+
+    let a = NDArray::<c64>::from_slice(&[2, 2], &[c64::new(0.0, 1.0), c64::new(2.0, 0.0), c64::new(0.0, 0.0), c64::new(1.0, 0.0)]);
+    let x = NDArray::<c64>::new(&[2], c64::new(1.0, 0.0));
+    let mut y = NDArray::<c64>::new(&[2], c64::new(0.0, 0.0));
+    y.gemv_trans(c64::new(1.0, 0.0), &a, Transpose::ConjTrans, &x, c64::new(0.0, 0.0));
+    assert!(y == NDArray::<c64>::from_slice(&[2], &[c64::new(0.0, -1.0), c64::new(3.0, 0.0)]));
+}
+
+#[test]
+fn symm() {
+    let a = NDArray::<f64>::from_slice(&[2, 2], &[1.0, 2.0, 99.0, 3.0]);
+    let b = NDArray::<f64>::from_slice(&[2, 2], &[1.0, 1.0, 0.0, 1.0]);
+    let mut c = NDArray::<f64>::new(&[2, 2], 1.0);
+    c.symm(1.0, &a, Side::Left, Uplo::Upper, &b, 0.0);
+    assert!(c == NDArray::<f64>::from_slice(&[2, 2], &[1.0, 3.0, 2.0, 5.0]));
+    c.symm(1.0, &a.transposed(), Side::Right, Uplo::Lower, &b, 1.0);
+    assert!(c == NDArray::<f64>::from_slice(&[2, 2], &[4.0, 8.0, 4.0, 8.0]));
+    assert!(c.try_symm(1.0, &NDArray::<f64>::new(&[3, 3], 1.0), Side::Left, Uplo::Upper, &b, 0.0).is_err());
+}
+
+#[test]
+fn syrk_syr2k() {
+    let a = NDArray::<f32>::from_slice(&[2, 2], &[1.0, 2.0, 3.0, 4.0]);
+    // Only the requested triangle of c is written
+    let mut c = NDArray::<f32>::new(&[2, 2], -1.0);
+    c.syrk(1.0, &a, Uplo::Upper, Transpose::NoTrans, 0.0);
+    assert!(c == NDArray::<f32>::from_slice(&[2, 2], &[5.0, 11.0, -1.0, 25.0]));
+    let mut c = NDArray::<f32>::new(&[2, 2], -1.0);
+    c.syrk(1.0, &a, Uplo::Lower, Transpose::Trans, 0.0);
+    assert!(c == NDArray::<f32>::from_slice(&[2, 2], &[10.0, -1.0, 14.0, 20.0]));
+
+    let id = NDArray::<f32>::from_slice(&[2, 2], &[1.0, 0.0, 0.0, 1.0]);
+    let mut c = NDArray::<f32>::new(&[2, 2], 0.0);
+    c.syr2k(1.0, &id, &a, Uplo::Upper, Transpose::NoTrans, 0.0);
+    assert!(c == NDArray::<f32>::from_slice(&[2, 2], &[2.0, 5.0, 0.0, 8.0]));
+    assert!(c.try_syr2k(1.0, &id, &NDArray::<f32>::new(&[3, 2], 1.0), Uplo::Upper, Transpose::NoTrans, 0.0).is_err());
+}
+
+#[test]
+fn herk() {
+    let a = NDArray::<c64>::from_slice(&[2, 2], &[c64::new(1.0, 1.0), c64::new(2.0, 0.0), c64::new(0.0, 0.0), c64::new(0.0, 1.0)]);
+    let mut c = NDArray::<c64>::new(&[2, 2], c64::new(0.0, 0.0));
+    c.herk(c64::new(1.0, 0.0), &a, Uplo::Upper, Transpose::NoTrans, c64::new(0.0, 0.0));
+    assert!(c == NDArray::<c64>::from_slice(&[2, 2], &[c64::new(6.0, 0.0), c64::new(0.0, -2.0), c64::new(0.0, 0.0), c64::new(1.0, 0.0)]));
+    match c.try_herk(c64::new(1.0, 1.0), &a, Uplo::Upper, Transpose::NoTrans, c64::new(0.0, 0.0)) {
+        Err(Error::InvalidArgument(_)) => {},
+        _ => panic!("expected an invalid argument"),
+    }
+    let mut d = NDArray::<c32>::new(&[2, 2], c32::new(0.0, 0.0));
+    assert!(d.try_herk(c32::new(1.0, 0.0), &NDArray::<c32>::new(&[2, 2], c32::new(1.0, 0.0)), Uplo::Lower, Transpose::Trans, c32::new(0.0, -1.0)).is_err());
+    assert!(d == NDArray::<c32>::new(&[2, 2], c32::new(0.0, 0.0)));
+}
+
+#[test]
+fn trmm_trsm() {
+    let a = NDArray::<f64>::from_slice(&[2, 2], &[2.0, 1.0, 99.0, 4.0]);
+    let ones = NDArray::<f64>::new(&[2, 2], 1.0);
+    let mut b = NDArray::<f64>::copy(&ones);
+    b.trmm(1.0, &a, Side::Left, Uplo::Upper, Transpose::NoTrans, Diag::NonUnit);
+    assert!(b == NDArray::<f64>::from_slice(&[2, 2], &[3.0, 3.0, 4.0, 4.0]));
+    b.trsm(1.0, &a, Side::Left, Uplo::Upper, Transpose::NoTrans, Diag::NonUnit);
+    assert!(b == ones);
+    b.trmm(1.0, &a, Side::Right, Uplo::Upper, Transpose::NoTrans, Diag::NonUnit);
+    assert!(b == NDArray::<f64>::from_slice(&[2, 2], &[2.0, 5.0, 2.0, 5.0]));
+    b.trsm(2.0, &a, Side::Right, Uplo::Upper, Transpose::NoTrans, Diag::NonUnit);
+    assert!(b == NDArray::<f64>::new(&[2, 2], 2.0));
+    assert!(b.try_trsm(1.0, &NDArray::<f64>::new(&[3, 3], 1.0), Side::Left, Uplo::Upper, Transpose::NoTrans, Diag::Unit).is_err());
+}
+
+#[test]
+fn batched_gemm() {
+    let a = NDArray::<f32>::from_slice(&[2, 2, 2], &[1.0, 2.0, 3.0, 4.0, 1.0, 0.0, 0.0, 1.0]);
+    let b = NDArray::<f32>::from_slice(&[2, 2], &[0.0, 1.0, 1.0, 0.0]);
+    let mut c = NDArray::<f32>::new(&[2, 2, 2], 1.0);
+    c.batched_gemm(1.0, &a, &b, 0.0);
+    assert!(c == NDArray::<f32>::from_slice(&[2, 2, 2], &[2.0, 1.0, 4.0, 3.0, 0.0, 1.0, 1.0, 0.0]));
+    let id = NDArray::<f32>::from_slice(&[2, 2], &[1.0, 0.0, 0.0, 1.0]);
+    c.batched_gemm(1.0, &id, &a, 1.0);
+    assert!(c == NDArray::<f32>::from_slice(&[2, 2, 2], &[3.0, 3.0, 7.0, 7.0, 1.0, 1.0, 1.0, 1.0]));
+    assert!(c.try_batched_gemm(1.0, &NDArray::<f32>::new(&[3, 2, 2], 1.0), &b, 0.0).is_err());
+    assert!(c.try_batched_gemm(1.0, &a, &NDArray::<f32>::new(&[3, 2], 1.0), 0.0).is_err());
+    assert!(NDArray::<f32>::new(&[2, 2], 0.0).try_batched_gemm(1.0, &a, &b, 0.0).is_err());
+
+    let a = NDArray::<c64>::from_slice(&[2, 1, 1], &[c64::new(0.0, 1.0), c64::new(2.0, 0.0)]);
+    let b = NDArray::<c64>::from_slice(&[1, 1], &[c64::new(0.0, 1.0)]);
+    let mut c = NDArray::<c64>::new(&[2, 1, 1], c64::new(0.0, 0.0));
+    c.batched_gemm(c64::new(1.0, 0.0), &a, &b, c64::new(0.0, 0.0));
+    assert!(c == NDArray::<c64>::from_slice(&[2, 1, 1], &[c64::new(-1.0, 0.0), c64::new(0.0, 2.0)]));
+}
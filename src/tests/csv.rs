@@ -1,5 +1,10 @@
-use array::{NDArray, NDData};
+use std::error::Error as StdError;
+use std::fs::File;
+use std::io::Write;
+
+use array::{NDArray, NDData, NDViewable, NDRange};
 use array::csv::CSVFile;
+use error::Error;
 
 #[test]
 fn array() {
@@ -10,7 +15,7 @@ fn array() {
             array[&[i, j]] = (i * 2 + j * 5) as f64;
         }
     }
-    assert!(csvfile.write_data(&array) == Ok(()));
+    csvfile.write_data(&array).unwrap();
     let array2 : NDArray<f64> = csvfile.read_array().unwrap();
     assert!(array2.shape() == array.shape());
     assert!(array2.strides() == array.strides());
@@ -30,7 +35,7 @@ fn rows() {
             array[&[i, j]] = (i * 2 + j * 5) as f64;
         }
     }
-    assert!(csvfile.write_data(&array) == Ok(()));
+    csvfile.write_data(&array).unwrap();
     for i in 0..array.shape()[0] {
         let row : NDArray<f64> = csvfile.read_row(i).unwrap();
         for j in 0..row.shape()[0] {
@@ -48,7 +53,7 @@ fn columns() {
             array[&[i, j]] = (i * 2 + j * 5) as f64;
         }
     }
-    assert!(csvfile.write_data(&array) == Ok(()));
+    csvfile.write_data(&array).unwrap();
     for j in 0..array.shape()[1] {
         let column : NDArray<f64> = csvfile.read_column(j).unwrap();
         for i in 0..column.shape()[0] {
@@ -56,3 +61,63 @@ fn columns() {
         }
     }
 }
+
+#[test]
+fn view() {
+    let mut array = NDArray::<f64>::new(&[10, 5], 0.0);
+    let mut csvfile = CSVFile::new("/tmp/test4.csv");
+    for i in 0..array.shape()[0] {
+        for j in 0..array.shape()[1] {
+            array[&[i, j]] = (i * 2 + j * 5) as f64;
+        }
+    }
+    csvfile.write_data(&array.view(&[NDRange::all().step(-3), NDRange::new(1, 4)])).unwrap();
+    let array2 : NDArray<f64> = csvfile.read_array().unwrap();
+    assert!(array2.shape() == &[4, 3]);
+    for i in 0..array2.shape()[0] {
+        for j in 0..array2.shape()[1] {
+            assert!(array[&[9 - i * 3, j + 1]] == array2[&[i,j]]);
+        }
+    }
+    csvfile.write_data(&array.view(&[NDRange::all(), NDRange::index(2)])).unwrap();
+    let row : NDArray<f64> = csvfile.read_row(0).unwrap();
+    assert!(row == array.view(&[NDRange::all(), NDRange::index(2)]));
+}
+
+#[test]
+fn missing_file() {
+    let csvfile = CSVFile::new("/tmp/rds_missing_file.csv");
+    match csvfile.read_array::<f64>() {
+        Err(ref e @ Error::Io(_)) => assert!(StdError::source(e).is_some()),
+        Err(e) => panic!("unexpected error {}", e),
+        Ok(_) => panic!("reading a missing file succeeded"),
+    }
+}
+
+#[test]
+fn ragged_rows() {
+    let mut file = File::create("/tmp/test5.csv").unwrap();
+    file.write_all(b"1,2,3\n4,5\n").unwrap();
+    let mut csvfile = CSVFile::new("/tmp/test5.csv");
+    csvfile.flexible = true;
+    match csvfile.read_array::<f64>() {
+        Err(Error::ShapeMismatch { expected, found }) => {
+            assert!(expected == vec![3]);
+            assert!(found == vec![2]);
+        },
+        Err(e) => panic!("unexpected error {}", e),
+        Ok(_) => panic!("reading ragged rows succeeded"),
+    }
+}
+
+#[test]
+fn parse_error_source() {
+    let mut file = File::create("/tmp/test6.csv").unwrap();
+    file.write_all(b"1,2,3\n4,5\n").unwrap();
+    let csvfile = CSVFile::new("/tmp/test6.csv");
+    match csvfile.read_array::<f64>() {
+        Err(ref e @ Error::Parse { .. }) => assert!(StdError::source(e).is_some()),
+        Err(e) => panic!("unexpected error {}", e),
+        Ok(_) => panic!("reading ragged rows succeeded"),
+    }
+}
@@ -0,0 +1,50 @@
+use types::complex::c32;
+use array::{NDArray, NDSliceable, NDViewable, NDRange};
+
+#[test]
+fn nested_brackets() {
+    let array = NDArray::<i32>::from_slice(&[2, 3], &[1, 2, 3, 4, 50, 6]);
+    assert_eq!(format!("{}", array), "[[ 1,  2,  3],\n [ 4, 50,  6]]");
+    let cube = NDArray::<u8>::from_slice(&[2, 2, 2], &[1, 2, 3, 4, 5, 6, 7, 8]);
+    assert_eq!(format!("{}", cube), "[[[1, 2],\n  [3, 4]],\n\n [[5, 6],\n  [7, 8]]]");
+    assert_eq!(format!("{}", NDArray::<i64>::new(&[], -3)), "-3");
+    assert_eq!(format!("{}", NDArray::<i64>::new(&[0], 0)), "[]");
+}
+
+#[test]
+fn slices_and_views() {
+    let array = NDArray::<i32>::from_slice(&[2, 3], &[1, 2, 3, 4, 5, 6]);
+    assert_eq!(format!("{}", array.slice(&[1])), "[4, 5, 6]");
+    assert_eq!(format!("{}", array.view(&[NDRange::all(), NDRange::all().step(-2)])), "[[3, 1],\n [6, 4]]");
+}
+
+#[test]
+fn precision() {
+    let array = NDArray::<f64>::from_slice(&[3], &[1.0, 2.5, -0.125]);
+    assert_eq!(format!("{}", array), "[     1,    2.5, -0.125]");
+    assert_eq!(format!("{:.2}", array), "[ 1.00,  2.50, -0.12]");
+    let complex = NDArray::<c32>::from_slice(&[2], &[c32::new(1.0, -1.0), c32::new(0.5, 2.0)]);
+    assert_eq!(format!("{:.1}", complex), "[1.0-1.0j, 0.5+2.0j]");
+}
+
+#[test]
+fn elision() {
+    let mut array = NDArray::<u32>::new(&[2000], 0);
+    for i in 0..2000 {
+        array[&[i]] = i as u32;
+    }
+    assert_eq!(format!("{}", array), "[   0,    1,    2, ..., 1997, 1998, 1999]");
+    assert!(format!("{:#}", array).matches(",").count() == 1999);
+    let matrix = NDArray::<u8>::new(&[100, 100], 1);
+    let printed = format!("{}", matrix);
+    assert!(printed.starts_with("[[1, 1, 1, ..., 1, 1, 1],\n [1, 1, 1, ..., 1, 1, 1],\n"));
+    assert!(printed.contains(" ...,\n"));
+    assert!(printed.lines().count() == 7);
+}
+
+#[test]
+fn debug() {
+    let array = NDArray::<i32>::from_slice(&[2, 2], &[1, 2, 3, 4]);
+    assert_eq!(format!("{:?}", array), "NDArray([[1, 2],\n         [3, 4]], shape=[2, 2])");
+    assert_eq!(format!("{:?}", array.slice(&[0])), "NDSlice([1, 2], shape=[2])");
+}
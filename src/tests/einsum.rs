@@ -0,0 +1,69 @@
+use types::complex::c64;
+use array::{NDArray, NDData, NDViewable};
+use array::einsum::{einsum, try_einsum};
+use blas::Blas;
+use error::Error;
+
+#[test]
+fn products() {
+    let a = NDArray::<f64>::from_slice(&[2, 2], &[1.0, 2.0, 3.0, 4.0]);
+    let b = NDArray::<f64>::from_slice(&[2, 2], &[5.0, 6.0, 7.0, 8.0]);
+    let ab = NDArray::<f64>::from_slice(&[2, 2], &[19.0, 22.0, 43.0, 50.0]);
+    assert!(einsum("ij,jk->ik", &[&a, &b]) == ab);
+    assert!(einsum("ij,jk", &[&a, &b]) == ab);
+    assert!(einsum("ij,jk->ki", &[&a, &b]) == ab.transposed());
+    assert!(einsum("ji,jk->ik", &[&a.transposed(), &b]) == ab);
+    let x = NDArray::<f64>::from_slice(&[2], &[1.0, 1.0]);
+    assert!(einsum("ij,j->i", &[&a, &x]) == NDArray::<f64>::from_slice(&[2], &[3.0, 7.0]));
+    assert!(einsum("j,jk->k", &[&x, &a]) == NDArray::<f64>::from_slice(&[2], &[4.0, 6.0]));
+    assert!(einsum("i,i->", &[&x, &x]) == NDArray::<f64>::new(&[], 2.0));
+    assert!(einsum("i,j->ij", &[&x, &NDArray::<f64>::from_slice(&[3], &[1.0, 2.0, 3.0])]) == NDArray::<f64>::from_slice(&[2, 3], &[1.0, 2.0, 3.0, 1.0, 2.0, 3.0]));
+
+    let c = NDArray::<c64>::from_slice(&[1, 2], &[c64::new(0.0, 1.0), c64::new(1.0, 0.0)]);
+    let d = NDArray::<c64>::from_slice(&[2, 1], &[c64::new(0.0, 1.0), c64::new(2.0, 0.0)]);
+    assert!(einsum("ij,jk->ik", &[&c, &d]) == NDArray::<c64>::from_slice(&[1, 1], &[c64::new(1.0, 0.0)]));
+}
+
+#[test]
+fn generic() {
+    let a = NDArray::<i32>::from_slice(&[3, 3], &[1, 2, 3, 4, 5, 6, 7, 8, 9]);
+    assert!(einsum("ii->", &[&a]) == NDArray::<i32>::new(&[], 15));
+    assert!(einsum("ii", &[&a]) == NDArray::<i32>::new(&[], 15));
+    assert!(einsum("ii->i", &[&a]) == NDArray::<i32>::from_slice(&[3], &[1, 5, 9]));
+    assert!(einsum("ij->ji", &[&a]) == a.transposed());
+    assert!(einsum("ij->j", &[&a]) == NDArray::<i32>::from_slice(&[3], &[12, 15, 18]));
+    assert!(einsum("ij,jk->ik", &[&a, &a]) == NDArray::<i32>::from_slice(&[3, 3], &[30, 36, 42, 66, 81, 96, 102, 126, 150]));
+    let id = NDArray::<i32>::from_slice(&[3, 3], &[1, 0, 0, 0, 1, 0, 0, 0, 1]);
+    assert!(einsum("ij,jk,kl->il", &[&a, &id, &a]) == einsum("ij,jk->ik", &[&a, &a]));
+
+    // Batch dimensions shared with the output use the generic loop
+    let batch = NDArray::<f32>::from_slice(&[2, 2, 2], &[1.0, 2.0, 3.0, 4.0, 1.0, 0.0, 0.0, 1.0]);
+    let mut expected = NDArray::<f32>::new(&[2, 2, 2], 0.0);
+    expected.batched_gemm(1.0, &batch, &batch, 0.0);
+    assert!(einsum("bij,bjk->bik", &[&batch, &batch]) == expected);
+}
+
+#[test]
+fn errors() {
+    let a = NDArray::<f32>::new(&[2, 3], 1.0);
+    let b = NDArray::<f32>::new(&[2, 3], 1.0);
+    match try_einsum("ij,jk->ik", &[&a, &b]) {
+        Err(Error::ShapeMismatch { expected, found }) => assert!(expected == vec![3, 3] && found == vec![2, 3]),
+        _ => panic!("expected a shape mismatch"),
+    }
+    match try_einsum("ijk,jk->i", &[&a, &b]) {
+        Err(Error::DimensionMismatch { expected, found }) => assert!(expected == 3 && found == 2),
+        _ => panic!("expected a dimension mismatch"),
+    }
+    match try_einsum("ij->ij", &[&a, &b]) {
+        Err(Error::InvalidArgument(_)) => {},
+        _ => panic!("expected an invalid argument for a wrong number of operands"),
+    }
+    for subscripts in &["i1,jk->ik", "ij,ij->k", "ij,ij->ii"] {
+        match try_einsum(subscripts, &[&a, &b]) {
+            Err(Error::Parse { .. }) => {},
+            _ => panic!("expected a parse error for {}", subscripts),
+        }
+    }
+    assert!(try_einsum("ij,ij->ij", &[&a, &b]).unwrap() == a);
+}
@@ -0,0 +1,561 @@
+extern crate flate2;
+
+use std::fs::{File, remove_file};
+use std::io::Write;
+use std::process::Command;
+
+use self::flate2::Compression;
+use self::flate2::write::ZlibEncoder;
+
+use types::complex::{c32, c64};
+use array::{NDArray, NDData, NDViewable};
+use array::hdf5::HDF5File;
+use array::map::NDMappable;
+use array::ndindex::NDIndex;
+use error::Error;
+
+const UNDEFINED : [u8;8] = [0xffu8;8];
+
+fn u16le(v : usize) -> Vec<u8> {
+    vec![v as u8, (v >> 8) as u8]
+}
+
+fn u32le(v : usize) -> Vec<u8> {
+    (0..4).map(|i| (v >> (8 * i)) as u8).collect()
+}
+
+fn u64le(v : u64) -> Vec<u8> {
+    (0..8).map(|i| (v >> (8 * i)) as u8).collect()
+}
+
+/// Append a version 2 object header to file and return its address. Checksums are left to zero
+/// as the reader does not verify them.
+fn object_header(file : &mut Vec<u8>, messages : &[(u8, Vec<u8>)]) -> u64 {
+    let address = file.len() as u64;
+    let size = messages.iter().fold(0, |acc, m| acc + 4 + m.1.len());
+    file.extend_from_slice(b"OHDR");
+    file.extend_from_slice(&[2, 0x2]);
+    file.extend(u32le(size));
+    for &(kind, ref data) in messages {
+        file.push(kind);
+        file.extend(u16le(data.len()));
+        file.push(0);
+        file.extend_from_slice(data);
+    }
+    file.extend_from_slice(&[0;4]);
+    address
+}
+
+fn dataspace(shape : &[usize]) -> (u8, Vec<u8>) {
+    let mut data = vec![2, shape.len() as u8, 0, 1];
+    for &s in shape {
+        data.extend(u64le(s as u64));
+    }
+    (0x1, data)
+}
+
+fn float64() -> Vec<u8> {
+    let mut data = vec![0x11, 0x20, 0x3f, 0];
+    data.extend(u32le(8));
+    data.extend_from_slice(&[0, 0, 64, 0, 52, 11, 0, 52]);
+    data.extend(u32le(1023));
+    data
+}
+
+fn link(name : &str, address : u64) -> (u8, Vec<u8>) {
+    let mut data = vec![1, 0, name.len() as u8];
+    data.extend_from_slice(name.as_bytes());
+    data.extend(u64le(address));
+    (0x6, data)
+}
+
+fn group(file : &mut Vec<u8>, links : &[(&str, u64)]) -> u64 {
+    let mut messages = vec![(0x2, [&[0u8, 0u8][..], &UNDEFINED, &UNDEFINED].concat())];
+    for &(name, address) in links {
+        messages.push(link(name, address));
+    }
+    object_header(file, &messages)
+}
+
+/// Build a file with a version 2 superblock containing:
+/// * /contiguous: a 2x3 big endian i16 dataset with a contiguous layout,
+/// * /complex: a complex compound dataset of length 3 with a compact layout,
+/// * /group/chunked: a 5x3 f64 dataset split in 2x2 chunks, shuffled and deflated, where the
+///   chunk at [2, 2] was never written,
+/// * /huge and /large: datasets whose shape overflows or exceeds the file size,
+/// * /wrapped: /group/chunked with a single chunk at an offset overflowing the dataset.
+fn build(path : &str) {
+    let mut file = Vec::<u8>::new();
+    file.extend_from_slice(&[0x89, b'H', b'D', b'F', b'\r', b'\n', 0x1a, b'\n', 2, 8, 8, 0]);
+    file.extend(u64le(0));
+    file.extend_from_slice(&UNDEFINED);
+    file.extend_from_slice(&[0;20]);
+
+    // /contiguous
+    let raw_address = file.len() as u64;
+    for i in 0..2 {
+        for j in 0..3 {
+            file.extend_from_slice(&[0, (i * 3 + j * 5) as u8]);
+        }
+    }
+    let mut layout = vec![3, 1];
+    layout.extend(u64le(raw_address));
+    layout.extend(u64le(12));
+    let contiguous = object_header(&mut file, &[
+        dataspace(&[2, 3]),
+        (0x3, [&[0x10, 0x09, 0, 0][..], &u32le(2), &[0, 0, 16, 0]].concat()),
+        (0x8, layout),
+    ]);
+
+    // /complex
+    let mut datatype = vec![0x16, 2, 0, 0];
+    datatype.extend(u32le(16));
+    for (name, offset) in vec![(b'r', 0), (b'i', 8)] {
+        datatype.extend_from_slice(&[name, 0, 0, 0, 0, 0, 0, 0]);
+        datatype.extend(u32le(offset));
+        datatype.extend_from_slice(&[0;28]);
+        datatype.extend(float64());
+    }
+    let mut layout = vec![3, 0];
+    layout.extend(u16le(48));
+    for v in &[1.0f64, -1.0, 2.5, 0.0, 0.0, 3.0] {
+        layout.extend(u64le(v.to_bits()));
+    }
+    let complex = object_header(&mut file, &[
+        dataspace(&[3]),
+        (0x3, datatype),
+        (0x8, layout),
+    ]);
+
+    // /group/chunked
+    let mut chunks = Vec::<(u64, usize, usize, usize)>::new();
+    for &(ci, cj) in &[(0, 0), (0, 2), (2, 0), (4, 0), (4, 2)] {
+        let mut raw = Vec::<u8>::new();
+        for i in ci..ci + 2 {
+            for j in cj..cj + 2 {
+                raw.extend(u64le(((i * 3 + j * 5) as f64).to_bits()));
+            }
+        }
+        let shuffled : Vec<u8> = (0..raw.len()).map(|k| raw[(k % 4) * 8 + k / 4]).collect();
+        let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(&shuffled).unwrap();
+        let compressed = encoder.finish().unwrap();
+        chunks.push((file.len() as u64, compressed.len(), ci, cj));
+        file.extend(compressed);
+    }
+    let btree = file.len() as u64;
+    file.extend_from_slice(b"TREE");
+    file.extend_from_slice(&[1, 0]);
+    file.extend(u16le(chunks.len()));
+    file.extend_from_slice(&UNDEFINED);
+    file.extend_from_slice(&UNDEFINED);
+    for &(address, size, ci, cj) in &chunks {
+        file.extend(u32le(size));
+        file.extend(u32le(0));
+        file.extend(u64le(ci as u64));
+        file.extend(u64le(cj as u64));
+        file.extend(u64le(0));
+        file.extend(u64le(address));
+    }
+    file.extend(u32le(0));
+    file.extend(u32le(0));
+    file.extend(u64le(6));
+    file.extend(u64le(4));
+    file.extend(u64le(0));
+    let mut layout = vec![3, 2, 3];
+    layout.extend(u64le(btree));
+    layout.extend(u32le(2));
+    layout.extend(u32le(2));
+    layout.extend(u32le(8));
+    let mut filters = vec![2, 2];
+    filters.extend([u16le(2), u16le(0), u16le(1), u32le(8)].concat());
+    filters.extend([u16le(1), u16le(0), u16le(1), u32le(6)].concat());
+    let chunked = object_header(&mut file, &[
+        dataspace(&[5, 3]),
+        (0x3, float64()),
+        (0x8, layout),
+        (0xb, filters),
+    ]);
+
+    // /huge and /large
+    let mut layout = vec![3, 1];
+    layout.extend(u64le(raw_address));
+    layout.extend(u64le(12));
+    let huge = object_header(&mut file, &[
+        dataspace(&[1 << 40, 1 << 40]),
+        (0x3, float64()),
+        (0x8, layout.clone()),
+    ]);
+    let large = object_header(&mut file, &[
+        dataspace(&[1 << 28]),
+        (0x3, float64()),
+        (0x8, layout),
+    ]);
+
+    // /wrapped
+    let wrapped_btree = file.len() as u64;
+    file.extend_from_slice(b"TREE");
+    file.extend_from_slice(&[1, 0]);
+    file.extend(u16le(1));
+    file.extend_from_slice(&UNDEFINED);
+    file.extend_from_slice(&UNDEFINED);
+    file.extend(u32le(chunks[0].1));
+    file.extend(u32le(0));
+    file.extend(u64le(u64::max_value()));
+    file.extend(u64le(0));
+    file.extend(u64le(0));
+    file.extend(u64le(chunks[0].0));
+    file.extend(u32le(0));
+    file.extend(u32le(0));
+    file.extend(u64le(6));
+    file.extend(u64le(4));
+    file.extend(u64le(0));
+    let mut layout = vec![3, 2, 3];
+    layout.extend(u64le(wrapped_btree));
+    layout.extend(u32le(2));
+    layout.extend(u32le(2));
+    layout.extend(u32le(8));
+    let mut filters = vec![2, 2];
+    filters.extend([u16le(2), u16le(0), u16le(1), u32le(8)].concat());
+    filters.extend([u16le(1), u16le(0), u16le(1), u32le(6)].concat());
+    let wrapped = object_header(&mut file, &[
+        dataspace(&[5, 3]),
+        (0x3, float64()),
+        (0x8, layout),
+        (0xb, filters),
+    ]);
+
+    let sub = group(&mut file, &[("chunked", chunked)]);
+    let root = group(&mut file, &[("contiguous", contiguous), ("complex", complex), ("group", sub), ("huge", huge), ("large", large), ("wrapped", wrapped)]);
+    let eof = file.len() as u64;
+    file[28..36].copy_from_slice(&u64le(eof));
+    file[36..44].copy_from_slice(&u64le(root));
+
+    File::create(path).unwrap().write_all(&file).unwrap();
+}
+
+#[test]
+fn list() {
+    const PATH : &'static str = "/tmp/rds_hdf5_1.h5";
+    build(PATH);
+    let hdf5file = HDF5File::new(PATH);
+    assert_eq!(hdf5file.groups("/").unwrap(), vec!["group".to_string()]);
+    assert_eq!(hdf5file.datasets("/").unwrap(), vec!["contiguous", "complex", "huge", "large", "wrapped"]);
+    assert_eq!(hdf5file.datasets("/group").unwrap(), vec!["chunked".to_string()]);
+    assert!(hdf5file.groups("/group").unwrap().is_empty());
+    assert_eq!(hdf5file.shape("/group/chunked").unwrap(), vec![5, 3]);
+    assert_eq!(hdf5file.shape("contiguous").unwrap(), vec![2, 3]);
+}
+
+#[test]
+fn read() {
+    const PATH : &'static str = "/tmp/rds_hdf5_2.h5";
+    build(PATH);
+    let hdf5file = HDF5File::new(PATH);
+
+    let contiguous : NDArray<f32> = hdf5file.read_dataset("/contiguous").unwrap();
+    assert_eq!(contiguous.shape(), &[2, 3]);
+    for i in 0..2 {
+        for j in 0..3 {
+            assert_eq!(contiguous[&[i, j]], (i * 3 + j * 5) as f32);
+        }
+    }
+
+    let complex : NDArray<c64> = hdf5file.read_dataset("/complex").unwrap();
+    assert!(complex == NDArray::from_slice(&[3], &[c64::new(1.0, -1.0), c64::new(2.5, 0.0), c64::new(0.0, 3.0)]));
+
+    let chunked : NDArray<i32> = hdf5file.read_dataset("/group/chunked").unwrap();
+    assert_eq!(chunked.shape(), &[5, 3]);
+    for i in 0..5 {
+        for j in 0..3 {
+            let expected = if (i == 2 || i == 3) && j == 2 { 0 } else { (i * 3 + j * 5) as i32 };
+            assert_eq!(chunked[&[i, j]], expected);
+        }
+    }
+}
+
+#[test]
+fn errors() {
+    const PATH : &'static str = "/tmp/rds_hdf5_3.h5";
+    build(PATH);
+    let hdf5file = HDF5File::new(PATH);
+    match hdf5file.read_dataset::<f64>("/missing") {
+        Err(Error::Format(_)) => {},
+        _ => panic!("Reading a missing dataset should fail")
+    }
+    match hdf5file.read_dataset::<f64>("/group") {
+        Err(Error::Format(_)) => {},
+        _ => panic!("Reading a group should fail")
+    }
+    match hdf5file.read_dataset::<f64>("/huge") {
+        Err(Error::Format(_)) => {},
+        _ => panic!("Reading a dataset whose size overflows should fail")
+    }
+    match hdf5file.read_dataset::<f64>("/large") {
+        Err(Error::Format(_)) => {},
+        _ => panic!("Reading a dataset larger than the file should fail")
+    }
+    // The chunk outside of the dataset is ignored
+    assert!(hdf5file.read_dataset::<f64>("/wrapped").unwrap() == NDArray::<f64>::new(&[5, 3], 0.0));
+    match hdf5file.datasets("/contiguous") {
+        Err(Error::Format(_)) => {},
+        _ => panic!("Listing a dataset should fail")
+    }
+    match HDF5File::new("/tmp/rds_hdf5_missing.h5").groups("/") {
+        Err(Error::Io(_)) => {},
+        _ => panic!("Opening a missing file should fail")
+    }
+    match HDF5File::new("Cargo.toml").groups("/") {
+        Err(Error::Format(_)) => {},
+        _ => panic!("Opening a file which is not HDF5 should fail")
+    }
+}
+
+#[test]
+fn write() {
+    const PATH : &'static str = "/tmp/rds_hdf5_4.h5";
+    let a = NDArray::<i16>::from_slice(&[2, 3], &[1, -2, 3, -4, 5, -6]);
+    let b = NDArray::<f64>::from_slice(&[5, 3], &(0..15).map(|x| x as f64 * 0.5).collect::<Vec<f64>>());
+    let c = NDArray::<c64>::from_slice(&[3], &[c64::new(1.0, -1.0), c64::new(2.5, 0.0), c64::new(0.0, 3.0)]);
+
+    let _ = remove_file(PATH);
+    let mut hdf5file = HDF5File::new(PATH);
+    hdf5file.write_data("/a", &a).unwrap();
+    hdf5file.write_data("/t", &a.transposed()).unwrap();
+    hdf5file.chunk_shape = Some(vec![2, 2]);
+    hdf5file.compression = Some(6);
+    hdf5file.write_data("/group/sub/b", &b).unwrap();
+    hdf5file.chunk_shape = None;
+    hdf5file.write_data("/c", &c).unwrap();
+    hdf5file.compression = None;
+    hdf5file.create_group("/empty").unwrap();
+    hdf5file.write_string_attribute("/", "title", "Measures").unwrap();
+    hdf5file.write_attribute("/group/sub/b", "scale", 0.5f32).unwrap();
+    hdf5file.write_attribute("/group", "count", 3u32).unwrap();
+    hdf5file.write_attribute("/group", "count", 4u32).unwrap();
+    hdf5file.close().unwrap();
+
+    let hdf5file = HDF5File::new(PATH);
+    assert_eq!(hdf5file.groups("/").unwrap(), vec!["empty".to_string(), "group".to_string()]);
+    assert_eq!(hdf5file.datasets("/").unwrap(), vec!["a".to_string(), "c".to_string(), "t".to_string()]);
+    assert_eq!(hdf5file.groups("/group").unwrap(), vec!["sub".to_string()]);
+    assert_eq!(hdf5file.datasets("/group/sub").unwrap(), vec!["b".to_string()]);
+    assert!(hdf5file.datasets("/empty").unwrap().is_empty());
+
+    assert!(hdf5file.read_dataset::<i16>("/a").unwrap() == a);
+    assert!(hdf5file.read_dataset::<i16>("/t").unwrap() == NDArray::copy(&a.transposed()));
+    assert!(hdf5file.read_dataset::<f64>("/group/sub/b").unwrap() == b);
+    assert!(hdf5file.read_dataset::<c64>("/c").unwrap() == c);
+    assert!(hdf5file.read_dataset::<f32>("/a").unwrap() == NDArray::<f32>::cast(&a));
+
+    assert_eq!(hdf5file.attributes("/").unwrap(), vec!["title".to_string()]);
+    assert_eq!(hdf5file.read_string_attribute("/", "title").unwrap(), "Measures");
+    assert_eq!(hdf5file.read_attribute::<f64>("/group/sub/b", "scale").unwrap(), 0.5);
+    assert_eq!(hdf5file.attributes("/group").unwrap(), vec!["count".to_string()]);
+    assert_eq!(hdf5file.read_attribute::<u32>("/group", "count").unwrap(), 4);
+}
+
+#[test]
+fn write_large() {
+    const PATH : &'static str = "/tmp/rds_hdf5_5.h5";
+    let _ = remove_file(PATH);
+    let mut hdf5file = HDF5File::new(PATH);
+    // More links than a symbol table node and more chunks than a B-tree node can hold
+    let names : Vec<String> = (0..40).map(|i| format!("d{:02}", i)).collect();
+    for (i, name) in names.iter().enumerate() {
+        hdf5file.write_data(&format!("/many/{}", name), &NDArray::<u8>::new(&[2], i as u8)).unwrap();
+    }
+    let x = NDArray::<i64>::from_slice(&[3, 100], &(0..300).map(|x| x - 150).collect::<Vec<i64>>());
+    hdf5file.chunk_shape = Some(vec![2, 1]);
+    hdf5file.write_data("/x", &x).unwrap();
+    hdf5file.write_data("/scalar", &NDArray::<f32>::new(&[], 2.0)).unwrap_err();
+    hdf5file.chunk_shape = None;
+    hdf5file.write_data("/scalar", &NDArray::<f32>::new(&[], 2.0)).unwrap();
+    hdf5file.close().unwrap();
+
+    let hdf5file = HDF5File::new(PATH);
+    assert_eq!(hdf5file.datasets("/many").unwrap(), names);
+    for (i, name) in names.iter().enumerate() {
+        assert!(hdf5file.read_dataset::<u8>(&format!("/many/{}", name)).unwrap() == NDArray::<u8>::new(&[2], i as u8));
+    }
+    assert!(hdf5file.read_dataset::<i64>("/x").unwrap() == x);
+    assert!(hdf5file.read_dataset::<f32>("/scalar").unwrap() == NDArray::<f32>::new(&[], 2.0));
+}
+
+#[test]
+fn write_errors() {
+    const PATH : &'static str = "/tmp/rds_hdf5_6.h5";
+    let a = NDArray::<f32>::new(&[2, 2], 1.0);
+    let _ = remove_file(PATH);
+    let mut hdf5file = HDF5File::new(PATH);
+    hdf5file.write_data("/a", &a).unwrap();
+    match hdf5file.write_data("/a/b", &a) {
+        Err(Error::Format(_)) => {},
+        _ => panic!("Writing a dataset inside a dataset should fail")
+    }
+    match hdf5file.write_data("/", &a) {
+        Err(Error::Format(_)) => {},
+        _ => panic!("Replacing the root group should fail")
+    }
+    match hdf5file.write_attribute("/missing", "x", 1u8) {
+        Err(Error::Format(_)) => {},
+        _ => panic!("Writing an attribute on a missing object should fail")
+    }
+    hdf5file.chunk_shape = Some(vec![2]);
+    match hdf5file.write_data("/b", &a) {
+        Err(Error::DimensionMismatch { expected : 2, found : 1 }) => {},
+        _ => panic!("Writing with a chunk shape of the wrong dimension should fail")
+    }
+    hdf5file.write_attribute("/a", "x", 1u8).unwrap();
+    match hdf5file.read_string_attribute("/a", "x") {
+        Err(Error::Format(_)) => {},
+        _ => panic!("Reading the file before it is closed should fail")
+    }
+    hdf5file.close().unwrap();
+    match hdf5file.read_string_attribute("/a", "x") {
+        Err(Error::UnsupportedType(_)) => {},
+        _ => panic!("Reading a numerical attribute as a string should fail")
+    }
+
+    // Neither a new HDF5File nor a closed one replace an existing file
+    hdf5file.chunk_shape = None;
+    for hdf5file in &mut [hdf5file, HDF5File::new(PATH)] {
+        match hdf5file.write_data("/b", &a) {
+            Err(Error::Io(_)) => {},
+            _ => panic!("Writing to an existing file should fail")
+        }
+    }
+    assert!(HDF5File::new(PATH).read_dataset::<f32>("/a").unwrap() == a);
+}
+
+#[test]
+fn write_drop() {
+    const PATH : &'static str = "/tmp/rds_hdf5_9.h5";
+    let a = NDArray::<u16>::from_slice(&[3], &[1, 2, 3]);
+    let _ = remove_file(PATH);
+    {
+        let mut hdf5file = HDF5File::new(PATH);
+        hdf5file.write_data("/group/a", &a).unwrap();
+        hdf5file.write_attribute("/group", "x", 2i8).unwrap();
+    }
+    // The file is completed when the HDF5File is dropped
+    let hdf5file = HDF5File::new(PATH);
+    assert!(hdf5file.read_dataset::<u16>("/group/a").unwrap() == a);
+    assert_eq!(hdf5file.read_attribute::<i8>("/group", "x").unwrap(), 2);
+}
+
+const TYPES : [&'static str; 10] = ["int8", "int16", "int32", "int64", "uint8", "uint16", "uint32", "uint64", "float32", "float64"];
+
+/// The array of the test vectors, each element being the sum of its indices weighted by 3, 5 and 7.
+fn expected(shape : &[usize]) -> NDArray<f64> {
+    let mut array = NDArray::<f64>::new(shape, 0.0);
+    let mut idx = vec![0usize; shape.len()];
+    for _ in 0..array.size() {
+        array[&idx[..]] = idx.iter().zip(&[3, 5, 7]).fold(0.0, |acc, (&i, &w)| acc + (i * w) as f64);
+        idx.inc_ro(shape);
+    }
+    array
+}
+
+fn expected_complex(shape : &[usize]) -> NDArray<c64> {
+    expected(shape).map(|&x| c64::new(x, -x))
+}
+
+#[test]
+fn read_h5py() {
+    const TMP_DIR : &'static str = "/tmp/rds_hdf5_7/";
+    assert!(Command::new("python").arg("test_vector/hdf5/generate.py").arg(TMP_DIR).status().unwrap().success());
+    let shapes : [&[usize]; 3] = [&[40], &[20, 15], &[4, 5, 6]];
+
+    let hdf5file = HDF5File::new(&format!("{}contiguous.h5", TMP_DIR));
+    for t in &TYPES {
+        for bo in &["le", "be"] {
+            for (d, shape) in shapes.iter().enumerate() {
+                let name = format!("/{}d_{}_{}", d + 1, bo, t);
+                assert!(hdf5file.read_dataset::<f64>(&name).unwrap() == expected(shape), "{}", name);
+            }
+        }
+    }
+    assert!(hdf5file.read_dataset::<c64>("/complex64").unwrap() == expected_complex(&[20, 15]));
+    assert!(hdf5file.read_dataset::<c64>("/complex128").unwrap() == expected_complex(&[20, 15]));
+
+    let hdf5file = HDF5File::new(&format!("{}chunked.h5", TMP_DIR));
+    for t in &TYPES {
+        assert!(hdf5file.read_dataset::<f64>(&format!("/2d_{}", t)).unwrap() == expected(&[20, 15]), "2d_{}", t);
+        assert!(hdf5file.read_dataset::<f64>(&format!("/3d_{}", t)).unwrap() == expected(&[4, 5, 6]), "3d_{}", t);
+    }
+    assert!(hdf5file.read_dataset::<f64>("/uncompressed").unwrap() == expected(&[20, 15]));
+    assert!(hdf5file.read_dataset::<c64>("/complex128").unwrap() == expected_complex(&[20, 15]));
+    let sparse = hdf5file.read_dataset::<f64>("/sparse").unwrap();
+    let full = expected(&[20, 15]);
+    for i in 0..20 {
+        for j in 0..15 {
+            assert_eq!(sparse[&[i, j]], if i < 6 && j < 4 { full[&[i, j]] } else { 0.0 });
+        }
+    }
+
+    for name in &["groups.h5", "groups_latest.h5"] {
+        let hdf5file = HDF5File::new(&format!("{}{}", TMP_DIR, name));
+        let mut groups = hdf5file.groups("/").unwrap();
+        groups.sort();
+        assert_eq!(groups, vec!["a".to_string(), "empty".to_string(), "many".to_string()]);
+        assert_eq!(hdf5file.groups("/a").unwrap(), vec!["b".to_string()]);
+        assert_eq!(hdf5file.datasets("/a/b").unwrap(), vec!["data".to_string()]);
+        assert!(hdf5file.datasets("/empty").unwrap().is_empty());
+        assert!(hdf5file.read_dataset::<f64>("/a/b/data").unwrap() == expected(&[4, 5, 6]));
+        let mut many = hdf5file.datasets("/many").unwrap();
+        many.sort();
+        assert_eq!(many, (0..40).map(|i| format!("d{:02}", i)).collect::<Vec<String>>());
+        for i in 0..40 {
+            assert!(hdf5file.read_dataset::<u8>(&format!("/many/d{:02}", i)).unwrap() == NDArray::<u8>::new(&[2], i as u8));
+        }
+
+        assert_eq!(hdf5file.read_string_attribute("/", "title").unwrap(), "Measures");
+        assert_eq!(hdf5file.read_attribute::<i64>("/a", "count").unwrap(), 4);
+        assert_eq!(hdf5file.read_attribute::<f64>("/a/b/data", "scale").unwrap(), 0.5);
+        assert_eq!(hdf5file.read_attribute::<c64>("/a/b/data", "offset").unwrap(), c64::new(1.0, -2.0));
+        let mut attributes = hdf5file.attributes("/a/b/data").unwrap();
+        attributes.sort();
+        assert_eq!(attributes, vec!["offset".to_string(), "scale".to_string()]);
+    }
+}
+
+fn write_types(hdf5file : &mut HDF5File, group : &str, shape : &[usize]) {
+    let array = expected(shape);
+    let name = |t : &str| format!("{}/{}_{}d", group, t, shape.len());
+    hdf5file.write_data(&name("int8"), &NDArray::<i8>::cast(&array)).unwrap();
+    hdf5file.write_data(&name("int16"), &NDArray::<i16>::cast(&array)).unwrap();
+    hdf5file.write_data(&name("int32"), &NDArray::<i32>::cast(&array)).unwrap();
+    hdf5file.write_data(&name("int64"), &NDArray::<i64>::cast(&array)).unwrap();
+    hdf5file.write_data(&name("uint8"), &NDArray::<u8>::cast(&array)).unwrap();
+    hdf5file.write_data(&name("uint16"), &NDArray::<u16>::cast(&array)).unwrap();
+    hdf5file.write_data(&name("uint32"), &NDArray::<u32>::cast(&array)).unwrap();
+    hdf5file.write_data(&name("uint64"), &NDArray::<u64>::cast(&array)).unwrap();
+    hdf5file.write_data(&name("float32"), &NDArray::<f32>::cast(&array)).unwrap();
+    hdf5file.write_data(&name("float64"), &array).unwrap();
+    let complex = expected_complex(shape);
+    hdf5file.write_data(&name("complex64"), &NDArray::<c32>::cast(&complex)).unwrap();
+    hdf5file.write_data(&name("complex128"), &complex).unwrap();
+}
+
+#[test]
+fn write_h5py() {
+    const TMP_DIR : &'static str = "/tmp/rds_hdf5_8/";
+    let _ = ::std::fs::create_dir(TMP_DIR);
+    let _ = remove_file(format!("{}written.h5", TMP_DIR));
+    let mut hdf5file = HDF5File::new(&format!("{}written.h5", TMP_DIR));
+    for shape in &[&[40][..], &[20, 15], &[4, 5, 6]] {
+        write_types(&mut hdf5file, "", shape);
+    }
+    hdf5file.chunk_shape = Some(vec![6, 4]);
+    hdf5file.compression = Some(6);
+    write_types(&mut hdf5file, "/chunked", &[20, 15]);
+    hdf5file.chunk_shape = None;
+    hdf5file.compression = None;
+    hdf5file.create_group("/empty").unwrap();
+    hdf5file.write_string_attribute("/", "title", "Measures").unwrap();
+    hdf5file.write_attribute("/chunked", "count", 4u32).unwrap();
+    hdf5file.write_attribute("/chunked/float64_2d", "scale", 0.5f64).unwrap();
+    hdf5file.close().unwrap();
+    assert!(Command::new("python").arg("test_vector/hdf5/verify.py").arg(TMP_DIR).status().unwrap().success());
+}
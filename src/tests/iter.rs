@@ -0,0 +1,117 @@
+use array::{NDArray, NDData, NDDataMut, NDSliceable, NDViewable, NDViewableMut, NDRange};
+use array::iter::{NDIterable, NDIterableMut};
+
+#[test]
+fn elements() {
+    let array = NDArray::<u32>::from_slice(&[2, 3], &[1, 2, 3, 4, 5, 6]);
+    let ro : Vec<u32> = array.iter().cloned().collect();
+    assert!(ro == vec![1, 2, 3, 4, 5, 6]);
+    let co : Vec<u32> = array.iter_co().cloned().collect();
+    assert!(co == vec![1, 4, 2, 5, 3, 6]);
+    assert!(array.iter().len() == 6);
+    assert!(array.slice(&[1]).iter().fold(0, |acc, &x| acc + x) == 15);
+    let view = array.view(&[NDRange::all().step(-1), NDRange::all().step(2)]);
+    let elements : Vec<u32> = view.iter().cloned().collect();
+    assert!(elements == vec![4, 6, 1, 3]);
+    let scalar = NDArray::<u32>::new(&[], 7);
+    assert!(scalar.iter().cloned().collect::<Vec<u32>>() == vec![7]);
+    let empty = NDArray::<u32>::new(&[2, 0], 7);
+    assert!(empty.iter().next().is_none());
+}
+
+#[test]
+fn indexed() {
+    let array = NDArray::<f64>::from_slice(&[2, 2], &[1.0, 2.0, 3.0, 4.0]);
+    let pairs : Vec<(Vec<usize>, f64)> = array.indexed_iter().map(|(idx, &x)| (idx, x)).collect();
+    assert!(pairs == vec![(vec![0, 0], 1.0), (vec![0, 1], 2.0), (vec![1, 0], 3.0), (vec![1, 1], 4.0)]);
+    let pairs : Vec<(Vec<usize>, f64)> = array.indexed_iter_co().map(|(idx, &x)| (idx, x)).collect();
+    assert!(pairs == vec![(vec![0, 0], 1.0), (vec![1, 0], 3.0), (vec![0, 1], 2.0), (vec![1, 1], 4.0)]);
+    for (idx, x) in array.transposed().indexed_iter() {
+        assert!(*x == array[&[idx[1], idx[0]]]);
+    }
+}
+
+#[test]
+fn mutable() {
+    let mut array = NDArray::<i32>::new(&[2, 3], 0);
+    for (i, x) in array.iter_mut().enumerate() {
+        *x = i as i32;
+    }
+    assert!(array == NDArray::<i32>::from_slice(&[2, 3], &[0, 1, 2, 3, 4, 5]));
+    for (i, x) in array.iter_mut_co().enumerate() {
+        *x = i as i32;
+    }
+    assert!(array == NDArray::<i32>::from_slice(&[2, 3], &[0, 2, 4, 1, 3, 5]));
+    for x in array.view_mut(&[NDRange::all(), NDRange::index(1)]).iter_mut() {
+        *x = -1;
+    }
+    assert!(array == NDArray::<i32>::from_slice(&[2, 3], &[0, -1, 4, 1, -1, 5]));
+    for x in array.view_mut(&[NDRange::all().step(-1), NDRange::all().step(2)]).iter_mut() {
+        *x *= 10;
+    }
+    assert!(array == NDArray::<i32>::from_slice(&[2, 3], &[0, -1, 40, 10, -1, 50]));
+}
+
+#[test]
+fn axis() {
+    let array = NDArray::<u8>::from_slice(&[2, 3], &[1, 2, 3, 4, 5, 6]);
+    let rows : Vec<NDArray<u8>> = array.outer_iter().map(|row| row.to_contiguous()).collect();
+    assert!(rows.len() == 2);
+    assert!(rows[0] == NDArray::<u8>::from_slice(&[3], &[1, 2, 3]));
+    assert!(rows[1] == NDArray::<u8>::from_slice(&[3], &[4, 5, 6]));
+    let sums : Vec<u8> = array.axis_iter(1).map(|column| column.iter().fold(0, |acc, &x| acc + x)).collect();
+    assert!(sums == vec![5, 7, 9]);
+    let cube = NDArray::<u8>::new(&[2, 3, 4], 1);
+    let channels : Vec<Vec<usize>> = cube.axis_iter(2).map(|channel| channel.shape().to_vec()).collect();
+    assert!(channels == vec![vec![2, 3]; 4]);
+    let reversed = array.view(&[NDRange::all().step(-1)]);
+    assert!(reversed.outer_iter().next().unwrap() == NDArray::<u8>::from_slice(&[3], &[4, 5, 6]));
+}
+
+#[test]
+#[should_panic]
+fn axis_out_of_bound() {
+    let array = NDArray::<u8>::new(&[2, 3], 0);
+    array.axis_iter(2);
+}
+
+/// Mutable data whose rows all designate the same storage.
+struct Repeated {
+    shape : Vec<usize>,
+    strides : Vec<usize>,
+    data : Vec<u8>,
+}
+
+impl NDData<u8> for Repeated {
+    fn shape(&self) -> &[usize] {
+        &self.shape
+    }
+
+    fn strides(&self) -> &[usize] {
+        &self.strides
+    }
+
+    fn get_data(&self) -> &[u8] {
+        &self.data
+    }
+}
+
+impl NDDataMut<u8> for Repeated {
+    fn get_data_mut(&mut self) -> &mut [u8] {
+        &mut self.data
+    }
+}
+
+#[test]
+#[should_panic]
+fn mutable_zero_stride() {
+    let mut repeated = Repeated { shape : vec![2, 3], strides : vec![0, 1], data : vec![0; 3] };
+    repeated.iter_mut();
+}
+
+#[test]
+#[should_panic]
+fn mutable_overlapping_strides() {
+    let mut repeated = Repeated { shape : vec![3, 3], strides : vec![2, 1], data : vec![0; 7] };
+    repeated.iter_mut_co();
+}
@@ -0,0 +1,50 @@
+use array::{NDArray, NDData, NDSliceable, NDViewable, NDRange};
+use array::join::{concatenate, stack, vstack, hstack};
+
+#[test]
+fn concatenate_axis() {
+    let a = NDArray::<i32>::from_slice(&[2, 2], &[1, 2, 3, 4]);
+    let b = NDArray::<i32>::from_slice(&[1, 2], &[5, 6]);
+    let c = NDArray::<i32>::from_slice(&[2, 1], &[7, 8]);
+    assert!(concatenate(&[&a, &b], 0) == NDArray::<i32>::from_slice(&[3, 2], &[1, 2, 3, 4, 5, 6]));
+    assert!(concatenate(&[&a, &c, &a], 1) == NDArray::<i32>::from_slice(&[2, 5], &[1, 2, 7, 1, 2, 3, 4, 8, 3, 4]));
+    let view = a.view(&[NDRange::all().step(-1), NDRange::index(1)]);
+    let slice = a.slice(&[0]);
+    assert!(concatenate(&[&view, &slice], 0) == NDArray::<i32>::from_slice(&[4], &[4, 2, 1, 2]));
+}
+
+#[test]
+fn stack_axis() {
+    let a = NDArray::<f64>::from_slice(&[3], &[1.0, 2.0, 3.0]);
+    let b = NDArray::<f64>::from_slice(&[3], &[4.0, 5.0, 6.0]);
+    assert!(stack(&[&a, &b], 0) == NDArray::<f64>::from_slice(&[2, 3], &[1.0, 2.0, 3.0, 4.0, 5.0, 6.0]));
+    assert!(stack(&[&a, &b], 1) == NDArray::<f64>::from_slice(&[3, 2], &[1.0, 4.0, 2.0, 5.0, 3.0, 6.0]));
+    let m = NDArray::<f64>::new(&[2, 2], 1.0);
+    assert!(stack(&[&m, &m, &m], 2).shape() == &[2, 2, 3]);
+}
+
+#[test]
+fn vstack_hstack() {
+    let a = NDArray::<u8>::from_slice(&[2], &[1, 2]);
+    let b = NDArray::<u8>::from_slice(&[2], &[3, 4]);
+    let m = NDArray::<u8>::from_slice(&[2, 2], &[5, 6, 7, 8]);
+    assert!(vstack(&[&a, &b, &m]) == NDArray::<u8>::from_slice(&[4, 2], &[1, 2, 3, 4, 5, 6, 7, 8]));
+    assert!(hstack(&[&a, &b]) == NDArray::<u8>::from_slice(&[4], &[1, 2, 3, 4]));
+    assert!(hstack(&[&m, &m]) == NDArray::<u8>::from_slice(&[2, 4], &[5, 6, 5, 6, 7, 8, 7, 8]));
+}
+
+#[test]
+#[should_panic]
+fn concatenate_shape_mismatch() {
+    let a = NDArray::<i32>::new(&[2, 2], 0);
+    let b = NDArray::<i32>::new(&[2, 3], 0);
+    concatenate(&[&a, &b], 0);
+}
+
+#[test]
+#[should_panic]
+fn stack_shape_mismatch() {
+    let a = NDArray::<i32>::new(&[2], 0);
+    let b = NDArray::<i32>::new(&[3], 0);
+    stack(&[&a, &b], 0);
+}
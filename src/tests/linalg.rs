@@ -0,0 +1,198 @@
+use types::complex::{c32, c64, Complex};
+use array::{NDArray, NDData, NDViewable};
+use array::map::NDMappable;
+use blas::Blas;
+use error::Error;
+use linalg::Linalg;
+
+fn assert_close_f64(a : &NDData<f64>, b : &NDData<f64>, tol : f64) {
+    assert!(a.shape() == b.shape());
+    let diff = a.zip_map(b, |x, y| (x - y).abs());
+    assert!(diff.fold(0.0, |acc, &x| if x > acc { x } else { acc }) < tol, "{} != {}", a.view(&[]), b.view(&[]));
+}
+
+fn assert_close_c64(a : &NDData<c64>, b : &NDData<c64>, tol : f64) {
+    assert!(a.shape() == b.shape());
+    let diff = a.zip_map(b, |&x, &y| (x - y).abs());
+    assert!(diff.fold(0.0, |acc, &x| if x > acc { x } else { acc }) < tol, "{} != {}", a.view(&[]), b.view(&[]));
+}
+
+fn matmul_f64(a : &NDData<f64>, b : &NDData<f64>) -> NDArray<f64> {
+    let mut c = NDArray::<f64>::new(&[a.shape()[0], b.shape()[1]], 0.0);
+    c.gemm(1.0, a, b, 0.0);
+    c
+}
+
+fn matmul_c64(a : &NDData<c64>, b : &NDData<c64>) -> NDArray<c64> {
+    let mut c = NDArray::<c64>::new(&[a.shape()[0], b.shape()[1]], c64::new(0.0, 0.0));
+    c.gemm(c64::new(1.0, 0.0), a, b, c64::new(0.0, 0.0));
+    c
+}
+
+fn identity_f64(n : usize) -> NDArray<f64> {
+    let mut identity = NDArray::<f64>::new(&[n, n], 0.0);
+    for i in 0..n {
+        identity[&[i, i]] = 1.0;
+    }
+    identity
+}
+
+#[test]
+fn solve() {
+    let a = NDArray::<f64>::from_slice(&[3, 3], &[2.0, 1.0, -1.0, -3.0, -1.0, 2.0, -2.0, 1.0, 2.0]);
+    let b = NDArray::<f64>::from_slice(&[3], &[8.0, -11.0, -3.0]);
+    let x = a.solve(&b).unwrap();
+    assert_close_f64(&x, &NDArray::<f64>::from_slice(&[3], &[2.0, 3.0, -1.0]), 1e-12);
+
+    let b = NDArray::<f64>::from_slice(&[3, 2], &[8.0, 1.0, -11.0, 0.0, -3.0, 0.0]);
+    let x = a.transposed().solve(&b).unwrap();
+    assert_close_f64(&matmul_f64(&a.transposed(), &x), &b, 1e-12);
+
+    let a = NDArray::<c32>::from_slice(&[2, 2], &[c32::new(1.0, 1.0), c32::new(0.0, 0.0), c32::new(0.0, 0.0), c32::new(0.0, 2.0)]);
+    let b = NDArray::<c32>::from_slice(&[2], &[c32::new(2.0, 0.0), c32::new(2.0, 0.0)]);
+    let x = a.solve(&b).unwrap();
+    assert!((x[&[0]] - c32::new(1.0, -1.0)).abs() < 1e-6);
+    assert!((x[&[1]] - c32::new(0.0, -1.0)).abs() < 1e-6);
+}
+
+#[test]
+fn solve_errors() {
+    let singular = NDArray::<f64>::from_slice(&[2, 2], &[1.0, 2.0, 2.0, 4.0]);
+    match singular.solve(&NDArray::<f64>::new(&[2], 1.0)) {
+        Err(Error::Lapack { ref routine, info }) => {
+            assert!(routine == "dgesv");
+            assert!(info > 0);
+        },
+        _ => panic!("expected a LAPACK error"),
+    }
+    let rectangular = NDArray::<f64>::new(&[2, 3], 1.0);
+    match rectangular.solve(&NDArray::<f64>::new(&[2], 1.0)) {
+        Err(Error::ShapeMismatch { expected, found }) => {
+            assert!(expected == vec![2, 2]);
+            assert!(found == vec![2, 3]);
+        },
+        _ => panic!("expected a shape mismatch"),
+    }
+    assert!(identity_f64(2).solve(&NDArray::<f64>::new(&[3], 1.0)).is_err());
+}
+
+#[test]
+fn lu() {
+    let a = NDArray::<f64>::from_slice(&[3, 2], &[1.0, 2.0, 3.0, 4.0, 5.0, 7.0]);
+    let lu = a.lu().unwrap();
+    assert!(lu.l.shape() == &[3, 2]);
+    assert!(lu.u.shape() == &[2, 2]);
+    assert!(lu.l[&[0, 0]] == 1.0 && lu.l[&[0, 1]] == 0.0 && lu.u[&[1, 0]] == 0.0);
+    let mut permuted = NDArray::<f64>::new(&[3, 2], 0.0);
+    for i in 0..3 {
+        for j in 0..2 {
+            permuted[&[i, j]] = a[&[lu.permutation[i], j]];
+        }
+    }
+    assert_close_f64(&matmul_f64(&lu.l, &lu.u), &permuted, 1e-12);
+    // The pivot of the first column is its largest element
+    assert!(lu.permutation[0] == 2);
+
+    let lu = NDArray::<f32>::from_slice(&[2, 2], &[1.0, 2.0, 2.0, 4.0]).lu().unwrap();
+    assert!(lu.u[&[1, 1]] == 0.0);
+}
+
+#[test]
+fn cholesky() {
+    let a = NDArray::<f64>::from_slice(&[3, 3], &[4.0, 12.0, -16.0, 12.0, 37.0, -43.0, -16.0, -43.0, 98.0]);
+    let cholesky = a.cholesky().unwrap();
+    assert_close_f64(&cholesky.l, &NDArray::<f64>::from_slice(&[3, 3], &[2.0, 0.0, 0.0, 6.0, 1.0, 0.0, -8.0, 5.0, 3.0]), 1e-12);
+
+    let a = NDArray::<c64>::from_slice(&[2, 2], &[c64::new(2.0, 0.0), c64::new(0.0, -1.0), c64::new(0.0, 1.0), c64::new(2.0, 0.0)]);
+    let l = a.cholesky().unwrap().l;
+    let lh = l.transposed().map(|x| x.conj());
+    assert_close_c64(&matmul_c64(&l, &lh), &a, 1e-12);
+
+    match NDArray::<f32>::from_slice(&[2, 2], &[1.0, 2.0, 2.0, 1.0]).cholesky() {
+        Err(Error::Lapack { ref routine, info }) => {
+            assert!(routine == "spotrf");
+            assert!(info == 2);
+        },
+        _ => panic!("expected a LAPACK error"),
+    }
+}
+
+#[test]
+fn qr() {
+    let a = NDArray::<f64>::from_slice(&[3, 2], &[12.0, -51.0, 6.0, 167.0, -4.0, 24.0]);
+    let qr = a.qr().unwrap();
+    assert!(qr.q.shape() == &[3, 2]);
+    assert!(qr.r.shape() == &[2, 2]);
+    assert!(qr.r[&[1, 0]] == 0.0);
+    assert_close_f64(&matmul_f64(&qr.q, &qr.r), &a, 1e-10);
+    assert_close_f64(&matmul_f64(&qr.q.transposed(), &qr.q), &identity_f64(2), 1e-12);
+
+    let a = NDArray::<c64>::from_slice(&[2, 3], &[c64::new(1.0, 1.0), c64::new(2.0, 0.0), c64::new(0.0, 1.0), c64::new(0.0, -1.0), c64::new(1.0, 0.0), c64::new(3.0, 2.0)]);
+    let qr = a.qr().unwrap();
+    assert!(qr.q.shape() == &[2, 2]);
+    assert!(qr.r.shape() == &[2, 3]);
+    assert_close_c64(&matmul_c64(&qr.q, &qr.r), &a, 1e-12);
+}
+
+#[test]
+fn svd() {
+    let a = NDArray::<f64>::from_slice(&[2, 3], &[3.0, 2.0, 2.0, 2.0, 3.0, -2.0]);
+    let svd = a.svd().unwrap();
+    assert!(svd.u.shape() == &[2, 2]);
+    assert!(svd.vt.shape() == &[2, 3]);
+    assert_close_f64(&svd.s, &NDArray::<f64>::from_slice(&[2], &[5.0, 3.0]), 1e-10);
+    let mut us = NDArray::<f64>::copy(&svd.u);
+    for i in 0..2 {
+        for j in 0..2 {
+            us[&[i, j]] *= svd.s[&[j]];
+        }
+    }
+    assert_close_f64(&matmul_f64(&us, &svd.vt), &a, 1e-10);
+
+    let a = NDArray::<f32>::from_slice(&[3, 1], &[3.0, 0.0, 4.0]);
+    let svd = a.svd().unwrap();
+    assert!((svd.s[&[0]] - 5.0).abs() < 1e-5);
+}
+
+#[test]
+fn eigh() {
+    let a = NDArray::<f64>::from_slice(&[2, 2], &[2.0, 1.0, 1.0, 2.0]);
+    let eigen = a.eigh().unwrap();
+    assert_close_f64(&eigen.values, &NDArray::<f64>::from_slice(&[2], &[1.0, 3.0]), 1e-12);
+    let av = matmul_f64(&a, &eigen.vectors);
+    for j in 0..2 {
+        for i in 0..2 {
+            assert!((av[&[i, j]] - eigen.values[&[j]] * eigen.vectors[&[i, j]]).abs() < 1e-12);
+        }
+    }
+
+    let a = NDArray::<c32>::from_slice(&[2, 2], &[c32::new(2.0, 0.0), c32::new(0.0, -1.0), c32::new(0.0, 1.0), c32::new(2.0, 0.0)]);
+    let eigen = a.eigh().unwrap();
+    assert!((eigen.values[&[0]] - 1.0).abs() < 1e-5);
+    assert!((eigen.values[&[1]] - 3.0).abs() < 1e-5);
+}
+
+#[test]
+fn eig() {
+    let rotation = NDArray::<f64>::from_slice(&[2, 2], &[0.0, -1.0, 1.0, 0.0]);
+    let eigen = rotation.eig().unwrap();
+    assert_close_c64(&eigen.values, &NDArray::<c64>::from_slice(&[2], &[c64::new(0.0, 1.0), c64::new(0.0, -1.0)]), 1e-12);
+    let complex = rotation.map(|&x| c64::new(x, 0.0));
+    let av = matmul_c64(&complex, &eigen.vectors);
+    for j in 0..2 {
+        for i in 0..2 {
+            assert!((av[&[i, j]] - eigen.values[&[j]] * eigen.vectors[&[i, j]]).abs() < 1e-12);
+        }
+    }
+
+    let a = NDArray::<c64>::from_slice(&[3, 3], &[c64::new(1.0, 0.0), c64::new(2.0, 1.0), c64::new(0.0, 0.0),
+                                                  c64::new(0.0, 0.0), c64::new(3.0, 0.0), c64::new(1.0, -1.0),
+                                                  c64::new(1.0, 0.0), c64::new(0.0, 0.0), c64::new(2.0, 2.0)]);
+    let eigen = a.eig().unwrap();
+    let av = matmul_c64(&a, &eigen.vectors);
+    for j in 0..3 {
+        for i in 0..3 {
+            assert!((av[&[i, j]] - eigen.values[&[j]] * eigen.vectors[&[i, j]]).abs() < 1e-10);
+        }
+    }
+}
@@ -0,0 +1,43 @@
+use array::{NDArray, NDData, NDSliceable, NDSliceableMut, NDViewable, NDRange};
+use array::map::{NDMappable, NDMappableMut};
+
+#[test]
+fn map() {
+    let array = NDArray::<f64>::from_slice(&[2, 2], &[1.0, 4.0, 9.0, 16.0]);
+    assert!(array.map(|x| x.sqrt()) == NDArray::<f64>::from_slice(&[2, 2], &[1.0, 2.0, 3.0, 4.0]));
+    let mask = array.map(|&x| x > 5.0);
+    assert!(mask == NDArray::<bool>::from_slice(&[2, 2], &[false, false, true, true]));
+    let column = array.view(&[NDRange::all(), NDRange::index(1)]);
+    assert!(column.map(|&x| x as u8) == NDArray::<u8>::from_slice(&[2], &[4, 16]));
+    let transposed = array.transposed().map(|&x| x as i32);
+    assert!(transposed == NDArray::<i32>::from_slice(&[2, 2], &[1, 9, 4, 16]));
+}
+
+#[test]
+fn map_inplace() {
+    let mut array = NDArray::<i32>::from_slice(&[2, 3], &[1, -2, 3, -4, 5, -6]);
+    array.slice_mut(&[1]).map_inplace(|x| *x = -*x);
+    assert!(array == NDArray::<i32>::from_slice(&[2, 3], &[1, -2, 3, 4, -5, 6]));
+    array.map_inplace(|x| if *x < 0 { *x = 0 });
+    assert!(array == NDArray::<i32>::from_slice(&[2, 3], &[1, 0, 3, 4, 0, 6]));
+}
+
+#[test]
+fn zip_map() {
+    let values = NDArray::<f32>::from_slice(&[2, 2], &[1.0, 2.0, 3.0, 4.0]);
+    let thresholds = NDArray::<f32>::from_slice(&[2], &[1.5, 3.5]);
+    let above = values.zip_map(&thresholds, |v, t| v > t);
+    assert!(above == NDArray::<bool>::from_slice(&[2, 2], &[false, false, true, true]));
+    let counts = NDArray::<u8>::from_slice(&[2, 1], &[2, 3]);
+    let scaled = values.zip_map(&counts, |&v, &c| v * c as f32);
+    assert!(scaled == NDArray::<f32>::from_slice(&[2, 2], &[2.0, 4.0, 9.0, 12.0]));
+}
+
+#[test]
+fn fold() {
+    let array = NDArray::<u32>::from_slice(&[2, 3], &[1, 2, 3, 4, 5, 6]);
+    assert!(array.fold(0, |acc, &x| acc + x) == 21);
+    assert!(array.slice(&[1]).fold(1, |acc, &x| acc * x) == 120);
+    let digits = array.transposed().fold(String::new(), |acc, x| acc + &x.to_string());
+    assert!(digits == "142536");
+}
@@ -0,0 +1,86 @@
+use types::complex::c32;
+use array::{NDArray, NDData, NDSliceableMut, NDViewable, NDRange};
+use array::mask::{NDComparable, NDSelectable, NDSelectableMut};
+
+#[test]
+fn comparisons() {
+    let array = NDArray::<i32>::from_slice(&[2, 2], &[1, 2, 3, 4]);
+    let threshold = NDArray::<i32>::new(&[], 2);
+    assert!(array.elem_gt(&threshold) == NDArray::<bool>::from_slice(&[2, 2], &[false, false, true, true]));
+    assert!(array.elem_ge(&threshold) == NDArray::<bool>::from_slice(&[2, 2], &[false, true, true, true]));
+    assert!(array.elem_lt(&threshold) == NDArray::<bool>::from_slice(&[2, 2], &[true, false, false, false]));
+    assert!(array.elem_le(&threshold) == NDArray::<bool>::from_slice(&[2, 2], &[true, true, false, false]));
+    let row = NDArray::<i32>::from_slice(&[2], &[1, 4]);
+    assert!(array.elem_eq(&row) == NDArray::<bool>::from_slice(&[2, 2], &[true, false, false, true]));
+    assert!(array.elem_ne(&row) == NDArray::<bool>::from_slice(&[2, 2], &[false, true, true, false]));
+    let complex = NDArray::<c32>::from_slice(&[2], &[c32::new(1.0, 1.0), c32::new(0.0, 1.0)]);
+    assert!(complex.elem_eq(&NDArray::<c32>::new(&[], c32::new(0.0, 1.0))) == NDArray::<bool>::from_slice(&[2], &[false, true]));
+}
+
+#[test]
+fn scalar_comparisons() {
+    let array = NDArray::<f64>::from_slice(&[2, 2], &[0.25, 0.5, 0.75, 1.0]);
+    assert!(array.gt(0.5) == NDArray::<bool>::from_slice(&[2, 2], &[false, false, true, true]));
+    assert!(array.ge(0.5) == NDArray::<bool>::from_slice(&[2, 2], &[false, true, true, true]));
+    assert!(array.lt(0.5) == NDArray::<bool>::from_slice(&[2, 2], &[true, false, false, false]));
+    assert!(array.le(0.5) == NDArray::<bool>::from_slice(&[2, 2], &[true, true, false, false]));
+    assert!(array.equal(0.75) == NDArray::<bool>::from_slice(&[2, 2], &[false, false, true, false]));
+    assert!(array.not_equal(0.75) == NDArray::<bool>::from_slice(&[2, 2], &[true, true, false, true]));
+    assert!(array.select(&array.gt(0.5)) == NDArray::<f64>::from_slice(&[2], &[0.75, 1.0]));
+    let view = array.view(&[NDRange::all(), NDRange::index(1)]);
+    assert!(view.lt(1.0) == NDArray::<bool>::from_slice(&[2], &[true, false]));
+}
+
+#[test]
+fn select() {
+    let array = NDArray::<f64>::from_slice(&[2, 3], &[0.5, 3.0, -1.0, 7.0, 2.0, 9.0]);
+    let above = array.select(&array.elem_gt(&NDArray::<f64>::new(&[], 2.5)));
+    assert!(above == NDArray::<f64>::from_slice(&[3], &[3.0, 7.0, 9.0]));
+    let view = array.transposed();
+    assert!(view.select(&view.elem_gt(&NDArray::<f64>::new(&[], 2.5))) == NDArray::<f64>::from_slice(&[3], &[7.0, 3.0, 9.0]));
+    let none = array.select(&NDArray::<bool>::new(&[2, 3], false));
+    assert!(none.shape() == &[0]);
+}
+
+#[test]
+fn masked_assign() {
+    let mut array = NDArray::<i16>::from_slice(&[2, 3], &[-1, 2, -3, 4, -5, 6]);
+    let mask = array.elem_lt(&NDArray::<i16>::new(&[], 0));
+    array.masked_assign(&mask, 0);
+    assert!(array == NDArray::<i16>::from_slice(&[2, 3], &[0, 2, 0, 4, 0, 6]));
+    array.slice_mut(&[1]).masked_assign(&NDArray::<bool>::from_slice(&[3], &[true, false, false]), 9);
+    assert!(array == NDArray::<i16>::from_slice(&[2, 3], &[0, 2, 0, 9, 0, 6]));
+}
+
+#[test]
+fn take() {
+    let array = NDArray::<u8>::from_slice(&[3, 2], &[1, 2, 3, 4, 5, 6]);
+    assert!(array.take(&[2, 0, 2], 0) == NDArray::<u8>::from_slice(&[3, 2], &[5, 6, 1, 2, 5, 6]));
+    assert!(array.take(&[1], 1) == NDArray::<u8>::from_slice(&[3, 1], &[2, 4, 6]));
+    assert!(array.take(&[], 0).shape() == &[0, 2]);
+    let view = array.view(&[NDRange::all().step(-1)]);
+    assert!(view.take(&[0], 0) == NDArray::<u8>::from_slice(&[1, 2], &[5, 6]));
+}
+
+#[test]
+fn put() {
+    let mut array = NDArray::<u8>::new(&[3, 2], 0);
+    array.put(&[2, 0], &NDArray::<u8>::from_slice(&[2, 2], &[1, 2, 3, 4]), 0);
+    assert!(array == NDArray::<u8>::from_slice(&[3, 2], &[3, 4, 0, 0, 1, 2]));
+    array.put(&[1], &NDArray::<u8>::from_slice(&[3, 1], &[7, 8, 9]), 1);
+    assert!(array == NDArray::<u8>::from_slice(&[3, 2], &[3, 7, 0, 8, 1, 9]));
+}
+
+#[test]
+#[should_panic]
+fn take_out_of_bound() {
+    let array = NDArray::<u8>::new(&[3, 2], 0);
+    array.take(&[3], 0);
+}
+
+#[test]
+#[should_panic]
+fn select_wrong_shape() {
+    let array = NDArray::<u8>::new(&[3, 2], 0);
+    array.select(&NDArray::<bool>::new(&[2], true));
+}
@@ -0,0 +1,311 @@
+extern crate flate2;
+
+use std::fmt::Display;
+use std::fs::{File, remove_file};
+use std::io::Write;
+use std::process::Command;
+
+use self::flate2::Compression;
+use self::flate2::write::ZlibEncoder;
+
+use types::complex::{c32, c64};
+use types::RDSTyped;
+use types::cast::Cast;
+use array::{NDArray, NDData, NDViewable};
+use array::matlab::MatlabFile;
+use array::iter::NDIterable;
+use array::map::NDMappable;
+use array::ndindex::NDIndex;
+use error::Error;
+
+/// Encode v on n bytes with the given endianess.
+fn uint(v : u64, n : usize, big_endian : bool) -> Vec<u8> {
+    let mut b : Vec<u8> = (0..n).map(|i| (v >> (8 * i)) as u8).collect();
+    if big_endian {
+        b.reverse();
+    }
+    b
+}
+
+/// Encode a data element in the normal format, padded to 8 bytes.
+fn element(kind : u32, data : &[u8], big_endian : bool) -> Vec<u8> {
+    let mut e = uint(kind as u64, 4, big_endian);
+    e.extend(uint(data.len() as u64, 4, big_endian));
+    e.extend_from_slice(data);
+    while e.len() % 8 != 0 {
+        e.push(0);
+    }
+    e
+}
+
+/// Encode a matrix element with its array name in the small data element format.
+fn matrix(class : u8, complex : bool, shape : &[u32], name : &str, parts : &[Vec<u8>], big_endian : bool) -> Vec<u8> {
+    let mut m = element(6, &[uint(class as u64 | if complex { 0x800 } else { 0 }, 4, big_endian), vec![0; 4]].concat(), big_endian);
+    m.extend(element(5, &shape.iter().flat_map(|&s| uint(s as u64, 4, big_endian)).collect::<Vec<u8>>(), big_endian));
+    assert!(name.len() <= 4);
+    m.extend(uint(((name.len() as u64) << 16) | 1, 4, big_endian));
+    m.extend_from_slice(name.as_bytes());
+    m.extend(vec![0; 4 - name.len()]);
+    for part in parts {
+        m.extend_from_slice(part);
+    }
+    element(14, &m, big_endian)
+}
+
+fn header(big_endian : bool) -> Vec<u8> {
+    let mut h = b"MATLAB 5.0 MAT-file, Platform: rds".to_vec();
+    h.resize(116, b' ');
+    h.extend(vec![0; 8]);
+    h.extend(uint(0x0100, 2, big_endian));
+    h.extend_from_slice(if big_endian { b"MI" } else { b"IM" });
+    h
+}
+
+/// Build a little endian file containing:
+/// * a: a 2x3 double matrix stored as miUINT8,
+/// * z: a 2x1 complex single matrix,
+/// * b: a compressed 2x2x2 int16 matrix,
+/// * s: a char array.
+fn build(path : &str) {
+    let mut file = header(false);
+    // a = [0 1 2; 3 4 5], stored in column-major order
+    file.extend(matrix(6, false, &[2, 3], "a", &[element(2, &[0, 3, 1, 4, 2, 5], false)], false));
+    let re : Vec<u8> = [1.0f32, 2.0].iter().flat_map(|v| uint(v.to_bits() as u64, 4, false)).collect();
+    let im : Vec<u8> = [-1.0f32, 0.5].iter().flat_map(|v| uint(v.to_bits() as u64, 4, false)).collect();
+    file.extend(matrix(7, true, &[2, 1], "z", &[element(7, &re, false), element(7, &im, false)], false));
+    let values : Vec<u8> = (0..8).flat_map(|i| uint((i as i16 - 4) as u64, 2, false)).collect();
+    let b = matrix(10, false, &[2, 2, 2], "b", &[element(3, &values, false)], false);
+    let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(&b).unwrap();
+    let compressed = encoder.finish().unwrap();
+    file.extend(uint(15, 4, false));
+    file.extend(uint(compressed.len() as u64, 4, false));
+    file.extend(compressed);
+    file.extend(matrix(4, false, &[1, 2], "s", &[element(4, &uint(0x0069_0068, 4, false), false)], false));
+    File::create(path).unwrap().write_all(&file).unwrap();
+}
+
+#[test]
+fn read() {
+    const PATH : &'static str = "/tmp/rds_matlab_1.mat";
+    build(PATH);
+    let matfile = MatlabFile::new(PATH);
+    assert_eq!(matfile.variables().unwrap(), vec!["a".to_string(), "z".to_string(), "b".to_string(), "s".to_string()]);
+
+    let a : NDArray<f64> = matfile.read_variable("a").unwrap();
+    assert!(a == NDArray::from_slice(&[2, 3], &[0.0, 1.0, 2.0, 3.0, 4.0, 5.0]));
+
+    let z : NDArray<c32> = matfile.read_variable("z").unwrap();
+    assert!(z == NDArray::from_slice(&[2, 1], &[c32::new(1.0, -1.0), c32::new(2.0, 0.5)]));
+
+    let b : NDArray<i32> = matfile.read_variable("b").unwrap();
+    assert_eq!(b.shape(), &[2, 2, 2]);
+    for i in 0..2 {
+        for j in 0..2 {
+            for k in 0..2 {
+                assert_eq!(b[&[i, j, k]], (i + 2 * j + 4 * k) as i32 - 4);
+            }
+        }
+    }
+}
+
+#[test]
+fn read_big_endian() {
+    const PATH : &'static str = "/tmp/rds_matlab_2.mat";
+    let mut file = header(true);
+    let values : Vec<u8> = (0..6).flat_map(|i| uint((i as f64).to_bits(), 8, true)).collect();
+    file.extend(matrix(6, false, &[3, 2], "x", &[element(9, &values, true)], true));
+    File::create(PATH).unwrap().write_all(&file).unwrap();
+
+    let x : NDArray<f32> = MatlabFile::new(PATH).read_variable("x").unwrap();
+    assert!(x == NDArray::from_slice(&[3, 2], &[0.0, 3.0, 1.0, 4.0, 2.0, 5.0]));
+}
+
+#[test]
+fn errors() {
+    const PATH : &'static str = "/tmp/rds_matlab_3.mat";
+    build(PATH);
+    let matfile = MatlabFile::new(PATH);
+    match matfile.read_variable::<f64>("s") {
+        Err(Error::UnsupportedType(_)) => {},
+        _ => panic!("Reading a char array should fail")
+    }
+    match matfile.read_variable::<f64>("missing") {
+        Err(Error::Format(_)) => {},
+        _ => panic!("Reading a missing variable should fail")
+    }
+
+    // Dimensions overflowing or exceeding the data of the variable
+    let mut file = header(false);
+    file.extend(matrix(6, false, &[u32::max_value(); 3], "h", &[element(2, &[0; 6], false)], false));
+    file.extend(matrix(6, false, &[100000, 100000], "l", &[element(2, &[0; 6], false)], false));
+    File::create(PATH).unwrap().write_all(&file).unwrap();
+    for name in &["h", "l"] {
+        match matfile.read_variable::<f64>(name) {
+            Err(Error::Format(_)) => {},
+            _ => panic!("Reading a variable larger than its data should fail")
+        }
+    }
+    match MatlabFile::new("/tmp/rds_matlab_missing.mat").variables() {
+        Err(Error::Io(_)) => {},
+        _ => panic!("Opening a missing file should fail")
+    }
+    match MatlabFile::new("Cargo.toml").variables() {
+        Err(Error::Format(_)) => {},
+        _ => panic!("Opening a file which is not a MAT-file should fail")
+    }
+}
+
+#[test]
+fn write() {
+    const PATH : &'static str = "/tmp/rds_matlab_4.mat";
+    let a = NDArray::<f64>::from_slice(&[2, 3], &[0.0, 1.5, -2.0, 3.0, 4.0, 5.0]);
+    let b = NDArray::<i16>::from_slice(&[2, 2, 2], &[-4, -3, -2, -1, 0, 1, 2, 3]);
+    let z = NDArray::<c64>::from_slice(&[2, 1], &[c64::new(1.0, -1.0), c64::new(2.0, 0.5)]);
+    let w = NDArray::<c32>::from_slice(&[1, 2], &[c32::new(0.0, 1.0), c32::new(2.0, 0.0)]);
+    let v = NDArray::<u64>::from_slice(&[3], &[1, 2, u64::max_value()]);
+
+    let _ = remove_file(PATH);
+    let mut matfile = MatlabFile::new(PATH);
+    matfile.write_data("a", &a).unwrap();
+    matfile.write_data("transposed", &a.transposed()).unwrap();
+    matfile.compression = true;
+    matfile.write_data("b", &b).unwrap();
+    matfile.write_data("z", &z).unwrap();
+    matfile.compression = false;
+    matfile.write_data("w", &w).unwrap();
+    matfile.write_data("v", &v).unwrap();
+    matfile.write_data("s", &NDArray::<u8>::new(&[], 7)).unwrap();
+    matfile.write_data("b", &NDArray::<f32>::cast(&b)).unwrap();
+
+    let matfile = MatlabFile::new(PATH);
+    assert_eq!(matfile.variables().unwrap(), vec!["a", "transposed", "b", "z", "w", "v", "s"].iter().map(|s| s.to_string()).collect::<Vec<String>>());
+    assert!(matfile.read_variable::<f64>("a").unwrap() == a);
+    assert!(matfile.read_variable::<f64>("transposed").unwrap() == NDArray::copy(&a.transposed()));
+    assert!(matfile.read_variable::<f32>("b").unwrap() == NDArray::<f32>::cast(&b));
+    assert!(matfile.read_variable::<c64>("z").unwrap() == z);
+    assert!(matfile.read_variable::<c32>("w").unwrap() == w);
+    assert!(matfile.read_variable::<u64>("v").unwrap() == NDArray::from_slice(&[1, 3], &[1, 2, u64::max_value()]));
+    assert!(matfile.read_variable::<u8>("s").unwrap() == NDArray::new(&[1, 1], 7));
+
+    let mut matfile = MatlabFile::new(PATH);
+    match matfile.write_data("a", &a) {
+        Err(Error::Io(_)) => {},
+        _ => panic!("Writing to an existing file should fail")
+    }
+    assert_eq!(MatlabFile::new(PATH).variables().unwrap().len(), 7);
+}
+
+#[test]
+fn write_replace() {
+    const PATH : &'static str = "/tmp/rds_matlab_7.mat";
+    let _ = remove_file(PATH);
+    let mut matfile = MatlabFile::new(PATH);
+    for &compression in &[false, true] {
+        matfile.compression = compression;
+        for (i, name) in ["first", "middle", "last"].iter().enumerate() {
+            matfile.write_data(name, &NDArray::<i32>::new(&[i + 1, 3], i as i32)).unwrap();
+        }
+        // A larger and then a smaller middle variable move the following ones
+        matfile.write_data("middle", &NDArray::<f64>::new(&[10, 10], -1.0)).unwrap();
+        assert!(MatlabFile::new(PATH).read_variable::<f64>("middle").unwrap() == NDArray::new(&[10, 10], -1.0));
+        matfile.write_data("middle", &NDArray::<u8>::new(&[1], 9)).unwrap();
+        matfile.write_data("last", &NDArray::<i8>::new(&[2, 2], -3)).unwrap();
+
+        let reader = MatlabFile::new(PATH);
+        assert_eq!(reader.variables().unwrap(), vec!["first".to_string(), "middle".to_string(), "last".to_string()]);
+        assert!(reader.read_variable::<i32>("first").unwrap() == NDArray::new(&[1, 3], 0));
+        assert!(reader.read_variable::<u8>("middle").unwrap() == NDArray::new(&[1, 1], 9));
+        assert!(reader.read_variable::<i8>("last").unwrap() == NDArray::new(&[2, 2], -3));
+    }
+}
+
+#[test]
+fn write_errors() {
+    let mut matfile = MatlabFile::new("/tmp/rds_matlab_5.mat");
+    let a = NDArray::<f64>::new(&[2, 2], 1.0);
+    for name in &["", "1a", "a-b", "_a"] {
+        match matfile.write_data(name, &a) {
+            Err(Error::Format(_)) => {},
+            _ => panic!("Writing a variable named '{}' should fail", name)
+        }
+    }
+}
+
+const TYPES : [&'static str; 10] = ["int8", "int16", "int32", "int64", "uint8", "uint16", "uint32", "uint64", "float32", "float64"];
+
+/// The array of the test vectors, each element being the sum of its indices weighted by 3, 5 and 7.
+fn expected(shape : &[usize]) -> NDArray<f64> {
+    let mut array = NDArray::<f64>::new(shape, 0.0);
+    let mut idx = vec![0usize; shape.len()];
+    for _ in 0..array.size() {
+        array[&idx[..]] = idx.iter().zip(&[3, 5, 7]).fold(0.0, |acc, (&i, &w)| acc + (i * w) as f64);
+        idx.inc_ro(shape);
+    }
+    array
+}
+
+#[test]
+fn read_scipy() {
+    const TMP_DIR : &'static str = "/tmp/rds_matlab_6/";
+    assert!(Command::new("python").arg("test_vector/matlab/generate.py").arg(TMP_DIR).status().unwrap().success());
+    for name in &["uncompressed.mat", "compressed.mat"] {
+        let matfile = MatlabFile::new(&format!("{}{}", TMP_DIR, name));
+        let mut variables = matfile.variables().unwrap();
+        variables.sort();
+        let mut names : Vec<String> = TYPES.iter().chain(&["complex64", "complex128"]).flat_map(|t| {
+            vec![format!("v_{}", t), format!("m_{}", t), format!("n_{}", t)]
+        }).filter(|n| !n.starts_with("v_complex")).collect();
+        names.sort();
+        assert_eq!(variables, names);
+
+        for t in &TYPES {
+            // Vectors are stored as row vectors
+            let v = matfile.read_variable::<f64>(&format!("v_{}", t)).unwrap();
+            assert_eq!(v.shape(), &[1, 40]);
+            assert!(v.iter().zip(expected(&[40]).iter()).all(|(x, y)| x == y), "v_{}", t);
+            assert!(matfile.read_variable::<f64>(&format!("m_{}", t)).unwrap() == expected(&[20, 15]), "m_{}", t);
+            assert!(matfile.read_variable::<f64>(&format!("n_{}", t)).unwrap() == expected(&[4, 5, 6]), "n_{}", t);
+        }
+        for t in &["complex64", "complex128"] {
+            for shape in &[&[20, 15][..], &[4, 5, 6]] {
+                let name = format!("{}_{}", if shape.len() == 2 { "m" } else { "n" }, t);
+                let complex = expected(shape).map(|&x| c64::new(x, -x));
+                assert!(matfile.read_variable::<c64>(&name).unwrap() == complex, "{}", name);
+            }
+        }
+    }
+}
+
+fn write_as<T : Copy + RDSTyped + Display>(matfile : &mut MatlabFile, name : &str, array : &NDArray<c64>) where c64 : Cast<T> {
+    matfile.write_data(name, &NDArray::<T>::cast(array)).unwrap();
+}
+
+#[test]
+fn write_scipy() {
+    const TMP_DIR : &'static str = "/tmp/rds_matlab_8/";
+    let _ = ::std::fs::create_dir(TMP_DIR);
+    let _ = remove_file(format!("{}written.mat", TMP_DIR));
+    let mut matfile = MatlabFile::new(&format!("{}written.mat", TMP_DIR));
+    for (prefix, shape) in vec![("v", &[40][..]), ("m", &[20, 15]), ("n", &[4, 5, 6])] {
+        let array = expected(shape).map(|&x| c64::new(x, -x));
+        let name = |t : &str| format!("{}_{}", prefix, t);
+        matfile.compression = prefix == "m";
+        write_as::<i8>(&mut matfile, &name("int8"), &array);
+        write_as::<i16>(&mut matfile, &name("int16"), &array);
+        write_as::<i32>(&mut matfile, &name("int32"), &array);
+        write_as::<i64>(&mut matfile, &name("int64"), &array);
+        write_as::<u8>(&mut matfile, &name("uint8"), &array);
+        write_as::<u16>(&mut matfile, &name("uint16"), &array);
+        write_as::<u32>(&mut matfile, &name("uint32"), &array);
+        write_as::<u64>(&mut matfile, &name("uint64"), &array);
+        write_as::<f32>(&mut matfile, &name("float32"), &array);
+        write_as::<f64>(&mut matfile, &name("float64"), &array);
+        write_as::<c32>(&mut matfile, &name("complex64"), &array);
+        write_as::<c64>(&mut matfile, &name("complex128"), &array);
+    }
+    // Replace a variable in the middle of the file
+    matfile.write_data("m_int16", &NDArray::<i16>::new(&[2], 0)).unwrap();
+    write_as::<i16>(&mut matfile, "m_int16", &expected(&[20, 15]).map(|&x| c64::new(x, -x)));
+    assert!(Command::new("python").arg("test_vector/matlab/verify.py").arg(TMP_DIR).status().unwrap().success());
+}
@@ -2,6 +2,20 @@ pub mod types;
 pub mod array;
 pub mod csv;
 pub mod numpy;
+pub mod npz;
+pub mod hdf5;
+pub mod matlab;
 pub mod edf;
 pub mod blas;
 pub mod backend;
+pub mod ops;
+pub mod iter;
+pub mod reduce;
+pub mod join;
+pub mod map;
+pub mod mask;
+pub mod einsum;
+pub mod parallel;
+pub mod display;
+#[cfg(feature = "lapack")]
+pub mod linalg;
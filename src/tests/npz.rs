@@ -0,0 +1,356 @@
+extern crate flate2;
+
+use std::fmt::Display;
+use std::fs::{File, metadata, remove_file};
+use std::io::Write;
+use std::process::Command;
+
+use self::flate2::Crc;
+
+use types::complex::{c32, c64};
+use types::RDSTyped;
+use types::cast::Cast;
+use array::{NDArray, NDData};
+use array::iter::NDIterable;
+use array::map::NDMappable;
+use array::ndindex::NDIndex;
+use array::npz::NpzFile;
+use error::Error;
+
+fn uint(v : u64, n : usize) -> Vec<u8> {
+    (0..n).map(|i| (v >> (8 * i)) as u8).collect()
+}
+
+/// Encode a little endian int32 `.npy` file of the given shape holding 0, 1, 2...
+fn npy(shape : &str, size : usize) -> Vec<u8> {
+    let mut header = format!("{{'descr': '<i4', 'fortran_order': False, 'shape': ({}), }}", shape).into_bytes();
+    while (header.len() + 11) % 16 != 0 {
+        header.push(b' ');
+    }
+    header.push(b'\n');
+    let mut file = vec![0x93u8, b'N', b'U', b'M', b'P', b'Y', 1, 0];
+    file.extend(uint(header.len() as u64, 2));
+    file.extend(header);
+    for i in 0..size {
+        file.extend(uint(i as u64, 4));
+    }
+    file
+}
+
+/// Build an archive as written by numpy: local headers with a ZIP64 extra field and saturated
+/// sizes, followed by a data descriptor, and a ZIP64 extra field in the central directory.
+fn build(path : &str, corrupt : bool) {
+    let members = vec![("x.npy", npy("2, 3", 6)), ("y.npy", npy("4,", 4))];
+    let mut archive = Vec::<u8>::new();
+    let mut directory = Vec::<u8>::new();
+    for (name, data) in members {
+        let crc = {
+            let mut crc = Crc::new();
+            crc.update(&data);
+            crc.sum() ^ if corrupt { 1 } else { 0 }
+        };
+        let offset = archive.len();
+        let mut zip64 = uint(1, 2);
+        zip64.extend(uint(16, 2));
+        zip64.extend(uint(data.len() as u64, 8));
+        zip64.extend(uint(data.len() as u64, 8));
+
+        archive.extend(uint(0x04034b50, 4));
+        archive.extend(uint(45, 2));
+        archive.extend(uint(0x8, 2));
+        archive.extend(vec![0; 10]);
+        archive.extend(uint(0xffffffff, 4));
+        archive.extend(uint(0xffffffff, 4));
+        archive.extend(uint(name.len() as u64, 2));
+        archive.extend(uint(zip64.len() as u64, 2));
+        archive.extend_from_slice(name.as_bytes());
+        archive.extend_from_slice(&zip64);
+        archive.extend_from_slice(&data);
+        archive.extend(uint(0x08074b50, 4));
+        archive.extend(uint(crc as u64, 4));
+        archive.extend(uint(data.len() as u64, 8));
+        archive.extend(uint(data.len() as u64, 8));
+
+        directory.extend(uint(0x02014b50, 4));
+        directory.extend(uint(45, 2));
+        directory.extend(uint(45, 2));
+        directory.extend(uint(0x8, 2));
+        directory.extend(vec![0; 6]);
+        directory.extend(uint(crc as u64, 4));
+        directory.extend(uint(0xffffffff, 4));
+        directory.extend(uint(0xffffffff, 4));
+        directory.extend(uint(name.len() as u64, 2));
+        directory.extend(uint(zip64.len() as u64, 2));
+        directory.extend(vec![0; 10]);
+        directory.extend(uint(offset as u64, 4));
+        directory.extend_from_slice(name.as_bytes());
+        directory.extend_from_slice(&zip64);
+    }
+    let offset = archive.len();
+    archive.extend_from_slice(&directory);
+    archive.extend(uint(0x06054b50, 4));
+    archive.extend(vec![0; 4]);
+    archive.extend(uint(2, 2));
+    archive.extend(uint(2, 2));
+    archive.extend(uint(directory.len() as u64, 4));
+    archive.extend(uint(offset as u64, 4));
+    archive.extend(uint(7, 2));
+    archive.extend_from_slice(b"comment");
+    File::create(path).unwrap().write_all(&archive).unwrap();
+}
+
+#[test]
+fn read() {
+    const PATH : &'static str = "/tmp/rds_npz_1.npz";
+    build(PATH, false);
+    let npz_file = NpzFile::new(PATH);
+    assert_eq!(npz_file.members().unwrap(), vec!["x".to_string(), "y".to_string()]);
+
+    let x : NDArray<f64> = npz_file.read_array("x").unwrap();
+    assert_eq!(x.shape(), &[2, 3]);
+    for i in 0..2 {
+        for j in 0..3 {
+            assert_eq!(x[&[i, j]], (i * 3 + j) as f64);
+        }
+    }
+    let y : NDArray<u8> = npz_file.read_array("y.npy").unwrap();
+    assert_eq!(y.shape(), &[4]);
+    for i in 0..4 {
+        assert_eq!(y[&[i]], i as u8);
+    }
+}
+
+#[test]
+fn write() {
+    const PATH : &'static str = "/tmp/rds_npz_2.npz";
+    let mut a = NDArray::<f64>::new(&[3, 4], 0.0);
+    let mut b = NDArray::<i16>::new(&[2, 2, 5], 0);
+    let mut c = NDArray::<c32>::new(&[3], c32::new(0.0, 0.0));
+    for i in 0..3 {
+        for j in 0..4 {
+            a[&[i, j]] = (i * 4 + j) as f64 / 8.0;
+        }
+        c[&[i]] = c32::new(i as f32, -(i as f32));
+    }
+    for i in 0..20 {
+        b[&[i / 10, (i / 5) % 2, i % 5]] = i as i16 - 10;
+    }
+
+    for &compression in &[false, true] {
+        let _ = remove_file(PATH);
+        let mut npz_file = NpzFile::new(PATH);
+        npz_file.compression = compression;
+        npz_file.write_data("a", &a).unwrap();
+        // The central directory is only written on close
+        match NpzFile::new(PATH).members() {
+            Err(Error::Format(_)) => {},
+            _ => panic!("listing the members of an archive not closed should fail")
+        }
+        npz_file.write_data("b", &NDArray::<i16>::new(&[1], 0)).unwrap();
+        npz_file.write_data("c", &c).unwrap();
+        // Writing a member again replaces it
+        npz_file.write_data("b", &b).unwrap();
+        npz_file.close().unwrap();
+
+        let npz_file = NpzFile::new(PATH);
+        assert_eq!(npz_file.members().unwrap(), vec!["a".to_string(), "b".to_string(), "c".to_string()]);
+        let ra : NDArray<f64> = npz_file.read_array("a").unwrap();
+        assert_eq!(ra.shape(), a.shape());
+        assert!(ra.iter().zip(a.iter()).all(|(x, y)| x == y));
+        let rb : NDArray<f32> = npz_file.read_array("b").unwrap();
+        assert_eq!(rb.shape(), b.shape());
+        assert!(rb.iter().zip(b.iter()).all(|(&x, &y)| x == y as f32));
+        let rc : NDArray<c32> = npz_file.read_array("c").unwrap();
+        assert_eq!(rc.shape(), c.shape());
+        assert!(rc.iter().zip(c.iter()).all(|(x, y)| x == y));
+    }
+
+    // An existing archive is never replaced, even once closed
+    let mut npz_file = NpzFile::new(PATH);
+    match npz_file.write_data("a", &a) {
+        Err(Error::Io(_)) => {},
+        _ => panic!("writing over an existing file should fail")
+    }
+    npz_file.close().unwrap();
+    assert_eq!(NpzFile::new(PATH).members().unwrap().len(), 3);
+
+    // Deflated members are smaller for redundant data
+    let zeros = NDArray::<f64>::new(&[100, 100], 0.0);
+    let mut sizes = Vec::<u64>::new();
+    for &compression in &[false, true] {
+        let _ = remove_file(PATH);
+        let mut npz_file = NpzFile::new(PATH);
+        npz_file.compression = compression;
+        npz_file.write_data("zeros", &zeros).unwrap();
+        npz_file.close().unwrap();
+        sizes.push(metadata(PATH).unwrap().len());
+    }
+    assert!(sizes[1] < sizes[0] / 10);
+    let r : NDArray<f64> = NpzFile::new(PATH).read_array("zeros").unwrap();
+    assert!(r.iter().all(|&v| v == 0.0));
+}
+
+#[test]
+fn write_drop() {
+    const PATH : &'static str = "/tmp/rds_npz_4.npz";
+    let _ = remove_file(PATH);
+    {
+        let mut npz_file = NpzFile::new(PATH);
+        npz_file.write_data("x", &NDArray::<u8>::new(&[2, 3], 7)).unwrap();
+    }
+    let npz_file = NpzFile::new(PATH);
+    assert_eq!(npz_file.members().unwrap(), vec!["x".to_string()]);
+    let x : NDArray<u8> = npz_file.read_array("x").unwrap();
+    assert_eq!(x.shape(), &[2, 3]);
+    assert!(x.iter().all(|&v| v == 7));
+}
+
+#[test]
+fn write_zip64() {
+    const PATH : &'static str = "/tmp/rds_npz_5.npz";
+    let _ = remove_file(PATH);
+    // More than 65535 members need the ZIP64 end of central directory record
+    let mut npz_file = NpzFile::new(PATH);
+    for i in 0..0x10001 {
+        npz_file.write_data(&format!("a{}", i), &NDArray::<u32>::new(&[1], i)).unwrap();
+    }
+    npz_file.close().unwrap();
+    let npz_file = NpzFile::new(PATH);
+    assert_eq!(npz_file.members().unwrap().len(), 0x10001);
+    let last : NDArray<u32> = npz_file.read_array("a65536").unwrap();
+    assert_eq!(last[&[0]], 0x10000);
+}
+
+const TYPES : [&'static str; 12] = ["int8", "int16", "int32", "int64", "uint8", "uint16", "uint32", "uint64", "float32", "float64", "complex64", "complex128"];
+
+/// The array of the test vectors, each element being the sum of its indices weighted by 3, 5 and 7.
+fn expected(shape : &[usize]) -> NDArray<f64> {
+    let mut array = NDArray::<f64>::new(shape, 0.0);
+    let mut idx = vec![0usize; shape.len()];
+    for _ in 0..array.size() {
+        array[&idx[..]] = idx.iter().zip(&[3, 5, 7]).fold(0.0, |acc, (&i, &w)| acc + (i * w) as f64);
+        idx.inc_ro(shape);
+    }
+    array
+}
+
+#[test]
+fn read_numpy() {
+    const TMP_DIR : &'static str = "/tmp/rds_npz_6/";
+    assert!(Command::new("python").arg("test_vector/npz/generate.py").arg(TMP_DIR).status().unwrap().success());
+    for file in &["savez.npz", "savez_compressed.npz", "unseekable.npz"] {
+        let npz_file = NpzFile::new(&format!("{}{}", TMP_DIR, file));
+        let mut members = npz_file.members().unwrap();
+        members.sort();
+        let mut names : Vec<String> = TYPES.iter().flat_map(|t| {
+            vec![format!("v_{}", t), format!("m_{}", t), format!("n_{}", t)]
+        }).chain(vec!["f_float64".to_string()]).collect();
+        names.sort();
+        assert_eq!(members, names);
+
+        for t in &TYPES {
+            for (prefix, shape) in vec![("v", &[40][..]), ("m", &[20, 15]), ("n", &[4, 5, 6])] {
+                let name = format!("{}_{}", prefix, t);
+                let array : NDArray<c64> = npz_file.read_array(&name).unwrap();
+                let imaginary = if t.starts_with("complex") { -1.0 } else { 0.0 };
+                assert!(array == expected(shape).map(|&x| c64::new(x, imaginary * x)), "{} in {}", name, file);
+            }
+        }
+        let f : NDArray<f64> = npz_file.read_array("f_float64").unwrap();
+        assert!(f == expected(&[20, 15]), "f_float64 in {}", file);
+    }
+
+    let npz_file = NpzFile::new(&format!("{}many.npz", TMP_DIR));
+    assert_eq!(npz_file.members().unwrap().len(), 70000);
+    let last : NDArray<i32> = npz_file.read_array("a69999").unwrap();
+    assert_eq!(last[&[0]], 69999);
+}
+
+fn write_as<T : Copy + RDSTyped + Display>(npz_file : &mut NpzFile, name : &str, array : &NDArray<c64>) where c64 : Cast<T> {
+    npz_file.write_data(name, &NDArray::<T>::cast(array)).unwrap();
+}
+
+#[test]
+fn write_numpy() {
+    const TMP_DIR : &'static str = "/tmp/rds_npz_7/";
+    let _ = ::std::fs::create_dir(TMP_DIR);
+    let _ = remove_file(format!("{}written.npz", TMP_DIR));
+    let mut npz_file = NpzFile::new(&format!("{}written.npz", TMP_DIR));
+    for (prefix, shape) in vec![("v", &[40][..]), ("m", &[20, 15]), ("n", &[4, 5, 6])] {
+        let array = expected(shape).map(|&x| c64::new(x, -x));
+        let name = |t : &str| format!("{}_{}", prefix, t);
+        npz_file.compression = prefix == "m";
+        write_as::<i8>(&mut npz_file, &name("int8"), &array);
+        write_as::<i16>(&mut npz_file, &name("int16"), &array);
+        write_as::<i32>(&mut npz_file, &name("int32"), &array);
+        write_as::<i64>(&mut npz_file, &name("int64"), &array);
+        write_as::<u8>(&mut npz_file, &name("uint8"), &array);
+        write_as::<u16>(&mut npz_file, &name("uint16"), &array);
+        write_as::<u32>(&mut npz_file, &name("uint32"), &array);
+        write_as::<u64>(&mut npz_file, &name("uint64"), &array);
+        write_as::<f32>(&mut npz_file, &name("float32"), &array);
+        write_as::<f64>(&mut npz_file, &name("float64"), &array);
+        write_as::<c32>(&mut npz_file, &name("complex64"), &array);
+        write_as::<c64>(&mut npz_file, &name("complex128"), &array);
+    }
+    // Replace a member, leaving the previous one unreferenced in the archive
+    npz_file.write_data("m_int16", &NDArray::<i16>::new(&[2], 0)).unwrap();
+    write_as::<i16>(&mut npz_file, "m_int16", &expected(&[20, 15]).map(|&x| c64::new(x, -x)));
+    npz_file.close().unwrap();
+    assert!(Command::new("python").arg("test_vector/npz/verify.py").arg(TMP_DIR).status().unwrap().success());
+}
+
+#[test]
+fn errors() {
+    const PATH : &'static str = "/tmp/rds_npz_3.npz";
+    build(PATH, true);
+    let npz_file = NpzFile::new(PATH);
+    match npz_file.read_array::<f64>("z") {
+        Err(Error::Format(_)) => {},
+        _ => panic!("reading a missing member should fail")
+    }
+    match npz_file.read_array::<f64>("x") {
+        Err(Error::Format(_)) => {},
+        _ => panic!("reading a member with a wrong CRC-32 should fail")
+    }
+
+    File::create(PATH).unwrap().write_all(b"not a zip archive").unwrap();
+    match NpzFile::new(PATH).members() {
+        Err(Error::Format(_)) => {},
+        _ => panic!("listing the members of a file which is not a zip archive should fail")
+    }
+    match NpzFile::new(PATH).write_data("", &NDArray::<f64>::new(&[1], 0.0)) {
+        Err(Error::Format(_)) => {},
+        _ => panic!("writing a member without name should fail")
+    }
+    // A ZIP64 central directory whose end overflows
+    let mut archive = uint(0x06064b50, 4);
+    archive.extend(uint(44, 8));
+    archive.extend(uint(45, 2));
+    archive.extend(uint(45, 2));
+    archive.extend(vec![0; 8]);
+    archive.extend(uint(1, 8));
+    archive.extend(uint(1, 8));
+    archive.extend(uint(16, 8));
+    archive.extend(uint(u64::max_value() - 8, 8));
+    archive.extend(uint(0x07064b50, 4));
+    archive.extend(uint(0, 4));
+    archive.extend(uint(0, 8));
+    archive.extend(uint(1, 4));
+    archive.extend(uint(0x06054b50, 4));
+    archive.extend(vec![0; 4]);
+    archive.extend(uint(0xffff, 2));
+    archive.extend(uint(0xffff, 2));
+    archive.extend(uint(0xffffffff, 4));
+    archive.extend(uint(0xffffffff, 4));
+    archive.extend(uint(0, 2));
+    File::create(PATH).unwrap().write_all(&archive).unwrap();
+    match NpzFile::new(PATH).members() {
+        Err(Error::Format(_)) => {},
+        _ => panic!("listing the members of an archive whose central directory overflows should fail")
+    }
+    match NpzFile::new("/tmp/rds_npz_missing/a.npz").members() {
+        Err(Error::Io(_)) => {},
+        _ => panic!("opening a missing file should fail")
+    }
+}
@@ -0,0 +1,168 @@
+use types::complex::{c32, c64};
+use array::{NDArray, NDData, NDSliceable, NDSliceableMut, NDViewable, NDViewableMut, NDRange};
+use array::broadcast::broadcast_shape;
+
+#[test]
+fn broadcasting_shape() {
+    assert!(broadcast_shape(&[3, 4], &[3, 4]) == Some(vec![3, 4]));
+    assert!(broadcast_shape(&[3, 4], &[4]) == Some(vec![3, 4]));
+    assert!(broadcast_shape(&[3, 1], &[1, 4]) == Some(vec![3, 4]));
+    assert!(broadcast_shape(&[2, 1, 4], &[3, 1]) == Some(vec![2, 3, 4]));
+    assert!(broadcast_shape(&[], &[2, 2]) == Some(vec![2, 2]));
+    assert!(broadcast_shape(&[3, 4], &[3]) == None);
+    assert!(broadcast_shape(&[2, 3], &[3, 3]) == None);
+}
+
+#[test]
+fn broadcast_view() {
+    let row = NDArray::<f64>::from_slice(&[3], &[1.0, 2.0, 3.0]);
+    let broadcasted = row.broadcast(&[2, 3]);
+    assert!(broadcasted.shape() == &[2, 3]);
+    assert!(broadcasted.strides() == &[0, 1]);
+    for i in 0..2 {
+        for j in 0..3 {
+            assert!(broadcasted[&[i, j]] == (j + 1) as f64);
+        }
+    }
+}
+
+#[test]
+#[should_panic]
+fn broadcast_view_invalid() {
+    let row = NDArray::<f64>::from_slice(&[3], &[1.0, 2.0, 3.0]);
+    row.broadcast(&[3, 2]);
+}
+
+#[test]
+fn same_shape() {
+    let array1 = NDArray::<f64>::from_slice(&[2, 2], &[1.0, 2.0, 3.0, 4.0]);
+    let array2 = NDArray::<f64>::from_slice(&[2, 2], &[4.0, 3.0, 2.0, 1.0]);
+    assert!(&array1 + &array2 == NDArray::<f64>::new(&[2, 2], 5.0));
+    assert!(&array1 - &array2 == NDArray::<f64>::from_slice(&[2, 2], &[-3.0, -1.0, 1.0, 3.0]));
+    assert!(&array1 * &array2 == NDArray::<f64>::from_slice(&[2, 2], &[4.0, 6.0, 6.0, 4.0]));
+    assert!(&array1 / &array2 == NDArray::<f64>::from_slice(&[2, 2], &[0.25, 2.0 / 3.0, 1.5, 4.0]));
+}
+
+#[test]
+fn broadcasting() {
+    let matrix = NDArray::<f32>::from_slice(&[2, 3], &[1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+    let row = NDArray::<f32>::from_slice(&[3], &[1.0, 2.0, 3.0]);
+    let column = NDArray::<f32>::from_slice(&[2, 1], &[10.0, 20.0]);
+    let scalar = NDArray::<f32>::new(&[], 2.0);
+
+    assert!(&matrix - &row == NDArray::<f32>::from_slice(&[2, 3], &[0.0, 0.0, 0.0, 3.0, 3.0, 3.0]));
+    assert!(&row + &matrix == NDArray::<f32>::from_slice(&[2, 3], &[2.0, 4.0, 6.0, 5.0, 7.0, 9.0]));
+    assert!(&matrix * &column == NDArray::<f32>::from_slice(&[2, 3], &[10.0, 20.0, 30.0, 80.0, 100.0, 120.0]));
+    assert!(&matrix / &scalar == NDArray::<f32>::from_slice(&[2, 3], &[0.5, 1.0, 1.5, 2.0, 2.5, 3.0]));
+    let outer = &column * &row;
+    assert!(outer == NDArray::<f32>::from_slice(&[2, 3], &[10.0, 20.0, 30.0, 20.0, 40.0, 60.0]));
+}
+
+#[test]
+fn broadcasting_slices_and_views() {
+    let mut array = NDArray::<i32>::new(&[2, 3, 3], 1);
+    let slice = array.slice(&[1]);
+    let view = slice.view(&[NDRange::all(), NDRange::index(0)]);
+    let result = &view + &slice;
+    assert!(result == NDArray::<i32>::new(&[3, 3], 2));
+    let column = NDArray::<i32>::from_slice(&[3, 1], &[1, 2, 3]);
+    let result = &array * &column;
+    assert!(result.shape() == &[2, 3, 3]);
+    assert!(result[&[1, 2, 0]] == 3);
+    {
+        let mut slice = array.slice_mut(&[0]);
+        slice *= &column;
+    }
+    {
+        let mut view = array.view_mut(&[NDRange::all(), NDRange::all().step(-1), NDRange::index(1)]);
+        view -= &NDArray::<i32>::from_slice(&[3], &[1, 1, 0]);
+    }
+    assert!(array.slice(&[0]) == NDArray::<i32>::from_slice(&[3, 3], &[1, 1, 1, 2, 1, 2, 3, 2, 3]));
+    assert!(array.slice(&[1]) == NDArray::<i32>::from_slice(&[3, 3], &[1, 1, 1, 1, 0, 1, 1, 0, 1]));
+}
+
+#[test]
+fn in_place() {
+    let mut array = NDArray::<c32>::new(&[2, 2], c32::new(1.0, 1.0));
+    array += &NDArray::<c32>::from_slice(&[2], &[c32::new(1.0, 0.0), c32::new(0.0, 1.0)]);
+    array *= &NDArray::<c32>::new(&[], c32::new(0.0, 1.0));
+    array /= &NDArray::<c32>::new(&[2, 1], c32::new(1.0, 0.0));
+    assert!(array == NDArray::<c32>::from_slice(&[2, 2], &[c32::new(-1.0, 2.0), c32::new(-2.0, 1.0), c32::new(-1.0, 2.0), c32::new(-2.0, 1.0)]));
+}
+
+#[test]
+#[should_panic]
+fn incompatible_shapes() {
+    let array1 = NDArray::<f64>::new(&[2, 3], 0.0);
+    let array2 = NDArray::<f64>::new(&[2], 0.0);
+    let _ = &array1 + &array2;
+}
+
+#[test]
+#[should_panic]
+fn in_place_incompatible_shapes() {
+    let mut array1 = NDArray::<f64>::new(&[3], 0.0);
+    array1 += &NDArray::<f64>::new(&[2, 3], 0.0);
+}
+
+#[test]
+fn integer_types() {
+    let array1 = NDArray::<u8>::from_slice(&[2, 2], &[10, 20, 30, 40]);
+    let array2 = NDArray::<u8>::from_slice(&[2], &[3, 6]);
+    assert!(&array1 + &array2 == NDArray::<u8>::from_slice(&[2, 2], &[13, 26, 33, 46]));
+    assert!(&array1 - &array2 == NDArray::<u8>::from_slice(&[2, 2], &[7, 14, 27, 34]));
+    assert!(&array1 * &array2 == NDArray::<u8>::from_slice(&[2, 2], &[30, 120, 90, 240]));
+    assert!(&array1 / &array2 == NDArray::<u8>::from_slice(&[2, 2], &[3, 3, 10, 6]));
+    assert!(&array1 % &array2 == NDArray::<u8>::from_slice(&[2, 2], &[1, 2, 0, 4]));
+    let mut array3 = NDArray::<i64>::from_slice(&[3], &[-7, 8, 9]);
+    array3 %= &NDArray::<i64>::new(&[], 4);
+    assert!(array3 == NDArray::<i64>::from_slice(&[3], &[-3, 0, 1]));
+}
+
+#[test]
+fn negation() {
+    let array = NDArray::<i16>::from_slice(&[2, 2], &[1, -2, 3, -4]);
+    assert!(-&array == NDArray::<i16>::from_slice(&[2, 2], &[-1, 2, -3, 4]));
+    assert!(-&array.slice(&[1]) == NDArray::<i16>::from_slice(&[2], &[-3, 4]));
+    let view = array.view(&[NDRange::all().step(-1), NDRange::index(0)]);
+    assert!(-&view == NDArray::<i16>::from_slice(&[2], &[-3, -1]));
+    let complex = NDArray::<c32>::new(&[1], c32::new(1.0, -1.0));
+    assert!(-&complex == NDArray::<c32>::new(&[1], c32::new(-1.0, 1.0)));
+}
+
+#[test]
+fn scalar_operands() {
+    let array = NDArray::<i32>::from_slice(&[2, 2], &[1, 2, 3, 4]);
+    assert!(&array + 1 == NDArray::<i32>::from_slice(&[2, 2], &[2, 3, 4, 5]));
+    assert!(&array - 1 == NDArray::<i32>::from_slice(&[2, 2], &[0, 1, 2, 3]));
+    assert!(&array * 3 == NDArray::<i32>::from_slice(&[2, 2], &[3, 6, 9, 12]));
+    assert!(&array / 2 == NDArray::<i32>::from_slice(&[2, 2], &[0, 1, 1, 2]));
+    assert!(&array % 2 == NDArray::<i32>::from_slice(&[2, 2], &[1, 0, 1, 0]));
+    assert!(&array.slice(&[0]) * 2 == NDArray::<i32>::from_slice(&[2], &[2, 4]));
+    let mut array = NDArray::<f64>::from_slice(&[3], &[1.0, 2.0, 3.0]);
+    array *= 2.0;
+    array -= 1.0;
+    array /= 2.0;
+    array %= 1.0;
+    assert!(array == NDArray::<f64>::new(&[3], 0.5));
+    let mut complex = NDArray::<c64>::new(&[2, 2], c64::new(1.0, 0.0));
+    {
+        let mut slice = complex.slice_mut(&[0]);
+        slice *= c64::new(0.0, 2.0);
+        slice += c64::new(1.0, 0.0);
+    }
+    assert!(complex.slice(&[0]) == NDArray::<c64>::new(&[2], c64::new(1.0, 2.0)));
+    assert!(complex.slice(&[1]) == NDArray::<c64>::new(&[2], c64::new(1.0, 0.0)));
+}
+
+#[test]
+fn blas_fast_path_strided() {
+    let mut array = NDArray::<f32>::from_slice(&[2, 3], &[1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+    let other = NDArray::<f32>::from_slice(&[2, 3], &[1.0, 1.0, 1.0, 2.0, 2.0, 2.0]);
+    {
+        let mut view = array.view_mut(&[NDRange::all(), NDRange::all().step(-1)]);
+        view -= &other.view(&[NDRange::all().step(-1)]);
+        view *= 2.0;
+    }
+    assert!(array == NDArray::<f32>::from_slice(&[2, 3], &[-2.0, 0.0, 2.0, 6.0, 8.0, 10.0]));
+}
@@ -0,0 +1,105 @@
+use std::cell::Cell;
+use std::panic;
+use std::rc::Rc;
+
+use array::{NDArray, NDData, NDDataMut, NDViewable, NDViewableMut};
+use array::map::{NDMappable, NDMappableMut};
+use array::reduce::NDReducible;
+use parallel;
+
+fn large_array() -> NDArray<i64> {
+    let data : Vec<i64> = (0..512 * 256).map(|i| (i % 1000) as i64).collect();
+    NDArray::<i64>::from_slice(&[512, 256], &data[..])
+}
+
+#[test]
+fn settings() {
+    parallel::set_local_num_threads(Some(3));
+    assert!(parallel::num_threads() == 3);
+    assert!(parallel::is_parallel(parallel::PARALLEL_THRESHOLD));
+    assert!(!parallel::is_parallel(parallel::PARALLEL_THRESHOLD - 1));
+    parallel::set_local_num_threads(Some(1));
+    assert!(!parallel::is_parallel(parallel::PARALLEL_THRESHOLD));
+    parallel::set_local_num_threads(None);
+    assert!(parallel::num_threads() >= 1);
+}
+
+#[test]
+fn split() {
+    parallel::set_local_num_threads(Some(4));
+    assert!(parallel::split(10, 1) == vec![0..10]);
+    assert!(parallel::split(10, parallel::PARALLEL_THRESHOLD) == vec![0..3, 3..6, 6..9, 9..10]);
+    let ranges = parallel::split(parallel::PARALLEL_THRESHOLD + 1, 1);
+    assert!(ranges.len() == 4 && ranges[0].start == 0 && ranges[3].end == parallel::PARALLEL_THRESHOLD + 1);
+    for i in 1..4 {
+        assert!(ranges[i].start == ranges[i - 1].end);
+    }
+    assert!(parallel::ro_index(7, &[2, 3, 2]) == vec![1, 0, 1]);
+}
+
+#[test]
+fn map_reduce() {
+    let array = large_array();
+    parallel::set_local_num_threads(Some(1));
+    let mapped = array.transposed().par_map(|&x| x * 2);
+    let sum0 = array.sum(0, false);
+    let sum1 = array.sum(1, false);
+    let copy = NDArray::<i64>::copy(&array.transposed());
+    parallel::set_local_num_threads(Some(4));
+    assert!(array.transposed().par_map(|&x| x * 2) == mapped);
+    assert!(array.transposed().map(|&x| x * 2) == mapped);
+    assert!(array.sum(0, false) == sum0);
+    assert!(array.sum(1, false) == sum1);
+    assert!(NDArray::<i64>::copy(&array.transposed()) == copy);
+    assert!(copy.shape() == &[256, 512] && copy[&[3, 2]] == array[&[2, 3]]);
+    assert!(array.par_zip_map(&array, |a, b| a + b) == mapped.transposed());
+    assert!(array.zip_map(&array, |a, b| a + b) == mapped.transposed());
+    let mut different = NDArray::<i64>::copy(&array);
+    different[&[511, 255]] += 1;
+    assert!(different != array);
+    parallel::set_local_num_threads(None);
+}
+
+#[test]
+fn assign() {
+    parallel::set_local_num_threads(Some(4));
+    let array = large_array();
+    let mut doubled = NDArray::<i64>::copy(&array);
+    doubled.par_map_inplace(|x| *x *= 2);
+    assert!(doubled == array.map(|&x| x * 2));
+    let mut transposed = NDArray::<i64>::copy(&array.transposed());
+    transposed.view_mut(&[]).par_map_inplace(|x| *x *= 2);
+    assert!(transposed == doubled.transposed());
+    let mut transposed = NDArray::<i64>::new(&[256, 512], 0);
+    transposed.assign(&array.transposed());
+    assert!(transposed.transposed() == array);
+    let mut result = NDArray::<i64>::copy(&array);
+    result.view_mut(&[]).assign(&doubled);
+    result += &array;
+    assert!(result == array.map(|&x| x * 3));
+    parallel::set_local_num_threads(None);
+}
+
+#[test]
+fn unshared() {
+    // The sequential operations accept elements and closures which cannot be shared between threads
+    let counter = Cell::new(0);
+    let array = large_array();
+    let mapped = array.map(|&x| { counter.set(counter.get() + 1); Rc::new(x) });
+    assert!(counter.get() == array.size());
+    assert!(mapped == mapped.map(|x| x.clone()));
+    let mut sums = array.zip_map(&mapped, |&a, b| Rc::new(a + **b));
+    sums.map_inplace(|x| *x = Rc::new(**x / 2));
+    assert!(sums.map(|x| **x) == array);
+}
+
+#[test]
+fn panic_propagation() {
+    parallel::set_local_num_threads(Some(4));
+    let array = large_array();
+    let result = panic::catch_unwind(|| array.par_map(|&x| if x == 999 { panic!("999") } else { x }));
+    assert!(result.is_err());
+    // The pool is still usable after a panic in one of its jobs
+    assert!(array.sum(1, false).size() == 512);
+    parallel::set_local_num_threads(None);
+}
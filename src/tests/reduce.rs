@@ -0,0 +1,120 @@
+use std::f64::NAN;
+
+use types::complex::{c32, c64};
+use array::{NDArray, NDData, NDViewable, NDRange};
+use array::iter::NDIterable;
+use array::reduce::NDReducible;
+
+#[test]
+fn sum_product() {
+    let array = NDArray::<i32>::from_slice(&[2, 3], &[1, 2, 3, 4, 5, 6]);
+    assert!(array.sum(0, false) == NDArray::<i32>::from_slice(&[3], &[5, 7, 9]));
+    assert!(array.sum(1, false) == NDArray::<i32>::from_slice(&[2], &[6, 15]));
+    assert!(array.product(1, false) == NDArray::<i32>::from_slice(&[2], &[6, 120]));
+    let kept = array.sum(1, true);
+    assert!(kept.shape() == &[2, 1]);
+    assert!(kept == NDArray::<i32>::from_slice(&[2, 1], &[6, 15]));
+    let vector = NDArray::<u8>::from_slice(&[4], &[1, 2, 3, 4]);
+    assert!(vector.sum(0, false) == NDArray::<u8>::new(&[], 10));
+    let complex = NDArray::<c32>::new(&[2, 2], c32::new(1.0, 1.0));
+    assert!(complex.sum(0, false) == NDArray::<c32>::new(&[2], c32::new(2.0, 2.0)));
+    let empty = NDArray::<f32>::new(&[0, 2], 1.0);
+    assert!(empty.sum(0, false) == NDArray::<f32>::new(&[2], 0.0));
+}
+
+#[test]
+fn mean() {
+    let array = NDArray::<f64>::from_slice(&[2, 2, 2], &[1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0]);
+    assert!(array.mean(0, false) == NDArray::<f64>::from_slice(&[2, 2], &[3.0, 4.0, 5.0, 6.0]));
+    assert!(array.mean(2, true) == NDArray::<f64>::from_slice(&[2, 2, 1], &[1.5, 3.5, 5.5, 7.5]));
+    let integers = NDArray::<i64>::from_slice(&[3], &[1, 2, 4]);
+    assert!(integers.mean(0, false) == NDArray::<i64>::new(&[], 2));
+}
+
+#[test]
+fn mean_overflow() {
+    // The sum and the count overflow the type of the data
+    let bytes = NDArray::<u8>::new(&[256], 200);
+    assert!(bytes.mean(0, false) == NDArray::<u8>::new(&[], 200));
+    let signed = NDArray::<i8>::new(&[3, 300], -100);
+    assert!(signed.mean(1, false) == NDArray::<i8>::new(&[3], -100));
+    let large = NDArray::<u64>::from_slice(&[2], &[u64::max_value(), u64::max_value() - 2]);
+    assert!(large.mean(0, false) == NDArray::<u64>::new(&[], u64::max_value() - 1));
+}
+
+#[test]
+fn extrema() {
+    let array = NDArray::<i16>::from_slice(&[2, 3], &[3, -1, 3, 0, 7, -2]);
+    assert!(array.min(0, false) == NDArray::<i16>::from_slice(&[3], &[0, -1, -2]));
+    assert!(array.max(1, false) == NDArray::<i16>::from_slice(&[2], &[3, 7]));
+    assert!(array.argmin(1, false) == NDArray::<usize>::from_slice(&[2], &[1, 2]));
+    assert!(array.argmax(1, true) == NDArray::<usize>::from_slice(&[2, 1], &[0, 1]));
+    let view = array.view(&[NDRange::all(), NDRange::all().step(-1)]);
+    assert!(view.argmax(1, false) == NDArray::<usize>::from_slice(&[2], &[0, 1]));
+    assert!(view.transposed().min(1, false) == NDArray::<i16>::from_slice(&[3], &[-2, -1, 0]));
+}
+
+#[test]
+fn nan() {
+    let array = NDArray::<f64>::from_slice(&[2, 3], &[1.0, NAN, 3.0, NAN, NAN, -1.0]);
+    assert!(array.sum(1, false)[&[0]].is_nan());
+    assert!(array.max(1, false)[&[1]].is_nan());
+    assert!(array.argmin(1, false) == NDArray::<usize>::from_slice(&[2], &[1, 0]));
+    assert!(array.nansum(1, false) == NDArray::<f64>::from_slice(&[2], &[4.0, -1.0]));
+    assert!(array.nanmean(1, false) == NDArray::<f64>::from_slice(&[2], &[2.0, -1.0]));
+    assert!(array.nanmin(1, false) == NDArray::<f64>::from_slice(&[2], &[1.0, -1.0]));
+    assert!(array.nanmax(1, false) == NDArray::<f64>::from_slice(&[2], &[3.0, -1.0]));
+    assert!(array.nanargmax(1, false) == NDArray::<usize>::from_slice(&[2], &[2, 2]));
+    let nanmax = array.nanmax(0, false);
+    assert!(nanmax[&[0]] == 1.0 && nanmax[&[1]].is_nan() && nanmax[&[2]] == 3.0);
+}
+
+#[test]
+#[should_panic]
+fn nanargmin_all_nan() {
+    let array = NDArray::<f32>::new(&[2, 2], ::std::f32::NAN);
+    array.nanargmin(0, false);
+}
+
+#[test]
+#[should_panic]
+fn min_empty_axis() {
+    let array = NDArray::<u32>::new(&[2, 0], 0);
+    array.min(1, false);
+}
+
+#[test]
+#[should_panic]
+fn mean_empty_axis() {
+    let array = NDArray::<f64>::new(&[0, 2], 0.0);
+    array.mean(0, false);
+}
+
+#[test]
+#[should_panic]
+fn mean_empty_axis_integer() {
+    let array = NDArray::<i32>::new(&[2, 0], 0);
+    array.mean(1, true);
+}
+
+#[test]
+#[should_panic]
+fn nanmean_empty_axis() {
+    let array = NDArray::<c64>::new(&[0], c64::new(0.0, 0.0));
+    array.nanmean(0, false);
+}
+
+#[test]
+fn mean_all_nan() {
+    let array = NDArray::<f32>::new(&[3, 2], ::std::f32::NAN);
+    assert!(array.nanmean(0, false).iter().all(|x| x.is_nan()));
+    // An empty result has no lane to reduce
+    assert!(NDArray::<i64>::new(&[0, 3], 0).mean(1, false).shape() == &[0]);
+}
+
+#[test]
+#[should_panic]
+fn axis_out_of_bound() {
+    let array = NDArray::<u32>::new(&[2, 3], 0);
+    array.sum(2, false);
+}
@@ -235,7 +235,10 @@ impl FromStr for c32 {
 
 impl fmt::Display for c32 {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{}{:+}j", self.re, self.im)
+        match f.precision() {
+            Some(p) => write!(f, "{:.*}{:+.*}j", p, self.re, p, self.im),
+            None => write!(f, "{}{:+}j", self.re, self.im)
+        }
     }
 }
 
@@ -421,6 +424,9 @@ impl FromStr for c64 {
 
 impl fmt::Display for c64 {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{}{:+}j", self.re, self.im)
+        match f.precision() {
+            Some(p) => write!(f, "{:.*}{:+.*}j", p, self.re, p, self.im),
+            None => write!(f, "{}{:+}j", self.re, self.im)
+        }
     }
 }
@@ -0,0 +1,50 @@
+import sys
+import os
+import numpy as np
+import h5py
+
+directory = sys.argv[1]
+if os.path.isdir(directory) == False:
+    os.mkdir(directory)
+
+# Every element is the sum of its indices weighted by 3, 5 and 7
+array1d = np.fromfunction(lambda i: 3*i, (40,))
+array2d = np.fromfunction(lambda i, j: 3*i + 5*j, (20, 15))
+array3d = np.fromfunction(lambda i, j, k: 3*i + 5*j + 7*k, (4, 5, 6))
+
+types = ['int8', 'int16', 'int32', 'int64', 'uint8', 'uint16', 'uint32', 'uint64', 'float32', 'float64']
+
+# Contiguous datasets of every type in both byte orders, and complex compound datasets
+with h5py.File(os.path.join(directory, 'contiguous.h5'), 'w') as f:
+    for t in types:
+        for bo, name in [('<', 'le'), ('>', 'be')]:
+            dt = np.dtype(t).newbyteorder(bo)
+            f.create_dataset('1d_' + name + '_' + t, data=array1d.astype(dt))
+            f.create_dataset('2d_' + name + '_' + t, data=array2d.astype(dt))
+            f.create_dataset('3d_' + name + '_' + t, data=array3d.astype(dt))
+    for t in ['complex64', 'complex128']:
+        f.create_dataset(t, data=(array2d - 1j * array2d).astype(t))
+
+# Chunked datasets with the shuffle and deflate filters, chunks not dividing the shape, and a
+# dataset where only the first chunk was written
+with h5py.File(os.path.join(directory, 'chunked.h5'), 'w') as f:
+    for t in types:
+        f.create_dataset('2d_' + t, data=array2d.astype(t), chunks=(6, 4), compression='gzip', shuffle=True)
+        f.create_dataset('3d_' + t, data=array3d.astype(t), chunks=(3, 2, 4), compression='gzip', compression_opts=9, shuffle=True, fletcher32=True)
+    f.create_dataset('uncompressed', data=array2d, chunks=(7, 15))
+    f.create_dataset('complex128', data=array2d - 1j * array2d, chunks=(5, 5), compression='gzip', shuffle=True)
+    sparse = f.create_dataset('sparse', shape=(20, 15), dtype='float64', chunks=(6, 4), compression='gzip')
+    sparse[0:6, 0:4] = array2d[0:6, 0:4]
+
+# Nested groups and attributes, with the original file format and the latest one
+for name, libver in [('groups.h5', 'earliest'), ('groups_latest.h5', 'latest')]:
+    with h5py.File(os.path.join(directory, name), 'w', libver=libver) as f:
+        f.attrs['title'] = np.bytes_('Measures')
+        f.create_group('empty')
+        group = f.create_group('a/b')
+        f['a'].attrs['count'] = np.int32(4)
+        data = group.create_dataset('data', data=array3d)
+        data.attrs['scale'] = np.float32(0.5)
+        data.attrs['offset'] = np.complex128(1 - 2j)
+        for i in range(40):
+            f.create_dataset('many/d{:02}'.format(i), data=np.full((2,), i, dtype='uint8'))
@@ -0,0 +1,42 @@
+import sys
+import os
+import numpy as np
+import h5py
+
+directory = sys.argv[1]
+
+def expected(shape):
+    weights = [3, 5, 7]
+    return np.fromfunction(lambda *idx: sum(w * i for w, i in zip(weights, idx)), shape)
+
+def fail(message):
+    print('Fail: ' + message)
+    sys.exit(1)
+
+# The datasets written by HDF5File, whose name starts with the name of their type
+with h5py.File(os.path.join(directory, 'written.h5'), 'r') as f:
+    datasets = []
+    f.visititems(lambda name, obj: datasets.append(name) if isinstance(obj, h5py.Dataset) else None)
+    if len(datasets) == 0:
+        fail('no dataset')
+    for name in datasets:
+        array = f[name][()]
+        t = name.split('/')[-1].split('_')[0]
+        if array.dtype != np.dtype(t):
+            fail('{}: type {} instead of {}'.format(name, array.dtype, t))
+        reference = expected(array.shape)
+        if array.dtype.kind == 'c':
+            reference = reference - 1j * reference
+        if not np.array_equal(array, reference.astype(array.dtype)):
+            fail('{}: wrong values'.format(name))
+    chunked = f['chunked/float64_2d']
+    if chunked.chunks != (6, 4) or chunked.compression != 'gzip':
+        fail('chunked/float64_2d is not chunked and deflated')
+    if f.attrs['title'] != b'Measures':
+        fail('title attribute is {}'.format(f.attrs['title']))
+    if f['chunked'].attrs['count'] != 4 or f['chunked'].attrs['count'].dtype != np.dtype('uint32'):
+        fail('count attribute is {}'.format(f['chunked'].attrs['count']))
+    if f['chunked/float64_2d'].attrs['scale'] != 0.5:
+        fail('scale attribute is {}'.format(f['chunked/float64_2d'].attrs['scale']))
+    if sorted(f['empty'].keys()) != []:
+        fail('empty group is not empty')
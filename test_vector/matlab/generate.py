@@ -0,0 +1,28 @@
+import sys
+import os
+import numpy as np
+from scipy.io import savemat
+
+directory = sys.argv[1]
+if os.path.isdir(directory) == False:
+    os.mkdir(directory)
+
+# Every element is the sum of its indices weighted by 3, 5 and 7
+array1d = np.fromfunction(lambda i: 3*i, (40,))
+array2d = np.fromfunction(lambda i, j: 3*i + 5*j, (20, 15))
+array3d = np.fromfunction(lambda i, j, k: 3*i + 5*j + 7*k, (4, 5, 6))
+
+types = ['int8', 'int16', 'int32', 'int64', 'uint8', 'uint16', 'uint32', 'uint64', 'float32', 'float64']
+
+# Vectors are saved as 1xN row vectors
+variables = {}
+for t in types:
+    variables['v_' + t] = array1d.astype(t)
+    variables['m_' + t] = array2d.astype(t)
+    variables['n_' + t] = array3d.astype(t)
+for t in ['complex64', 'complex128']:
+    variables['m_' + t] = (array2d - 1j * array2d).astype(t)
+    variables['n_' + t] = (array3d - 1j * array3d).astype(t)
+
+savemat(os.path.join(directory, 'uncompressed.mat'), variables, do_compression=False)
+savemat(os.path.join(directory, 'compressed.mat'), variables, do_compression=True)
@@ -0,0 +1,33 @@
+import sys
+import os
+import numpy as np
+from scipy.io import loadmat
+
+directory = sys.argv[1]
+
+def expected(shape):
+    weights = [3, 5, 7]
+    return np.fromfunction(lambda *idx: sum(w * i for w, i in zip(weights, idx)), shape)
+
+# The variables written by MatlabFile, named after the dimension and the type of their array.
+# Vectors are read as row vectors.
+variables = loadmat(os.path.join(directory, 'written.mat'))
+names = [n for n in variables if not n.startswith('__')]
+if len(names) != 36:
+    print('Fail: {} variables instead of 36'.format(len(names)))
+    sys.exit(1)
+for name in names:
+    array = variables[name]
+    prefix, t = name.split('_', 1)
+    if array.dtype != np.dtype(t):
+        print('{}: Fail: type {} instead of {}'.format(name, array.dtype, t))
+        sys.exit(1)
+    shape = {'v': (40,), 'm': (20, 15), 'n': (4, 5, 6)}[prefix]
+    reference = expected(shape)
+    if prefix == 'v':
+        reference = reference.reshape((1, 40))
+    if array.dtype.kind == 'c':
+        reference = reference - 1j * reference
+    if array.shape != reference.shape or not np.array_equal(array, reference.astype(array.dtype)):
+        print('{}: Fail: wrong values'.format(name))
+        sys.exit(1)
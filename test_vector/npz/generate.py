@@ -0,0 +1,47 @@
+import sys
+import os
+import io
+import numpy as np
+
+directory = sys.argv[1]
+if os.path.isdir(directory) == False:
+    os.mkdir(directory)
+
+# Every element is the sum of its indices weighted by 3, 5 and 7
+array1d = np.fromfunction(lambda i: 3*i, (40,))
+array2d = np.fromfunction(lambda i, j: 3*i + 5*j, (20, 15))
+array3d = np.fromfunction(lambda i, j, k: 3*i + 5*j + 7*k, (4, 5, 6))
+
+types = ['int8', 'int16', 'int32', 'int64', 'uint8', 'uint16', 'uint32', 'uint64', 'float32', 'float64']
+
+arrays = {}
+for t in types:
+    arrays['v_' + t] = array1d.astype(t)
+    arrays['m_' + t] = array2d.astype(t)
+    arrays['n_' + t] = array3d.astype(t)
+for t in ['complex64', 'complex128']:
+    arrays['v_' + t] = (array1d - 1j * array1d).astype(t)
+    arrays['m_' + t] = (array2d - 1j * array2d).astype(t)
+    arrays['n_' + t] = (array3d - 1j * array3d).astype(t)
+# Stored in column-major order with fortran_order set in the header
+arrays['f_float64'] = np.asfortranarray(array2d)
+
+# numpy writes every member with a ZIP64 extra field in its local header
+np.savez(os.path.join(directory, 'savez.npz'), **arrays)
+np.savez_compressed(os.path.join(directory, 'savez_compressed.npz'), **arrays)
+
+# Without seek, zipfile can not update the local headers and follows each member with a data
+# descriptor, announced by the flag 0x08
+class Unseekable(io.RawIOBase):
+    def __init__(self, f):
+        self.f = f
+    def writable(self):
+        return True
+    def write(self, b):
+        return self.f.write(b)
+
+with open(os.path.join(directory, 'unseekable.npz'), 'wb') as f:
+    np.savez_compressed(Unseekable(f), **arrays)
+
+# More than 65535 members require the ZIP64 end of central directory record
+np.savez(os.path.join(directory, 'many.npz'), **{'a{}'.format(i): np.array([i], dtype='int32') for i in range(70000)})
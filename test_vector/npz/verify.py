@@ -0,0 +1,27 @@
+import sys
+import os
+import numpy as np
+
+directory = sys.argv[1]
+
+def expected(shape):
+    weights = [3, 5, 7]
+    return np.fromfunction(lambda *idx: sum(w * i for w, i in zip(weights, idx)), shape)
+
+# The members written by NpzFile, named after the dimension and the type of their array
+archive = np.load(os.path.join(directory, 'written.npz'))
+if len(archive.files) != 36:
+    print('Fail: {} members instead of 36'.format(len(archive.files)))
+    sys.exit(1)
+for name in archive.files:
+    array = archive[name]
+    prefix, t = name.split('_', 1)
+    if array.dtype != np.dtype(t):
+        print('{}: Fail: type {} instead of {}'.format(name, array.dtype, t))
+        sys.exit(1)
+    reference = expected({'v': (40,), 'm': (20, 15), 'n': (4, 5, 6)}[prefix])
+    if array.dtype.kind == 'c':
+        reference = reference - 1j * reference
+    if array.shape != reference.shape or not np.array_equal(array, reference.astype(array.dtype)):
+        print('{}: Fail: wrong values'.format(name))
+        sys.exit(1)